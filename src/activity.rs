@@ -0,0 +1,396 @@
+//! Canonical activity taxonomy. Gemini names the same behavior many ways
+//! ("Walking", "walking around", "strolls"); everything that aggregates
+//! activities groups by the canonical name instead of the raw one.
+
+use crate::entities::pet::Species;
+use serde::Serialize;
+
+/// Prefix for activities that don't map onto the taxonomy, e.g. `other:zoomies`.
+pub const OTHER_PREFIX: &str = "other:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeciesGroup {
+    Dog,
+    Cat,
+    Other,
+}
+
+impl SpeciesGroup {
+    /// Buckets the `pets.species` value.
+    pub fn from_species(species: &str) -> Self {
+        match Species::of(species) {
+            Species::Dog => SpeciesGroup::Dog,
+            Species::Cat => SpeciesGroup::Cat,
+            _ => SpeciesGroup::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanonicalActivity {
+    Sleeping,
+    Resting,
+    Walking,
+    Running,
+    Playing,
+    Eating,
+    Drinking,
+    Grooming,
+    Exploring,
+    Pacing,
+    Restlessness,
+    AttentionSeeking,
+    DoorWatching,
+    Hiding,
+    Scratching,
+    // Dog-specific
+    Barking,
+    Whining,
+    Chewing,
+    Digging,
+    // Cat-specific
+    Meowing,
+    Climbing,
+    Kneading,
+    LitterBox,
+}
+
+pub const ALL_ACTIVITIES: &[CanonicalActivity] = &[
+    CanonicalActivity::Sleeping,
+    CanonicalActivity::Resting,
+    CanonicalActivity::Walking,
+    CanonicalActivity::Running,
+    CanonicalActivity::Playing,
+    CanonicalActivity::Eating,
+    CanonicalActivity::Drinking,
+    CanonicalActivity::Grooming,
+    CanonicalActivity::Exploring,
+    CanonicalActivity::Pacing,
+    CanonicalActivity::Restlessness,
+    CanonicalActivity::AttentionSeeking,
+    CanonicalActivity::DoorWatching,
+    CanonicalActivity::Hiding,
+    CanonicalActivity::Scratching,
+    CanonicalActivity::Barking,
+    CanonicalActivity::Whining,
+    CanonicalActivity::Chewing,
+    CanonicalActivity::Digging,
+    CanonicalActivity::Meowing,
+    CanonicalActivity::Climbing,
+    CanonicalActivity::Kneading,
+    CanonicalActivity::LitterBox,
+];
+
+impl CanonicalActivity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CanonicalActivity::Sleeping => "sleeping",
+            CanonicalActivity::Resting => "resting",
+            CanonicalActivity::Walking => "walking",
+            CanonicalActivity::Running => "running",
+            CanonicalActivity::Playing => "playing",
+            CanonicalActivity::Eating => "eating",
+            CanonicalActivity::Drinking => "drinking",
+            CanonicalActivity::Grooming => "grooming",
+            CanonicalActivity::Exploring => "exploring",
+            CanonicalActivity::Pacing => "pacing",
+            CanonicalActivity::Restlessness => "restlessness",
+            CanonicalActivity::AttentionSeeking => "attention_seeking",
+            CanonicalActivity::DoorWatching => "door_watching",
+            CanonicalActivity::Hiding => "hiding",
+            CanonicalActivity::Scratching => "scratching",
+            CanonicalActivity::Barking => "barking",
+            CanonicalActivity::Whining => "whining",
+            CanonicalActivity::Chewing => "chewing",
+            CanonicalActivity::Digging => "digging",
+            CanonicalActivity::Meowing => "meowing",
+            CanonicalActivity::Climbing => "climbing",
+            CanonicalActivity::Kneading => "kneading",
+            CanonicalActivity::LitterBox => "litter_box",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CanonicalActivity::Sleeping => "Sleeping",
+            CanonicalActivity::Resting => "Resting",
+            CanonicalActivity::Walking => "Walking",
+            CanonicalActivity::Running => "Running",
+            CanonicalActivity::Playing => "Playing",
+            CanonicalActivity::Eating => "Eating",
+            CanonicalActivity::Drinking => "Drinking",
+            CanonicalActivity::Grooming => "Grooming",
+            CanonicalActivity::Exploring => "Exploring",
+            CanonicalActivity::Pacing => "Pacing",
+            CanonicalActivity::Restlessness => "Restless",
+            CanonicalActivity::AttentionSeeking => "Attention-seeking",
+            CanonicalActivity::DoorWatching => "Watching the door",
+            CanonicalActivity::Hiding => "Hiding",
+            CanonicalActivity::Scratching => "Scratching",
+            CanonicalActivity::Barking => "Barking",
+            CanonicalActivity::Whining => "Whining",
+            CanonicalActivity::Chewing => "Chewing",
+            CanonicalActivity::Digging => "Digging",
+            CanonicalActivity::Meowing => "Meowing",
+            CanonicalActivity::Climbing => "Climbing",
+            CanonicalActivity::Kneading => "Kneading",
+            CanonicalActivity::LitterBox => "Using the litter box",
+        }
+    }
+
+    /// Species the activity applies to; empty means every species.
+    pub fn species(&self) -> &'static [SpeciesGroup] {
+        match self {
+            CanonicalActivity::Barking
+            | CanonicalActivity::Whining
+            | CanonicalActivity::Chewing
+            | CanonicalActivity::Digging => &[SpeciesGroup::Dog],
+            CanonicalActivity::Meowing
+            | CanonicalActivity::Climbing
+            | CanonicalActivity::Kneading
+            | CanonicalActivity::LitterBox => &[SpeciesGroup::Cat],
+            _ => &[],
+        }
+    }
+
+    /// Normalized phrases (lowercase, single-spaced) that map onto this activity.
+    fn synonyms(&self) -> &'static [&'static str] {
+        match self {
+            CanonicalActivity::Sleeping => &[
+                "sleeping", "sleep", "sleeps", "asleep", "napping", "nap", "naps", "dozing",
+                "snoozing",
+            ],
+            CanonicalActivity::Resting => &[
+                "resting",
+                "rest",
+                "rests",
+                "lying down",
+                "lying",
+                "laying",
+                "lounging",
+                "relaxing",
+                "sitting",
+                "sits",
+            ],
+            CanonicalActivity::Walking => &[
+                "walking",
+                "walk",
+                "walks",
+                "strolling",
+                "stroll",
+                "strolls",
+                "wandering",
+                "wanders",
+                "moving around",
+            ],
+            CanonicalActivity::Running => {
+                &["running", "run", "runs", "sprinting", "zoomies", "dashing"]
+            }
+            CanonicalActivity::Playing => &[
+                "playing",
+                "play",
+                "plays",
+                "playing with toy",
+                "fetching",
+                "fetch",
+                "pouncing",
+            ],
+            CanonicalActivity::Eating => &["eating", "eat", "eats", "feeding", "chewing food"],
+            CanonicalActivity::Drinking => &[
+                "drinking",
+                "drink",
+                "drinks",
+                "drinking water",
+                "lapping water",
+            ],
+            CanonicalActivity::Grooming => &[
+                "grooming",
+                "groom",
+                "grooms",
+                "licking",
+                "self grooming",
+                "cleaning itself",
+            ],
+            CanonicalActivity::Exploring => &[
+                "exploring",
+                "explore",
+                "explores",
+                "sniffing",
+                "sniffs",
+                "investigating",
+            ],
+            CanonicalActivity::Pacing => &["pacing", "pace", "paces"],
+            CanonicalActivity::Restlessness => &[
+                "restlessness",
+                "restless",
+                "fidgeting",
+                "position changes",
+                "agitated",
+            ],
+            CanonicalActivity::AttentionSeeking => &[
+                "attention seeking",
+                "attention",
+                "seeking attention",
+                "begging",
+            ],
+            CanonicalActivity::DoorWatching => &[
+                "door watching",
+                "watching door",
+                "waiting at door",
+                "door proximity",
+                "staring at door",
+            ],
+            CanonicalActivity::Hiding => &["hiding", "hide", "hides", "cowering"],
+            CanonicalActivity::Scratching => {
+                &["scratching", "scratch", "scratches", "scratching furniture"]
+            }
+            CanonicalActivity::Barking => &[
+                "barking",
+                "bark",
+                "barks",
+                "howling",
+                "howl",
+                "vocalizing",
+                "vocalization",
+            ],
+            CanonicalActivity::Whining => &["whining", "whine", "whines", "whimpering"],
+            CanonicalActivity::Chewing => &["chewing", "chew", "chews", "gnawing"],
+            CanonicalActivity::Digging => &["digging", "dig", "digs"],
+            CanonicalActivity::Meowing => &[
+                "meowing",
+                "meow",
+                "meows",
+                "yowling",
+                "vocalizing",
+                "vocalization",
+            ],
+            CanonicalActivity::Climbing => {
+                &["climbing", "climb", "climbs", "jumping up", "perching"]
+            }
+            CanonicalActivity::Kneading => &["kneading", "knead", "kneads", "making biscuits"],
+            CanonicalActivity::LitterBox => &["litter box", "using litter box", "litter"],
+        }
+    }
+
+    pub fn applies_to(&self, species: SpeciesGroup) -> bool {
+        // Unknown species can do anything
+        species == SpeciesGroup::Other
+            || self.species().is_empty()
+            || self.species().contains(&species)
+    }
+}
+
+/// Lowercases, turns `-`/`_` into spaces and collapses whitespace.
+fn normalize_phrase(raw: &str) -> String {
+    raw.to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn lookup(phrase: &str, species: SpeciesGroup) -> Option<CanonicalActivity> {
+    ALL_ACTIVITIES
+        .iter()
+        .copied()
+        .filter(|a| a.applies_to(species))
+        .find(|a| a.synonyms().contains(&phrase))
+}
+
+/// Maps a raw activity name onto the taxonomy for a species. Tries the whole
+/// phrase first, then each word ("walking around" -> walking). Names that
+/// still don't match come back as `other:<normalized raw>`.
+pub fn normalize(raw: &str, species: &str) -> String {
+    let species = SpeciesGroup::from_species(species);
+    let phrase = normalize_phrase(raw);
+
+    lookup(&phrase, species)
+        .or_else(|| phrase.split(' ').find_map(|word| lookup(word, species)))
+        .map(|a| a.as_str().to_string())
+        .unwrap_or_else(|| format!("{}{}", OTHER_PREFIX, phrase))
+}
+
+/// Canonical name of one entry in an `activities` JSON array, normalizing
+/// entries stored before the taxonomy existed.
+pub fn canonical_of(entry: &serde_json::Value, species: &str) -> Option<String> {
+    if let Some(canonical) = entry["canonical_activity"].as_str() {
+        return Some(canonical.to_string());
+    }
+    entry["activity"]
+        .as_str()
+        .map(|raw| normalize(raw, species))
+}
+
+/// Adds `canonical_activity` to each entry of an `activities` JSON array,
+/// keeping the raw `activity` Gemini returned.
+pub fn annotate_activities(activities: &mut serde_json::Value, species: &str) {
+    if let Some(entries) = activities.as_array_mut() {
+        for entry in entries {
+            if let Some(canonical) = canonical_of(entry, species) {
+                entry["canonical_activity"] = serde_json::Value::String(canonical);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn synonyms_map_to_the_canonical_name() {
+        assert_eq!(normalize("Walking", "dog"), "walking");
+        assert_eq!(normalize("strolls", "dog"), "walking");
+        assert_eq!(normalize("  Napping ", "cat"), "sleeping");
+        assert_eq!(normalize("Lying-Down", "dog"), "resting");
+        assert_eq!(normalize("making_biscuits", "cat"), "kneading");
+    }
+
+    #[test]
+    fn unmatched_phrases_fall_back_to_a_word() {
+        assert_eq!(normalize("walking around the garden", "dog"), "walking");
+        assert_eq!(normalize("very restless tonight", "cat"), "restlessness");
+    }
+
+    #[test]
+    fn species_decides_ambiguous_sounds() {
+        assert_eq!(normalize("vocalizing", "dog"), "barking");
+        assert_eq!(normalize("vocalizing", "cat"), "meowing");
+        // Cats don't bark, so it isn't forced onto a cat activity
+        assert_eq!(normalize("barking", "cat"), "other:barking");
+    }
+
+    #[test]
+    fn unknown_activities_keep_their_normalized_name() {
+        assert_eq!(normalize("Tail   Chasing", "dog"), "other:tail chasing");
+    }
+
+    #[test]
+    fn no_phrase_maps_to_two_activities_for_one_species() {
+        for species in [SpeciesGroup::Dog, SpeciesGroup::Cat] {
+            let mut seen = std::collections::HashMap::new();
+            for activity in ALL_ACTIVITIES.iter().filter(|a| a.applies_to(species)) {
+                for phrase in activity.synonyms() {
+                    if let Some(other) = seen.insert(*phrase, *activity) {
+                        panic!("{:?} maps to {:?} and {:?}", phrase, other, activity);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn annotation_keeps_raw_names_and_stored_canonicals() {
+        let mut activities = json!([
+            {"activity": "Sniffing around"},
+            {"activity": "zooming", "canonical_activity": "running"},
+        ]);
+        annotate_activities(&mut activities, "dog");
+        assert_eq!(activities[0]["activity"], "Sniffing around");
+        assert_eq!(activities[0]["canonical_activity"], "exploring");
+        assert_eq!(activities[1]["canonical_activity"], "running");
+    }
+}
@@ -1,6 +1,7 @@
-use crate::entities::alerts;
+use crate::entities::{alerts, notification_log, pet_video, NotificationLog};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -53,42 +54,133 @@ pub enum AlertType {
     // Worker-detected alerts
     UnusualBehavior,
     ProcessingError,
+    /// A pet's camera has stopped sending clips on its monitoring schedule
+    UploadsOverdue,
     QueueDepthHigh,
     // Generic fallback
     Comfort,
 }
 
-impl ToString for AlertType {
-    fn to_string(&self) -> String {
-        match self {
-            AlertType::Pacing => "pacing".to_string(),
-            AlertType::Vocalization => "vocalization".to_string(),
-            AlertType::PositionChanges => "position_changes".to_string(),
-            AlertType::DoorProximity => "door_proximity".to_string(),
-            AlertType::Restlessness => "restlessness".to_string(),
-            AlertType::AttentionSeeking => "attention_seeking".to_string(),
-            AlertType::UnusualBehavior => "unusual_behavior".to_string(),
-            AlertType::ProcessingError => "processing_error".to_string(),
-            AlertType::QueueDepthHigh => "queue_depth_high".to_string(),
-            AlertType::Comfort => "comfort".to_string(),
+impl std::fmt::Display for AlertType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AlertType::Pacing => "pacing",
+            AlertType::Vocalization => "vocalization",
+            AlertType::PositionChanges => "position_changes",
+            AlertType::DoorProximity => "door_proximity",
+            AlertType::Restlessness => "restlessness",
+            AlertType::AttentionSeeking => "attention_seeking",
+            AlertType::UnusualBehavior => "unusual_behavior",
+            AlertType::ProcessingError => "processing_error",
+            AlertType::UploadsOverdue => "uploads_overdue",
+            AlertType::QueueDepthHigh => "queue_depth_high",
+            AlertType::Comfort => "comfort",
+        };
+        f.write_str(name)
+    }
+}
+
+impl AlertType {
+    /// Types that are raised for a pet and can be tuned per pet.
+    pub const PET_ALERT_TYPES: &'static [AlertType] = &[
+        AlertType::Pacing,
+        AlertType::Vocalization,
+        AlertType::PositionChanges,
+        AlertType::DoorProximity,
+        AlertType::Restlessness,
+        AlertType::AttentionSeeking,
+        AlertType::UnusualBehavior,
+        AlertType::ProcessingError,
+        AlertType::UploadsOverdue,
+        AlertType::Comfort,
+    ];
+
+    /// Problems with the recording pipeline rather than the pet's behavior.
+    /// They stay low severity and only ever get an email notice.
+    pub fn is_pipeline_notice(&self) -> bool {
+        matches!(self, AlertType::ProcessingError | AlertType::UploadsOverdue)
+    }
+
+    /// Alerts about the platform itself, handled by operators instead of pet owners.
+    pub fn is_operator_alert(&self) -> bool {
+        matches!(self, AlertType::QueueDepthHigh)
+    }
+}
+
+use crate::notifications::preferences;
+use crate::notifications::routing::{self, CRITICAL_CHANNELS};
+use crate::notifications::{record_notification, Channel, NotificationRecord, TwilioNotifier};
+
+/// notification_log kind for the follow-up sent when an alert resolves.
+const RECOVERY_KIND: &str = "recovery";
+const DEFAULT_RECOVERY_COOLDOWN_MINS: i64 = 60;
+
+/// Alerts that have been neither acknowledged by the owner nor resolved.
+pub fn open_alert_condition() -> Condition {
+    Condition::all()
+        .add(alerts::Column::UserAcknowledgedAt.is_null())
+        .add(
+            Condition::any().add(alerts::Column::Outcome.is_null()).add(
+                Condition::all()
+                    .add(alerts::Column::Outcome.not_like("Resolved%"))
+                    .add(alerts::Column::Outcome.not_like("Resolution%")),
+            ),
+        )
+}
+
+/// Severity for an alert seen `count` times in its fold window, given the
+/// level it arrived with and the level of the open alert it folds into.
+fn repeat_severity(
+    severity_level: &str,
+    existing_level: Option<&str>,
+    count: u64,
+    escalation_threshold: u64,
+    pipeline_notice: bool,
+) -> String {
+    if pipeline_notice {
+        // Pipeline failures stay informational no matter how often they repeat
+        return "low".to_string();
+    }
+    // A repeat never downgrades an alert that was already critical
+    if severity_level == "critical" || existing_level == Some("critical") {
+        return "critical".to_string();
+    }
+    if count >= escalation_threshold {
+        return "high".to_string();
+    }
+    severity_level.to_string()
+}
+
+/// Distinct channels an alert's owner was notified on, in first-sent order.
+fn notified_channels<'a>(sent: impl IntoIterator<Item = &'a str>) -> Vec<Channel> {
+    let mut channels: Vec<Channel> = Vec::new();
+    for channel in sent.into_iter().filter_map(Channel::parse) {
+        if !channels.contains(&channel) {
+            channels.push(channel);
         }
     }
+    channels
 }
 
-use crate::notifications::TwilioNotifier;
-use sea_orm::ActiveValue::NotSet;
+/// Whole minutes from an alert's first occurrence to `now`, at least one.
+fn episode_minutes(alert: &alerts::Model, now: chrono::NaiveDateTime) -> i64 {
+    let started_at = alert.first_seen_at.unwrap_or(alert.created_at);
+    (now - started_at).num_minutes().max(1)
+}
 
 // Intervention Logic
 pub struct ComfortLoop {
     db: DatabaseConnection,
+    redis_client: redis::Client,
     notifier: TwilioNotifier,
     gemini: crate::gemini::GeminiClient,
 }
 
 impl ComfortLoop {
-    pub async fn new(db: DatabaseConnection) -> Self {
+    pub async fn new(db: DatabaseConnection, redis_client: redis::Client) -> Self {
         Self {
             db,
+            redis_client,
             notifier: TwilioNotifier::new().await,
             gemini: crate::gemini::GeminiClient::new(),
         }
@@ -97,7 +189,10 @@ impl ComfortLoop {
     pub async fn process_alert(&self, payload: AlertPayload) {
         info!("Processing alert: {:?}", payload);
 
-        let alert_uuid = Uuid::new_v4();
+        if payload.alert_type.is_operator_alert() {
+            self.notify_operators(&payload).await;
+            return;
+        }
 
         // 1. Persist Initial Alert
         // Parse pet_id from string to i32 (as per schema)
@@ -124,42 +219,88 @@ impl ComfortLoop {
             })
             .unwrap_or_else(|| "low".to_string());
 
-        // 2a. Check recent alert count for escalation (Last 1 hour)
-        let one_hour_ago = chrono::Utc::now().naive_utc() - chrono::Duration::hours(1);
-        let recent_alert_count = match alerts::Entity::find()
+        // Owner-muted types produce no alert row or notification, except critical ones
+        if severity_level != "critical"
+            && crate::entities::alert_mute::is_muted(
+                &self.db,
+                db_pet_id,
+                &payload.alert_type.to_string(),
+            )
+            .await
+        {
+            info!(
+                "Alert type {} muted for pet {}; dropping alert",
+                payload.alert_type, db_pet_id
+            );
+            metrics::counter!("petpulse_alerts_muted_total", "source" => "agent").increment(1);
+            return;
+        }
+
+        let alert_settings = crate::entities::pet_alert_setting::find_for(
+            &self.db,
+            db_pet_id,
+            &payload.alert_type.to_string(),
+        )
+        .await;
+        let escalation_threshold = alert_settings
+            .as_ref()
+            .map(|s| s.escalation_threshold)
+            .unwrap_or(crate::entities::pet_alert_setting::DEFAULT_ESCALATION_THRESHOLD)
+            .max(2) as u64;
+
+        // 2a. Fold repeats into the open alert of the same type from the last hour
+        let now = chrono::Utc::now().naive_utc();
+        let one_hour_ago = now - chrono::Duration::hours(1);
+        let existing_alert = match alerts::Entity::find()
             .filter(alerts::Column::PetId.eq(db_pet_id))
             .filter(alerts::Column::AlertType.eq(payload.alert_type.to_string()))
-            .filter(alerts::Column::CreatedAt.gte(one_hour_ago))
-            .count(&self.db)
+            .filter(
+                Condition::any()
+                    .add(alerts::Column::LastSeenAt.gte(one_hour_ago))
+                    .add(alerts::Column::CreatedAt.gte(one_hour_ago)),
+            )
+            .filter(open_alert_condition())
+            .order_by_desc(alerts::Column::CreatedAt)
+            .one(&self.db)
             .await
         {
-            Ok(count) => count,
+            Ok(alert) => alert,
             Err(e) => {
-                error!("Failed to count recent alerts: {}", e);
-                0 // Default to 0 so current alert makes it 1
+                error!("Failed to look up open alert: {}", e);
+                None // Treat as a fresh alert
             }
         };
 
-        // Include current alert in count for logic
-        let current_alert_count = recent_alert_count + 1;
+        let alert_uuid = existing_alert
+            .as_ref()
+            .map(|a| a.id)
+            .unwrap_or_else(Uuid::new_v4);
+
+        // Occurrence count including the current one drives escalation
+        let current_alert_count = existing_alert
+            .as_ref()
+            .map(|a| a.occurrence_count as u64 + 1)
+            .unwrap_or(1);
 
         info!(
             "Alert count for pet_id={}, type={} in last hour: {} (including current)",
-            db_pet_id,
-            payload.alert_type.to_string(),
-            current_alert_count
+            db_pet_id, payload.alert_type, current_alert_count
         );
 
-        // 2b. Force Severity Escalation (5th+ alert = High)
-        let final_severity = if current_alert_count >= 5 && severity_level != "critical" {
+        // 2b. Force Severity Escalation (nth+ alert = High, 5th unless the pet's settings say otherwise)
+        let final_severity = repeat_severity(
+            &severity_level,
+            existing_alert.as_ref().map(|a| a.severity_level.as_str()),
+            current_alert_count,
+            escalation_threshold,
+            payload.alert_type.is_pipeline_notice(),
+        );
+        if final_severity == "high" && severity_level != "high" {
             info!(
                 "Escalating alert {} to HIGH severity due to repetition (count: {})",
                 alert_uuid, current_alert_count
             );
-            "high".to_string()
-        } else {
-            severity_level
-        };
+        }
 
         // 3. Persist Alert (now that we have final severity)
         let critical_indicators = payload.critical_indicators.clone().or_else(|| {
@@ -176,51 +317,136 @@ impl ComfortLoop {
             })
         });
 
-        let active_model = alerts::ActiveModel {
-            id: Set(alert_uuid),
-            pet_id: Set(db_pet_id),
-            alert_type: Set(payload.alert_type.to_string()),
-            severity: Set(match final_severity.as_str() {
-                "critical" => "critical".to_string(),
-                "high" => "high".to_string(),
-                _ => payload.severity.clone(),
-            }),
-            message: Set(payload.message.clone()),
-            severity_level: Set(final_severity.clone()),
-            critical_indicators: Set(critical_indicators
-                .clone()
-                .map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null))),
-            recommended_actions: Set(recommended_actions
-                .clone()
-                .map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null))),
-            payload: Set(serde_json::to_value(&payload).unwrap_or_default()),
-            created_at: Set(chrono::Utc::now().naive_utc()),
-            ..Default::default()
+        let severity = match final_severity.as_str() {
+            "critical" => "critical".to_string(),
+            "high" => "high".to_string(),
+            _ => payload.severity.clone(),
+        };
+        let critical_indicators_json = critical_indicators
+            .clone()
+            .map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null));
+        let recommended_actions_json = recommended_actions
+            .clone()
+            .map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null));
+
+        let video_uuid = payload
+            .video_id
+            .as_deref()
+            .and_then(|v| Uuid::parse_str(v).ok());
+
+        let persist_result = if let Some(existing) = &existing_alert {
+            // Update in place: same row, one more occurrence
+            let mut active: alerts::ActiveModel = existing.clone().into();
+            active.occurrence_count = Set(existing.occurrence_count + 1);
+            active.first_seen_at = Set(Some(existing.first_seen_at.unwrap_or(existing.created_at)));
+            active.last_seen_at = Set(Some(now));
+            active.severity = Set(severity);
+            active.severity_level = Set(final_severity.clone());
+            active.message = Set(payload.message.clone());
+            active.payload = Set(serde_json::to_value(&payload).unwrap_or_default());
+            if video_uuid.is_some() {
+                active.video_id = Set(video_uuid);
+            }
+            if critical_indicators_json.is_some() {
+                active.critical_indicators = Set(critical_indicators_json);
+            }
+            if recommended_actions_json.is_some() {
+                active.recommended_actions = Set(recommended_actions_json);
+            }
+            active.update(&self.db).await.map(|_| ())
+        } else {
+            let active_model = alerts::ActiveModel {
+                id: Set(alert_uuid),
+                pet_id: Set(db_pet_id),
+                alert_type: Set(payload.alert_type.to_string()),
+                severity: Set(severity),
+                message: Set(payload.message.clone()),
+                severity_level: Set(final_severity.clone()),
+                critical_indicators: Set(critical_indicators_json),
+                recommended_actions: Set(recommended_actions_json),
+                payload: Set(serde_json::to_value(&payload).unwrap_or_default()),
+                created_at: Set(now),
+                occurrence_count: Set(1),
+                first_seen_at: Set(Some(now)),
+                last_seen_at: Set(Some(now)),
+                video_id: Set(video_uuid),
+                ..Default::default()
+            };
+            alerts::Entity::insert(active_model)
+                .exec(&self.db)
+                .await
+                .map(|_| ())
         };
 
-        if let Err(e) = alerts::Entity::insert(active_model).exec(&self.db).await {
-            error!("Failed to insert alert into DB: {}", e);
+        if let Err(e) = persist_result {
+            error!("Failed to persist alert into DB: {}", e);
             return;
         }
 
         info!("Alert {} persisted to database", alert_uuid);
 
-        // 4. Decide Intervention (escalating based on count)
-        let intervention = self
-            .decide_intervention(&payload, current_alert_count, &final_severity)
-            .await;
+        // New alert changes the owner's dashboard; drop the cached copy
+        if let Ok(Some(pet)) = crate::entities::pet::Entity::find_by_id(db_pet_id)
+            .one(&self.db)
+            .await
+        {
+            crate::api::dashboard::invalidate_dashboard_cache(&self.redis_client, pet.user_id)
+                .await;
+        }
+
+        // Pipeline notices get an email notice only: no autonomous action, no SMS.
+        // Repeats within the hour were folded above and don't notify again.
+        if payload.alert_type.is_pipeline_notice() {
+            if existing_alert.is_none() && alert_settings.as_ref().is_none_or(|s| s.enabled) {
+                self.notify_pipeline_notice(&payload, alert_uuid, db_pet_id)
+                    .await;
+            }
+            return;
+        }
+
+        // 4. Decide Intervention (escalating based on count). Types the pet's
+        // settings turn down are kept for history only, unless critical.
+        let suppressed = final_severity != "critical"
+            && alert_settings
+                .as_ref()
+                .is_some_and(|s| !s.allows(&final_severity));
+        let intervention = if suppressed {
+            info!(
+                "Alert type {} at {} is turned down for pet {}; recording only",
+                payload.alert_type, final_severity, db_pet_id
+            );
+            metrics::counter!("petpulse_alerts_suppressed_settings_total").increment(1);
+            Intervention::LogOnly
+        } else {
+            self.decide_intervention(
+                &payload,
+                current_alert_count,
+                escalation_threshold,
+                &final_severity,
+            )
+            .await
+        };
 
         // 4. Execute Action
-        self.execute_action(&intervention, &payload).await;
+        let notified = self.execute_action(&intervention, &payload).await;
 
         // 5. Update DB with Action
-        let update_model = alerts::ActiveModel {
+        let mut update_model = alerts::ActiveModel {
             id: Set(alert_uuid),
             intervention_action: Set(Some(format!("{:?}", intervention))),
             intervention_time: Set(Some(chrono::Utc::now().naive_utc())),
             ..Default::default()
         };
 
+        // Notifications are logged so the all-clear knows what went out
+        let notified_channels = self.record_notified(alert_uuid, "alert", &notified).await;
+        if !notified_channels.is_empty() {
+            update_model.notification_sent = Set(true);
+            let channel_names: Vec<&str> = notified_channels.iter().map(|c| c.as_str()).collect();
+            update_model.notification_channels = Set(Some(serde_json::json!(channel_names)));
+            update_model.user_notified_at = Set(Some(chrono::Utc::now().naive_utc()));
+        }
+
         if let Err(e) = alerts::Entity::update(update_model).exec(&self.db).await {
             error!("Failed to update alert intervention: {}", e);
         }
@@ -256,7 +482,6 @@ impl ComfortLoop {
 
         // Check if new videos have been analyzed as normal (is_unusual = false)
         // We check if the latest video for this pet is NOT unusual
-        use crate::entities::pet_video;
         let latest_video = pet_video::Entity::find()
             .filter(pet_video::Column::PetId.eq(db_pet_id))
             .filter(pet_video::Column::Status.eq("PROCESSED"))
@@ -266,16 +491,22 @@ impl ComfortLoop {
             .ok()
             .flatten();
 
-        let outcome = if let Some(video) = latest_video {
-            if !video.is_unusual {
+        let (outcome, resolving_video) = match latest_video {
+            Some(video) if !video.is_unusual => {
                 info!("Latest video shows normal behavior - alert resolved");
-                "Resolution: Pet behavior returned to normal. Alert resolved."
-            } else {
+                (
+                    "Resolution: Pet behavior returned to normal. Alert resolved.",
+                    Some(video),
+                )
+            }
+            Some(_) => {
                 info!("Latest video still shows unusual behavior - alert persists");
-                "Alert persists: Unusual behavior continues. May trigger escalation on next alert."
+                (
+                    "Alert persists: Unusual behavior continues. May trigger escalation on next alert.",
+                    None,
+                )
             }
-        } else {
-            "No new video data available for resolution check."
+            None => ("No new video data available for resolution check.", None),
         };
 
         info!("{}", outcome);
@@ -288,9 +519,129 @@ impl ComfortLoop {
         };
         if let Err(e) = alerts::Entity::update(outcome_model).exec(&self.db).await {
             error!("Failed to update alert outcome: {}", e);
+            return;
+        }
+
+        if let Some(video) = resolving_video {
+            self.notify_recovery(alert_uuid, &video).await;
         }
     }
 
+    /// Sends the owner an "all clear" once an alert resolves on its own, on the
+    /// same channels its notifications went out on. Alerts that never reached
+    /// the owner resolve silently.
+    async fn notify_recovery(&self, alert_id: Uuid, resolving_video: &pet_video::Model) {
+        let sent = match NotificationLog::find()
+            .filter(notification_log::Column::AlertId.eq(alert_id))
+            .filter(notification_log::Column::Status.eq("sent"))
+            .filter(notification_log::Column::Kind.ne(RECOVERY_KIND))
+            .all(&self.db)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("Failed to load notifications for alert {}: {}", alert_id, e);
+                return;
+            }
+        };
+
+        let channels = notified_channels(sent.iter().map(|n| n.channel.as_str()));
+        if channels.is_empty() {
+            info!(
+                "Alert {} resolved without an owner notification; no all-clear sent",
+                alert_id
+            );
+            return;
+        }
+
+        let (alert, pet) = match alerts::Entity::find_by_id(alert_id)
+            .find_also_related(crate::entities::pet::Entity)
+            .one(&self.db)
+            .await
+        {
+            Ok(Some((alert, Some(pet)))) => (alert, pet),
+            _ => {
+                error!("Failed to load alert {} for all-clear", alert_id);
+                return;
+            }
+        };
+        let owner = match crate::entities::user::Entity::find_by_id(pet.user_id)
+            .one(&self.db)
+            .await
+        {
+            Ok(Some(u)) => u,
+            _ => {
+                error!("Owner not found for alert {}; skipping all-clear", alert_id);
+                return;
+            }
+        };
+
+        if !owner.recovery_notifications_enabled {
+            metrics::counter!("petpulse_recovery_notifications_total", "result" => "disabled")
+                .increment(1);
+            return;
+        }
+
+        // One all-clear per owner per cooldown window, however many alerts resolve
+        let now = chrono::Utc::now().naive_utc();
+        let cooldown_mins = std::env::var("RECOVERY_NOTIFICATION_COOLDOWN_MINS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_RECOVERY_COOLDOWN_MINS);
+        let recent = NotificationLog::find()
+            .filter(notification_log::Column::UserId.eq(owner.id))
+            .filter(notification_log::Column::Kind.eq(RECOVERY_KIND))
+            .filter(notification_log::Column::Status.eq("sent"))
+            .filter(
+                notification_log::Column::CreatedAt
+                    .gte(now - chrono::Duration::minutes(cooldown_mins)),
+            )
+            .count(&self.db)
+            .await
+            .unwrap_or(0);
+        if recent > 0 {
+            info!(
+                "All-clear for alert {} suppressed; owner {} got one in the last {} min",
+                alert_id, owner.id, cooldown_mins
+            );
+            metrics::counter!("petpulse_recovery_notifications_total", "result" => "rate_limited")
+                .increment(1);
+            return;
+        }
+
+        let episode_minutes = episode_minutes(&alert, now);
+        let video_link = format!("https://petpulse.dashboard/videos/{}", resolving_video.id);
+
+        for channel in routing::reachable_channels(&channels, owner.phone.as_deref()) {
+            let result = self
+                .notifier
+                .send_recovery_notice(
+                    channel,
+                    &owner.email,
+                    owner.phone.as_deref(),
+                    &pet.name,
+                    episode_minutes,
+                    &video_link,
+                )
+                .await;
+
+            record_notification(
+                &self.db,
+                NotificationRecord {
+                    alert_id: Some(alert_id),
+                    user_id: owner.id,
+                    channel,
+                    kind: RECOVERY_KIND,
+                    reminder_number: None,
+                    result: &result,
+                },
+            )
+            .await;
+        }
+
+        metrics::counter!("petpulse_recovery_notifications_total", "result" => "sent").increment(1);
+    }
+
     async fn handle_critical_alert(
         &self,
         payload: &AlertPayload,
@@ -300,27 +651,15 @@ impl ComfortLoop {
     ) {
         info!("🚨 HANDLING CRITICAL ALERT: {}", alert_uuid);
 
-        // Fetch owner email and name from DB
         let db_pet_id = payload.pet_id.parse::<i32>().unwrap_or(1);
-        let owner_info = match crate::entities::pet::Entity::find_by_id(db_pet_id)
-            .find_also_related(crate::entities::user::Entity)
-            .one(&self.db)
-            .await
-        {
-            Ok(Some((pet, Some(user)))) => Some((user.email, user.name, pet.name)),
-            _ => None,
-        };
-
-        let (owner_email, _owner_name, pet_name) = match owner_info {
-            Some((email, name, p_name)) => (email, name, p_name),
-            None => {
-                error!("CRITICAL: Failed to find owner info for pet_id={}. Cannot send critical alert.", db_pet_id);
-                return;
-            }
+        let Some((pet, recipients)) = self.alert_recipients(db_pet_id).await else {
+            error!(
+                "CRITICAL: Failed to find owner info for pet_id={}. Cannot send critical alert.",
+                db_pet_id
+            );
+            return;
         };
 
-        let owner_phone = std::env::var("OWNER_PHONE").unwrap_or("+15550000000".to_string());
-
         let video_link = if let Some(vid) = &payload.video_id {
             // In a real scenario, generate a signed URL here.
             // For now, use a direct link placeholder
@@ -329,12 +668,11 @@ impl ComfortLoop {
             "https://petpulse.dashboard".to_string()
         };
 
-        // Send Notifications
-        self.notifier
-            .notify_critical_alert(
-                &owner_email,
-                &owner_phone,
-                &pet_name,
+        // Send Notifications on the channels each recipient can be reached on and wants
+        let notified = self
+            .notify_recipients(
+                &recipients,
+                &pet.name,
                 "CRITICAL",
                 payload
                     .message
@@ -345,13 +683,25 @@ impl ComfortLoop {
                 &video_link,
             )
             .await;
+        let channels = self
+            .record_notified(alert_uuid, "critical", &notified)
+            .await;
+        if channels.is_empty() {
+            info!(
+                "Every recipient of pet {} has all channels disabled for critical alerts; alert {} recorded only",
+                db_pet_id, alert_uuid
+            );
+        }
 
         // Update Database Tracking
         let update_model = alerts::ActiveModel {
             id: Set(alert_uuid),
-            notification_sent: Set(true),
-            notification_channels: Set(Some(serde_json::json!(["email", "sms"]))),
-            user_notified_at: Set(Some(chrono::Utc::now().naive_utc())),
+            notification_sent: Set(!channels.is_empty()),
+            notification_channels: Set(Some(serde_json::json!(channels
+                .iter()
+                .map(|c| c.as_str())
+                .collect::<Vec<_>>()))),
+            user_notified_at: Set((!channels.is_empty()).then(|| chrono::Utc::now().naive_utc())),
             intervention_action: Set(Some("CRITICAL_NOTIFICATION_SENT".to_string())),
             outcome: Set(Some("Waiting for user acknowledgement".to_string())),
             ..Default::default()
@@ -362,10 +712,150 @@ impl ComfortLoop {
         }
     }
 
+    /// Sends an operator alert to OPS_ALERT_EMAIL and/or OPS_ALERT_WEBHOOK_URL.
+    /// These never touch the alerts table, which is keyed by pet.
+    async fn notify_operators(&self, payload: &AlertPayload) {
+        let resolved = payload.state.as_deref() == Some("ok");
+        let title = payload
+            .title
+            .clone()
+            .unwrap_or_else(|| payload.alert_type.to_string());
+        let message = payload.message.clone().unwrap_or_default();
+        let mut delivered = false;
+
+        if let Ok(ops_email) = std::env::var("OPS_ALERT_EMAIL") {
+            let subject = if resolved {
+                format!("[PetPulse ops] RESOLVED: {}", title)
+            } else {
+                format!("[PetPulse ops] {}", title)
+            };
+            let body = crate::notifications::NotificationTemplates::operator_alert_email(
+                &title,
+                &message,
+                resolved,
+                payload.context.as_ref(),
+            );
+            match self.notifier.send_email(&ops_email, &subject, &body).await {
+                Ok(()) => delivered = true,
+                Err(e) => error!("Failed to email operator alert: {}", e),
+            }
+        }
+
+        if let Ok(webhook_url) = std::env::var("OPS_ALERT_WEBHOOK_URL") {
+            match reqwest::Client::new()
+                .post(&webhook_url)
+                .json(payload)
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => delivered = true,
+                Ok(resp) => error!("Operator webhook returned {}", resp.status()),
+                Err(e) => error!("Failed to call operator webhook: {}", e),
+            }
+        }
+
+        if !delivered {
+            error!(
+                "Operator alert not delivered (set OPS_ALERT_EMAIL or OPS_ALERT_WEBHOOK_URL): {} - {}",
+                title, message
+            );
+        }
+        metrics::counter!(
+            "petpulse_operator_alerts_total",
+            "type" => payload.alert_type.to_string(),
+            "state" => if resolved { "resolved" } else { "firing" }
+        )
+        .increment(1);
+    }
+
+    /// Emails the owner about a processing failure or overdue uploads.
+    async fn notify_pipeline_notice(&self, payload: &AlertPayload, alert_uuid: Uuid, pet_id: i32) {
+        let (owner_id, owner_email, pet_name) =
+            match crate::entities::pet::Entity::find_by_id(pet_id)
+                .find_also_related(crate::entities::user::Entity)
+                .one(&self.db)
+                .await
+            {
+                Ok(Some((pet, Some(user)))) => (user.id, user.email, pet.name),
+                _ => {
+                    error!(
+                        "Failed to find owner for pet_id={}; skipping processing error notice",
+                        pet_id
+                    );
+                    return;
+                }
+            };
+
+        let stage = payload
+            .context
+            .as_ref()
+            .and_then(|c| c.get("stage"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("processing");
+        let video_link = payload
+            .video_id
+            .as_ref()
+            .map(|v| format!("https://petpulse.dashboard/videos/{}", v))
+            .unwrap_or_else(|| "https://petpulse.dashboard".to_string());
+
+        let (subject, body) = if payload.alert_type == AlertType::UploadsOverdue {
+            let minutes = payload.metric_value.unwrap_or_default() as i64;
+            (
+                format!("{}'s camera has stopped sending clips", pet_name),
+                crate::notifications::NotificationTemplates::uploads_overdue_email(
+                    &pet_name, minutes,
+                ),
+            )
+        } else {
+            (
+                format!("PetPulse couldn't analyze a video of {}", pet_name),
+                crate::notifications::NotificationTemplates::processing_error_email(
+                    &pet_name,
+                    stage,
+                    &video_link,
+                ),
+            )
+        };
+        let result = self
+            .notifier
+            .send_email(&owner_email, &subject, &body)
+            .await;
+
+        record_notification(
+            &self.db,
+            NotificationRecord {
+                alert_id: Some(alert_uuid),
+                user_id: owner_id,
+                channel: Channel::Email,
+                kind: if payload.alert_type == AlertType::UploadsOverdue {
+                    "uploads_overdue"
+                } else {
+                    "processing_error"
+                },
+                reminder_number: None,
+                result: &result,
+            },
+        )
+        .await;
+
+        let update_model = alerts::ActiveModel {
+            id: Set(alert_uuid),
+            notification_sent: Set(result.is_ok()),
+            notification_channels: Set(Some(serde_json::json!(["email"]))),
+            user_notified_at: Set(Some(chrono::Utc::now().naive_utc())),
+            intervention_action: Set(Some("LogOnly".to_string())),
+            ..Default::default()
+        };
+        if let Err(e) = alerts::Entity::update(update_model).exec(&self.db).await {
+            error!("Failed to update processing error alert: {}", e);
+        }
+    }
+
     async fn decide_intervention(
         &self,
         payload: &AlertPayload,
         alert_count: u64,
+        escalation_threshold: u64,
         severity_level: &str,
     ) -> Intervention {
         // If critical, immediately escalate to Notification (handled in main loop branching, but good for safety)
@@ -378,9 +868,21 @@ impl ComfortLoop {
             "Deciding intervention for alert_type={:?}, alert_count={}",
             payload.alert_type, alert_count
         );
+        // The ladder is anchored to the escalation threshold (5 by default):
+        // two alerts before it the owner's voice plays, one before it the owner
+        // is also notified, and from it on every alert notifies.
         match alert_count {
-            0..=2 => match payload.alert_type {
-                // 1st and 2nd alert
+            n if n >= escalation_threshold => {
+                // Threshold reached (5+ alerts by default) - High Severity (Controlled by final_severity logic)
+                // Just notify, but strict.
+                info!(
+                    "Alert escalation: {}+ alerts (High Severity) - Notifying user",
+                    escalation_threshold
+                );
+                Intervention::NotifyUser(NotificationLevel::Standard)
+            }
+            n if n + 2 < escalation_threshold => match payload.alert_type {
+                // Early alerts (1st and 2nd by default)
                 AlertType::Pacing | AlertType::Restlessness => {
                     Intervention::AdjustEnvironment(EnvironmentAction::DimLights)
                 }
@@ -390,15 +892,15 @@ impl ComfortLoop {
                 AlertType::UnusualBehavior => Intervention::PlayCalmingMusic,
                 _ => Intervention::LogOnly,
             },
-            3 => match payload.alert_type {
-                // 3rd alert
+            n if n + 2 == escalation_threshold => match payload.alert_type {
+                // Two below the threshold (3rd by default)
                 AlertType::Pacing | AlertType::Restlessness => Intervention::PlayOwnerVoice,
                 AlertType::Vocalization => Intervention::DispenseTreat,
                 _ => Intervention::PlayOwnerVoice,
             },
-            4 => {
-                // 4th alert - Notify User AND Last Autonomous Action
-                info!("Alert escalation: 4th alert - Notifying user and taking final autonomous action");
+            _ => {
+                // One below the threshold (4th by default) - Notify User AND Last Autonomous Action
+                info!("Alert escalation: last alert before the threshold - Notifying user and taking final autonomous action");
                 // We return a composite or just notify for now as per "user preference" request implies notification is key.
                 // But user asked for "autonomous agent one last time".
                 // Let's assume we do PlayOwnerVoice + Notify.
@@ -409,12 +911,6 @@ impl ComfortLoop {
 
                 Intervention::NotifyUser(NotificationLevel::Standard)
             }
-            _ => {
-                // 5+ alerts - High Severity (Controlled by final_severity logic)
-                // Just notify, but strict.
-                info!("Alert escalation: 5+ alerts (High Severity) - Notifying user");
-                Intervention::NotifyUser(NotificationLevel::Standard)
-            }
         }
     }
 
@@ -515,9 +1011,117 @@ impl ComfortLoop {
         );
     }
 
-    async fn execute_action(&self, action: &Intervention, payload: &AlertPayload) {
+    /// The pet and everyone who hears about its alerts: the owner first, then
+    /// accepted caretakers.
+    async fn alert_recipients(
+        &self,
+        pet_id: i32,
+    ) -> Option<(
+        crate::entities::pet::Model,
+        Vec<crate::entities::user::Model>,
+    )> {
+        let (pet, owner) = match crate::entities::pet::Entity::find_by_id(pet_id)
+            .find_also_related(crate::entities::user::Entity)
+            .one(&self.db)
+            .await
+        {
+            Ok(Some((pet, Some(owner)))) => (pet, owner),
+            _ => return None,
+        };
+
+        let mut recipients = vec![owner];
+        match crate::entities::pet_caretaker::accepted_users(&self.db, pet_id).await {
+            Ok(caretakers) => recipients.extend(caretakers),
+            Err(e) => error!("Failed to load caretakers for pet {}: {}", pet_id, e),
+        }
+        Some((pet, recipients))
+    }
+
+    /// Sends the alert to each recipient on the channels they can be reached
+    /// on and haven't disabled for this severity. Returns who got what.
+    #[allow(clippy::too_many_arguments)]
+    async fn notify_recipients(
+        &self,
+        recipients: &[crate::entities::user::Model],
+        pet_name: &str,
+        severity: &str,
+        message: &str,
+        indicators: &[String],
+        actions: &[String],
+        video_link: &str,
+    ) -> Vec<(i32, Vec<Channel>)> {
+        let mut notified = Vec::new();
+        for user in recipients {
+            let channels = routing::reachable_channels(CRITICAL_CHANNELS, user.phone.as_deref());
+            let channels = preferences::preferred_channels(
+                &self.db,
+                user.id,
+                &channels,
+                &severity.to_lowercase(),
+            )
+            .await;
+            if channels.is_empty() {
+                continue;
+            }
+
+            self.notifier
+                .notify_critical_alert(
+                    &channels,
+                    &user.email,
+                    user.phone.as_deref(),
+                    pet_name,
+                    severity,
+                    message,
+                    indicators,
+                    actions,
+                    video_link,
+                )
+                .await;
+            notified.push((user.id, channels));
+        }
+        notified
+    }
+
+    /// Logs each recipient's notifications and returns every channel used.
+    async fn record_notified(
+        &self,
+        alert_id: Uuid,
+        kind: &'static str,
+        notified: &[(i32, Vec<Channel>)],
+    ) -> Vec<Channel> {
+        let mut used: Vec<Channel> = Vec::new();
+        for (user_id, channels) in notified {
+            for channel in channels {
+                record_notification(
+                    &self.db,
+                    NotificationRecord {
+                        alert_id: Some(alert_id),
+                        user_id: *user_id,
+                        channel: *channel,
+                        kind,
+                        reminder_number: None,
+                        result: &Ok(()),
+                    },
+                )
+                .await;
+                if !used.contains(channel) {
+                    used.push(*channel);
+                }
+            }
+        }
+        used
+    }
+
+    /// Carries out the intervention. Returns the channels each recipient was
+    /// notified on, empty unless the intervention is `NotifyUser`.
+    async fn execute_action(
+        &self,
+        action: &Intervention,
+        payload: &AlertPayload,
+    ) -> Vec<(i32, Vec<Channel>)> {
         info!("Executing intervention: {:?}", action);
         // TODO: Call Smart Home API / IoT Hub
+        let mut notified = Vec::new();
         match action {
             Intervention::PlayCalmingMusic => info!("🎶 Action: Playing calming music playlist"),
             Intervention::PlayOwnerVoice => info!("🗣️ Action: Playing owner voice note"),
@@ -528,31 +1132,15 @@ impl ComfortLoop {
             Intervention::NotifyUser(level) => {
                 info!("📱 Action: Notifying user (Level: {:?})", level);
 
-                // Fetch owner email and pet name from DB
                 let db_pet_id = payload.pet_id.parse::<i32>().unwrap_or(1);
-                let info = match crate::entities::pet::Entity::find_by_id(db_pet_id)
-                    .find_also_related(crate::entities::user::Entity)
-                    .one(&self.db)
-                    .await
-                {
-                    Ok(Some((pet, Some(user)))) => Some((user.email, user.name, pet.name)),
-                    _ => None,
-                };
-
-                let (owner_email, _owner_name, pet_name) = match info {
-                    Some((email, name, p_name)) => (email, name, p_name),
-                    None => {
-                        error!(
-                            "Failed to find owner info for pet_id={}. Cannot notify user.",
-                            db_pet_id
-                        );
-                        return;
-                    }
+                let Some((pet, recipients)) = self.alert_recipients(db_pet_id).await else {
+                    error!(
+                        "Failed to find owner info for pet_id={}. Cannot notify user.",
+                        db_pet_id
+                    );
+                    return Vec::new();
                 };
 
-                let owner_phone =
-                    std::env::var("OWNER_PHONE").unwrap_or("+15550000000".to_string());
-
                 let severity_str = match level {
                     NotificationLevel::Critical => "CRITICAL",
                     NotificationLevel::Standard => "HIGH",
@@ -564,11 +1152,10 @@ impl ComfortLoop {
                     .map(|v| format!("https://petpulse.dashboard/videos/{}", v))
                     .unwrap_or_else(|| "https://petpulse.dashboard".to_string());
 
-                self.notifier
-                    .notify_critical_alert(
-                        &owner_email,
-                        &owner_phone,
-                        &pet_name,
+                notified = self
+                    .notify_recipients(
+                        &recipients,
+                        &pet.name,
                         severity_str,
                         payload.message.as_deref().unwrap_or("Alert triggered"),
                         &[],
@@ -579,6 +1166,7 @@ impl ComfortLoop {
             }
             Intervention::LogOnly => info!("📝 Action: Logging alert only"),
         }
+        notified
     }
 }
 
@@ -603,3 +1191,123 @@ pub enum NotificationLevel {
     Standard,
     Critical,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QuerySelect, QueryTrait};
+
+    #[test]
+    fn repeats_escalate_to_high_at_the_threshold() {
+        assert_eq!(repeat_severity("medium", None, 1, 5, false), "medium");
+        assert_eq!(
+            repeat_severity("medium", Some("medium"), 4, 5, false),
+            "medium"
+        );
+        assert_eq!(
+            repeat_severity("medium", Some("medium"), 5, 5, false),
+            "high"
+        );
+        assert_eq!(repeat_severity("low", Some("low"), 9, 5, false), "high");
+    }
+
+    #[test]
+    fn repeats_never_downgrade_a_critical_alert() {
+        assert_eq!(
+            repeat_severity("low", Some("critical"), 2, 5, false),
+            "critical"
+        );
+        assert_eq!(
+            repeat_severity("low", Some("critical"), 7, 5, false),
+            "critical"
+        );
+        assert_eq!(repeat_severity("critical", None, 7, 5, false), "critical");
+    }
+
+    #[test]
+    fn processing_errors_stay_low_however_often_they_repeat() {
+        assert!(AlertType::ProcessingError.is_pipeline_notice());
+        assert_eq!(AlertType::ProcessingError.to_string(), "processing_error");
+        assert_eq!(
+            repeat_severity("medium", Some("critical"), 9, 5, true),
+            "low"
+        );
+    }
+
+    #[test]
+    fn processing_error_email_names_the_stage_and_video() {
+        let body = crate::notifications::NotificationTemplates::processing_error_email(
+            "Biscuit",
+            "analysis",
+            "https://petpulse.dashboard/videos/abc",
+        );
+        assert!(body.contains("recent video of Biscuit"));
+        assert!(body.contains("The analysis step failed"));
+        assert!(body.contains(r#"href="https://petpulse.dashboard/videos/abc""#));
+    }
+
+    #[test]
+    fn all_clear_goes_to_each_notified_channel_once() {
+        assert_eq!(
+            notified_channels(["sms", "email", "sms", "push"]),
+            [Channel::Sms, Channel::Email]
+        );
+        assert!(notified_channels(["push"]).is_empty());
+        for channel in crate::notifications::routing::ALL_CHANNELS {
+            assert_eq!(Channel::parse(channel.as_str()), Some(*channel));
+        }
+    }
+
+    #[test]
+    fn episode_runs_from_the_first_occurrence() {
+        let created_at = chrono::NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut alert: alerts::Model = serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "pet_id": 1,
+            "alert_type": "pacing",
+            "severity": "high",
+            "payload": {},
+            "created_at": created_at,
+            "severity_level": "high",
+            "notification_sent": true,
+            "occurrence_count": 3,
+            "reminder_count": 0,
+        }))
+        .unwrap();
+        let now = created_at + chrono::Duration::minutes(40);
+        assert_eq!(episode_minutes(&alert, now), 40);
+
+        alert.first_seen_at = Some(created_at - chrono::Duration::minutes(20));
+        assert_eq!(episode_minutes(&alert, now), 60);
+        // Resolved within the same minute still reads as one
+        assert_eq!(episode_minutes(&alert, alert.first_seen_at.unwrap()), 1);
+    }
+
+    #[test]
+    fn recovery_sms_names_the_pet_and_duration() {
+        let sms = crate::notifications::NotificationTemplates::recovery_sms(
+            "Biscuit",
+            25,
+            "https://petpulse.dashboard/videos/abc",
+        );
+        assert!(sms.contains("Biscuit is back to normal after about 25 min"));
+        assert!(sms.ends_with("View: https://petpulse.dashboard/videos/abc"));
+    }
+
+    #[test]
+    fn open_alerts_exclude_acknowledged_and_resolved() {
+        let sql = alerts::Entity::find()
+            .select_only()
+            .column(alerts::Column::Id)
+            .filter(open_alert_condition())
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""user_acknowledged_at" IS NULL"#), "{sql}");
+        assert!(sql.contains(r#""outcome" IS NULL"#), "{sql}");
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolved%'"#), "{sql}");
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolution%'"#), "{sql}");
+    }
+}
@@ -1 +1,2 @@
 pub mod comfort_loop;
+pub mod reminders;
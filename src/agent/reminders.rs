@@ -0,0 +1,224 @@
+use crate::agent::comfort_loop::open_alert_condition;
+use crate::entities::{alerts, pet, user};
+use crate::notifications::{
+    preferences, record_notification, routing, NotificationRecord, TwilioNotifier,
+};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use tracing::{error, info};
+
+const DEFAULT_REMINDER_INTERVALS_MINS: &[i64] = &[10, 30];
+const DEFAULT_MAX_REMINDERS: i32 = 3;
+const REMINDER_POLL_SECS: u64 = 60;
+
+/// Re-notifies owners about critical alerts they haven't acknowledged.
+/// Reminders go to the owner only; emergency contacts are handled separately
+/// through quick actions.
+pub struct ReminderScheduler {
+    db: DatabaseConnection,
+    notifier: TwilioNotifier,
+    /// Minutes after the initial notification at which each reminder is due.
+    /// Reminders past the end of the list repeat at the last interval.
+    intervals_mins: Vec<i64>,
+    max_reminders: i32,
+}
+
+impl ReminderScheduler {
+    pub async fn new(db: DatabaseConnection) -> Self {
+        let intervals_mins = std::env::var("ALERT_REMINDER_INTERVALS_MINS")
+            .ok()
+            .map(|v| {
+                v.split(',')
+                    .filter_map(|m| m.trim().parse::<i64>().ok())
+                    .filter(|m| *m > 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_REMINDER_INTERVALS_MINS.to_vec());
+
+        let max_reminders = std::env::var("ALERT_REMINDER_MAX")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_REMINDERS);
+
+        Self {
+            db,
+            notifier: TwilioNotifier::new().await,
+            intervals_mins,
+            max_reminders,
+        }
+    }
+
+    /// Minutes after the initial notification when reminder `n` (1-based) is due.
+    fn due_after_mins(&self, reminder_number: i32) -> i64 {
+        let idx = (reminder_number - 1).max(0) as usize;
+        match self.intervals_mins.get(idx) {
+            Some(m) => *m,
+            None => {
+                let last = *self.intervals_mins.last().unwrap_or(&10);
+                let extra = (idx + 1 - self.intervals_mins.len()) as i64;
+                last + last * extra
+            }
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Reminder scheduler started (intervals={:?} min, max={})",
+            self.intervals_mins, self.max_reminders
+        );
+        loop {
+            self.send_due_reminders().await;
+            tokio::time::sleep(tokio::time::Duration::from_secs(REMINDER_POLL_SECS)).await;
+        }
+    }
+
+    fn pending_condition(&self) -> Condition {
+        Condition::all()
+            .add(alerts::Column::SeverityLevel.eq("critical"))
+            .add(alerts::Column::NotificationSent.eq(true))
+            .add(alerts::Column::UserNotifiedAt.is_not_null())
+            .add(alerts::Column::ReminderCount.lt(self.max_reminders))
+            .add(open_alert_condition())
+    }
+
+    async fn send_due_reminders(&self) {
+        let candidates = match alerts::Entity::find()
+            .filter(self.pending_condition())
+            .find_also_related(pet::Entity)
+            .all(&self.db)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("Failed to load alerts awaiting acknowledgement: {}", e);
+                return;
+            }
+        };
+
+        let now = chrono::Utc::now().naive_utc();
+
+        for (alert, pet) in candidates {
+            let Some(pet) = pet else { continue };
+            let Some(notified_at) = alert.user_notified_at else {
+                continue;
+            };
+
+            let reminder_number = alert.reminder_count + 1;
+            let due_at =
+                notified_at + chrono::Duration::minutes(self.due_after_mins(reminder_number));
+            if now < due_at {
+                continue;
+            }
+
+            self.send_reminder(alert, pet, reminder_number, now - notified_at)
+                .await;
+        }
+    }
+
+    async fn send_reminder(
+        &self,
+        alert: alerts::Model,
+        pet: pet::Model,
+        reminder_number: i32,
+        open_for: chrono::Duration,
+    ) {
+        // Claim the reminder first so an acknowledgement that landed since the
+        // scan (or a concurrent scheduler) stops it from going out
+        let claimed = alerts::Entity::update_many()
+            .col_expr(
+                alerts::Column::ReminderCount,
+                sea_orm::sea_query::Expr::value(reminder_number),
+            )
+            .col_expr(
+                alerts::Column::LastReminderAt,
+                sea_orm::sea_query::Expr::value(chrono::Utc::now().naive_utc()),
+            )
+            .filter(alerts::Column::Id.eq(alert.id))
+            .filter(alerts::Column::ReminderCount.eq(alert.reminder_count))
+            .filter(open_alert_condition())
+            .exec(&self.db)
+            .await;
+
+        match claimed {
+            Ok(res) if res.rows_affected == 1 => {}
+            Ok(_) => {
+                info!(
+                    "Alert {} acknowledged or already reminded; skipping",
+                    alert.id
+                );
+                return;
+            }
+            Err(e) => {
+                error!("Failed to claim reminder for alert {}: {}", alert.id, e);
+                return;
+            }
+        }
+
+        let owner = match user::Entity::find_by_id(pet.user_id).one(&self.db).await {
+            Ok(Some(u)) => u,
+            _ => {
+                error!(
+                    "Owner not found for alert {}; cannot send reminder",
+                    alert.id
+                );
+                return;
+            }
+        };
+
+        let video_link = format!("https://petpulse.dashboard/alerts/{}", alert.id);
+        let description = alert
+            .message
+            .clone()
+            .unwrap_or_else(|| "Critical health indicator detected".to_string());
+
+        info!(
+            "⏰ Sending reminder #{} for alert {} (open {} min)",
+            reminder_number,
+            alert.id,
+            open_for.num_minutes()
+        );
+
+        let channels = routing::reachable_channels(
+            routing::reminder_channels(reminder_number),
+            owner.phone.as_deref(),
+        );
+        let channels =
+            preferences::preferred_channels(&self.db, owner.id, &channels, "critical").await;
+        if channels.is_empty() {
+            info!(
+                "Owner {} can't be reached on reminder #{}'s channels for alert {}; skipping",
+                owner.id, reminder_number, alert.id
+            );
+        }
+        for channel in &channels {
+            let result = self
+                .notifier
+                .send_alert_reminder(
+                    *channel,
+                    &owner.email,
+                    owner.phone.as_deref(),
+                    &pet.name,
+                    reminder_number,
+                    open_for.num_minutes(),
+                    &description,
+                    &video_link,
+                )
+                .await;
+
+            record_notification(
+                &self.db,
+                NotificationRecord {
+                    alert_id: Some(alert.id),
+                    user_id: owner.id,
+                    channel: *channel,
+                    kind: "reminder",
+                    reminder_number: Some(reminder_number),
+                    result: &result,
+                },
+            )
+            .await;
+        }
+
+        metrics::counter!("petpulse_alert_reminders_sent_total").increment(1);
+    }
+}
@@ -0,0 +1,205 @@
+//! Alert webhook delivery to the agent service. Each alert is tried a few
+//! times with backoff; if the agent still can't be reached (it's restarting,
+//! say) the payload is parked on the `alert_outbox` list and a background task
+//! sends it once the agent answers again, so an alert is late rather than lost.
+
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+pub const ALERT_OUTBOX: &str = "alert_outbox";
+const ATTEMPTS: u32 = 3;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+const DRAIN_INTERVAL_SECS: u64 = 30;
+/// Entries sent per drain pass, so a long outbox doesn't hog the task.
+const DRAIN_BATCH: usize = 100;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OutboxEntry {
+    url: String,
+    payload: Value,
+    queued_at: DateTime<Utc>,
+}
+
+enum Failure {
+    /// The agent answered and refused the alert; sending it again won't help
+    Rejected(String),
+    /// The agent couldn't be reached or had a server error
+    Unavailable(String),
+}
+
+fn record(result: &'static str) {
+    metrics::counter!("petpulse_alert_webhook_total", "result" => result).increment(1);
+}
+
+async fn post(url: &str, payload: &Value) -> Result<(), Failure> {
+    match CLIENT.post(url).json(payload).send().await {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) => {
+            let status = resp.status();
+            let body = resp
+                .text()
+                .await
+                .unwrap_or_else(|_| "<unable to read response>".to_string());
+            let error = format!("{} - {}", status, body);
+            if status.is_server_error() {
+                Err(Failure::Unavailable(error))
+            } else {
+                Err(Failure::Rejected(error))
+            }
+        }
+        Err(e) => Err(Failure::Unavailable(e.to_string())),
+    }
+}
+
+/// POSTs `payload` to the agent at `url`, backing off 1s then 2s between
+/// attempts. Alerts the agent can't take right now go to the outbox.
+pub async fn deliver<T: Serialize>(conn: &mut MultiplexedConnection, url: &str, payload: &T) {
+    let payload = match serde_json::to_value(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::error!("Failed to serialize alert webhook payload: {}", e);
+            record("dropped");
+            return;
+        }
+    };
+
+    let mut last_error = String::new();
+    for attempt in 0..ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_secs(1 << (attempt - 1))).await;
+        }
+        match post(url, &payload).await {
+            Ok(()) => {
+                tracing::info!("Successfully sent alert webhook to agent service");
+                record("delivered");
+                return;
+            }
+            Err(Failure::Rejected(e)) => {
+                tracing::error!("Agent service rejected alert webhook: {}", e);
+                record("rejected");
+                return;
+            }
+            Err(Failure::Unavailable(e)) => {
+                tracing::warn!(
+                    "Alert webhook to agent service failed (attempt {}): {}",
+                    attempt + 1,
+                    e
+                );
+                last_error = e;
+            }
+        }
+    }
+
+    let entry = OutboxEntry {
+        url: url.to_string(),
+        payload,
+        queued_at: Utc::now(),
+    };
+    match push(conn, &entry).await {
+        Ok(()) => {
+            tracing::warn!(
+                "Agent service unreachable after {} attempts ({}); alert parked on {}",
+                ATTEMPTS,
+                last_error,
+                ALERT_OUTBOX
+            );
+            record("outboxed");
+        }
+        Err(e) => {
+            tracing::error!(
+                "Agent service unreachable and alert could not be parked on {}: {}",
+                ALERT_OUTBOX,
+                e
+            );
+            record("dropped");
+        }
+    }
+}
+
+async fn push(conn: &mut MultiplexedConnection, entry: &OutboxEntry) -> redis::RedisResult<()> {
+    let raw = serde_json::to_string(entry).map_err(|e| {
+        redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "unserializable outbox entry",
+            e.to_string(),
+        ))
+    })?;
+    conn.rpush(ALERT_OUTBOX, raw).await
+}
+
+pub async fn len(conn: &mut MultiplexedConnection) -> redis::RedisResult<u64> {
+    conn.llen(ALERT_OUTBOX).await
+}
+
+/// Sends parked alerts oldest first, stopping at the first one the agent
+/// still can't take (it goes back on the front). Returns how many were sent.
+async fn drain(conn: &mut MultiplexedConnection) -> redis::RedisResult<usize> {
+    let mut sent = 0;
+    for _ in 0..DRAIN_BATCH {
+        let Some(raw): Option<String> = conn.lpop(ALERT_OUTBOX, None).await? else {
+            break;
+        };
+        let Ok(entry) = serde_json::from_str::<OutboxEntry>(&raw) else {
+            tracing::error!("Dropping unreadable {} entry: {}", ALERT_OUTBOX, raw);
+            record("dropped");
+            continue;
+        };
+        match post(&entry.url, &entry.payload).await {
+            Ok(()) => {
+                record("outbox_delivered");
+                sent += 1;
+            }
+            Err(Failure::Rejected(e)) => {
+                tracing::error!(
+                    "Agent service rejected alert parked since {}: {}",
+                    entry.queued_at,
+                    e
+                );
+                record("rejected");
+            }
+            Err(Failure::Unavailable(e)) => {
+                let _: () = conn.lpush(ALERT_OUTBOX, raw).await?;
+                tracing::debug!("Agent service still unreachable: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Retries parked alerts every `DRAIN_INTERVAL_SECS`.
+pub async fn start_drain(redis_client: redis::Client) {
+    tokio::spawn(async move {
+        tracing::info!("Alert outbox drain started");
+        loop {
+            tokio::time::sleep(Duration::from_secs(DRAIN_INTERVAL_SECS)).await;
+            let mut conn = match redis_client.get_multiplexed_async_connection().await {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("Alert outbox: Failed to get redis conn: {}", e);
+                    continue;
+                }
+            };
+            match drain(&mut conn).await {
+                Ok(0) => {}
+                Ok(sent) => tracing::info!("Sent {} parked alerts from {}", sent, ALERT_OUTBOX),
+                Err(e) => tracing::error!("Failed to drain {}: {}", ALERT_OUTBOX, e),
+            }
+            if let Ok(depth) = len(&mut conn).await {
+                metrics::gauge!("petpulse_alert_outbox_depth").set(depth as f64);
+            }
+        }
+    });
+}
@@ -0,0 +1,495 @@
+use super::client_ip::ClientIp;
+use super::critical_alerts::{AlertListResponse, AlertResponse};
+use super::error::ApiError;
+use super::pagination::Pagination;
+use super::usage::{self, MonthParams, UsageMonth};
+use super::video::VideoWithPet;
+use crate::dead_letter;
+use crate::entities::{alerts, pet, pet_video, user, video_tag};
+use crate::poison;
+use crate::storage_cleanup;
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use google_cloud_storage::client::Client as GcsClient;
+use redis::AsyncCommands;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Select, Set,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct ReconcileParams {
+    /// Defaults to a report-only run; pass `dry_run=false` to delete orphans
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+// POST /internal/storage/reconcile - Start an orphaned GCS object cleanup run
+pub async fn start_storage_reconcile(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Query(params): Query<ReconcileParams>,
+) -> Response {
+    let bucket = match std::env::var("GCS_BUCKET_NAME") {
+        Ok(b) => b,
+        Err(_) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": "GCS_BUCKET_NAME not set"})),
+            )
+                .into_response()
+        }
+    };
+
+    let run = match storage_cleanup::start_reconcile_run(&db, params.dry_run).await {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+
+    let run_id = run.id;
+    tokio::spawn(async move {
+        storage_cleanup::run_reconcile(&db, &gcs_client, &bucket, run).await;
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "run_id": run_id,
+            "dry_run": params.dry_run,
+            "status": "running"
+        })),
+    )
+        .into_response()
+}
+
+// GET /internal/storage/reconcile/runs - Audit trail of recent cleanup runs
+pub async fn list_storage_reconcile_runs(Extension(db): Extension<DatabaseConnection>) -> Response {
+    match storage_cleanup::recent_runs(&db, 20).await {
+        Ok(runs) => (StatusCode::OK, Json(runs)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+const USAGE_OVERVIEW_TOP_PETS: usize = 20;
+
+// GET /internal/usage/monthly?month=YYYY-MM - Platform-wide usage and the costliest pets
+pub async fn get_usage_overview(
+    Extension(db): Extension<DatabaseConnection>,
+    Query(params): Query<MonthParams>,
+) -> Result<Response, ApiError> {
+    let month = UsageMonth::parse(params.month.as_deref())?;
+    let totals = usage::usage_by_pet(&db, None, &month).await?;
+
+    let mut ranked: Vec<(i32, &usage::UsageTotals)> =
+        totals.iter().map(|(id, t)| (*id, t)).collect();
+    ranked.sort_by(|a, b| {
+        b.1.estimated_cost_usd()
+            .total_cmp(&a.1.estimated_cost_usd())
+    });
+    ranked.truncate(USAGE_OVERVIEW_TOP_PETS);
+
+    let ids: Vec<i32> = ranked.iter().map(|(id, _)| *id).collect();
+    let pets: HashMap<i32, pet::Model> = pet::Entity::find()
+        .filter(pet::Column::Id.is_in(ids))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+
+    let top_pets: Vec<serde_json::Value> = ranked
+        .into_iter()
+        .map(|(id, t)| {
+            let p = pets.get(&id);
+            json!({
+                "pet_id": id,
+                "name": p.map(|p| p.name.clone()),
+                "user_id": p.map(|p| p.user_id),
+                "usage": t.report(&month),
+            })
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "total": usage::rollup(totals.values()).report(&month),
+            "pet_count": totals.len(),
+            "top_pets": top_pets,
+        })),
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct AdminListParams {
+    pub user_id: Option<i32>,
+    pub pet_id: Option<i32>,
+    pub severity_level: Option<String>,
+    pub status: Option<String>,
+}
+
+/// Narrows a pet-scoped query to one pet or to every pet of one user.
+async fn filter_by_owner<E: EntityTrait>(
+    db: &DatabaseConnection,
+    mut query: Select<E>,
+    pet_column: E::Column,
+    params: &AdminListParams,
+) -> Result<Select<E>, ApiError> {
+    if let Some(pet_id) = params.pet_id {
+        query = query.filter(pet_column.eq(pet_id));
+    }
+    if let Some(user_id) = params.user_id {
+        let pet_ids: Vec<i32> = pet::Entity::find()
+            .filter(pet::Column::UserId.eq(user_id))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        query = query.filter(pet_column.is_in(pet_ids));
+    }
+    Ok(query)
+}
+
+async fn pets_by_id(
+    db: &DatabaseConnection,
+    ids: impl IntoIterator<Item = i32>,
+) -> Result<HashMap<i32, pet::Model>, ApiError> {
+    let ids: Vec<i32> = ids.into_iter().collect();
+    Ok(pet::Entity::find()
+        .filter(pet::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect())
+}
+
+// GET /admin/alerts - Alerts across every user, optionally filtered by user, pet or severity
+pub async fn list_alerts(
+    Extension(db): Extension<DatabaseConnection>,
+    pagination: Pagination,
+    Query(params): Query<AdminListParams>,
+) -> Result<Response, ApiError> {
+    let mut query =
+        filter_by_owner(&db, alerts::Entity::find(), alerts::Column::PetId, &params).await?;
+    if let Some(severity) = &params.severity_level {
+        query = query.filter(alerts::Column::SeverityLevel.eq(severity));
+    }
+    query = query.order_by_desc(Expr::cust(
+        "COALESCE(alerts.last_seen_at, alerts.created_at)",
+    ));
+
+    let total = query.clone().count(&db).await?;
+    let page = query
+        .paginate(&db, pagination.page_size)
+        .fetch_page(pagination.index())
+        .await?;
+
+    let pets = pets_by_id(&db, page.iter().map(|a| a.pet_id)).await?;
+    let alerts = page
+        .into_iter()
+        .map(|alert| {
+            let pet_name = pets.get(&alert.pet_id).map(|p| p.name.clone());
+            AlertResponse::from_model(alert, pet_name)
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(AlertListResponse {
+            alerts,
+            total,
+            page: pagination.page,
+            page_size: pagination.page_size,
+        }),
+    )
+        .into_response())
+}
+
+// GET /admin/videos?status=FAILED - Videos across every user in any pipeline state
+pub async fn list_videos(
+    Extension(db): Extension<DatabaseConnection>,
+    pagination: Pagination,
+    Query(params): Query<AdminListParams>,
+) -> Result<Response, ApiError> {
+    let mut query = filter_by_owner(
+        &db,
+        pet_video::Entity::find(),
+        pet_video::Column::PetId,
+        &params,
+    )
+    .await?;
+    if let Some(status) = &params.status {
+        query = query.filter(pet_video::Column::Status.eq(status.to_uppercase()));
+    }
+    query = query.order_by_desc(pet_video::Column::CreatedAt);
+
+    let total = query.clone().count(&db).await?;
+    let page = query
+        .paginate(&db, pagination.page_size)
+        .fetch_page(pagination.index())
+        .await?;
+
+    let pets = pets_by_id(&db, page.iter().map(|v| v.pet_id)).await?;
+    let mut tags = video_tag::tags_by_video(&db, page.iter().map(|v| v.id).collect()).await?;
+    let videos: Vec<VideoWithPet> = page
+        .into_iter()
+        .map(|video| VideoWithPet {
+            pet: pets.get(&video.pet_id).cloned(),
+            tags: tags.remove(&video.id).unwrap_or_default(),
+            alerts: None,
+            video,
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "videos": videos,
+            "total": total,
+            "page": pagination.page,
+            "page_size": pagination.page_size,
+        })),
+    )
+        .into_response())
+}
+
+// GET /admin/users - Every account, newest first
+pub async fn list_users(
+    Extension(db): Extension<DatabaseConnection>,
+    pagination: Pagination,
+) -> Result<Response, ApiError> {
+    let query = user::Entity::find().order_by_desc(user::Column::CreatedAt);
+
+    let total = query.clone().count(&db).await?;
+    let users: Vec<serde_json::Value> = query
+        .paginate(&db, pagination.page_size)
+        .fetch_page(pagination.index())
+        .await?
+        .into_iter()
+        .map(|u| {
+            json!({
+                "id": u.id,
+                "email": u.email,
+                "name": u.name,
+                "created_at": u.created_at,
+                "timezone": u.timezone,
+                "is_admin": u.is_admin,
+            })
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "users": users,
+            "total": total,
+            "page": pagination.page,
+            "page_size": pagination.page_size,
+        })),
+    )
+        .into_response())
+}
+
+// GET /admin/dlq - Video jobs that failed for good, oldest first
+pub async fn list_dead_letters(
+    Extension(redis_client): Extension<redis::Client>,
+    pagination: Pagination,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let total = dead_letter::len(&mut conn)
+        .await
+        .map_err(ApiError::internal)?;
+    let entries = dead_letter::list(
+        &mut conn,
+        pagination.index() * pagination.page_size,
+        pagination.page_size,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "entries": entries,
+            "total": total,
+            "page": pagination.page,
+            "page_size": pagination.page_size,
+        })),
+    )
+        .into_response())
+}
+
+// GET /admin/poison/:queue - Payloads the workers couldn't decode, oldest first
+pub async fn list_poison_messages(
+    Extension(redis_client): Extension<redis::Client>,
+    Path(queue): Path<String>,
+    pagination: Pagination,
+) -> Result<Response, ApiError> {
+    let queue =
+        poison::known_queue(&queue).ok_or_else(|| ApiError::not_found("queue_not_found"))?;
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let total = poison::len(&mut conn, queue)
+        .await
+        .map_err(ApiError::internal)?;
+    let entries = poison::list(
+        &mut conn,
+        queue,
+        pagination.index() * pagination.page_size,
+        pagination.page_size,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "queue": queue,
+            "entries": entries,
+            "total": total,
+            "page": pagination.page,
+            "page_size": pagination.page_size,
+        })),
+    )
+        .into_response())
+}
+
+// DELETE /admin/poison/:queue - Drop everything on a poison list
+pub async fn purge_poison_messages(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    Path(queue): Path<String>,
+) -> Result<Response, ApiError> {
+    let queue =
+        poison::known_queue(&queue).ok_or_else(|| ApiError::not_found("queue_not_found"))?;
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let purged = poison::purge(&mut conn, queue)
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!(queue, purged, "Poison list purged");
+    crate::audit::record(
+        &db,
+        user_id,
+        "purge_poison_messages",
+        Some(("queue", queue.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "queue": queue, "purged": purged })),
+    )
+        .into_response())
+}
+
+// POST /admin/dlq/:video_id/requeue - Take a video off the DLQ and run it through analysis again
+pub async fn requeue_dead_letter(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    Path(video_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let entry = dead_letter::take(&mut conn, video_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::not_found("dlq_entry_not_found"))?;
+
+    let Some(video) = pet_video::Entity::find_by_id(video_id).one(&db).await? else {
+        // Nothing left to replay; the entry stays dropped
+        return Err(ApiError::not_found("video_not_found"));
+    };
+
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let mut active: pet_video::ActiveModel = video.into();
+    active.status = Set("PENDING".to_string());
+    active.retry_count = Set(0);
+    active.queued_at = Set(Some(now));
+    active.download_started_at = Set(None);
+    active.analysis_started_at = Set(None);
+    active.completed_at = Set(None);
+    active.error_message = Set(None);
+    active.error_stage = Set(None);
+    active.updated_at = Set(now);
+    let video = match active.update(&db).await {
+        Ok(video) => video,
+        Err(e) => {
+            // Put it back so the failure can be retried
+            let _ = dead_letter::push(&mut conn, &entry).await;
+            return Err(e.into());
+        }
+    };
+
+    let payload = entry.replay_payload();
+    let queue_position: u64 = conn
+        .rpush(crate::jobs::queue_of(&payload), payload)
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!(%video_id, replay_count = entry.replay_count + 1, "Video requeued from the DLQ");
+    crate::video_events::publish(
+        &mut conn,
+        crate::video_events::VideoEventKind::Queued,
+        &video,
+    )
+    .await;
+    metrics::counter!("petpulse_video_dlq_requeued_total").increment(1);
+    crate::audit::record(
+        &db,
+        user_id,
+        "requeue_dead_letter",
+        Some(("video", video_id.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "status": "queued",
+            "video_id": video_id,
+            "queue_position": queue_position,
+            "replay_count": entry.replay_count + 1,
+        })),
+    )
+        .into_response())
+}
@@ -0,0 +1,292 @@
+use super::error::{ApiError, FieldError};
+use super::pet::owned_pet;
+use crate::agent::comfort_loop::AlertType;
+use crate::entities::alert_mute;
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest a single mute may last (90 days).
+const MAX_MUTE_MINUTES: i64 = 90 * 24 * 60;
+const MAX_MUTE_NOTE_LEN: usize = 500;
+const MUTE_HISTORY_LIMIT: u64 = 20;
+
+#[derive(Deserialize)]
+pub struct MuteRequest {
+    duration_minutes: i64,
+    note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MuteView {
+    pub id: Uuid,
+    pub alert_type: String,
+    pub muted_by: i32,
+    pub note: Option<String>,
+    pub muted_until: chrono::NaiveDateTime,
+    pub unmuted_at: Option<chrono::NaiveDateTime>,
+    pub unmuted_by: Option<i32>,
+    pub created_at: chrono::NaiveDateTime,
+    pub active: bool,
+    pub remaining_seconds: i64,
+}
+
+impl From<alert_mute::Model> for MuteView {
+    fn from(m: alert_mute::Model) -> Self {
+        let now = chrono::Utc::now().naive_utc();
+        let active = m.unmuted_at.is_none() && m.muted_until > now;
+        Self {
+            remaining_seconds: if active {
+                (m.muted_until - now).num_seconds()
+            } else {
+                0
+            },
+            active,
+            id: m.id,
+            alert_type: m.alert_type,
+            muted_by: m.muted_by,
+            note: m.note,
+            muted_until: m.muted_until,
+            unmuted_at: m.unmuted_at,
+            unmuted_by: m.unmuted_by,
+            created_at: m.created_at,
+        }
+    }
+}
+
+/// Parses a snake_case alert type from the path. Operator alerts aren't
+/// pet-scoped and can't be muted per pet.
+pub(crate) fn parse_alert_type(raw: &str) -> Result<AlertType, ApiError> {
+    serde_json::from_value::<AlertType>(serde_json::Value::String(raw.to_string()))
+        .ok()
+        .filter(|t| !t.is_operator_alert())
+        .ok_or_else(|| {
+            ApiError::validation(vec![FieldError::new("alert_type", "alert_type.unknown")])
+        })
+}
+
+/// Lifts every active mute of this type for the pet, recording who did it.
+async fn lift_active_mutes(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    alert_type: &str,
+    user_id: i32,
+) -> Result<u64, sea_orm::DbErr> {
+    let res = alert_mute::Entity::update_many()
+        .col_expr(
+            alert_mute::Column::UnmutedAt,
+            sea_orm::sea_query::Expr::value(chrono::Utc::now().naive_utc()),
+        )
+        .col_expr(
+            alert_mute::Column::UnmutedBy,
+            sea_orm::sea_query::Expr::value(user_id),
+        )
+        .filter(alert_mute::Column::PetId.eq(pet_id))
+        .filter(alert_mute::Column::AlertType.eq(alert_type))
+        .filter(alert_mute::active_condition())
+        .exec(db)
+        .await?;
+    Ok(res.rows_affected)
+}
+
+// POST /pets/:id/alert-types/:type/mute - Silence one alert type for a while
+// (critical alerts still get through)
+pub async fn mute_alert_type(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path((pet_id, raw_type)): Path<(i32, String)>,
+    Json(payload): Json<MuteRequest>,
+) -> Result<Response, ApiError> {
+    let alert_type = parse_alert_type(&raw_type)?.to_string();
+
+    let mut errors = Vec::new();
+    if !(1..=MAX_MUTE_MINUTES).contains(&payload.duration_minutes) {
+        errors.push(FieldError::new("duration_minutes", "field.out_of_range"));
+    }
+    if payload
+        .note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_MUTE_NOTE_LEN)
+    {
+        errors.push(FieldError::new("note", "field.too_long"));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    owned_pet(&db, pet_id, user_id).await?;
+
+    // A new mute replaces any running one for the same type
+    lift_active_mutes(&db, pet_id, &alert_type, user_id).await?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let mute = alert_mute::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        pet_id: Set(pet_id),
+        alert_type: Set(alert_type.clone()),
+        muted_by: Set(user_id),
+        note: Set(payload.note),
+        muted_until: Set(now + chrono::Duration::minutes(payload.duration_minutes)),
+        unmuted_at: Set(None),
+        unmuted_by: Set(None),
+        created_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+
+    tracing::info!(
+        pet_id,
+        user_id,
+        alert_type = %alert_type,
+        until = %mute.muted_until,
+        "Alert type muted"
+    );
+
+    Ok((StatusCode::CREATED, Json(MuteView::from(mute))).into_response())
+}
+
+// DELETE /pets/:id/alert-types/:type/mute - Lift a mute before it expires
+pub async fn unmute_alert_type(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path((pet_id, raw_type)): Path<(i32, String)>,
+) -> Result<Response, ApiError> {
+    let alert_type = parse_alert_type(&raw_type)?.to_string();
+    owned_pet(&db, pet_id, user_id).await?;
+
+    if lift_active_mutes(&db, pet_id, &alert_type, user_id).await? == 0 {
+        return Err(ApiError::not_found("mute_not_found"));
+    }
+
+    tracing::info!(pet_id, user_id, alert_type = %alert_type, "Alert type unmuted");
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+// GET /pets/:id/alert-settings - Per-type settings, active mutes with remaining
+// time, plus recent history
+pub async fn get_alert_settings(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+) -> Result<Response, ApiError> {
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let history: Vec<MuteView> = alert_mute::Entity::find()
+        .filter(alert_mute::Column::PetId.eq(pet_id))
+        .order_by_desc(alert_mute::Column::CreatedAt)
+        .limit(MUTE_HISTORY_LIMIT)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(MuteView::from)
+        .collect();
+
+    let active: Vec<&MuteView> = history.iter().filter(|m| m.active).collect();
+    let alert_types = super::alert_settings::settings_for_pet(&db, pet_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "pet_id": pet_id,
+            "alert_types": alert_types,
+            "mutes": active,
+            "mute_history": history,
+        })),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::error_code;
+
+    fn mute(
+        muted_until: chrono::NaiveDateTime,
+        unmuted_at: Option<chrono::NaiveDateTime>,
+    ) -> MuteView {
+        let now = chrono::Utc::now().naive_utc();
+        MuteView::from(alert_mute::Model {
+            id: Uuid::new_v4(),
+            pet_id: 1,
+            alert_type: String::from("vocalization"),
+            muted_by: 1,
+            note: None,
+            muted_until,
+            unmuted_at,
+            unmuted_by: unmuted_at.map(|_| 1),
+            created_at: now,
+        })
+    }
+
+    #[tokio::test]
+    async fn only_pet_alert_types_can_be_muted() {
+        assert_eq!(
+            parse_alert_type("vocalization").unwrap(),
+            AlertType::Vocalization
+        );
+        for raw in ["queue_depth_high", "Vocalization", "barking"] {
+            let response = parse_alert_type(raw).unwrap_err().into_response();
+            assert_eq!(
+                error_code(response).await,
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    String::from("validation_failed")
+                ),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn mute_view_counts_down_only_while_active() {
+        let now = chrono::Utc::now().naive_utc();
+        let running = mute(now + chrono::Duration::minutes(30), None);
+        assert!(running.active);
+        assert!((1_790..=1_800).contains(&running.remaining_seconds));
+
+        let lifted = mute(now + chrono::Duration::minutes(30), Some(now));
+        assert!(!lifted.active);
+        assert_eq!(lifted.remaining_seconds, 0);
+
+        let expired = mute(now - chrono::Duration::minutes(1), None);
+        assert!(!expired.active);
+        assert_eq!(expired.remaining_seconds, 0);
+    }
+
+    #[tokio::test]
+    async fn mute_request_is_validated_before_the_pet_is_loaded() {
+        let response = mute_alert_type(
+            Extension(DatabaseConnection::Disconnected),
+            Extension(1),
+            Path((1, String::from("vocalization"))),
+            Json(MuteRequest {
+                duration_minutes: MAX_MUTE_MINUTES + 1,
+                note: Some("x".repeat(MAX_MUTE_NOTE_LEN + 1)),
+            }),
+        )
+        .await
+        .unwrap_err()
+        .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "validation_failed");
+        let fields: Vec<&str> = body["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, ["duration_minutes", "note"]);
+    }
+}
@@ -0,0 +1,192 @@
+use super::alert_mutes::parse_alert_type;
+use super::error::{ApiError, FieldError};
+use super::pet::owned_pet;
+use crate::agent::comfort_loop::AlertType;
+use crate::entities::pet_alert_setting::{self, DEFAULT_ESCALATION_THRESHOLD};
+use crate::notifications::preferences::{severity_rank, SEVERITY_LEVELS};
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    Set,
+};
+use serde::{Deserialize, Serialize};
+
+const MIN_ESCALATION_THRESHOLD: i32 = 2;
+const MAX_ESCALATION_THRESHOLD: i32 = 50;
+
+#[derive(Deserialize)]
+pub struct UpdateAlertSettingRequest {
+    enabled: Option<bool>,
+    min_severity: Option<String>,
+    escalation_threshold: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct AlertSettingView {
+    pub alert_type: String,
+    pub enabled: bool,
+    pub min_severity: String,
+    pub escalation_threshold: i32,
+    /// False when the type is on its defaults (no stored row)
+    pub customized: bool,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl AlertSettingView {
+    fn new(alert_type: String, setting: Option<pet_alert_setting::Model>) -> Self {
+        match setting {
+            Some(s) => Self {
+                alert_type,
+                enabled: s.enabled,
+                min_severity: s.min_severity,
+                escalation_threshold: s.escalation_threshold,
+                customized: true,
+                updated_at: Some(s.updated_at),
+            },
+            None => Self {
+                alert_type,
+                enabled: true,
+                min_severity: SEVERITY_LEVELS[0].to_string(),
+                escalation_threshold: DEFAULT_ESCALATION_THRESHOLD,
+                customized: false,
+                updated_at: None,
+            },
+        }
+    }
+}
+
+/// Every pet alert type with its effective settings, including ones left on
+/// defaults.
+pub(crate) async fn settings_for_pet(
+    db: &DatabaseConnection,
+    pet_id: i32,
+) -> Result<Vec<AlertSettingView>, sea_orm::DbErr> {
+    let mut settings = pet_alert_setting::Entity::find()
+        .filter(pet_alert_setting::Column::PetId.eq(pet_id))
+        .all(db)
+        .await?;
+
+    Ok(AlertType::PET_ALERT_TYPES
+        .iter()
+        .map(|alert_type| {
+            let alert_type = alert_type.to_string();
+            let setting = settings
+                .iter()
+                .position(|s| s.alert_type == alert_type)
+                .map(|i| settings.swap_remove(i));
+            AlertSettingView::new(alert_type, setting)
+        })
+        .collect())
+}
+
+// PUT /pets/:id/alert-settings/:type - Create or update the settings for one alert type
+pub async fn update_alert_setting(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path((pet_id, raw_type)): Path<(i32, String)>,
+    Json(payload): Json<UpdateAlertSettingRequest>,
+) -> Result<Response, ApiError> {
+    let alert_type = parse_alert_type(&raw_type)?.to_string();
+
+    let mut errors = Vec::new();
+    let min_severity = payload.min_severity.map(|s| s.trim().to_lowercase());
+    if min_severity
+        .as_deref()
+        .is_some_and(|s| severity_rank(s).is_none())
+    {
+        errors.push(FieldError::new("min_severity", "field.invalid_format"));
+    }
+    if payload
+        .escalation_threshold
+        .is_some_and(|t| !(MIN_ESCALATION_THRESHOLD..=MAX_ESCALATION_THRESHOLD).contains(&t))
+    {
+        errors.push(FieldError::new(
+            "escalation_threshold",
+            "field.out_of_range",
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let existing = pet_alert_setting::Entity::find()
+        .filter(pet_alert_setting::Column::PetId.eq(pet_id))
+        .filter(pet_alert_setting::Column::AlertType.eq(alert_type.as_str()))
+        .one(&db)
+        .await?;
+    let setting = match existing {
+        Some(existing) => {
+            let mut active = existing.into_active_model();
+            if let Some(enabled) = payload.enabled {
+                active.enabled = Set(enabled);
+            }
+            if let Some(min_severity) = min_severity {
+                active.min_severity = Set(min_severity);
+            }
+            if let Some(threshold) = payload.escalation_threshold {
+                active.escalation_threshold = Set(threshold);
+            }
+            active.updated_at = Set(now);
+            active.update(&db).await?
+        }
+        None => {
+            pet_alert_setting::ActiveModel {
+                pet_id: Set(pet_id),
+                alert_type: Set(alert_type.clone()),
+                enabled: Set(payload.enabled.unwrap_or(true)),
+                min_severity: Set(min_severity.unwrap_or_else(|| SEVERITY_LEVELS[0].to_string())),
+                escalation_threshold: Set(payload
+                    .escalation_threshold
+                    .unwrap_or(DEFAULT_ESCALATION_THRESHOLD)),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            }
+            .insert(&db)
+            .await?
+        }
+    };
+
+    tracing::info!(
+        pet_id,
+        user_id,
+        alert_type = %alert_type,
+        enabled = setting.enabled,
+        min_severity = %setting.min_severity,
+        escalation_threshold = setting.escalation_threshold,
+        "Pet alert setting updated"
+    );
+    Ok((
+        StatusCode::OK,
+        Json(AlertSettingView::new(alert_type, Some(setting))),
+    )
+        .into_response())
+}
+
+// DELETE /pets/:id/alert-settings/:type - Put an alert type back on its defaults
+pub async fn reset_alert_setting(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path((pet_id, raw_type)): Path<(i32, String)>,
+) -> Result<Response, ApiError> {
+    let alert_type = parse_alert_type(&raw_type)?.to_string();
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let res = pet_alert_setting::Entity::delete_many()
+        .filter(pet_alert_setting::Column::PetId.eq(pet_id))
+        .filter(pet_alert_setting::Column::AlertType.eq(alert_type.as_str()))
+        .exec(&db)
+        .await?;
+    if res.rows_affected == 0 {
+        return Err(ApiError::not_found("alert_setting_not_found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
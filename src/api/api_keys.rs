@@ -0,0 +1,228 @@
+use super::error::{ApiError, FieldError};
+use super::secret::{hash_secret, random_secret, split_token, verify_secret};
+use crate::entities::api_key;
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Keys look like `pp_<key id>.<secret>`.
+pub const API_KEY_PREFIX: &str = "pp_";
+const MAX_ACTIVE_KEYS: u64 = 20;
+const MAX_KEY_NAME_LEN: usize = 100;
+
+/// Resolves a presented key to its owner. Revoked keys stop working on the
+/// next request since every call checks the row.
+pub(crate) async fn resolve_api_key(db: &DatabaseConnection, key: &str) -> Option<i32> {
+    let (key_id, secret) = split_token(key.strip_prefix(API_KEY_PREFIX)?)?;
+    let key = match api_key::Entity::find_by_id(key_id).one(db).await {
+        Ok(Some(k)) if k.revoked_at.is_none() => k,
+        Ok(_) => return None,
+        Err(e) => {
+            tracing::error!("Failed to look up API key: {}", e);
+            return None;
+        }
+    };
+    if !verify_secret(secret, &key.key_hash) {
+        return None;
+    }
+
+    let user_id = key.user_id;
+    let mut active = key.into_active_model();
+    active.last_used_at = Set(Some(chrono::Utc::now().naive_utc()));
+    if let Err(e) = active.update(db).await {
+        tracing::warn!("Failed to record API key use: {}", e);
+    }
+    Some(user_id)
+}
+
+#[derive(Deserialize)]
+pub struct CreateApiKeyRequest {
+    name: String,
+}
+
+// POST /users/api-keys - Create a device key; the secret is only returned here
+pub async fn create_api_key(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<Response, ApiError> {
+    let name = payload.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "name",
+            "field.required",
+        )]));
+    }
+    if name.chars().count() > MAX_KEY_NAME_LEN {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "name",
+            "field.too_long",
+        )]));
+    }
+
+    let active_keys = api_key::Entity::find()
+        .filter(api_key::Column::UserId.eq(user_id))
+        .filter(api_key::Column::RevokedAt.is_null())
+        .count(&db)
+        .await?;
+    if active_keys >= MAX_ACTIVE_KEYS {
+        return Err(ApiError::new(StatusCode::CONFLICT, "api_key_limit_reached"));
+    }
+
+    let secret = random_secret();
+    let key = api_key::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        name: Set(name),
+        key_hash: Set(hash_secret(&secret)?),
+        last_used_at: Set(None),
+        revoked_at: Set(None),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    }
+    .insert(&db)
+    .await?;
+
+    tracing::info!(user_id, key_id = %key.id, "API key created");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": key.id,
+            "name": key.name,
+            "key": format!("{}{}.{}", API_KEY_PREFIX, key.id, secret),
+            "created_at": key.created_at,
+        })),
+    )
+        .into_response())
+}
+
+// GET /users/api-keys - The caller's keys, without secrets
+pub async fn list_api_keys(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+) -> Result<Response, ApiError> {
+    let keys = api_key::Entity::find()
+        .filter(api_key::Column::UserId.eq(user_id))
+        .order_by_desc(api_key::Column::CreatedAt)
+        .all(&db)
+        .await?;
+
+    Ok((StatusCode::OK, Json(keys)).into_response())
+}
+
+// DELETE /users/api-keys/:id - Revoke a key
+pub async fn revoke_api_key(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(key_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let key = api_key::Entity::find_by_id(key_id)
+        .filter(api_key::Column::UserId.eq(user_id))
+        .filter(api_key::Column::RevokedAt.is_null())
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("api_key_not_found"))?;
+
+    let mut active = key.into_active_model();
+    active.revoked_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active.update(&db).await?;
+
+    tracing::info!(user_id, key_id = %key_id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{database, owner_with_pet};
+
+    /// Creates a key for user 1 the way a client does, returning it whole.
+    async fn issue_key(db: &DatabaseConnection) -> (Uuid, String) {
+        let payload = serde_json::from_value(json!({"name": "Porch camera"})).unwrap();
+        let response = create_api_key(Extension(db.clone()), Extension(1), Json(payload))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (
+            body["id"].as_str().unwrap().parse().unwrap(),
+            body["key"].as_str().unwrap().to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn valid_keys_resolve_and_record_their_use() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (id, key) = issue_key(&db).await;
+
+        assert_eq!(resolve_api_key(&db, &key).await, Some(1));
+        let stored = api_key::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn malformed_keys_are_refused() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (id, key) = issue_key(&db).await;
+        let unprefixed = key.strip_prefix(API_KEY_PREFIX).unwrap();
+
+        for key in [
+            unprefixed.to_string(),
+            format!("xx_{}", unprefixed),
+            format!("{}{}", API_KEY_PREFIX, id),
+            format!("{}not-a-uuid.secret", API_KEY_PREFIX),
+            String::new(),
+        ] {
+            assert_eq!(resolve_api_key(&db, &key).await, None, "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn wrong_secrets_and_unknown_ids_are_refused() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (id, key) = issue_key(&db).await;
+        let secret = key.rsplit('.').next().unwrap();
+
+        let wrong_secret = format!("{}{}.{}", API_KEY_PREFIX, id, random_secret());
+        assert_eq!(resolve_api_key(&db, &wrong_secret).await, None);
+        let unknown_id = format!("{}{}.{}", API_KEY_PREFIX, Uuid::new_v4(), secret);
+        assert_eq!(resolve_api_key(&db, &unknown_id).await, None);
+        let stored = api_key::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.last_used_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn revoked_keys_stop_working() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (id, key) = issue_key(&db).await;
+        assert_eq!(resolve_api_key(&db, &key).await, Some(1));
+
+        revoke_api_key(Extension(db.clone()), Extension(1), Path(id))
+            .await
+            .unwrap();
+        assert_eq!(resolve_api_key(&db, &key).await, None);
+    }
+}
@@ -1,17 +1,31 @@
-use crate::entities::user;
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::login_throttle::LoginThrottle;
+use super::secret::{hash_secret, random_secret, split_token, verify_secret};
+use super::session::{self, SessionClaims, SessionKeys, SessionMeta};
+use crate::entities::{password_reset_token, user};
+use crate::notifications::{NotificationTemplates, TwilioNotifier};
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
 use axum::{
-    extract::{Extension, Json},
-    http::StatusCode,
-    response::{IntoResponse, Response},
+    extract::{Extension, Json, Path, Query},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, QueryFilter, Set, TransactionTrait,
 };
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde_json::json;
 use tower_cookies::{Cookie, Cookies};
 use tracing::field::display;
+use uuid::Uuid;
+
+/// `password_hash` of accounts created through Google sign-in. It isn't a
+/// valid PHC string, so no password can ever verify against it.
+pub const OAUTH_ONLY_PASSWORD_HASH: &str = "!oauth";
 
 #[derive(serde::Deserialize)]
 pub struct RegisterRequest {
@@ -20,10 +34,77 @@ pub struct RegisterRequest {
     name: String,
 }
 
+const MIN_PASSWORD_LEN: usize = 8;
+
+fn validate_password(
+    field: &'static str,
+    password: &str,
+    email: &str,
+    errors: &mut Vec<FieldError>,
+) {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        errors.push(FieldError::new(field, "field.too_short"));
+    } else if password.trim().eq_ignore_ascii_case(email.trim()) {
+        errors.push(FieldError::new(field, "field.matches_email"));
+    }
+}
+
+/// Whether a stored hash was made with different Argon2 settings than we hash
+/// with today, so it should be replaced the next time we see the password.
+fn needs_rehash(hash: &PasswordHash) -> bool {
+    if hash.algorithm != argon2::Algorithm::default().ident()
+        || hash.version != Some(argon2::Version::default().into())
+    {
+        return true;
+    }
+    let current = argon2::Params::default();
+    match argon2::Params::try_from(hash) {
+        Ok(params) => {
+            params.m_cost() != current.m_cost()
+                || params.t_cost() != current.t_cost()
+                || params.p_cost() != current.p_cost()
+        }
+        Err(_) => true,
+    }
+}
+
+/// Replaces the stored hash with one using the current parameters. Best
+/// effort: the user is already authenticated, so failures are only logged.
+async fn rehash_password(db: &DatabaseConnection, user_id: i32, password: &str) {
+    let hash = match Argon2::default()
+        .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+    {
+        Ok(hash) => hash.to_string(),
+        Err(e) => {
+            tracing::warn!("Failed to re-hash password for user {}: {}", user_id, e);
+            return;
+        }
+    };
+    let result = user::Entity::update_many()
+        .col_expr(user::Column::PasswordHash, Expr::value(hash))
+        .filter(user::Column::Id.eq(user_id))
+        .exec(db)
+        .await;
+    match result {
+        Ok(_) => {
+            tracing::info!(user_id, "Upgraded password hash parameters");
+            metrics::counter!("petpulse_password_rehashes_total").increment(1);
+        }
+        Err(e) => tracing::warn!("Failed to store re-hashed password: {}", e),
+    }
+}
+
 pub async fn register(
     Extension(db): Extension<DatabaseConnection>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<RegisterRequest>,
 ) -> Response {
+    let mut errors = Vec::new();
+    validate_password("password", &payload.password, &payload.email, &mut errors);
+    if !errors.is_empty() {
+        return ApiError::validation(errors).into_response();
+    }
+
     // Hash password
     let salt = SaltString::generate(&mut OsRng);
     let argon2 = Argon2::default();
@@ -60,6 +141,7 @@ pub async fn register(
 
             metrics::counter!("petpulse_users_registered_total").increment(1);
             metrics::gauge!("petpulse_users_total").increment(1.0);
+            crate::audit::record(&db, user.id, "register", None, ip);
 
             (
                 StatusCode::CREATED,
@@ -103,11 +185,131 @@ pub struct LoginRequest {
     password: String,
 }
 
+/// Counts the failure against the email and IP, then returns the usual 401.
+async fn login_failed(
+    throttle: &LoginThrottle,
+    conn: Option<&mut redis::aio::MultiplexedConnection>,
+    email: &str,
+    ip: Option<&str>,
+    reason: &'static str,
+) -> Response {
+    metrics::counter!("petpulse_login_failures_total", "reason" => reason).increment(1);
+    if let Some(conn) = conn {
+        if let Err(e) = throttle.record_failure(conn, email, ip).await {
+            tracing::error!("Failed to record login failure: {}", e);
+        }
+    }
+
+    tracing::Span::current()
+        .record("table", "users")
+        .record("action", "login_user_failed")
+        .record("error", "invalid_credentials");
+
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "Invalid email or password"})),
+    )
+        .into_response()
+}
+
+fn session_meta(headers: &HeaderMap, ip: Option<String>) -> SessionMeta {
+    SessionMeta {
+        user_agent: headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        ip,
+    }
+}
+
+/// Issues a session for `user_id`, records it in Redis and sets the cookie.
+/// Shared by every sign-in method so they all produce the same session.
+async fn start_session(
+    session_keys: &SessionKeys,
+    conn: Option<&mut redis::aio::MultiplexedConnection>,
+    cookies: &Cookies,
+    user_id: i32,
+    meta: &SessionMeta,
+) -> Result<(), Response> {
+    let failed = || {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "Failed to create session"})),
+        )
+            .into_response()
+    };
+
+    let (token, claims) = session_keys.issue(user_id).map_err(|e| {
+        tracing::error!("Failed to sign session token: {}", e);
+        failed()
+    })?;
+    let stored = match conn {
+        Some(conn) => session::store_session(conn, &claims, meta).await,
+        None => Err(redis::RedisError::from((
+            redis::ErrorKind::IoError,
+            "Redis unavailable",
+        ))),
+    };
+    if let Err(e) = stored {
+        tracing::error!("Failed to store session: {}", e);
+        return Err(failed());
+    }
+
+    cookies.add(session_keys.cookie(token));
+    Ok(())
+}
+
 pub async fn login(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(session_keys): Extension<SessionKeys>,
+    Extension(redis_client): Extension<redis::Client>,
     cookies: Cookies,
+    headers: HeaderMap,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<LoginRequest>,
 ) -> Response {
+    let throttle = LoginThrottle::from_env();
+    let mut conn = match redis_client.get_multiplexed_async_connection().await {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            tracing::error!("Failed to connect to Redis for login throttling: {}", e);
+            None
+        }
+    };
+
+    // Locked out callers don't get to try the password at all
+    if let Some(conn) = conn.as_mut() {
+        match throttle.check(conn, &payload.email, ip.as_deref()).await {
+            Ok(Some(lockout)) => {
+                tracing::warn!(
+                    event = "login_locked",
+                    scope = lockout.scope.as_str(),
+                    email = %payload.email,
+                    ip = ip.as_deref().unwrap_or("unknown"),
+                    retry_after_secs = lockout.retry_after_secs,
+                    "Login locked after repeated failures"
+                );
+                tracing::Span::current()
+                    .record("table", "users")
+                    .record("action", "login_user_locked")
+                    .record("error", "login_locked");
+                metrics::counter!("petpulse_login_failures_total", "reason" => "locked")
+                    .increment(1);
+
+                let mut response =
+                    ApiError::new(StatusCode::TOO_MANY_REQUESTS, "login_locked").into_response();
+                response.headers_mut().insert(
+                    header::RETRY_AFTER,
+                    HeaderValue::from(lockout.retry_after_secs),
+                );
+                return response;
+            }
+            Ok(None) => {}
+            // Fail open: an unreachable Redis shouldn't lock everyone out
+            Err(e) => tracing::error!("Failed to check login throttle: {}", e),
+        }
+    }
+
     let user = match user::Entity::find()
         .filter(user::Column::Email.eq(payload.email.clone()))
         .one(&db)
@@ -115,11 +317,14 @@ pub async fn login(
     {
         Ok(Some(u)) => u,
         Ok(None) => {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(json!({"error": "Invalid email or password"})),
+            return login_failed(
+                &throttle,
+                conn.as_mut(),
+                &payload.email,
+                ip.as_deref(),
+                "unknown_email",
             )
-                .into_response()
+            .await
         }
         Err(e) => {
             return (
@@ -130,6 +335,18 @@ pub async fn login(
         }
     };
 
+    // Accounts created through Google have no password to check
+    if user.password_hash == OAUTH_ONLY_PASSWORD_HASH {
+        return login_failed(
+            &throttle,
+            conn.as_mut(),
+            &payload.email,
+            ip.as_deref(),
+            "oauth_only",
+        )
+        .await;
+    }
+
     let parsed_hash = match PasswordHash::new(&user.password_hash) {
         Ok(h) => h,
         Err(_) => {
@@ -143,33 +360,666 @@ pub async fn login(
 
     if Argon2::default()
         .verify_password(payload.password.as_bytes(), &parsed_hash)
-        .is_ok()
+        .is_err()
     {
-        // Set Cookie
-        let mut cookie = Cookie::new("petpulse_user", user.id.to_string());
-        cookie.set_path("/");
-        cookie.set_http_only(true);
-        cookies.add(cookie);
-
-        tracing::Span::current()
-            .record("table", "users")
-            .record("action", "login_user")
-            .record("user_id", user.id)
-            .record("user_email", &user.email)
-            .record("business_event", "User logged in successfully")
-            .record("error", tracing::field::Empty);
-
-        (StatusCode::OK, Json(json!({"message": "Login successful"}))).into_response()
-    } else {
-        tracing::Span::current()
-            .record("table", "users")
-            .record("action", "login_user_failed")
-            .record("error", "invalid_credentials");
+        return login_failed(
+            &throttle,
+            conn.as_mut(),
+            &payload.email,
+            ip.as_deref(),
+            "invalid_password",
+        )
+        .await;
+    }
+    if needs_rehash(&parsed_hash) {
+        rehash_password(&db, user.id, &payload.password).await;
+    }
 
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(json!({"error": "Invalid email or password"})),
+    let meta = session_meta(&headers, ip.clone());
+    if let Err(response) =
+        start_session(&session_keys, conn.as_mut(), &cookies, user.id, &meta).await
+    {
+        return response;
+    }
+    if let Some(conn) = conn.as_mut() {
+        if let Err(e) = throttle.reset(conn, &payload.email).await {
+            tracing::warn!("Failed to reset login failures: {}", e);
+        }
+    }
+
+    tracing::Span::current()
+        .record("table", "users")
+        .record("action", "login_user")
+        .record("user_id", user.id)
+        .record("user_email", &user.email)
+        .record("business_event", "User logged in successfully")
+        .record("error", tracing::field::Empty);
+    crate::audit::record(&db, user.id, "login", None, ip);
+
+    (StatusCode::OK, Json(json!({"message": "Login successful"}))).into_response()
+}
+
+// POST /logout - Revoke the current session and clear the cookie
+pub async fn logout(
+    Extension(session_keys): Extension<SessionKeys>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(claims): Extension<SessionClaims>,
+    cookies: Cookies,
+) -> Response {
+    let revoked = match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => session::revoke_session(&mut conn, &claims).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = revoked {
+        tracing::error!("Failed to revoke session: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "Failed to log out"})),
         )
-            .into_response()
+            .into_response();
+    }
+
+    cookies.add(session_keys.removal_cookie());
+
+    tracing::Span::current()
+        .record("table", "users")
+        .record("action", "logout_user")
+        .record("user_id", claims.sub)
+        .record("business_event", "User logged out")
+        .record("error", tracing::field::Empty);
+
+    (StatusCode::OK, Json(json!({"message": "Logged out"}))).into_response()
+}
+
+// GET /users/sessions - The caller's active sessions
+pub async fn list_sessions(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(claims): Extension<SessionClaims>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let sessions = session::list_sessions(&mut conn, claims.sub)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let sessions: Vec<_> = sessions
+        .into_iter()
+        .map(|s| {
+            let current = s.id == claims.jti;
+            let mut value = json!(s);
+            value["current"] = json!(current);
+            value
+        })
+        .collect();
+    Ok((StatusCode::OK, Json(sessions)).into_response())
+}
+
+// DELETE /users/sessions/:id - Revoke one of the caller's sessions
+pub async fn revoke_session(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(claims): Extension<SessionClaims>,
+    Path(session_id): Path<String>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let revoked = session::revoke_session_id(&mut conn, claims.sub, &session_id)
+        .await
+        .map_err(ApiError::internal)?;
+    if !revoked {
+        return Err(ApiError::not_found("session_not_found"));
+    }
+
+    tracing::info!(user_id = claims.sub, "Session revoked");
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+const DEFAULT_RESET_TOKEN_TTL_MINS: i64 = 60;
+
+fn reset_token_ttl_mins() -> i64 {
+    std::env::var("PASSWORD_RESET_TTL_MINS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|m| *m > 0)
+        .unwrap_or(DEFAULT_RESET_TOKEN_TTL_MINS)
+}
+
+#[derive(serde::Deserialize)]
+pub struct ForgotPasswordRequest {
+    email: String,
+}
+
+// POST /auth/forgot-password - Email a single-use reset link
+pub async fn forgot_password(
+    Extension(db): Extension<DatabaseConnection>,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> Response {
+    // Lookup and delivery happen off the request so neither the response nor
+    // its timing reveals whether the email is registered
+    tokio::spawn(async move {
+        if let Err(e) = send_password_reset(&db, &payload.email).await {
+            tracing::error!("Failed to send password reset: {}", e);
+        }
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({"message": "If that email is registered, a reset link is on its way"})),
+    )
+        .into_response()
+}
+
+async fn send_password_reset(db: &DatabaseConnection, email: &str) -> Result<(), String> {
+    let Some(user) = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(db)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let now = chrono::Utc::now().naive_utc();
+
+    // Only the newest link works
+    password_reset_token::Entity::update_many()
+        .col_expr(password_reset_token::Column::UsedAt, Expr::value(now))
+        .filter(password_reset_token::Column::UserId.eq(user.id))
+        .filter(password_reset_token::Column::UsedAt.is_null())
+        .exec(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let secret = random_secret();
+    let ttl_mins = reset_token_ttl_mins();
+    let token = password_reset_token::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user.id),
+        token_hash: Set(hash_secret(&secret).map_err(|_| "Failed to hash reset token")?),
+        expires_at: Set(now + chrono::Duration::minutes(ttl_mins)),
+        used_at: Set(None),
+        created_at: Set(now),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let reset_link = format!(
+        "https://petpulse.dashboard/reset-password?token={}.{}",
+        token.id, secret
+    );
+    let body = NotificationTemplates::password_reset_email(&user.name, &reset_link, ttl_mins);
+    TwilioNotifier::new()
+        .await
+        .send_email(&user.email, "Reset your PetPulse password", &body)
+        .await?;
+
+    metrics::counter!("petpulse_password_resets_total", "stage" => "requested").increment(1);
+    Ok(())
+}
+
+#[derive(serde::Deserialize)]
+pub struct ResetPasswordRequest {
+    token: String,
+    new_password: String,
+}
+
+// POST /auth/reset-password - Redeem a reset token and set a new password
+pub async fn reset_password(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<Response, ApiError> {
+    let invalid = || ApiError::new(StatusCode::BAD_REQUEST, "reset_token_invalid");
+    let (token_id, secret) = split_token(&payload.token).ok_or_else(invalid)?;
+    let token = password_reset_token::Entity::find_by_id(token_id)
+        .one(&db)
+        .await?
+        .filter(|t| t.used_at.is_none() && verify_secret(secret, &t.token_hash))
+        .ok_or_else(invalid)?;
+
+    let now = chrono::Utc::now().naive_utc();
+    if token.expires_at <= now {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "reset_token_expired",
+        ));
+    }
+
+    // The same policy as registration; the token stays usable for another try
+    let user = user::Entity::find_by_id(token.user_id)
+        .one(&db)
+        .await?
+        .ok_or_else(invalid)?;
+    let mut errors = Vec::new();
+    validate_password(
+        "new_password",
+        &payload.new_password,
+        &user.email,
+        &mut errors,
+    );
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    let password_hash = Argon2::default()
+        .hash_password(
+            payload.new_password.as_bytes(),
+            &SaltString::generate(&mut OsRng),
+        )
+        .map_err(ApiError::internal)?
+        .to_string();
+
+    // Consume the token and change the password together; the used_at guard
+    // makes a concurrent redemption of the same token lose
+    let txn = db.begin().await?;
+    let claimed = password_reset_token::Entity::update_many()
+        .col_expr(password_reset_token::Column::UsedAt, Expr::value(now))
+        .filter(password_reset_token::Column::Id.eq(token.id))
+        .filter(password_reset_token::Column::UsedAt.is_null())
+        .exec(&txn)
+        .await?;
+    if claimed.rows_affected != 1 {
+        return Err(invalid());
+    }
+    user::Entity::update_many()
+        .col_expr(user::Column::PasswordHash, Expr::value(password_hash))
+        .col_expr(user::Column::UpdatedAt, Expr::value(now))
+        .filter(user::Column::Id.eq(token.user_id))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+
+    // Anyone holding an old session is signed out
+    let revoked = match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => session::revoke_user_sessions(&mut conn, token.user_id).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = revoked {
+        tracing::error!(
+            "Failed to revoke sessions after password reset for user {}: {}",
+            token.user_id,
+            e
+        );
+    }
+
+    tracing::Span::current()
+        .record("table", "users")
+        .record("action", "reset_password")
+        .record("user_id", token.user_id)
+        .record("business_event", "Password reset")
+        .record("error", tracing::field::Empty);
+    metrics::counter!("petpulse_password_resets_total", "stage" => "completed").increment(1);
+
+    Ok((StatusCode::OK, Json(json!({"message": "Password updated"}))).into_response())
+}
+
+// ============================================================================
+// Google sign-in (OAuth 2.0 authorization code flow)
+// ============================================================================
+
+const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_USERINFO_URL: &str = "https://openidconnect.googleapis.com/v1/userinfo";
+const OAUTH_STATE_COOKIE: &str = "petpulse_oauth_state";
+const OAUTH_STATE_TTL_MINS: i64 = 10;
+const DEFAULT_OAUTH_SUCCESS_REDIRECT: &str = "https://petpulse.dashboard/";
+
+/// Google client settings. The sign-in routes are only mounted when all of
+/// GOOGLE_CLIENT_ID, GOOGLE_CLIENT_SECRET and GOOGLE_REDIRECT_URL are set.
+#[derive(Clone)]
+pub struct GoogleOAuthConfig {
+    client_id: String,
+    client_secret: String,
+    redirect_url: String,
+    /// Where the browser lands after a successful sign-in
+    success_redirect: String,
+}
+
+impl GoogleOAuthConfig {
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Some(Self {
+            client_id: var("GOOGLE_CLIENT_ID")?,
+            client_secret: var("GOOGLE_CLIENT_SECRET")?,
+            redirect_url: var("GOOGLE_REDIRECT_URL")?,
+            success_redirect: var("GOOGLE_OAUTH_SUCCESS_REDIRECT")
+                .unwrap_or_else(|| DEFAULT_OAUTH_SUCCESS_REDIRECT.to_string()),
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct GoogleTokenResponse {
+    access_token: String,
+}
+
+#[derive(serde::Deserialize)]
+struct GoogleUserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+}
+
+// GET /auth/google - Redirect to Google's consent screen
+pub async fn google_login(
+    Extension(config): Extension<GoogleOAuthConfig>,
+    Extension(session_keys): Extension<SessionKeys>,
+    cookies: Cookies,
+) -> Result<Response, ApiError> {
+    // CSRF protection: the callback must echo back the state we set here
+    let state = random_secret();
+    let url = reqwest::Url::parse_with_params(
+        GOOGLE_AUTH_URL,
+        &[
+            ("client_id", config.client_id.as_str()),
+            ("redirect_uri", config.redirect_url.as_str()),
+            ("response_type", "code"),
+            ("scope", "openid email profile"),
+            ("state", state.as_str()),
+            ("prompt", "select_account"),
+        ],
+    )
+    .map_err(ApiError::internal)?;
+
+    let mut cookie = Cookie::new(OAUTH_STATE_COOKIE, state);
+    cookie.set_path("/auth/google");
+    session_keys.cookie_config().apply(&mut cookie);
+    // Lax even if sessions are Strict: the cookie must survive Google's redirect back
+    cookie.set_same_site(tower_cookies::cookie::SameSite::Lax);
+    cookie.set_max_age(tower_cookies::cookie::time::Duration::minutes(
+        OAUTH_STATE_TTL_MINS,
+    ));
+    cookies.add(cookie);
+
+    Ok(Redirect::to(url.as_str()).into_response())
+}
+
+#[derive(serde::Deserialize)]
+pub struct GoogleCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+// GET /auth/google/callback - Finish sign-in: exchange the code, then create
+// or link the user by verified email and issue a session
+#[allow(clippy::too_many_arguments)]
+pub async fn google_callback(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(session_keys): Extension<SessionKeys>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(config): Extension<GoogleOAuthConfig>,
+    cookies: Cookies,
+    headers: HeaderMap,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<GoogleCallbackQuery>,
+) -> Result<Response, ApiError> {
+    let expected_state = cookies
+        .get(OAUTH_STATE_COOKIE)
+        .map(|c| c.value().to_string());
+    let mut state_cookie = Cookie::from(OAUTH_STATE_COOKIE);
+    state_cookie.set_path("/auth/google");
+    session_keys.cookie_config().apply(&mut state_cookie);
+    cookies.remove(state_cookie);
+
+    if let Some(error) = query.error {
+        tracing::info!("Google sign-in cancelled or refused: {}", error);
+        metrics::counter!("petpulse_oauth_logins_total", "result" => "refused").increment(1);
+        return Err(ApiError::unauthorized());
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "oauth_invalid_state",
+        ));
+    };
+    if expected_state.as_deref() != Some(state.as_str()) {
+        tracing::warn!("Google sign-in callback with mismatched state");
+        metrics::counter!("petpulse_oauth_logins_total", "result" => "bad_state").increment(1);
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "oauth_invalid_state",
+        ));
+    }
+
+    let profile = fetch_google_profile(&config, &code).await.map_err(|e| {
+        tracing::error!("{}", e);
+        metrics::counter!("petpulse_oauth_logins_total", "result" => "provider_error").increment(1);
+        ApiError::new(StatusCode::BAD_GATEWAY, "oauth_provider_error")
+    })?;
+    let email = match profile.email {
+        Some(email) if profile.email_verified => email,
+        _ => {
+            metrics::counter!("petpulse_oauth_logins_total", "result" => "unverified_email")
+                .increment(1);
+            return Err(ApiError::forbidden("oauth_email_unverified"));
+        }
+    };
+
+    let user = link_google_user(&db, &profile.sub, &email, profile.name).await?;
+
+    let mut conn = redis_client.get_multiplexed_async_connection().await.ok();
+    let meta = session_meta(&headers, ip.clone());
+    if let Err(response) =
+        start_session(&session_keys, conn.as_mut(), &cookies, user.id, &meta).await
+    {
+        return Ok(response);
+    }
+    crate::audit::record(&db, user.id, "login_google", None, ip);
+
+    tracing::Span::current()
+        .record("table", "users")
+        .record("action", "login_user_google")
+        .record("user_id", user.id)
+        .record("user_email", &user.email)
+        .record("business_event", "User logged in with Google")
+        .record("error", tracing::field::Empty);
+    metrics::counter!("petpulse_oauth_logins_total", "result" => "success").increment(1);
+
+    Ok(Redirect::to(&config.success_redirect).into_response())
+}
+
+async fn fetch_google_profile(
+    config: &GoogleOAuthConfig,
+    code: &str,
+) -> Result<GoogleUserInfo, String> {
+    let client = reqwest::Client::new();
+    let token: GoogleTokenResponse = client
+        .post(GOOGLE_TOKEN_URL)
+        .form(&[
+            ("code", code),
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("redirect_uri", config.redirect_url.as_str()),
+            ("grant_type", "authorization_code"),
+        ])
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Google token exchange failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Bad Google token response: {}", e))?;
+
+    client
+        .get(GOOGLE_USERINFO_URL)
+        .bearer_auth(&token.access_token)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Google userinfo request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Bad Google userinfo response: {}", e))
+}
+
+/// Finds the user for a Google account: by linked subject first, then by
+/// email (linking the account), otherwise creates a password-less user.
+async fn link_google_user(
+    db: &DatabaseConnection,
+    google_sub: &str,
+    email: &str,
+    name: Option<String>,
+) -> Result<user::Model, ApiError> {
+    if let Some(user) = user::Entity::find()
+        .filter(user::Column::GoogleSub.eq(google_sub))
+        .one(db)
+        .await?
+    {
+        return Ok(user);
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    if let Some(existing) = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(db)
+        .await?
+    {
+        tracing::info!(
+            user_id = existing.id,
+            "Linking Google account to existing user"
+        );
+        let mut active = existing.into_active_model();
+        active.google_sub = Set(Some(google_sub.to_string()));
+        active.updated_at = Set(now);
+        return Ok(active.update(db).await?);
+    }
+
+    let user = user::ActiveModel {
+        email: Set(email.to_string()),
+        password_hash: Set(OAUTH_ONLY_PASSWORD_HASH.to_string()),
+        name: Set(name.unwrap_or_else(|| email.to_string())),
+        google_sub: Set(Some(google_sub.to_string())),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    tracing::Span::current()
+        .record("user_id", user.id)
+        .record("business_event", "User registered with Google");
+    metrics::counter!("petpulse_users_registered_total").increment(1);
+    metrics::gauge!("petpulse_users_total").increment(1.0);
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_with(argon2: Argon2<'_>) -> String {
+        argon2
+            .hash_password(b"correct horse", &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn current_hashes_are_kept() {
+        let hash = hash_with(Argon2::default());
+        assert!(!needs_rehash(&PasswordHash::new(&hash).unwrap()));
+    }
+
+    #[test]
+    fn weaker_parameters_are_rehashed() {
+        let params = argon2::Params::new(8 * 1024, 1, 1, None).unwrap();
+        let hash = hash_with(Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            params,
+        ));
+        let parsed = PasswordHash::new(&hash).unwrap();
+        assert!(needs_rehash(&parsed));
+        // It still verifies, so the login that triggers the re-hash succeeds
+        assert!(Argon2::default()
+            .verify_password(b"correct horse", &parsed)
+            .is_ok());
+    }
+
+    #[test]
+    fn other_argon2_variants_are_rehashed() {
+        let hash = hash_with(Argon2::new(
+            argon2::Algorithm::Argon2i,
+            argon2::Version::V0x13,
+            argon2::Params::default(),
+        ));
+        assert!(needs_rehash(&PasswordHash::new(&hash).unwrap()));
+    }
+
+    #[test]
+    fn oauth_only_accounts_have_no_password() {
+        assert!(PasswordHash::new(OAUTH_ONLY_PASSWORD_HASH).is_err());
+    }
+
+    #[test]
+    fn password_policy() {
+        let check = |password: &str| {
+            let mut errors = Vec::new();
+            validate_password("password", password, "Ada@Example.com", &mut errors);
+            errors.iter().map(|e| e.code).collect::<Vec<_>>()
+        };
+        assert_eq!(check("short"), ["field.too_short"]);
+        assert_eq!(check(" ada@example.com "), ["field.matches_email"]);
+        assert!(check("long enough passphrase").is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_passwords_follow_the_registration_policy() {
+        let db = crate::test_support::database().await;
+        crate::test_support::owner_with_pet(&db, 1, 1).await;
+        let now = chrono::Utc::now().naive_utc();
+        let token = password_reset_token::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(1),
+            token_hash: Set(hash_secret("s3cret").unwrap()),
+            expires_at: Set(now + chrono::Duration::minutes(30)),
+            used_at: Set(None),
+            created_at: Set(now),
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        let reset = |new_password: &str| {
+            let payload = serde_json::from_value(json!({
+                "token": format!("{}.s3cret", token.id),
+                "new_password": new_password,
+            }))
+            .unwrap();
+            reset_password(
+                Extension(db.clone()),
+                Extension(redis::Client::open("redis://127.0.0.1:1/").unwrap()),
+                Json(payload),
+            )
+        };
+
+        let err = reset(" Owner1@example.com ")
+            .await
+            .unwrap_err()
+            .into_response();
+        let body = axum::body::to_bytes(err.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["fields"][0]["field"], "new_password");
+        assert_eq!(body["fields"][0]["code"], "field.matches_email");
+
+        let err = reset("short").await.unwrap_err().into_response();
+        let (status, code) = crate::test_support::error_code(err).await;
+        assert_eq!((status.as_u16(), code.as_str()), (422, "validation_failed"));
+
+        // Refusals leave the token usable
+        let response = reset("long enough passphrase").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
     }
 }
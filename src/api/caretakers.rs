@@ -0,0 +1,235 @@
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::pet::owned_pet;
+use crate::entities::{pet, pet_caretaker, user};
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, ModelTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct InviteCaretakerRequest {
+    email: String,
+}
+
+#[derive(Serialize)]
+struct CaretakerView {
+    id: Uuid,
+    pet_id: i32,
+    user_id: i32,
+    email: String,
+    name: String,
+    role: String,
+    accepted_at: Option<chrono::NaiveDateTime>,
+    created_at: chrono::NaiveDateTime,
+}
+
+impl CaretakerView {
+    fn new(caretaker: pet_caretaker::Model, user: &user::Model) -> Self {
+        Self {
+            id: caretaker.id,
+            pet_id: caretaker.pet_id,
+            user_id: caretaker.user_id,
+            email: user.email.clone(),
+            name: user.name.clone(),
+            role: caretaker.role,
+            accepted_at: caretaker.accepted_at,
+            created_at: caretaker.created_at,
+        }
+    }
+}
+
+// POST /pets/:id/caretakers - Invite another account to help look after a pet
+pub async fn invite_caretaker(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    Path(pet_id): Path<i32>,
+    Json(payload): Json<InviteCaretakerRequest>,
+) -> Result<Response, ApiError> {
+    let email = payload.email.trim();
+    if email.is_empty() {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "email",
+            "field.required",
+        )]));
+    }
+
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let invitee = user::Entity::find()
+        .filter(user::Column::Email.eq(email))
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("caretaker_user_not_found"))?;
+    if invitee.id == user_id {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "email",
+            "field.invalid_format",
+        )]));
+    }
+
+    let existing = pet_caretaker::Entity::find()
+        .filter(pet_caretaker::Column::PetId.eq(pet_id))
+        .filter(pet_caretaker::Column::UserId.eq(invitee.id))
+        .one(&db)
+        .await?;
+    if existing.is_some() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "caretaker_already_added",
+        ));
+    }
+
+    let caretaker = pet_caretaker::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        pet_id: Set(pet_id),
+        user_id: Set(invitee.id),
+        role: Set(pet_caretaker::ROLE_CARETAKER.to_string()),
+        invited_by: Set(user_id),
+        accepted_at: Set(None),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    }
+    .insert(&db)
+    .await?;
+
+    tracing::info!(
+        pet_id,
+        user_id,
+        invitee_id = invitee.id,
+        "Caretaker invited"
+    );
+    crate::audit::record(
+        &db,
+        user_id,
+        "invite_caretaker",
+        Some(("pet", pet_id.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CaretakerView::new(caretaker, &invitee)),
+    )
+        .into_response())
+}
+
+// GET /pets/:id/caretakers - Caretakers of a pet, pending invites included
+pub async fn list_caretakers(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+) -> Result<Response, ApiError> {
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let caretakers: Vec<CaretakerView> = pet_caretaker::Entity::find()
+        .filter(pet_caretaker::Column::PetId.eq(pet_id))
+        .find_also_related(user::Entity)
+        .order_by_asc(pet_caretaker::Column::CreatedAt)
+        .all(&db)
+        .await?
+        .into_iter()
+        .filter_map(|(c, u)| u.map(|u| CaretakerView::new(c, &u)))
+        .collect();
+
+    Ok((StatusCode::OK, Json(caretakers)).into_response())
+}
+
+// GET /caretaker-invites - Pending invites addressed to the caller
+pub async fn list_caretaker_invites(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+) -> Result<Response, ApiError> {
+    let invites: Vec<_> = pet_caretaker::Entity::find()
+        .filter(pet_caretaker::Column::UserId.eq(user_id))
+        .filter(pet_caretaker::Column::AcceptedAt.is_null())
+        .find_also_related(pet::Entity)
+        .order_by_desc(pet_caretaker::Column::CreatedAt)
+        .all(&db)
+        .await?
+        .into_iter()
+        .filter_map(|(invite, pet)| {
+            let pet = pet.filter(|p| !p.is_archived())?;
+            Some(json!({
+                "id": invite.id,
+                "pet_id": pet.id,
+                "pet_name": pet.name,
+                "species": pet.species,
+                "role": invite.role,
+                "invited_by": invite.invited_by,
+                "created_at": invite.created_at,
+            }))
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(invites)).into_response())
+}
+
+// POST /caretaker-invites/:id/accept - Accept a pending invite
+pub async fn accept_caretaker_invite(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(invite_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let invite = pet_caretaker::Entity::find_by_id(invite_id)
+        .filter(pet_caretaker::Column::UserId.eq(user_id))
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("caretaker_not_found"))?;
+
+    if invite.is_accepted() {
+        return Ok((StatusCode::OK, Json(invite)).into_response());
+    }
+
+    let mut active = invite.into_active_model();
+    active.accepted_at = Set(Some(chrono::Utc::now().naive_utc()));
+    let invite = active.update(&db).await?;
+
+    tracing::info!(pet_id = invite.pet_id, user_id, "Caretaker invite accepted");
+
+    Ok((StatusCode::OK, Json(invite)).into_response())
+}
+
+// DELETE /pets/:id/caretakers/:user_id - Remove a caretaker. The owner can
+// remove anyone; a caretaker can remove themselves (or decline an invite).
+pub async fn remove_caretaker(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    Path((pet_id, caretaker_id)): Path<(i32, i32)>,
+) -> Result<Response, ApiError> {
+    if caretaker_id != user_id {
+        owned_pet(&db, pet_id, user_id).await?;
+    }
+
+    let caretaker = pet_caretaker::Entity::find()
+        .filter(pet_caretaker::Column::PetId.eq(pet_id))
+        .filter(pet_caretaker::Column::UserId.eq(caretaker_id))
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("caretaker_not_found"))?;
+    caretaker.delete(&db).await?;
+
+    tracing::info!(pet_id, user_id, caretaker_id, "Caretaker removed");
+    crate::audit::record(
+        &db,
+        user_id,
+        "remove_caretaker",
+        Some(("pet", pet_id.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({"message": "Caretaker removed"})),
+    )
+        .into_response())
+}
@@ -0,0 +1,45 @@
+use crate::activity::{SpeciesGroup, ALL_ACTIVITIES};
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct ActivityCatalogParams {
+    /// Free-text species; limits the list to activities that apply to it
+    species: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ActivityCatalogEntry {
+    pub name: &'static str,
+    pub label: &'static str,
+    /// Empty when the activity applies to every species
+    pub species: &'static [SpeciesGroup],
+}
+
+// GET /catalog/activities - Canonical activity names the frontend keys icons on
+pub async fn list_activities(Query(params): Query<ActivityCatalogParams>) -> Response {
+    let species = params.species.as_deref().map(SpeciesGroup::from_species);
+    let activities: Vec<ActivityCatalogEntry> = ALL_ACTIVITIES
+        .iter()
+        .filter(|a| species.is_none_or(|s| a.applies_to(s)))
+        .map(|a| ActivityCatalogEntry {
+            name: a.as_str(),
+            label: a.label(),
+            species: a.species(),
+        })
+        .collect();
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "activities": activities,
+            "other_prefix": crate::activity::OTHER_PREFIX,
+        })),
+    )
+        .into_response()
+}
@@ -0,0 +1,178 @@
+//! The caller's IP for throttling and audit logs. It's the TCP peer address
+//! unless that peer is one of our own proxies (TRUSTED_PROXIES, a comma
+//! separated list of IPs or CIDRs), in which case `X-Forwarded-For` is read
+//! right to left and the first hop we don't run is the client. Anything a
+//! client writes into the header itself sits to the left of that and is
+//! ignored.
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{request::Parts, HeaderMap},
+};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+
+/// An address or network we trust to set `X-Forwarded-For`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedProxy {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl TrustedProxy {
+    /// Parses `10.0.0.1` or `10.0.0.0/8` (IPv6 alike).
+    pub fn parse(s: &str) -> Option<Self> {
+        let (addr, prefix) = match s.trim().split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+            None => (s.trim(), None),
+        };
+        let network: IpAddr = addr.parse().ok()?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { network, prefix })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+static TRUSTED_PROXIES: LazyLock<Vec<TrustedProxy>> = LazyLock::new(|| {
+    std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .filter_map(|s| {
+            let proxy = TrustedProxy::parse(s);
+            if proxy.is_none() {
+                tracing::warn!("Ignoring unparseable TRUSTED_PROXIES entry {:?}", s);
+            }
+            proxy
+        })
+        .collect()
+});
+
+/// Resolves the client address from the peer and its `X-Forwarded-For`.
+pub fn resolve(peer: IpAddr, headers: &HeaderMap, trusted: &[TrustedProxy]) -> IpAddr {
+    let is_trusted = |ip: IpAddr| trusted.iter().any(|proxy| proxy.contains(ip));
+    if !is_trusted(peer) {
+        return peer;
+    }
+
+    let mut client = peer;
+    let hops = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .collect::<Vec<_>>();
+    for hop in hops.into_iter().rev() {
+        // A hop that isn't an address can't be vouched for; stop at the last good one
+        let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        client = ip;
+        if !is_trusted(ip) {
+            break;
+        }
+    }
+    client
+}
+
+/// The caller's address, or None when the server wasn't started with
+/// connect info (it always is outside tests).
+pub struct ClientIp(pub Option<String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(peer)| resolve(peer.ip(), &parts.headers, &TRUSTED_PROXIES));
+        Ok(ClientIp(ip.map(|ip| ip.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xff(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", value.parse().unwrap());
+        headers
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn untrusted_peer_ignores_forwarded_for() {
+        let trusted = [TrustedProxy::parse("10.0.0.0/8").unwrap()];
+        let resolved = resolve(ip("203.0.113.9"), &xff("1.2.3.4"), &trusted);
+        assert_eq!(resolved, ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn no_trusted_proxies_uses_peer() {
+        let resolved = resolve(ip("10.0.0.2"), &xff("1.2.3.4"), &[]);
+        assert_eq!(resolved, ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn trusted_peer_takes_right_most_untrusted_hop() {
+        let trusted = [TrustedProxy::parse("10.0.0.0/8").unwrap()];
+        // The client prepended a forged 1.2.3.4; our proxy appended the real address
+        let headers = xff("1.2.3.4, 198.51.100.7, 10.0.0.3");
+        let resolved = resolve(ip("10.0.0.2"), &headers, &trusted);
+        assert_eq!(resolved, ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn all_trusted_hops_fall_back_to_left_most() {
+        let trusted = [TrustedProxy::parse("10.0.0.0/8").unwrap()];
+        let resolved = resolve(ip("10.0.0.2"), &xff("10.1.1.1, 10.0.0.3"), &trusted);
+        assert_eq!(resolved, ip("10.1.1.1"));
+    }
+
+    #[test]
+    fn garbage_hop_stops_the_walk() {
+        let trusted = [TrustedProxy::parse("10.0.0.0/8").unwrap()];
+        let resolved = resolve(ip("10.0.0.2"), &xff("1.2.3.4, nonsense"), &trusted);
+        assert_eq!(resolved, ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn parses_addresses_and_networks() {
+        assert!(TrustedProxy::parse("10.0.0.1")
+            .unwrap()
+            .contains(ip("10.0.0.1")));
+        assert!(!TrustedProxy::parse("10.0.0.1")
+            .unwrap()
+            .contains(ip("10.0.0.2")));
+        assert!(TrustedProxy::parse("fd00::/8")
+            .unwrap()
+            .contains(ip("fd12::1")));
+        assert!(TrustedProxy::parse("0.0.0.0/0")
+            .unwrap()
+            .contains(ip("8.8.8.8")));
+        assert!(TrustedProxy::parse("10.0.0.0/33").is_none());
+        assert!(TrustedProxy::parse("proxy").is_none());
+    }
+}
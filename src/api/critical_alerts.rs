@@ -1,32 +1,40 @@
-use crate::entities::{alerts, pet, prelude::*};
+use crate::api::client_ip::ClientIp;
+use crate::api::extract::{OwnedAlert, ReadableAlert, ReadablePet};
+use crate::api::pagination::Pagination;
+use crate::api::pet::accessible_pets;
+use crate::api::video::{load_video_previews, VideoPreview};
+use crate::entities::{alerts, notification_log, pet, prelude::*, NotificationLog};
 use axum::{
-    extract::{Extension, Path, Query},
+    extract::{Extension, Query},
     response::IntoResponse,
     Json,
 };
+use google_cloud_storage::client::Client as GcsClient;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tracing::{error, info};
+use tracing::error;
 use uuid::Uuid;
 
 #[derive(Deserialize)]
-pub struct PaginationParams {
-    #[serde(default = "default_page")]
-    pub page: u64,
-    #[serde(default = "default_page_size")]
-    pub page_size: u64,
+pub struct AlertListParams {
     pub severity_level: Option<String>,
+    /// Comma-separated list of related resources to embed (currently only `video`)
+    pub include: Option<String>,
 }
 
-fn default_page() -> u64 {
-    1
+#[derive(Deserialize)]
+pub struct IncludeParams {
+    pub include: Option<String>,
 }
-fn default_page_size() -> u64 {
-    10
+
+fn includes_video(include: &Option<String>) -> bool {
+    include
+        .as_deref()
+        .map(|i| i.split(',').any(|part| part.trim() == "video"))
+        .unwrap_or(false)
 }
 
 #[derive(Serialize)]
@@ -36,6 +44,8 @@ pub struct AlertResponse {
     pub pet_name: Option<String>,
     pub alert_type: String,
     pub severity_level: String,
+    /// Display label for `severity_level` in the request's Accept-Language
+    pub severity_label: String,
     pub message: Option<String>,
     pub critical_indicators: Option<serde_json::Value>,
     pub recommended_actions: Option<serde_json::Value>,
@@ -49,6 +59,72 @@ pub struct AlertResponse {
     pub notification_channels: Option<serde_json::Value>,
     pub intervention_action: Option<String>,
     pub video_id: Option<String>,
+    pub occurrence_count: i32,
+    pub first_seen_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    /// Present only with `?include=video`; null when the video no longer exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<Option<VideoPreview>>,
+}
+
+impl AlertResponse {
+    pub fn from_model(alert: alerts::Model, pet_name: Option<String>) -> Self {
+        Self {
+            video_id: alert.video_id.map(|id| id.to_string()).or_else(|| {
+                alert
+                    .payload
+                    .get("video_id")
+                    .and_then(|v| v.as_str().map(String::from))
+            }),
+            id: alert.id,
+            pet_id: alert.pet_id,
+            pet_name,
+            alert_type: alert.alert_type,
+            severity_label: crate::api::i18n::severity_label(&alert.severity_level),
+            severity_level: alert.severity_level,
+            message: alert.message,
+            critical_indicators: alert.critical_indicators,
+            recommended_actions: alert.recommended_actions,
+            created_at: alert.created_at,
+            outcome: alert.outcome,
+            user_response: alert.user_response,
+            user_acknowledged_at: alert.user_acknowledged_at,
+            user_notified_at: alert.user_notified_at,
+            notification_sent: alert.notification_sent,
+            notification_channels: alert.notification_channels,
+            intervention_action: alert.intervention_action,
+            occurrence_count: alert.occurrence_count,
+            first_seen_at: alert.first_seen_at,
+            last_seen_at: alert.last_seen_at,
+            video: None,
+        }
+    }
+}
+
+/// Embeds video previews into a page of alerts using a single batched lookup.
+async fn attach_video_previews(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    alerts: &mut [AlertResponse],
+) -> Result<(), sea_orm::DbErr> {
+    let video_ids: Vec<Uuid> = alerts
+        .iter()
+        .filter_map(|a| a.video_id.as_deref())
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect();
+
+    let previews = load_video_previews(db, gcs_client, video_ids).await?;
+
+    for alert in alerts.iter_mut() {
+        let preview = alert
+            .video_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .and_then(|id| previews.get(&id).cloned());
+        alert.video = Some(preview);
+    }
+
+    Ok(())
 }
 
 #[derive(Serialize)]
@@ -67,15 +143,13 @@ pub struct AcknowledgeRequest {
 // GET /alerts - List all alerts for authenticated user
 pub async fn list_user_alerts(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
     Extension(user_id): Extension<i32>,
-    Query(params): Query<PaginationParams>,
+    pagination: Pagination,
+    Query(params): Query<AlertListParams>,
 ) -> impl IntoResponse {
-    // Get all pets for this user first
-    let user_pets = match pet::Entity::find()
-        .filter(pet::Column::UserId.eq(user_id))
-        .all(&db)
-        .await
-    {
+    // Get all pets this user owns or cares for first
+    let user_pets = match accessible_pets(&db, user_id).await {
         Ok(pets) => pets,
         Err(e) => {
             error!("Failed to fetch user pets: {}", e);
@@ -95,8 +169,8 @@ pub async fn list_user_alerts(
             Json(AlertListResponse {
                 alerts: vec![],
                 total: 0,
-                page: params.page,
-                page_size: params.page_size,
+                page: pagination.page,
+                page_size: pagination.page_size,
             }),
         )
             .into_response();
@@ -109,7 +183,10 @@ pub async fn list_user_alerts(
         query = query.filter(alerts::Column::SeverityLevel.eq(severity));
     }
 
-    query = query.order_by_desc(alerts::Column::CreatedAt);
+    // Repeating alerts bubble up by their most recent occurrence
+    query = query.order_by_desc(Expr::cust(
+        "COALESCE(alerts.last_seen_at, alerts.created_at)",
+    ));
 
     // Get total count
     let total = match query.clone().count(&db).await {
@@ -125,8 +202,8 @@ pub async fn list_user_alerts(
     };
 
     // Fetch paginated results using paginate method
-    let paginator = query.paginate(&db, params.page_size);
-    let alerts_result = paginator.fetch_page(params.page - 1).await;
+    let paginator = query.paginate(&db, pagination.page_size);
+    let alerts_result = paginator.fetch_page(pagination.index()).await;
 
     match alerts_result {
         Ok(alerts) => {
@@ -134,40 +211,32 @@ pub async fn list_user_alerts(
             let pet_map: std::collections::HashMap<i32, String> =
                 user_pets.into_iter().map(|p| (p.id, p.name)).collect();
 
-            let response: Vec<AlertResponse> = alerts
+            let mut response: Vec<AlertResponse> = alerts
                 .into_iter()
-                .map(|alert| AlertResponse {
-                    id: alert.id,
-                    pet_id: alert.pet_id,
-                    pet_name: pet_map.get(&alert.pet_id).cloned(),
-                    alert_type: alert.alert_type,
-                    severity_level: alert.severity_level,
-                    message: alert.message,
-                    critical_indicators: alert.critical_indicators,
-                    recommended_actions: alert.recommended_actions,
-                    created_at: alert.created_at,
-                    outcome: alert.outcome,
-                    user_response: alert.user_response,
-                    user_acknowledged_at: alert.user_acknowledged_at,
-                    user_notified_at: alert.user_notified_at,
-                    notification_sent: alert.notification_sent,
-
-                    notification_channels: alert.notification_channels,
-                    intervention_action: alert.intervention_action,
-                    video_id: alert
-                        .payload
-                        .get("video_id")
-                        .and_then(|v| v.as_str().map(String::from)),
+                .map(|alert| {
+                    let pet_name = pet_map.get(&alert.pet_id).cloned();
+                    AlertResponse::from_model(alert, pet_name)
                 })
                 .collect();
 
+            if includes_video(&params.include) {
+                if let Err(e) = attach_video_previews(&db, &gcs_client, &mut response).await {
+                    error!("Failed to load alert videos: {}", e);
+                    return (
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to load alert videos",
+                    )
+                        .into_response();
+                }
+            }
+
             (
                 axum::http::StatusCode::OK,
                 Json(AlertListResponse {
                     alerts: response,
                     total,
-                    page: params.page,
-                    page_size: params.page_size,
+                    page: pagination.page,
+                    page_size: pagination.page_size,
                 }),
             )
                 .into_response()
@@ -186,33 +255,22 @@ pub async fn list_user_alerts(
 // GET /pets/:id/alerts - List alerts for specific pet
 pub async fn list_pet_alerts(
     Extension(db): Extension<DatabaseConnection>,
-    Extension(user_id): Extension<i32>,
-    Path(pet_id): Path<i32>,
-    Query(params): Query<PaginationParams>,
+    Extension(gcs_client): Extension<GcsClient>,
+    ReadablePet(pet): ReadablePet,
+    pagination: Pagination,
+    Query(params): Query<AlertListParams>,
 ) -> impl IntoResponse {
-    // Verify pet belongs to user
-    let pet = match pet::Entity::find_by_id(pet_id).one(&db).await {
-        Ok(Some(p)) if p.user_id == user_id => p,
-        Ok(Some(_)) => return (axum::http::StatusCode::FORBIDDEN, "Not your pet").into_response(),
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Pet not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch pet: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
-
     // Build query
-    let mut query = Alerts::find().filter(alerts::Column::PetId.eq(pet_id));
+    let mut query = Alerts::find().filter(alerts::Column::PetId.eq(pet.id));
 
     if let Some(severity) = &params.severity_level {
         query = query.filter(alerts::Column::SeverityLevel.eq(severity));
     }
 
-    query = query.order_by_desc(alerts::Column::CreatedAt);
+    // Repeating alerts bubble up by their most recent occurrence
+    query = query.order_by_desc(Expr::cust(
+        "COALESCE(alerts.last_seen_at, alerts.created_at)",
+    ));
 
     // Get total count
     let total = match query.clone().count(&db).await {
@@ -228,44 +286,34 @@ pub async fn list_pet_alerts(
     };
 
     // Fetch paginated results using paginate method
-    let paginator = query.paginate(&db, params.page_size);
-    let alerts_result = paginator.fetch_page(params.page - 1).await;
+    let paginator = query.paginate(&db, pagination.page_size);
+    let alerts_result = paginator.fetch_page(pagination.index()).await;
 
     match alerts_result {
         Ok(alerts) => {
-            let response: Vec<AlertResponse> = alerts
+            let mut response: Vec<AlertResponse> = alerts
                 .into_iter()
-                .map(|alert| AlertResponse {
-                    id: alert.id,
-                    pet_id: alert.pet_id,
-                    pet_name: Some(pet.name.clone()),
-                    alert_type: alert.alert_type,
-                    severity_level: alert.severity_level,
-                    message: alert.message,
-                    critical_indicators: alert.critical_indicators,
-                    recommended_actions: alert.recommended_actions,
-                    created_at: alert.created_at,
-                    outcome: alert.outcome,
-                    user_response: alert.user_response,
-                    user_acknowledged_at: alert.user_acknowledged_at,
-                    user_notified_at: alert.user_notified_at,
-                    notification_sent: alert.notification_sent,
-                    notification_channels: alert.notification_channels,
-                    intervention_action: alert.intervention_action,
-                    video_id: alert
-                        .payload
-                        .get("video_id")
-                        .and_then(|v| v.as_str().map(String::from)),
-                })
+                .map(|alert| AlertResponse::from_model(alert, Some(pet.name.clone())))
                 .collect();
 
+            if includes_video(&params.include) {
+                if let Err(e) = attach_video_previews(&db, &gcs_client, &mut response).await {
+                    error!("Failed to load alert videos: {}", e);
+                    return (
+                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to load alert videos",
+                    )
+                        .into_response();
+                }
+            }
+
             (
                 axum::http::StatusCode::OK,
                 Json(AlertListResponse {
                     alerts: response,
                     total,
-                    page: params.page,
-                    page_size: params.page_size,
+                    page: pagination.page,
+                    page_size: pagination.page_size,
                 }),
             )
                 .into_response()
@@ -300,28 +348,7 @@ pub async fn get_pending_critical_alerts(
         Ok(alerts) => {
             let response: Vec<AlertResponse> = alerts
                 .into_iter()
-                .map(|alert| AlertResponse {
-                    id: alert.id,
-                    pet_id: alert.pet_id,
-                    pet_name: None,
-                    alert_type: alert.alert_type,
-                    severity_level: alert.severity_level,
-                    message: alert.message,
-                    critical_indicators: alert.critical_indicators,
-                    recommended_actions: alert.recommended_actions,
-                    created_at: alert.created_at,
-                    outcome: alert.outcome,
-                    user_response: alert.user_response,
-                    user_acknowledged_at: alert.user_acknowledged_at,
-                    user_notified_at: alert.user_notified_at,
-                    notification_sent: alert.notification_sent,
-                    notification_channels: alert.notification_channels,
-                    intervention_action: alert.intervention_action,
-                    video_id: alert
-                        .payload
-                        .get("video_id")
-                        .and_then(|v| v.as_str().map(String::from)),
-                })
+                .map(|alert| AlertResponse::from_model(alert, None))
                 .collect();
 
             (axum::http::StatusCode::OK, Json(response)).into_response()
@@ -340,41 +367,38 @@ pub async fn get_pending_critical_alerts(
 // POST /alerts/:id/acknowledge
 pub async fn acknowledge_alert(
     Extension(db): Extension<DatabaseConnection>,
-    Path(alert_id): Path<Uuid>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    OwnedAlert { alert, .. }: OwnedAlert,
     Json(payload): Json<AcknowledgeRequest>,
 ) -> impl IntoResponse {
-    let alert = match Alerts::find_by_id(alert_id).one(&db).await {
-        Ok(Some(a)) => a,
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Alert not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch alert: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
+    // Calculate duration
+    let duration = chrono::Utc::now()
+        .naive_utc()
+        .signed_duration_since(alert.created_at);
+    crate::metrics::record_acknowledgment_time(duration.num_seconds() as f64);
 
+    let alert_id = alert.id;
     let mut active_model: alerts::ActiveModel = alert.into();
     active_model.user_acknowledged_at = Set(Some(chrono::Utc::now().naive_utc()));
     active_model.user_response = Set(Some(payload.response));
     active_model.outcome = Set(Some("Acknowledged by User".to_string()));
 
-    // Calculate duration
-    if let Ok(Some(alert_ro)) = alerts::Entity::find_by_id(alert_id).one(&db).await {
-        let duration = chrono::Utc::now()
-            .naive_utc()
-            .signed_duration_since(alert_ro.created_at);
-        crate::metrics::record_acknowledgment_time(duration.num_seconds() as f64);
-    }
-
     match active_model.update(&db).await {
-        Ok(_) => (
-            axum::http::StatusCode::OK,
-            Json(serde_json::json!({"status": "acknowledged"})),
-        )
-            .into_response(),
+        Ok(_) => {
+            crate::audit::record(
+                &db,
+                user_id,
+                "acknowledge_alert",
+                Some(("alert", alert_id.to_string())),
+                ip,
+            );
+            (
+                axum::http::StatusCode::OK,
+                Json(serde_json::json!({"status": "acknowledged"})),
+            )
+                .into_response()
+        }
         Err(e) => {
             error!("Failed to acknowledge alert: {}", e);
             (
@@ -389,35 +413,84 @@ pub async fn acknowledge_alert(
 // POST /alerts/:id/resolve
 pub async fn resolve_alert(
     Extension(db): Extension<DatabaseConnection>,
-    Path(alert_id): Path<Uuid>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    OwnedAlert { alert, .. }: OwnedAlert,
 ) -> impl IntoResponse {
-    let alert = match Alerts::find_by_id(alert_id).one(&db).await {
-        Ok(Some(a)) => a,
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Alert not found").into_response(),
+    let alert_id = alert.id;
+    let mut active_model: alerts::ActiveModel = alert.into();
+    active_model.outcome = Set(Some("Resolved".to_string())); // Standardized string
+
+    match active_model.update(&db).await {
+        Ok(_) => {
+            crate::audit::record(
+                &db,
+                user_id,
+                "resolve_alert",
+                Some(("alert", alert_id.to_string())),
+                ip,
+            );
+            (
+                axum::http::StatusCode::OK,
+                Json(serde_json::json!({"status": "resolved"})),
+            )
+                .into_response()
+        }
         Err(e) => {
-            error!("Failed to fetch alert: {}", e);
-            return (
+            error!("Failed to resolve alert: {}", e);
+            (
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
+                "Failed to update alert",
             )
-                .into_response();
+                .into_response()
         }
-    };
+    }
+}
 
-    let mut active_model: alerts::ActiveModel = alert.into();
-    active_model.outcome = Set(Some("Resolved".to_string())); // Standardized string
+// POST /alerts/:id/mark-expected - Add the alert's indicators to the pet's known behaviors
+pub async fn mark_alert_expected(
+    Extension(db): Extension<DatabaseConnection>,
+    OwnedAlert { alert, pet }: OwnedAlert,
+) -> impl IntoResponse {
+    let indicators: Vec<String> = alert
+        .critical_indicators
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if indicators.is_empty() {
+        return (
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+            "Alert has no indicators to mark as expected",
+        )
+            .into_response();
+    }
 
-    match active_model.update(&db).await {
-        Ok(_) => (
+    let behaviors = pet::merge_known_behaviors(pet.known_behavior_list(), indicators);
+
+    let mut active_pet: pet::ActiveModel = pet.into();
+    active_pet.known_behaviors = Set(serde_json::json!(behaviors));
+    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+
+    match active_pet.update(&db).await {
+        Ok(p) => (
             axum::http::StatusCode::OK,
-            Json(serde_json::json!({"status": "resolved"})),
+            Json(serde_json::json!({
+                "pet_id": p.id,
+                "known_behaviors": p.known_behaviors,
+            })),
         )
             .into_response(),
         Err(e) => {
-            error!("Failed to resolve alert: {}", e);
+            error!("Failed to update known behaviors: {}", e);
             (
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to update alert",
+                "Failed to update pet",
             )
                 .into_response()
         }
@@ -427,13 +500,68 @@ pub async fn resolve_alert(
 // GET /alerts/:id
 pub async fn get_alert(
     Extension(db): Extension<DatabaseConnection>,
-    Path(alert_id): Path<Uuid>,
+    Extension(gcs_client): Extension<GcsClient>,
+    ReadableAlert { alert, pet }: ReadableAlert,
+    Query(params): Query<IncludeParams>,
+) -> impl IntoResponse {
+    let mut response = [AlertResponse::from_model(alert, Some(pet.name))];
+
+    if includes_video(&params.include) {
+        if let Err(e) = attach_video_previews(&db, &gcs_client, &mut response).await {
+            error!("Failed to load alert video: {}", e);
+            return (
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load alert video",
+            )
+                .into_response();
+        }
+    }
+
+    let [response] = response;
+    (axum::http::StatusCode::OK, Json(response)).into_response()
+}
+
+#[derive(Serialize)]
+pub struct TimelineEvent {
+    pub at: chrono::NaiveDateTime,
+    pub event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminder_number: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl TimelineEvent {
+    fn new(at: chrono::NaiveDateTime, event: &str) -> Self {
+        Self {
+            at,
+            event: event.to_string(),
+            channel: None,
+            status: None,
+            reminder_number: None,
+            detail: None,
+        }
+    }
+}
+
+// GET /alerts/:id/timeline - Lifecycle of an alert including every notification sent
+pub async fn get_alert_timeline(
+    Extension(db): Extension<DatabaseConnection>,
+    ReadableAlert { alert, pet }: ReadableAlert,
 ) -> impl IntoResponse {
-    let alert = match Alerts::find_by_id(alert_id).one(&db).await {
-        Ok(Some(a)) => a,
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Alert not found").into_response(),
+    let notifications = match NotificationLog::find()
+        .filter(notification_log::Column::AlertId.eq(alert.id))
+        .order_by_asc(notification_log::Column::CreatedAt)
+        .all(&db)
+        .await
+    {
+        Ok(n) => n,
         Err(e) => {
-            error!("Failed to fetch alert: {}", e);
+            error!("Failed to fetch notification log: {}", e);
             return (
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
                 "Database error",
@@ -442,33 +570,254 @@ pub async fn get_alert(
         }
     };
 
-    let pet_name = match pet::Entity::find_by_id(alert.pet_id).one(&db).await {
-        Ok(Some(p)) => Some(p.name),
-        _ => None,
-    };
+    let mut events = vec![TimelineEvent::new(alert.created_at, "created")];
+
+    if alert.occurrence_count > 1 {
+        if let Some(last_seen) = alert.last_seen_at {
+            let mut event = TimelineEvent::new(last_seen, "last_seen");
+            event.detail = Some(format!("{} occurrences", alert.occurrence_count));
+            events.push(event);
+        }
+    }
+
+    if let Some(action_at) = alert.intervention_time {
+        let mut event = TimelineEvent::new(action_at, "intervention");
+        event.detail = alert.intervention_action.clone();
+        events.push(event);
+    }
+
+    for n in notifications {
+        let mut event = TimelineEvent::new(n.created_at, &n.kind);
+        event.channel = Some(n.channel);
+        event.status = Some(n.status);
+        event.reminder_number = n.reminder_number;
+        event.detail = n.error_message;
+        events.push(event);
+    }
+
+    if let Some(ack_at) = alert.user_acknowledged_at {
+        let mut event = TimelineEvent::new(ack_at, "acknowledged");
+        event.detail = alert.user_response.clone();
+        events.push(event);
+    }
+
+    events.sort_by_key(|e| e.at);
+
+    (
+        axum::http::StatusCode::OK,
+        Json(serde_json::json!({
+            "alert_id": alert.id,
+            "pet_id": pet.id,
+            "outcome": alert.outcome,
+            "reminder_count": alert.reminder_count,
+            "events": events,
+        })),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+pub struct HeatmapParams {
+    #[serde(default = "default_heatmap_days")]
+    pub days: i64,
+    pub alert_type: Option<String>,
+    pub severity_level: Option<String>,
+}
+
+fn default_heatmap_days() -> i64 {
+    90
+}
 
-    let response = AlertResponse {
-        id: alert.id,
-        pet_id: alert.pet_id,
-        pet_name,
-        alert_type: alert.alert_type,
-        severity_level: alert.severity_level,
-        message: alert.message,
-        critical_indicators: alert.critical_indicators,
-        recommended_actions: alert.recommended_actions,
-        created_at: alert.created_at,
-        outcome: alert.outcome,
-        user_response: alert.user_response,
-        user_acknowledged_at: alert.user_acknowledged_at,
-        user_notified_at: alert.user_notified_at,
-        notification_sent: alert.notification_sent,
-        notification_channels: alert.notification_channels,
-        intervention_action: alert.intervention_action,
-        video_id: alert
-            .payload
-            .get("video_id")
-            .and_then(|v| v.as_str().map(String::from)),
+const MAX_HEATMAP_DAYS: i64 = 365;
+
+/// 7x24 grid of alert counts. Rows are weekdays (Monday first), columns are hours.
+#[derive(Serialize)]
+pub struct HeatmapMatrix {
+    pub counts: Vec<Vec<i64>>,
+    pub weekday_totals: Vec<i64>,
+    pub hour_totals: Vec<i64>,
+    pub total: i64,
+}
+
+impl HeatmapMatrix {
+    fn new() -> Self {
+        Self {
+            counts: vec![vec![0; 24]; 7],
+            weekday_totals: vec![0; 7],
+            hour_totals: vec![0; 24],
+            total: 0,
+        }
+    }
+
+    fn add(&mut self, weekday: usize, hour: usize, count: i64) {
+        if weekday >= 7 || hour >= 24 {
+            return;
+        }
+        self.counts[weekday][hour] += count;
+        self.weekday_totals[weekday] += count;
+        self.hour_totals[hour] += count;
+        self.total += count;
+    }
+}
+
+#[derive(Serialize)]
+pub struct HeatmapResponse {
+    pub pet_id: i32,
+    pub days: i64,
+    pub timezone: String,
+    #[serde(flatten)]
+    pub overall: HeatmapMatrix,
+    pub by_outcome: std::collections::HashMap<String, HeatmapMatrix>,
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct HeatmapRow {
+    weekday: i32,
+    hour: i32,
+    outcome: Option<String>,
+    count: i64,
+}
+
+// GET /pets/:id/alerts/heatmap - Alert distribution by weekday and hour
+pub async fn get_pet_alert_heatmap(
+    Extension(db): Extension<DatabaseConnection>,
+    ReadablePet(pet): ReadablePet,
+    Query(params): Query<HeatmapParams>,
+) -> impl IntoResponse {
+    let days = params.days.clamp(1, MAX_HEATMAP_DAYS);
+    let since = chrono::Utc::now().naive_utc() - chrono::Duration::days(days);
+
+    // Buckets are the owner's local weekday and hour, so a morning pattern
+    // shows up in the morning wherever they live
+    let tz = crate::timezone::of_pet_owner(&db, pet.id).await;
+
+    // Single grouped query; served by idx_alerts_pet_id_created_at.
+    // alerts.created_at is stored as naive UTC.
+    let mut sql = String::from(
+        "SELECT (EXTRACT(ISODOW FROM (created_at AT TIME ZONE 'UTC') AT TIME ZONE $3)::int - 1) AS weekday, \
+         EXTRACT(HOUR FROM (created_at AT TIME ZONE 'UTC') AT TIME ZONE $3)::int AS hour, \
+         outcome, SUM(occurrence_count)::bigint AS count \
+         FROM alerts WHERE pet_id = $1 AND created_at >= $2",
+    );
+    let mut values: Vec<sea_orm::Value> =
+        vec![pet.id.into(), since.into(), tz.name().to_string().into()];
+
+    if let Some(alert_type) = &params.alert_type {
+        values.push(alert_type.clone().into());
+        sql.push_str(&format!(" AND alert_type = ${}", values.len()));
+    }
+    if let Some(severity) = &params.severity_level {
+        values.push(severity.clone().into());
+        sql.push_str(&format!(" AND severity_level = ${}", values.len()));
+    }
+    sql.push_str(" GROUP BY 1, 2, 3");
+
+    use sea_orm::FromQueryResult;
+    let rows = match HeatmapRow::find_by_statement(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DbBackend::Postgres,
+        sql,
+        values,
+    ))
+    .all(&db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!("Failed to compute alert heatmap: {}", e);
+            return (
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to compute heatmap",
+            )
+                .into_response();
+        }
     };
 
-    (axum::http::StatusCode::OK, Json(response)).into_response()
+    let mut overall = HeatmapMatrix::new();
+    let mut by_outcome: std::collections::HashMap<String, HeatmapMatrix> =
+        std::collections::HashMap::new();
+
+    for row in rows {
+        let (weekday, hour) = (row.weekday as usize, row.hour as usize);
+        overall.add(weekday, hour, row.count);
+        by_outcome
+            .entry(row.outcome.unwrap_or_else(|| "pending".to_string()))
+            .or_insert_with(HeatmapMatrix::new)
+            .add(weekday, hour, row.count);
+    }
+
+    (
+        axum::http::StatusCode::OK,
+        Json(HeatmapResponse {
+            pet_id: pet.id,
+            days,
+            timezone: tz.name().to_string(),
+            overall,
+            by_outcome,
+        }),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(video_id: Option<&str>, payload: serde_json::Value) -> alerts::Model {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "pet_id": 1,
+            "alert_type": "pacing",
+            "severity": "medium",
+            "payload": payload,
+            "created_at": "2026-03-09T09:00:00",
+            "severity_level": "medium",
+            "notification_sent": false,
+            "occurrence_count": 1,
+            "video_id": video_id,
+            "reminder_count": 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn include_takes_a_comma_separated_list() {
+        assert!(includes_video(&Some(String::from("video"))));
+        assert!(includes_video(&Some(String::from("pet, video"))));
+        assert!(!includes_video(&Some(String::from("videos"))));
+        assert!(!includes_video(&None));
+    }
+
+    #[test]
+    fn video_id_falls_back_to_the_payload() {
+        let linked = "6f1c8c8e-2a57-4d4e-9a57-0c8f3b1c5e21";
+        let legacy = "0b5c3e4a-8a57-4f4e-9a57-7c8f3b1c5e99";
+        let with_column = alert(Some(linked), serde_json::json!({"video_id": legacy}));
+        assert_eq!(
+            AlertResponse::from_model(with_column, None)
+                .video_id
+                .as_deref(),
+            Some(linked)
+        );
+        let payload_only = alert(None, serde_json::json!({"video_id": legacy}));
+        assert_eq!(
+            AlertResponse::from_model(payload_only, None)
+                .video_id
+                .as_deref(),
+            Some(legacy)
+        );
+        let neither = alert(None, serde_json::json!({}));
+        assert_eq!(AlertResponse::from_model(neither, None).video_id, None);
+    }
+
+    #[test]
+    fn video_is_only_serialized_when_requested() {
+        let mut response = AlertResponse::from_model(alert(None, serde_json::json!({})), None);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("video").is_none());
+
+        // Requested, but the video has since been deleted
+        response.video = Some(None);
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["video"], serde_json::Value::Null);
+    }
 }
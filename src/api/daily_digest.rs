@@ -1,16 +1,29 @@
-use crate::entities::{daily_digest, pet, pet_video, DailyDigest, PetVideo};
+use crate::api::error::{ApiError, FieldError};
+use crate::api::extract::{OwnedPet, ReadablePet};
+use crate::api::pagination::Pagination;
+use crate::api::upload_quota::{QuotaStatus, UploadQuota};
+use crate::entities::{daily_digest, pet, pet_video, user, DailyDigest, Pet, PetVideo};
+use crate::jobs::VideoJob;
+use crate::video_events::{self, VideoEventKind};
+use crate::video_queue::Priority;
 use axum::{
-    extract::{Extension, Multipart, Path, Query},
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    body::Bytes,
+    extract::{
+        multipart::{Field, MultipartError},
+        Extension, Multipart, Query,
+    },
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use chrono::Utc;
+use futures::channel::mpsc;
+use futures::SinkExt;
 use google_cloud_storage::client::Client as GcsClient;
 use google_cloud_storage::http::objects::upload::{UploadObjectRequest, UploadType};
 use redis::AsyncCommands;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    QueryOrder, QuerySelect, Set,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -21,365 +34,986 @@ pub struct GenerateDigestRequest {
     date: Option<chrono::NaiveDate>,
 }
 
-pub async fn upload_video(
-    Path(pet_id): Path<i32>,
-    Extension(db): Extension<DatabaseConnection>,
-    Extension(redis_client): Extension<redis::Client>,
-    Extension(gcs_client): Extension<GcsClient>,
-    mut multipart: Multipart,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let bucket_name = std::env::var("GCS_BUCKET_NAME").map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "GCS_BUCKET_NAME not set".to_string(),
-        )
-    })?;
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
 
-    // 1. Process Multipart
-    while let Some(field) = multipart
-        .next_field()
+/// Latest video backlog across both priority queues, preferring the value
+/// cached by the worker's queue monitor and falling back to LLEN when the
+/// cache has expired.
+async fn video_queue_depth(conn: &mut redis::aio::MultiplexedConnection) -> u64 {
+    if let Ok(Some(depth)) = conn
+        .get::<_, Option<u64>>(crate::worker::VIDEO_QUEUE_DEPTH_KEY)
         .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
     {
-        let name = field.name().unwrap_or("").to_string();
+        return depth;
+    }
+    let normal: u64 = conn.llen("video_queue").await.unwrap_or(0);
+    let high: u64 = conn.llen(crate::video_queue::HIGH).await.unwrap_or(0);
+    normal + high
+}
 
-        if name == "video" {
-            let file_name = field.file_name().unwrap_or("video.mp4").to_string();
-            let data = field
-                .bytes()
-                .await
-                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+/// Rough time until a newly queued video is picked up, based on average
+/// processing time spread across the worker pool. An autoscaling pool grows
+/// to its max under a backlog, so that's the count a backlog drains with.
+pub(crate) fn estimated_wait_secs(queue_depth: u64) -> u64 {
+    drain_secs(
+        queue_depth,
+        env_u64("VIDEO_AVG_PROCESSING_SECS", 30),
+        crate::worker::Concurrency::video_workers(),
+    )
+}
+
+fn drain_secs(queue_depth: u64, avg_secs: u64, workers: crate::worker::Concurrency) -> u64 {
+    queue_depth * avg_secs / workers.max.max(1) as u64
+}
+
+/// `video_queue` entry for a video, carrying the current trace context so the
+/// worker's span joins the request's trace, and when it was queued so the
+/// worker can measure the wait.
+pub(crate) fn video_job_payload(video_id: Uuid, priority: Priority) -> String {
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let mut carrier = std::collections::HashMap::new();
+    let propagator = TraceContextPropagator::new();
+    let context = tracing::Span::current().context();
+    propagator.inject_context(&context, &mut carrier);
+
+    VideoJob {
+        trace_context: Some(carrier),
+        ..VideoJob::new(video_id, priority)
+    }
+    .encode()
+}
+
+fn quota_exceeded(user_id: i32, status: &QuotaStatus) -> Response {
+    tracing::warn!(user_id, "Rejecting upload: daily upload quota exceeded");
+    metrics::counter!("petpulse_upload_quota_exceeded_total").increment(1);
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, status.retry_after_secs().to_string())],
+        Json(json!({
+            "error": "Daily upload quota exceeded",
+            "quota": status,
+        })),
+    )
+        .into_response()
+}
+
+const DEFAULT_MAX_UPLOAD_BYTES: u64 = 500 * 1024 * 1024;
+/// Room for multipart boundaries and the other form fields around the video.
+const MULTIPART_OVERHEAD_BYTES: u64 = 1024 * 1024;
+
+/// Largest video accepted by any upload path (`MAX_UPLOAD_BYTES`).
+pub fn max_video_bytes() -> u64 {
+    env_u64("MAX_UPLOAD_BYTES", DEFAULT_MAX_UPLOAD_BYTES).max(1)
+}
+
+/// Request body limit: a maximum-size video plus multipart framing.
+pub fn max_request_body_bytes() -> usize {
+    (max_video_bytes() + MULTIPART_OVERHEAD_BYTES) as usize
+}
+
+/// 413 for a video over [`max_video_bytes`], in the ApiError shape plus the limit.
+pub fn upload_too_large() -> Response {
+    let message = crate::api::i18n::translate("upload_too_large");
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({
+            "code": "upload_too_large",
+            "message": message,
+            "error": message,
+            "max_bytes": max_video_bytes(),
+        })),
+    )
+        .into_response()
+}
 
-            // Validate size
-            if data.len() > 500 * 1024 * 1024 {
-                // 500MB
-                return Err((StatusCode::PAYLOAD_TOO_LARGE, "File too large".to_string()));
+/// Outcome of the checks every upload passes before anything is stored.
+pub(crate) struct UploadAdmission {
+    quota_status: Option<QuotaStatus>,
+    delayed: bool,
+    estimated_wait: u64,
+}
+
+impl UploadAdmission {
+    /// Rejects an upload of `bytes` that doesn't fit in what's left of today's quota.
+    pub(crate) fn check_size(&self, user_id: i32, bytes: u64) -> Option<Response> {
+        self.check_many(user_id, 1, bytes)
+    }
+
+    /// Rejects `uploads` videos totalling `bytes` that don't fit in today's quota.
+    fn check_many(&self, user_id: i32, uploads: u64, bytes: u64) -> Option<Response> {
+        match &self.quota_status {
+            Some(status) if !status.allows_many(uploads, bytes) => {
+                Some(quota_exceeded(user_id, status))
             }
+            _ => None,
+        }
+    }
 
-            // GCS Upload
-            let file_uuid = Uuid::new_v4();
-            let ext = std::path::Path::new(&file_name)
-                .extension()
-                .and_then(|s| s.to_str())
-                .unwrap_or("mp4");
-            let object_name = format!("uploads/{}/{}.{}", pet_id, file_uuid, ext);
-            let mime_type = mime_guess::from_path(&file_name)
-                .first_or_octet_stream()
-                .to_string();
-
-            let upload_type =
-                UploadType::Simple(google_cloud_storage::http::objects::upload::Media {
-                    name: object_name.clone().into(),
-                    content_type: mime_type.into(),
-                    content_length: Some(data.len() as u64),
-                });
-
-            let _uploaded = gcs_client
-                .upload_object(
-                    &UploadObjectRequest {
-                        bucket: bucket_name.clone(),
-                        ..Default::default()
-                    },
-                    data,
-                    &upload_type,
-                )
-                .await
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        format!("GCS Upload Failed: {}", e),
-                    )
-                })?;
-
-            let gcs_path = format!("gs://{}/{}", bucket_name, object_name);
-
-            // 2. Create PetVideo Record
-            let now = Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
-            let pet_video = pet_video::ActiveModel {
-                id: Set(file_uuid),
-                pet_id: Set(pet_id),
-                file_path: Set(gcs_path.clone()),
-                status: Set("PENDING".to_string()),
-                retry_count: Set(0),
-                created_at: Set(now),
-                updated_at: Set(now),
-                ..Default::default()
-            };
+    fn queued_status(&self) -> &'static str {
+        if self.delayed {
+            "queued_delayed"
+        } else {
+            "queued"
+        }
+    }
 
-            let _saved_video = pet_video.insert(&db).await.map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("DB Error: {}", e),
-                )
-            })?;
-
-            tracing::Span::current()
-                .record("table", "pet_videos")
-                .record("action", "upload")
-                .record("video_id", file_uuid.to_string())
-                .record("pet_id", pet_id)
-                .record("business_event", "Video uploaded to GCS and recorded in DB");
-
-            metrics::counter!("petpulse_videos_uploaded_total", "pet_id" => pet_id.to_string())
-                .increment(1);
-            metrics::gauge!("petpulse_videos_total").increment(1.0);
-
-            // Increment per-pet count
-            let db_clone = db.clone();
-            tokio::spawn(async move {
-                crate::metrics::increment_pet_videos(&db_clone, pet_id).await;
-            });
-
-            // 3. Push to Redis
-            let mut conn = redis_client
-                .get_multiplexed_async_connection()
-                .await
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        format!("Redis Conn Error: {}", e),
-                    )
-                })?;
-
-            // Propagate Trace Context
-            use opentelemetry::propagation::TextMapPropagator;
-            use opentelemetry_sdk::propagation::TraceContextPropagator;
-            use tracing_opentelemetry::OpenTelemetrySpanExt;
-
-            let mut carrier = std::collections::HashMap::new();
-            let propagator = TraceContextPropagator::new();
-            let context = tracing::Span::current().context();
-            propagator.inject_context(&context, &mut carrier);
-
-            let payload = serde_json::json!({
-                "video_id": file_uuid,
-                "trace_context": carrier
-            })
-            .to_string();
-
-            let _: () = conn.rpush("video_queue", payload).await.map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Redis Push Error: {}", e),
-                )
-            })?;
+    pub(crate) fn queued_response(&self, video_id: Uuid) -> Response {
+        if self.delayed {
+            return Json(json!({
+                "status": "queued_delayed",
+                "video_id": video_id,
+                "estimated_wait_seconds": self.estimated_wait
+            }))
+            .into_response();
+        }
+        Json(json!({
+            "status": "queued",
+            "video_id": video_id
+        }))
+        .into_response()
+    }
+}
+
+/// Per-user daily quota and queue backpressure. The error is the response to
+/// send back unchanged.
+pub(crate) async fn admit_upload(
+    conn: &mut redis::aio::MultiplexedConnection,
+    user_id: i32,
+    pet_id: i32,
+) -> Result<UploadAdmission, Response> {
+    // A Redis hiccup shouldn't block uploads, so the quota fails open
+    let quota = UploadQuota::from_env();
+    let quota_status = match quota.status(conn, user_id).await {
+        Ok(status) if status.exhausted() => return Err(quota_exceeded(user_id, &status)),
+        Ok(status) => Some(status),
+        Err(e) => {
+            tracing::warn!("Failed to read upload quota for user {}: {}", user_id, e);
+            None
+        }
+    };
+
+    // Backpressure: refuse before touching GCS when the backlog is hopeless
+    let queue_depth = video_queue_depth(conn).await;
+    let estimated_wait = estimated_wait_secs(queue_depth);
+    if queue_depth >= env_u64("UPLOAD_QUEUE_HARD_LIMIT", 1000) {
+        tracing::warn!(
+            "Rejecting upload for pet_id={}: video_queue depth {} over hard limit",
+            pet_id,
+            queue_depth
+        );
+        metrics::counter!("petpulse_upload_backpressure_total", "threshold" => "hard_limit")
+            .increment(1);
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, estimated_wait.max(1).to_string())],
+            Json(json!({
+                "error": "Video processing is backlogged, please retry later",
+                "queue_depth": queue_depth,
+                "retry_after_seconds": estimated_wait.max(1)
+            })),
+        )
+            .into_response());
+    }
+    let delayed = queue_depth >= env_u64("UPLOAD_QUEUE_HIGH_WATER", 200);
+    if delayed {
+        metrics::counter!("petpulse_upload_backpressure_total", "threshold" => "high_water")
+            .increment(1);
+    }
+
+    Ok(UploadAdmission {
+        quota_status,
+        delayed,
+        estimated_wait,
+    })
+}
+
+const MAX_CAMERA_ID_LEN: usize = 128;
+/// How far ahead of our clock a camera's `recorded_at` may be.
+const RECORDED_AT_MAX_SKEW_SECS: i64 = 5 * 60;
+
+/// Capture details a camera may report alongside an upload, plus where to
+/// tell an integrating app once the video is processed.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordingMetadata {
+    #[serde(default)]
+    pub(crate) recorded_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    #[serde(default)]
+    pub(crate) camera_id: Option<String>,
+    #[serde(default)]
+    pub(crate) callback_url: Option<String>,
+    /// Only settable where the caller's entitlement is checked, so never
+    /// read from JSON bodies
+    #[serde(skip)]
+    pub(crate) priority: Priority,
+}
+
+impl RecordingMetadata {
+    pub(crate) fn parse_recorded_at(
+        raw: &str,
+    ) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        chrono::DateTime::parse_from_rfc3339(raw.trim())
+    }
+
+    /// Trims `camera_id`, rejects timestamps from the future and callback
+    /// URLs that aren't https on a public host.
+    pub(crate) fn validated(mut self) -> Result<Self, FieldError> {
+        self.camera_id = self
+            .camera_id
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        if self
+            .camera_id
+            .as_ref()
+            .is_some_and(|c| c.chars().count() > MAX_CAMERA_ID_LEN)
+        {
+            return Err(FieldError::new("camera_id", "field.too_long"));
+        }
+        let latest = Utc::now() + chrono::Duration::seconds(RECORDED_AT_MAX_SKEW_SECS);
+        if self.recorded_at.is_some_and(|at| at > latest) {
+            return Err(FieldError::new("recorded_at", "field.out_of_range"));
+        }
+        self.callback_url = match self.callback_url.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(url) => Some(
+                crate::callbacks::validate_callback_url(url)
+                    .map_err(|code| FieldError::new("callback_url", code))?,
+            ),
+        };
+        Ok(self)
+    }
+}
+
+/// Creates the `pet_video` row for an object already in GCS, pushes it onto
+/// the video queue for its priority and counts it against the uploader's quota.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn register_uploaded_video(
+    db: &DatabaseConnection,
+    conn: &mut redis::aio::MultiplexedConnection,
+    pet_id: i32,
+    user_id: i32,
+    video_id: Uuid,
+    gcs_path: String,
+    size_bytes: i64,
+    content_type: String,
+    recording: RecordingMetadata,
+) -> Result<(), String> {
+    let now = Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
+    let pet_video = pet_video::ActiveModel {
+        id: Set(video_id),
+        pet_id: Set(pet_id),
+        file_path: Set(gcs_path),
+        status: Set("PENDING".to_string()),
+        retry_count: Set(0),
+        created_at: Set(now),
+        updated_at: Set(now),
+        queued_at: Set(Some(now)),
+        size_bytes: Set(Some(size_bytes)),
+        content_type: Set(Some(content_type)),
+        recorded_at: Set(recording.recorded_at),
+        camera_id: Set(recording.camera_id),
+        callback_url: Set(recording.callback_url),
+        priority: Set(recording.priority.as_str().to_string()),
+        ..Default::default()
+    };
+
+    let pet_video = pet_video
+        .insert(db)
+        .await
+        .map_err(|e| format!("DB Error: {}", e))?;
+
+    tracing::Span::current()
+        .record("table", "pet_videos")
+        .record("action", "upload")
+        .record("video_id", video_id.to_string())
+        .record("pet_id", pet_id)
+        .record("business_event", "Video uploaded to GCS and recorded in DB");
+
+    metrics::counter!("petpulse_videos_uploaded_total", "pet_id" => pet_id.to_string())
+        .increment(1);
+    metrics::gauge!("petpulse_videos_total").increment(1.0);
+
+    // Increment per-pet count
+    let db_clone = db.clone();
+    tokio::spawn(async move {
+        crate::metrics::increment_pet_videos(&db_clone, pet_id).await;
+    });
 
-            tracing::info!("Enqueued video {} to video_queue", file_uuid);
+    let priority = recording.priority;
+    let _: () = conn
+        .rpush(priority.queue(), video_job_payload(video_id, priority))
+        .await
+        .map_err(|e| format!("Redis Push Error: {}", e))?;
+
+    tracing::info!("Enqueued video {} to {}", video_id, priority.queue());
+    video_events::publish(conn, VideoEventKind::Queued, &pet_video).await;
+
+    if let Err(e) = UploadQuota::from_env()
+        .record(conn, user_id, size_bytes as u64)
+        .await
+    {
+        tracing::warn!("Failed to record upload quota for user {}: {}", user_id, e);
+    }
+    Ok(())
+}
+
+fn upload_object_name(pet_id: i32, video_id: Uuid, file_name: &str) -> String {
+    let ext = std::path::Path::new(file_name)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("mp4");
+    format!("uploads/{}/{}.{}", pet_id, video_id, ext)
+}
+
+/// Chunks buffered between the request body and the GCS upload.
+const UPLOAD_STREAM_BUFFER: usize = 4;
+
+/// A video field streamed into GCS.
+struct StreamedVideo {
+    gcs_path: String,
+    content_type: String,
+    size_bytes: u64,
+}
+
+/// Why streaming a video field stopped. Nothing is left in GCS for any of these.
+enum StreamUploadError {
+    Multipart(MultipartError),
+    Empty,
+    TooLarge,
+    QuotaExceeded(Box<Response>),
+    UnsupportedFormat,
+    Storage(String),
+}
+
+/// The size and quota limits for a single upload of `size_bytes`.
+fn single_upload_limits(
+    admission: &UploadAdmission,
+    user_id: i32,
+) -> impl Fn(u64) -> Result<(), StreamUploadError> + '_ {
+    move |size_bytes| {
+        if size_bytes > max_video_bytes() {
+            return Err(StreamUploadError::TooLarge);
+        }
+        match admission.check_size(user_id, size_bytes) {
+            Some(rejection) => Err(StreamUploadError::QuotaExceeded(Box::new(rejection))),
+            None => Ok(()),
+        }
+    }
+}
 
-            return Ok(Json(json!({
-                "status": "queued",
-                "video_id": file_uuid
-            })));
+/// Streams one multipart `video` field into GCS as it arrives, so only a few
+/// chunks are ever held in memory. `check_limits` sees the bytes read so far
+/// after every chunk; an error from it (or the client's body failing) ends
+/// the GCS upload with an error, so no partial object is created.
+async fn stream_video_field(
+    field: &mut Field<'_>,
+    gcs_client: &GcsClient,
+    bucket: &str,
+    pet_id: i32,
+    video_id: Uuid,
+    check_limits: impl Fn(u64) -> Result<(), StreamUploadError>,
+) -> Result<StreamedVideo, StreamUploadError> {
+    let file_name = field.file_name().unwrap_or("video.mp4").to_string();
+    let mut size_bytes: u64 = 0;
+
+    // The content type is part of the upload metadata, so sniff before starting
+    let mut head = Vec::new();
+    while head.len() < crate::video_format::SNIFF_BYTES {
+        match field.chunk().await.map_err(StreamUploadError::Multipart)? {
+            Some(chunk) => {
+                size_bytes += chunk.len() as u64;
+                check_limits(size_bytes)?;
+                head.extend_from_slice(&chunk);
+            }
+            None => break,
         }
     }
+    if head.is_empty() {
+        return Err(StreamUploadError::Empty);
+    }
+    let content_type = crate::video_format::detect(&head, &file_name)
+        .ok_or(StreamUploadError::UnsupportedFormat)?;
+
+    let object_name = upload_object_name(pet_id, video_id, &file_name);
+    let upload_type = UploadType::Simple(google_cloud_storage::http::objects::upload::Media {
+        name: object_name.clone().into(),
+        content_type: content_type.clone().into(),
+        content_length: None,
+    });
+    let request = UploadObjectRequest {
+        bucket: bucket.to_string(),
+        ..Default::default()
+    };
+    let (mut tx, rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(UPLOAD_STREAM_BUFFER);
+    let client = gcs_client.clone();
+    let upload = tokio::spawn(async move {
+        client
+            .upload_streamed_object(&request, rx, &upload_type)
+            .await
+    });
 
-    Err((StatusCode::BAD_REQUEST, "No video field found".to_string()))
+    let mut outcome = Ok(());
+    let mut chunk = Bytes::from(head);
+    loop {
+        // A closed channel means the upload already failed; its error is below
+        if tx.send(Ok(chunk)).await.is_err() {
+            break;
+        }
+        chunk = match field.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => {
+                outcome = Err(StreamUploadError::Multipart(e));
+                break;
+            }
+        };
+        size_bytes += chunk.len() as u64;
+        if let Err(e) = check_limits(size_bytes) {
+            outcome = Err(e);
+            break;
+        }
+    }
+    if outcome.is_err() {
+        let _ = tx.send(Err(std::io::Error::other("upload aborted"))).await;
+    }
+    drop(tx);
+
+    let uploaded = upload
+        .await
+        .map_err(|e| StreamUploadError::Storage(e.to_string()))
+        .and_then(|r| r.map_err(|e| StreamUploadError::Storage(e.to_string())));
+    outcome?;
+    uploaded?;
+    Ok(StreamedVideo {
+        gcs_path: format!("gs://{}/{}", bucket, object_name),
+        content_type,
+        size_bytes,
+    })
 }
 
-pub async fn generate_daily_digest(
+pub async fn upload_video(
     Extension(db): Extension<DatabaseConnection>,
-    Json(payload): Json<GenerateDigestRequest>,
-    // Triggered manually or by cron.
-    // Payload can specify params, but let's assume we want to process ALL eligible digests for a given date?
-    // Or maybe for specific pet. Let's start with polling all. But for this endpoint, maybe for a specific pet?
-    // User requested "server auto-triggers". Let's assume this endpoint triggers for ALL users or we pass params.
-    // For simplicity, let's make this endpoint: trigger digest for All Pets for a specific Date (default today)
-) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let date = payload.date.unwrap_or_else(|| Utc::now().date_naive());
-
-    // We need to group videos by PetId.
-    // Since we don't have GROUP BY easily in ORM, let's fetch all processed videos for the date.
-    // Ideally query pets then videos.
-
-    use sea_orm::QuerySelect;
-
-    // 1. Find all videos processed on this date
-    // Note: 'created_at' is DateTimeWithTimeZone. We need to cast or filter by range.
-    let start_of_day = date
-        .and_hms_opt(0, 0, 0)
-        .unwrap()
-        .and_utc()
-        .with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
-    let end_of_day = date
-        .and_hms_opt(23, 59, 59)
-        .unwrap()
-        .and_utc()
-        .with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
-
-    let videos = PetVideo::find()
-        .filter(pet_video::Column::Status.eq("PROCESSED"))
-        .filter(pet_video::Column::CreatedAt.gte(start_of_day))
-        .filter(pet_video::Column::CreatedAt.lte(end_of_day))
-        .all(&db)
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    // Resolved before the multipart body is read, so uploads to a missing or
+    // someone else's pet are turned away without storing anything
+    OwnedPet(pet): OwnedPet,
+    mut multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let pet_id = pet.id;
+    let bucket_name = std::env::var("GCS_BUCKET_NAME").map_err(ApiError::internal)?;
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("DB Query Error: {}", e),
+        .map_err(ApiError::internal)?;
+
+    let admission = match admit_upload(&mut conn, user_id, pet_id).await {
+        Ok(admission) => admission,
+        Err(rejection) => return Ok(rejection),
+    };
+
+    // 1. Process Multipart. Metadata fields may come before or after the
+    // video, which is streamed to GCS as it's read.
+    let file_uuid = Uuid::new_v4();
+    let mut video = None;
+    let mut recording = RecordingMetadata::default();
+    let read = read_upload_fields(
+        &mut multipart,
+        &gcs_client,
+        &bucket_name,
+        (pet_id, user_id),
+        file_uuid,
+        &admission,
+        &mut video,
+        &mut recording,
+    )
+    .await;
+
+    // 2. Record it and queue it for analysis
+    let response = match (read, &video) {
+        (Err(rejection), _) => rejection,
+        (Ok(()), None) => {
+            ApiError::validation(vec![FieldError::new("video", "field.required")]).into_response()
+        }
+        (Ok(()), Some(_))
+            if recording.priority == Priority::High
+                && !crate::video_queue::user_allows_high(&db, user_id).await =>
+        {
+            ApiError::forbidden("priority_not_allowed").into_response()
+        }
+        (Ok(()), Some(stored)) => match recording.validated() {
+            Err(e) => ApiError::validation(vec![e]).into_response(),
+            Ok(recording) => match register_uploaded_video(
+                &db,
+                &mut conn,
+                pet_id,
+                user_id,
+                file_uuid,
+                stored.gcs_path.clone(),
+                stored.size_bytes as i64,
+                stored.content_type.clone(),
+                recording,
             )
-        })?;
+            .await
+            {
+                Ok(()) => admission.queued_response(file_uuid),
+                Err(e) => {
+                    tracing::error!("Failed to queue upload {}: {}", file_uuid, e);
+                    ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "upload_failed")
+                        .into_response()
+                }
+            },
+        },
+    };
 
-    // Group by PetID
-    let mut pet_videos_map: std::collections::HashMap<i32, Vec<pet_video::Model>> =
-        std::collections::HashMap::new();
-    for v in videos {
-        pet_videos_map.entry(v.pet_id).or_default().push(v);
+    // The object is only worth keeping once its row exists
+    if let Some(stored) = video.filter(|_| !response.status().is_success()) {
+        tokio::spawn(crate::storage_cleanup::delete_objects(
+            gcs_client.clone(),
+            vec![stored.gcs_path],
+        ));
     }
+    Ok(response)
+}
 
-    let mut generated_count = 0;
-
-    for (pet_id, clips) in pet_videos_map {
-        // Aggregate
-        let mut summaries = String::new();
-        let mut activities_list = Vec::new();
-        let mut unusual_events = Vec::new();
-        let mut mood_counts = std::collections::HashMap::new();
+/// The error for a multipart body that can't be read: too large for the
+/// request body limit, or malformed.
+fn multipart_error(e: &axum::extract::multipart::MultipartError) -> ApiError {
+    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "upload_too_large")
+    } else {
+        ApiError::new(StatusCode::BAD_REQUEST, "invalid_multipart")
+    }
+}
 
-        for clip in &clips {
-            // Description as Summary
-            if let Some(s) = &clip.description {
-                summaries.push_str(&format!("- {}\n", s));
-            }
+/// Reads `upload_video`'s multipart fields, streaming the first `video` field
+/// into GCS. Errors are the response to send back; a video already stored
+/// is left in `video` for the caller to clean up.
+#[allow(clippy::too_many_arguments)]
+async fn read_upload_fields(
+    multipart: &mut Multipart,
+    gcs_client: &GcsClient,
+    bucket: &str,
+    (pet_id, user_id): (i32, i32),
+    video_id: Uuid,
+    admission: &UploadAdmission,
+    video: &mut Option<StreamedVideo>,
+    recording: &mut RecordingMetadata,
+) -> Result<(), Response> {
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| multipart_error(&e).into_response())?
+    {
+        let name = field.name().unwrap_or("").to_string();
 
-            // Activities
-            if let Some(activities_val) = &clip.activities {
-                if let Some(acts) = activities_val.as_array() {
-                    // It's a list of objects now
-                    for a in acts {
-                        // The new schema for Activity: { activity, mood, description, ... }
-                        // We probably just want the activity name for the list
-                        if let Some(act_name) = a["activity"].as_str() {
-                            activities_list.push(act_name.to_string());
+        match name.as_str() {
+            "video" if video.is_none() => {
+                let streamed = stream_video_field(
+                    &mut field,
+                    gcs_client,
+                    bucket,
+                    pet_id,
+                    video_id,
+                    single_upload_limits(admission, user_id),
+                )
+                .await;
+                *video = Some(streamed.map_err(|e| {
+                    match e {
+                        StreamUploadError::TooLarge => upload_too_large(),
+                        StreamUploadError::QuotaExceeded(rejection) => *rejection,
+                        StreamUploadError::Multipart(e) => multipart_error(&e).into_response(),
+                        StreamUploadError::Empty => {
+                            ApiError::new(StatusCode::BAD_REQUEST, "upload_object_empty")
+                                .into_response()
+                        }
+                        StreamUploadError::UnsupportedFormat => ApiError::new(
+                            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                            "unsupported_video_format",
+                        )
+                        .into_response(),
+                        StreamUploadError::Storage(e) => {
+                            tracing::error!("Upload of video {} failed: {}", video_id, e);
+                            ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "upload_failed")
+                                .into_response()
                         }
                     }
+                })?);
+            }
+            "recorded_at" | "camera_id" | "callback_url" | "priority" => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| multipart_error(&e).into_response())?;
+                if name == "priority" {
+                    recording.priority = Priority::parse(&text).ok_or_else(|| {
+                        ApiError::validation(vec![FieldError::new(
+                            "priority",
+                            "field.invalid_format",
+                        )])
+                        .into_response()
+                    })?;
+                } else if name == "camera_id" {
+                    recording.camera_id = Some(text);
+                } else if name == "callback_url" {
+                    recording.callback_url = Some(text);
+                } else {
+                    let recorded_at =
+                        RecordingMetadata::parse_recorded_at(&text).map_err(|_| {
+                            ApiError::validation(vec![FieldError::new(
+                                "recorded_at",
+                                "field.invalid_format",
+                            )])
+                            .into_response()
+                        })?;
+                    recording.recorded_at = Some(recorded_at);
                 }
             }
+            _ => {}
+        }
+    }
+    Ok(())
+}
 
-            // Mood
-            if let Some(m) = &clip.mood {
-                *mood_counts.entry(m.to_string()).or_insert(0) += 1;
-            }
+/// Most clips accepted by one `upload_videos` request.
+const MAX_BATCH_FILES: usize = 20;
 
-            // Unusual
-            if clip.is_unusual {
-                // We don't have separate 'unusual_details' field, relying on description or just flagging it.
-                // Maybe we can check if any activity is unusual if we stored it?
-                // For now, just log the clip description
-                let details = clip
-                    .description
-                    .clone()
-                    .unwrap_or_else(|| "Unspecified".to_string());
-                unusual_events.push(format!(
-                    "Clip {} ({}): {}",
-                    clip.id, clip.created_at, details
-                ));
-            }
-        }
+#[derive(Debug, Serialize)]
+pub struct BatchUploadResult {
+    pub file_name: String,
+    pub video_id: Option<Uuid>,
+    /// `queued`, `queued_delayed` or `failed`
+    pub status: &'static str,
+    /// Catalog code explaining a failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
+}
 
-        if summaries.is_empty() && activities_list.is_empty() {
-            continue;
+impl BatchUploadResult {
+    fn failed(file_name: String, code: &'static str) -> Self {
+        Self {
+            file_name,
+            video_id: None,
+            status: "failed",
+            error: Some(code),
         }
+    }
+}
 
-        // Create Final Digest Text
-        let dominant_mood = mood_counts
-            .iter()
-            .max_by_key(|a| a.1)
-            .map(|(k, _)| k.clone())
-            .unwrap_or("Unknown".to_string());
-
-        let final_summary = format!(
-            "Daily Summary for {}\n\nHighlights:\n{}\n\nActivities:\n{}\n\nMood: {}\n\nUnusual Events:\n{}",
-            date,
-            summaries,
-            activities_list.join(", "),
-            dominant_mood,
-            if unusual_events.is_empty() { "None".to_string() } else { unusual_events.join("\n") }
-        );
+// POST /pets/:id/upload_videos - Upload several clips at once. Each `video`
+// field is streamed, stored and queued on its own, so one bad file doesn't
+// fail the rest. `camera_id` and `callback_url` fields apply to every clip
+// after them; a `recorded_at` field applies to the next clip only, which
+// fails if the timestamp doesn't parse. The clips stored from one request
+// share the single-upload size limit.
+pub async fn upload_videos(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    OwnedPet(pet): OwnedPet,
+    mut multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let bucket_name = std::env::var("GCS_BUCKET_NAME").map_err(ApiError::internal)?;
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
 
-        // Upsert DailyDigest
-        // Check if exists
-        let existing = DailyDigest::find()
-            .filter(daily_digest::Column::PetId.eq(pet_id))
-            .filter(daily_digest::Column::Date.eq(date))
-            .one(&db)
-            .await
-            .unwrap_or(None);
-
-        // Create JSON payloads
-        let moods: Vec<String> = mood_counts.keys().cloned().collect();
-        let moods_json = serde_json::to_value(moods).unwrap_or(serde_json::json!([]));
-        let activities_json =
-            serde_json::to_value(activities_list.clone()).unwrap_or(serde_json::json!([]));
-
-        // Convert unusual_events strings back to detailed objects if possible, or just list
-        // Since we only collected strings in previous loop, let's fix the loop to collect objects
-        // We will do it properly by maintaining 'unusual_events_list'
-        let mut unusual_events_objects = Vec::new();
-        for clip in &clips {
-            if clip.is_unusual {
-                unusual_events_objects.push(serde_json::json!({
-                    "video_id": clip.id,
-                    "description": clip.description.clone().unwrap_or("Unusual activity".to_string()),
-                    "timestamp": clip.created_at.to_rfc3339()
-                }));
+    let admission = match admit_upload(&mut conn, user_id, pet.id).await {
+        Ok(admission) => admission,
+        Err(rejection) => return Ok(rejection),
+    };
+
+    let mut results: Vec<BatchUploadResult> = Vec::new();
+    let (mut accepted, mut accepted_bytes) = (0u64, 0u64);
+    let mut camera_id: Option<String> = None;
+    let mut callback_url: Option<String> = None;
+    // Err when the client sent a `recorded_at` that isn't RFC 3339
+    let mut recorded_at: Option<Result<_, chrono::ParseError>> = None;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| multipart_error(&e))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        match name.as_str() {
+            "camera_id" => camera_id = field.text().await.ok(),
+            "callback_url" => callback_url = field.text().await.ok(),
+            "recorded_at" => {
+                recorded_at = field
+                    .text()
+                    .await
+                    .ok()
+                    .map(|t| RecordingMetadata::parse_recorded_at(&t));
             }
+            "video" => {
+                let file_name = field.file_name().unwrap_or("video.mp4").to_string();
+                let recorded_at = recorded_at.take().transpose();
+                if results.len() >= MAX_BATCH_FILES {
+                    results.push(BatchUploadResult::failed(
+                        file_name,
+                        "upload_batch_too_many",
+                    ));
+                    continue;
+                }
+                let Ok(recorded_at) = recorded_at else {
+                    results.push(BatchUploadResult::failed(file_name, "field.invalid_format"));
+                    continue;
+                };
+                let recording = RecordingMetadata {
+                    recorded_at,
+                    camera_id: camera_id.clone(),
+                    callback_url: callback_url.clone(),
+                    ..Default::default()
+                };
+                let recording = match recording.validated() {
+                    Ok(recording) => recording,
+                    Err(e) => {
+                        results.push(BatchUploadResult::failed(file_name, e.code));
+                        continue;
+                    }
+                };
+
+                let video_id = Uuid::new_v4();
+                let check_limits = |size: u64| {
+                    if accepted_bytes + size > max_video_bytes() {
+                        return Err(StreamUploadError::TooLarge);
+                    }
+                    match admission.check_many(user_id, accepted + 1, accepted_bytes + size) {
+                        Some(rejection) => {
+                            Err(StreamUploadError::QuotaExceeded(Box::new(rejection)))
+                        }
+                        None => Ok(()),
+                    }
+                };
+                let stored = match stream_video_field(
+                    &mut field,
+                    &gcs_client,
+                    &bucket_name,
+                    pet.id,
+                    video_id,
+                    check_limits,
+                )
+                .await
+                {
+                    Ok(stored) => stored,
+                    // The body itself is unreadable, so nothing after this clip is either
+                    Err(StreamUploadError::Multipart(e)) => {
+                        let code = if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                            "upload_too_large"
+                        } else {
+                            "invalid_multipart"
+                        };
+                        results.push(BatchUploadResult::failed(file_name, code));
+                        break;
+                    }
+                    Err(e) => {
+                        let code = match e {
+                            StreamUploadError::Empty => "upload_object_empty",
+                            StreamUploadError::TooLarge => "upload_too_large",
+                            StreamUploadError::QuotaExceeded(_) => "upload_quota_exceeded",
+                            StreamUploadError::UnsupportedFormat => "unsupported_video_format",
+                            StreamUploadError::Storage(e) => {
+                                tracing::error!("Batch upload of {} failed: {}", file_name, e);
+                                "upload_failed"
+                            }
+                            StreamUploadError::Multipart(_) => "invalid_multipart",
+                        };
+                        results.push(BatchUploadResult::failed(file_name, code));
+                        continue;
+                    }
+                };
+
+                let registered = register_uploaded_video(
+                    &db,
+                    &mut conn,
+                    pet.id,
+                    user_id,
+                    video_id,
+                    stored.gcs_path.clone(),
+                    stored.size_bytes as i64,
+                    stored.content_type,
+                    recording,
+                )
+                .await;
+                match registered {
+                    Ok(()) => {
+                        accepted += 1;
+                        accepted_bytes += stored.size_bytes;
+                        results.push(BatchUploadResult {
+                            file_name,
+                            video_id: Some(video_id),
+                            status: admission.queued_status(),
+                            error: None,
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to queue batch upload {}: {}", video_id, e);
+                        tokio::spawn(crate::storage_cleanup::delete_objects(
+                            gcs_client.clone(),
+                            vec![stored.gcs_path],
+                        ));
+                        results.push(BatchUploadResult::failed(file_name, "upload_failed"));
+                    }
+                }
+            }
+            _ => {}
         }
-        let unusual_events_json =
-            serde_json::to_value(unusual_events_objects).unwrap_or(serde_json::json!([]));
-
-        if let Some(digest) = existing {
-            let mut active: daily_digest::ActiveModel = digest.into();
-            active.summary = Set(final_summary);
-            active.moods = Set(Some(moods_json));
-            active.activities = Set(Some(activities_json));
-            active.unusual_events = Set(Some(unusual_events_json));
-            active.total_videos = Set(clips.len() as i32);
-            active.updated_at =
-                Set(Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap()));
-            let _ = active.update(&db).await;
-        } else {
-            let active = daily_digest::ActiveModel {
-                id: Set(Uuid::new_v4()),
-                pet_id: Set(pet_id),
-                date: Set(date),
-                summary: Set(final_summary),
-                moods: Set(Some(moods_json)),
-                activities: Set(Some(activities_json)),
-                unusual_events: Set(Some(unusual_events_json)),
-                total_videos: Set(clips.len() as i32),
-                created_at: Set(
-                    Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap())
-                ),
-                updated_at: Set(
-                    Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap())
-                ),
-            };
-            let _ = active.insert(&db).await;
-        }
+    }
 
-        generated_count += 1;
+    if results.is_empty() {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "video",
+            "field.required",
+        )]));
     }
 
-    Ok(Json(json!({
-        "message": "Daily digests generated",
-        "count": generated_count,
-        "date": date
-    })))
+    let failed = results.iter().filter(|r| r.video_id.is_none()).count();
+    tracing::info!(
+        pet_id = pet.id,
+        queued = accepted,
+        failed,
+        "Batch upload finished"
+    );
+    let mut body = json!({
+        "queued": accepted,
+        "failed": failed,
+        "results": results,
+    });
+    if admission.delayed {
+        body["estimated_wait_seconds"] = json!(admission.estimated_wait);
+    }
+    Ok(Json(body).into_response())
 }
 
+/// Query-string form of [`GenerateDigestRequest`]; `pet_id` limits the
+/// rebuild to one pet.
 #[derive(Deserialize)]
-pub struct DigestPaginationParams {
-    #[serde(default = "default_digest_page")]
-    pub page: u64,
-    #[serde(default = "default_digest_page_size")]
-    pub page_size: u64,
+pub struct GenerateDigestParams {
+    date: Option<chrono::NaiveDate>,
+    pet_id: Option<i32>,
 }
 
-fn default_digest_page() -> u64 {
-    1
+/// Pets with processed videos captured on `date` in their owner's timezone.
+async fn pets_with_videos_on(
+    db: &DatabaseConnection,
+    date: chrono::NaiveDate,
+) -> Result<Vec<i32>, sea_orm::DbErr> {
+    // `date` is each owner's local day, so fetch the widest UTC window that
+    // could contain it and bucket per owner timezone below.
+    let (start_of_day, end_of_day) = crate::timezone::widest_day_bounds(date);
+
+    type Captured = (
+        i32,
+        Option<sea_orm::prelude::DateTimeWithTimeZone>,
+        sea_orm::prelude::DateTimeWithTimeZone,
+    );
+    let videos: Vec<Captured> = PetVideo::find()
+        .select_only()
+        .columns([
+            pet_video::Column::PetId,
+            pet_video::Column::RecordedAt,
+            pet_video::Column::CreatedAt,
+        ])
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::captured_between(start_of_day, end_of_day))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let pet_ids: std::collections::HashSet<i32> = videos.iter().map(|v| v.0).collect();
+    let owner_tz: std::collections::HashMap<i32, chrono_tz::Tz> = Pet::find_active()
+        .filter(pet::Column::Id.is_in(pet_ids))
+        .find_also_related(user::Entity)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(p, owner)| {
+            let tz = owner
+                .as_ref()
+                .map(crate::timezone::of_user)
+                .unwrap_or(chrono_tz::Tz::UTC);
+            (p.id, tz)
+        })
+        .collect();
+
+    let mut pets: Vec<i32> = videos
+        .into_iter()
+        .filter_map(|(pet_id, recorded_at, created_at)| {
+            // Archived pets don't get digests
+            let tz = owner_tz.get(&pet_id)?;
+            let captured_at = recorded_at.unwrap_or(created_at);
+            (crate::timezone::local_date(&captured_at, *tz) == date).then_some(pet_id)
+        })
+        .collect();
+    pets.sort_unstable();
+    pets.dedup();
+    Ok(pets)
 }
-fn default_digest_page_size() -> u64 {
-    10
+
+// POST /internal/generate_daily_digest - Queue digest rebuilds for a date
+// (default today), for every pet with videos that day or just `pet_id`.
+// The digest workers do the building.
+pub async fn generate_daily_digest(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Query(params): Query<GenerateDigestParams>,
+    payload: Option<Json<GenerateDigestRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let date = params
+        .date
+        .or(payload.and_then(|Json(p)| p.date))
+        .unwrap_or_else(|| Utc::now().date_naive());
+
+    let pet_ids = match params.pet_id {
+        Some(pet_id) => {
+            Pet::find_active()
+                .filter(pet::Column::Id.eq(pet_id))
+                .one(&db)
+                .await?
+                .ok_or_else(|| ApiError::not_found("pet_not_found"))?;
+            vec![pet_id]
+        }
+        None => pets_with_videos_on(&db, date).await?,
+    };
+
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut queued = 0;
+    let mut already_queued = 0;
+    for pet_id in pet_ids {
+        let newly_queued = crate::worker::enqueue_digest_update(&mut conn, pet_id, date)
+            .await
+            .map_err(ApiError::internal)?;
+        if newly_queued {
+            queued += 1;
+        } else {
+            already_queued += 1;
+        }
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Daily digest rebuilds queued",
+            "count": queued,
+            "already_queued": already_queued,
+            "date": date
+        })),
+    ))
 }
 
 #[derive(Serialize)]
@@ -406,24 +1040,12 @@ pub struct DigestListResponse {
 // GET /pets/:id/digests - List daily digests for a pet
 pub async fn list_pet_digests(
     Extension(db): Extension<DatabaseConnection>,
-    Extension(user_id): Extension<i32>,
-    Path(pet_id): Path<i32>,
-    Query(params): Query<DigestPaginationParams>,
+    ReadablePet(pet): ReadablePet,
+    pagination: Pagination,
 ) -> impl IntoResponse {
-    // Verify pet belongs to user
-    let _pet = match pet::Entity::find_by_id(pet_id).one(&db).await {
-        Ok(Some(p)) if p.user_id == user_id => p,
-        Ok(Some(_)) => return (StatusCode::FORBIDDEN, "Not your pet").into_response(),
-        Ok(None) => return (StatusCode::NOT_FOUND, "Pet not found").into_response(),
-        Err(e) => {
-            tracing::error!("Failed to fetch pet: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
-        }
-    };
-
     // Build query
     let query = DailyDigest::find()
-        .filter(daily_digest::Column::PetId.eq(pet_id))
+        .filter(daily_digest::Column::PetId.eq(pet.id))
         .order_by_desc(daily_digest::Column::Date);
 
     // Get total count
@@ -436,8 +1058,8 @@ pub async fn list_pet_digests(
     };
 
     // Fetch paginated results
-    let paginator = query.paginate(&db, params.page_size);
-    let digests_result = paginator.fetch_page(params.page - 1).await;
+    let paginator = query.paginate(&db, pagination.page_size);
+    let digests_result = paginator.fetch_page(pagination.index()).await;
 
     match digests_result {
         Ok(digests) => {
@@ -461,8 +1083,8 @@ pub async fn list_pet_digests(
                 Json(DigestListResponse {
                     digests: response,
                     total,
-                    page: params.page,
-                    page_size: params.page_size,
+                    page: pagination.page,
+                    page_size: pagination.page_size,
                 }),
             )
                 .into_response()
@@ -473,3 +1095,401 @@ pub async fn list_pet_digests(
         }
     }
 }
+
+const DEFAULT_MOOD_TREND_DAYS: i64 = 30;
+const MAX_MOOD_TREND_DAYS: i64 = 90;
+
+#[derive(Deserialize)]
+pub struct MoodTrendParams {
+    #[serde(default = "default_mood_trend_days")]
+    pub days: i64,
+}
+
+fn default_mood_trend_days() -> i64 {
+    DEFAULT_MOOD_TREND_DAYS
+}
+
+#[derive(Serialize)]
+pub struct MoodTrendDay {
+    pub date: chrono::NaiveDate,
+    pub dominant_mood: Option<String>,
+    pub mood_counts: std::collections::BTreeMap<String, u32>,
+    /// `digest`, `videos`, or `none` when nothing was recorded that day
+    pub source: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct MoodTrendResponse {
+    pub pet_id: i32,
+    pub days: i64,
+    pub timezone: String,
+    pub trend: Vec<MoodTrendDay>,
+}
+
+/// Counts canonical moods; the dominant one is the most frequent, ties going
+/// to whichever was seen first.
+fn tally_moods<'a>(
+    moods: impl Iterator<Item = &'a str>,
+) -> (Option<String>, std::collections::BTreeMap<String, u32>) {
+    let mut order = Vec::new();
+    let mut counts = std::collections::BTreeMap::new();
+    for mood in moods.filter_map(crate::mood::normalize) {
+        if !counts.contains_key(&mood) {
+            order.push(mood.clone());
+        }
+        *counts.entry(mood).or_insert(0) += 1;
+    }
+    let dominant = order.into_iter().rev().max_by_key(|m| counts[m]);
+    (dominant, counts)
+}
+
+// GET /pets/:id/mood-trend?days=30 - Dominant mood and mood counts per day
+pub async fn get_mood_trend(
+    Extension(db): Extension<DatabaseConnection>,
+    ReadablePet(pet): ReadablePet,
+    Query(params): Query<MoodTrendParams>,
+) -> Result<Response, ApiError> {
+    let days = params.days.clamp(1, MAX_MOOD_TREND_DAYS);
+    let tz = crate::timezone::of_pet_owner(&db, pet.id).await;
+    let today = crate::timezone::local_date(&Utc::now(), tz);
+    let start = today - chrono::Duration::days(days - 1);
+
+    let digests: std::collections::HashMap<chrono::NaiveDate, daily_digest::Model> =
+        DailyDigest::find()
+            .filter(daily_digest::Column::PetId.eq(pet.id))
+            .filter(daily_digest::Column::Date.gte(start))
+            .filter(daily_digest::Column::Date.lte(today))
+            .all(&db)
+            .await?
+            .into_iter()
+            .map(|d| (d.date, d))
+            .collect();
+
+    // Days without a digest fall back to the moods of that day's processed clips
+    let (since, _) = crate::timezone::widest_day_bounds(start);
+    let mut clip_moods: std::collections::HashMap<chrono::NaiveDate, Vec<String>> =
+        std::collections::HashMap::new();
+    for video in PetVideo::find()
+        .filter(pet_video::Column::PetId.eq(pet.id))
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::Column::Mood.is_not_null())
+        .filter(pet_video::captured_between(
+            since,
+            Utc::now() + chrono::Duration::days(1),
+        ))
+        .all(&db)
+        .await?
+    {
+        let date = crate::timezone::local_date(&video.captured_at(), tz);
+        if date < start || digests.contains_key(&date) {
+            continue;
+        }
+        if let Some(mood) = video.mood {
+            clip_moods.entry(date).or_default().push(mood);
+        }
+    }
+
+    let trend = start
+        .iter_days()
+        .take_while(|date| *date <= today)
+        .map(|date| {
+            let (source, (dominant_mood, mood_counts)) = if let Some(digest) = digests.get(&date) {
+                let moods = digest
+                    .moods
+                    .as_ref()
+                    .and_then(|m| m.as_array())
+                    .map(|m| m.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                ("digest", tally_moods(moods.into_iter()))
+            } else if let Some(moods) = clip_moods.get(&date) {
+                ("videos", tally_moods(moods.iter().map(String::as_str)))
+            } else {
+                ("none", (None, Default::default()))
+            };
+            MoodTrendDay {
+                date,
+                dominant_mood,
+                mood_counts,
+                source,
+            }
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(MoodTrendResponse {
+            pet_id: pet.id,
+            days,
+            timezone: tz.name().to_string(),
+            trend,
+        }),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admission(uploads_remaining: u64, bytes_remaining: u64) -> UploadAdmission {
+        UploadAdmission {
+            quota_status: Some(QuotaStatus {
+                uploads_used: 0,
+                uploads_limit: uploads_remaining,
+                uploads_remaining,
+                bytes_used: 0,
+                bytes_limit: bytes_remaining,
+                bytes_remaining,
+                resets_at: chrono::Utc::now().naive_utc(),
+            }),
+            delayed: false,
+            estimated_wait: 0,
+        }
+    }
+
+    #[test]
+    fn recorded_at_must_be_rfc3339() {
+        assert!(RecordingMetadata::parse_recorded_at(" 2026-03-01T08:30:00+01:00 ").is_ok());
+        assert!(RecordingMetadata::parse_recorded_at("yesterday").is_err());
+        assert!(RecordingMetadata::parse_recorded_at("2026-03-01 08:30").is_err());
+    }
+
+    #[test]
+    fn batch_quota_counts_every_clip_so_far() {
+        let admission = admission(2, 1_000);
+        assert!(admission.check_many(1, 2, 1_000).is_none());
+        assert!(admission.check_many(1, 3, 10).is_some());
+        assert!(admission.check_many(1, 1, 1_001).is_some());
+    }
+
+    #[test]
+    fn wait_estimate_spreads_the_backlog_over_the_workers() {
+        // 30s per video across 3 workers unless the deployment says otherwise
+        assert_eq!(estimated_wait_secs(0), 0);
+        assert_eq!(estimated_wait_secs(200), 2_000);
+    }
+
+    #[test]
+    fn autoscaled_pools_drain_at_their_max() {
+        use crate::worker::Concurrency;
+
+        assert_eq!(drain_secs(200, 30, Concurrency::fixed(3)), 2_000);
+        let autoscaled = Concurrency {
+            min: 1,
+            max: 6,
+            adaptive: true,
+        };
+        assert_eq!(drain_secs(200, 30, autoscaled), 1_000);
+    }
+
+    #[tokio::test]
+    async fn delayed_uploads_report_the_estimated_wait() {
+        let body = |response: Response| async {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+        let id = Uuid::nil();
+
+        let on_time = admission(5, 100);
+        assert_eq!(on_time.queued_status(), "queued");
+        let json = body(on_time.queued_response(id)).await;
+        assert_eq!(json["status"], "queued");
+        assert!(json.get("estimated_wait_seconds").is_none());
+
+        let delayed = UploadAdmission {
+            delayed: true,
+            estimated_wait: 2_000,
+            ..admission(5, 100)
+        };
+        assert_eq!(delayed.queued_status(), "queued_delayed");
+        let json = body(delayed.queued_response(id)).await;
+        assert_eq!(json["status"], "queued_delayed");
+        assert_eq!(json["estimated_wait_seconds"], 2_000);
+    }
+
+    #[test]
+    fn single_upload_stops_at_quota_and_size_limit() {
+        let admission = admission(5, 100);
+        let check = single_upload_limits(&admission, 1);
+        assert!(check(100).is_ok());
+        assert!(matches!(
+            check(101),
+            Err(StreamUploadError::QuotaExceeded(_))
+        ));
+
+        let unlimited = UploadAdmission {
+            quota_status: None,
+            delayed: false,
+            estimated_wait: 0,
+        };
+        let check = single_upload_limits(&unlimited, 1);
+        assert!(check(max_video_bytes()).is_ok());
+        assert!(matches!(
+            check(max_video_bytes() + 1),
+            Err(StreamUploadError::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn object_names_keep_the_upload_extension() {
+        let id = Uuid::nil();
+        assert_eq!(
+            upload_object_name(3, id, "clip.webm"),
+            format!("uploads/3/{}.webm", id)
+        );
+        assert_eq!(
+            upload_object_name(3, id, "no_extension"),
+            format!("uploads/3/{}.mp4", id)
+        );
+    }
+
+    /// Streams `data` as a `video` field. Every case here stops before
+    /// GCS is contacted.
+    async fn stream(
+        data: &[u8],
+        check_limits: impl Fn(u64) -> Result<(), StreamUploadError>,
+    ) -> Result<StreamedVideo, StreamUploadError> {
+        let mut multipart = crate::test_support::multipart("video", "clip.mp4", data).await;
+        let mut field = multipart.next_field().await.unwrap().unwrap();
+        let gcs_client =
+            GcsClient::new(google_cloud_storage::client::ClientConfig::default().anonymous());
+        stream_video_field(
+            &mut field,
+            &gcs_client,
+            "bucket",
+            1,
+            Uuid::nil(),
+            check_limits,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn oversized_field_is_refused_while_streaming() {
+        let seen = std::sync::Mutex::new(Vec::new());
+        let result = stream(&vec![0u8; 256 * 1024], |size| {
+            seen.lock().unwrap().push(size);
+            if size > 16 {
+                Err(StreamUploadError::TooLarge)
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+        assert!(matches!(result, Err(StreamUploadError::TooLarge)));
+        // It stopped at the first chunk over the limit rather than reading it all
+        let seen = seen.into_inner().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0] < 256 * 1024);
+    }
+
+    #[tokio::test]
+    async fn empty_and_unrecognized_fields_are_refused() {
+        assert!(matches!(
+            stream(b"", |_| Ok(())).await,
+            Err(StreamUploadError::Empty)
+        ));
+        assert!(matches!(
+            stream(b"plain text, not a video", |_| Ok(())).await,
+            Err(StreamUploadError::UnsupportedFormat)
+        ));
+    }
+
+    /// What `upload_video` sends back for a body with one `name` field.
+    async fn upload_rejection(name: &str, data: &[u8]) -> (u16, String) {
+        let mut multipart = crate::test_support::multipart(name, "clip.mp4", data).await;
+        let gcs_client =
+            GcsClient::new(google_cloud_storage::client::ClientConfig::default().anonymous());
+        let rejection = read_upload_fields(
+            &mut multipart,
+            &gcs_client,
+            "bucket",
+            (1, 1),
+            Uuid::nil(),
+            &admission(5, 1_000),
+            &mut None,
+            &mut RecordingMetadata::default(),
+        )
+        .await
+        .unwrap_err();
+        let (status, code) = crate::test_support::error_code(rejection).await;
+        (status.as_u16(), code)
+    }
+
+    #[tokio::test]
+    async fn single_upload_refusals_carry_catalog_codes() {
+        assert_eq!(
+            upload_rejection("video", b"").await,
+            (400, "upload_object_empty".into())
+        );
+        assert_eq!(
+            upload_rejection("video", b"plain text, not a video").await,
+            (415, "unsupported_video_format".into())
+        );
+        assert_eq!(
+            upload_rejection("priority", b"urgent").await,
+            (422, "validation_failed".into())
+        );
+        assert_eq!(
+            upload_rejection("recorded_at", b"yesterday").await,
+            (422, "validation_failed".into())
+        );
+    }
+
+    /// Posts a one-clip multipart body to `uri` as `user_id`, through the
+    /// routes as the server mounts them.
+    async fn post_upload(db: &DatabaseConnection, user_id: i32, uri: &str) -> (u16, String) {
+        use tower::ServiceExt;
+
+        let routes = axum::Router::new()
+            .route("/pets/:id/upload_video", axum::routing::post(upload_video))
+            .route(
+                "/pets/:id/upload_videos",
+                axum::routing::post(upload_videos),
+            )
+            .layer(Extension(db.clone()))
+            .layer(Extension(
+                redis::Client::open("redis://127.0.0.1:1/").unwrap(),
+            ))
+            .layer(Extension(GcsClient::new(
+                google_cloud_storage::client::ClientConfig::default().anonymous(),
+            )))
+            .layer(Extension(user_id));
+        let body = "--X\r\nContent-Disposition: form-data; name=\"video\"; \
+                    filename=\"clip.mp4\"\r\n\r\nclip\r\n--X--\r\n";
+        let request = axum::http::Request::post(uri)
+            .header(
+                axum::http::header::CONTENT_TYPE,
+                "multipart/form-data; boundary=X",
+            )
+            .body(axum::body::Body::from(body))
+            .unwrap();
+        let (status, code) =
+            crate::test_support::error_code(routes.oneshot(request).await.unwrap()).await;
+        (status.as_u16(), code)
+    }
+
+    #[tokio::test]
+    async fn uploads_need_an_existing_pet_of_your_own() {
+        let db = crate::test_support::database().await;
+        crate::test_support::owner_with_pet(&db, 1, 1).await;
+        crate::test_support::owner_with_pet(&db, 2, 2).await;
+
+        for route in ["upload_video", "upload_videos"] {
+            assert_eq!(
+                post_upload(&db, 2, &format!("/pets/1/{}", route)).await,
+                (403, String::from("not_your_pet")),
+                "{route}"
+            );
+            assert_eq!(
+                post_upload(&db, 2, &format!("/pets/99/{}", route)).await,
+                (404, String::from("pet_not_found")),
+                "{route}"
+            );
+        }
+        let stored = PetVideo::find().count(&db).await.unwrap();
+        assert_eq!(stored, 0);
+    }
+}
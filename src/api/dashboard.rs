@@ -0,0 +1,414 @@
+use crate::agent::comfort_loop::open_alert_condition;
+use crate::entities::{alerts, daily_digest, pet, pet_video};
+use axum::{
+    extract::Extension,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use redis::AsyncCommands;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+const DASHBOARD_CACHE_TTL_SECS: u64 = 30;
+
+pub fn dashboard_cache_key(user_id: i32) -> String {
+    format!("dashboard:{}", user_id)
+}
+
+/// Drops the cached dashboard for a user. Called whenever a new alert is persisted.
+pub async fn invalidate_dashboard_cache(redis_client: &redis::Client, user_id: i32) {
+    match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => {
+            let _: redis::RedisResult<()> = conn.del(dashboard_cache_key(user_id)).await;
+        }
+        Err(e) => tracing::warn!("Failed to invalidate dashboard cache: {}", e),
+    }
+}
+
+#[derive(Serialize)]
+pub struct PetCard {
+    pub id: i32,
+    pub name: String,
+    pub species: String,
+    pub last_video_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub today_dominant_mood: Option<String>,
+    pub has_digest_today: bool,
+}
+
+#[derive(Serialize)]
+pub struct OpenAlertSummary {
+    pub total: i64,
+    pub by_severity: HashMap<String, i64>,
+}
+
+#[derive(Serialize)]
+pub struct UsageSummary {
+    pub total_videos: u64,
+    pub videos_today: u64,
+    /// Bytes held across every video, and the part of that uploaded today
+    pub storage_bytes: i64,
+    pub storage_bytes_today: i64,
+}
+
+#[derive(Serialize)]
+pub struct DashboardResponse {
+    pub pets: Vec<PetCard>,
+    pub recent_unusual_clips: Vec<pet_video::Model>,
+    pub open_alerts: OpenAlertSummary,
+    pub usage: UsageSummary,
+    pub generated_at: chrono::DateTime<Utc>,
+}
+
+/// Open alerts per severity, counted the same way the comfort loop and the
+/// reminders decide an alert still needs attention.
+fn open_alerts_query(pet_ids: Vec<i32>) -> Select<alerts::Entity> {
+    alerts::Entity::find()
+        .select_only()
+        .column(alerts::Column::SeverityLevel)
+        .column_as(alerts::Column::Id.count(), "count")
+        .filter(alerts::Column::PetId.is_in(pet_ids))
+        .filter(open_alert_condition())
+        .group_by(alerts::Column::SeverityLevel)
+}
+
+/// Video count and byte total over the given pets' videos.
+fn video_usage_query(pet_ids: Vec<i32>) -> Select<pet_video::Entity> {
+    pet_video::Entity::find()
+        .select_only()
+        .column_as(Expr::cust("COUNT(*)::bigint"), "count")
+        .column_as(
+            Expr::cust("COALESCE(SUM(size_bytes), 0)::bigint"),
+            "storage_bytes",
+        )
+        .filter(pet_video::Column::PetId.is_in(pet_ids))
+}
+
+/// The most frequent mood per pet. Ties keep whichever mood came first.
+fn dominant_moods(rows: Vec<(i32, String, i64)>) -> HashMap<i32, String> {
+    let mut dominant: HashMap<i32, (String, i64)> = HashMap::new();
+    for (pet_id, mood, count) in rows {
+        let entry = dominant.entry(pet_id).or_insert((mood.clone(), 0));
+        if count > entry.1 {
+            *entry = (mood, count);
+        }
+    }
+    dominant
+        .into_iter()
+        .map(|(pet_id, (mood, _))| (pet_id, mood))
+        .collect()
+}
+
+fn pet_cards(
+    pets: Vec<pet::Model>,
+    last_videos: &HashMap<i32, chrono::DateTime<chrono::FixedOffset>>,
+    mut dominant_moods: HashMap<i32, String>,
+    digests_today: &HashSet<i32>,
+) -> Vec<PetCard> {
+    pets.into_iter()
+        .map(|p| PetCard {
+            last_video_at: last_videos.get(&p.id).cloned(),
+            today_dominant_mood: dominant_moods.remove(&p.id),
+            has_digest_today: digests_today.contains(&p.id),
+            id: p.id,
+            name: p.name,
+            species: p.species,
+        })
+        .collect()
+}
+
+fn internal_error(e: sea_orm::DbErr) -> Response {
+    tracing::error!("Failed to build dashboard: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"error": e.to_string()})),
+    )
+        .into_response()
+}
+
+// GET /dashboard - Aggregated home screen data for the authenticated user
+pub async fn get_dashboard(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+) -> Response {
+    let cache_key = dashboard_cache_key(user_id);
+    let mut redis_conn = redis_client.get_multiplexed_async_connection().await.ok();
+
+    if let Some(conn) = redis_conn.as_mut() {
+        if let Ok(Some(cached)) = conn.get::<_, Option<String>>(&cache_key).await {
+            return (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/json")],
+                cached,
+            )
+                .into_response();
+        }
+    }
+
+    let pets = match pet::Entity::find_active()
+        .filter(pet::Column::UserId.eq(user_id))
+        .order_by_asc(pet::Column::Name)
+        .all(&db)
+        .await
+    {
+        Ok(p) => p,
+        Err(e) => return internal_error(e),
+    };
+    let pet_ids: Vec<i32> = pets.iter().map(|p| p.id).collect();
+
+    let today = Utc::now().date_naive();
+    let start_of_day = today
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
+
+    let last_videos = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::PetId)
+        .column_as(pet_video::Column::CreatedAt.max(), "last_video_at")
+        .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
+        .group_by(pet_video::Column::PetId)
+        .into_tuple::<(i32, chrono::DateTime<chrono::FixedOffset>)>()
+        .all(&db);
+
+    let today_moods = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::PetId)
+        .column(pet_video::Column::Mood)
+        .column_as(pet_video::Column::Id.count(), "count")
+        .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::Column::CreatedAt.gte(start_of_day))
+        .filter(pet_video::Column::Mood.is_not_null())
+        .group_by(pet_video::Column::PetId)
+        .group_by(pet_video::Column::Mood)
+        .into_tuple::<(i32, String, i64)>()
+        .all(&db);
+
+    let recent_unusual = pet_video::Entity::find()
+        .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::Column::IsUnusual.eq(true))
+        .filter(pet_video::Column::SuppressedByKnownBehavior.eq(false))
+        .order_by_desc(pet_video::Column::CreatedAt)
+        .limit(3)
+        .all(&db);
+
+    let open_alerts = open_alerts_query(pet_ids.clone())
+        .into_tuple::<(String, i64)>()
+        .all(&db);
+
+    let digests_today = daily_digest::Entity::find()
+        .select_only()
+        .column(daily_digest::Column::PetId)
+        .filter(daily_digest::Column::PetId.is_in(pet_ids.clone()))
+        .filter(daily_digest::Column::Date.eq(today))
+        .into_tuple::<i32>()
+        .all(&db);
+
+    let total_videos = video_usage_query(pet_ids.clone())
+        .into_tuple::<(i64, i64)>()
+        .one(&db);
+
+    let videos_today = video_usage_query(pet_ids.clone())
+        .filter(pet_video::Column::CreatedAt.gte(start_of_day))
+        .into_tuple::<(i64, i64)>()
+        .one(&db);
+
+    let (
+        last_videos,
+        today_moods,
+        recent_unusual,
+        open_alerts,
+        digests_today,
+        total_videos,
+        videos_today,
+    ) = tokio::join!(
+        last_videos,
+        today_moods,
+        recent_unusual,
+        open_alerts,
+        digests_today,
+        total_videos,
+        videos_today
+    );
+
+    let last_videos: HashMap<i32, _> = match last_videos {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => return internal_error(e),
+    };
+
+    let dominant_moods = match today_moods {
+        Ok(rows) => dominant_moods(rows),
+        Err(e) => return internal_error(e),
+    };
+
+    let digests_today: HashSet<i32> = match digests_today {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => return internal_error(e),
+    };
+
+    let by_severity: HashMap<String, i64> = match open_alerts {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => return internal_error(e),
+    };
+
+    let recent_unusual_clips = match recent_unusual {
+        Ok(v) => v,
+        Err(e) => return internal_error(e),
+    };
+
+    let usage = match (total_videos, videos_today) {
+        (Ok(total), Ok(today)) => {
+            let (total_videos, storage_bytes) = total.unwrap_or_default();
+            let (videos_today, storage_bytes_today) = today.unwrap_or_default();
+            UsageSummary {
+                total_videos: total_videos as u64,
+                videos_today: videos_today as u64,
+                storage_bytes,
+                storage_bytes_today,
+            }
+        }
+        (Err(e), _) | (_, Err(e)) => return internal_error(e),
+    };
+
+    let pet_cards = pet_cards(pets, &last_videos, dominant_moods, &digests_today);
+
+    let response = DashboardResponse {
+        pets: pet_cards,
+        recent_unusual_clips,
+        open_alerts: OpenAlertSummary {
+            total: by_severity.values().sum(),
+            by_severity,
+        },
+        usage,
+        generated_at: Utc::now(),
+    };
+
+    let body = match serde_json::to_string(&response) {
+        Ok(b) => b,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+
+    if let Some(conn) = redis_conn.as_mut() {
+        let _: redis::RedisResult<()> = conn
+            .set_ex(&cache_key, &body, DASHBOARD_CACHE_TTL_SECS)
+            .await;
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    fn pet(id: i32, name: &str) -> pet::Model {
+        let now = Utc::now().naive_utc();
+        pet::Model {
+            id,
+            user_id: 1,
+            name: String::from(name),
+            age: 3,
+            species: String::from("dog"),
+            breed: String::new(),
+            bio: String::new(),
+            created_at: now,
+            updated_at: now,
+            known_behaviors: json!([]),
+            static_check_disabled: false,
+            photo_path: None,
+            weight_kg: None,
+            medical_conditions: None,
+            medications: None,
+            vet_name: None,
+            vet_phone: None,
+            archived_at: None,
+            behavior_baseline: None,
+            monitoring_schedule: None,
+        }
+    }
+
+    #[test]
+    fn user_without_pets_gets_empty_cards() {
+        let cards = pet_cards(
+            Vec::new(),
+            &HashMap::new(),
+            dominant_moods(Vec::new()),
+            &HashSet::new(),
+        );
+        assert!(cards.is_empty());
+    }
+
+    #[test]
+    fn cards_carry_each_pets_own_figures() {
+        let last_video = Utc::now().fixed_offset();
+        let moods = dominant_moods(vec![
+            (1, String::from("calm"), 2),
+            (1, String::from("playful"), 5),
+            (2, String::from("anxious"), 1),
+        ]);
+        let cards = pet_cards(
+            vec![pet(1, "Rex"), pet(2, "Milo"), pet(3, "Luna")],
+            &HashMap::from([(1, last_video)]),
+            moods,
+            &HashSet::from([2]),
+        );
+
+        assert_eq!(cards[0].today_dominant_mood.as_deref(), Some("playful"));
+        assert_eq!(cards[0].last_video_at, Some(last_video));
+        assert!(!cards[0].has_digest_today);
+        assert_eq!(cards[1].today_dominant_mood.as_deref(), Some("anxious"));
+        assert!(cards[1].has_digest_today);
+        assert_eq!(cards[2].today_dominant_mood, None);
+        assert_eq!(cards[2].last_video_at, None);
+    }
+
+    #[test]
+    fn tied_moods_keep_the_first() {
+        let moods = dominant_moods(vec![
+            (1, String::from("calm"), 3),
+            (1, String::from("playful"), 3),
+        ]);
+        assert_eq!(moods.get(&1).map(String::as_str), Some("calm"));
+    }
+
+    #[test]
+    fn open_alerts_exclude_every_resolved_outcome() {
+        let sql = open_alerts_query(vec![1])
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""user_acknowledged_at" IS NULL"#));
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolved%'"#));
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolution%'"#));
+    }
+
+    #[test]
+    fn usage_sums_bytes_alongside_the_count() {
+        let sql = video_usage_query(vec![1, 2])
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains("COALESCE(SUM(size_bytes), 0)::bigint"));
+        assert!(sql.contains(r#""pet_id" IN (1, 2)"#));
+    }
+}
@@ -0,0 +1,308 @@
+//! Direct-to-GCS uploads. The client asks for a short-lived signed PUT URL,
+//! sends the file straight to the bucket and then confirms the upload, which
+//! checks the object landed and queues it like `upload_video` does. Large
+//! files never pass through the API this way; the multipart path stays for
+//! small files and clients that can't sign-and-PUT.
+
+use super::daily_digest::{
+    admit_upload, max_video_bytes, register_uploaded_video, upload_too_large, RecordingMetadata,
+};
+use super::error::ApiError;
+use super::extract::OwnedPet;
+use crate::storage_cleanup;
+use crate::video_format;
+use axum::{
+    extract::{Extension, Json},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::get::GetObjectRequest;
+use google_cloud_storage::sign::{SignedURLMethod, SignedURLOptions};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+const DEFAULT_UPLOAD_URL_TTL_SECS: u64 = 15 * 60;
+/// Signed PUT URLs are never valid for longer than this.
+const MAX_UPLOAD_URL_TTL_SECS: u64 = 60 * 60;
+/// How long after the URL expires a client can still confirm, covering an
+/// upload that started just before expiry.
+const CONFIRM_GRACE_SECS: u64 = 60 * 60;
+const CONFIRM_LOCK_SECS: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingUpload {
+    user_id: i32,
+    pet_id: i32,
+    bucket: String,
+    object_name: String,
+    #[serde(default)]
+    recording: RecordingMetadata,
+}
+
+fn upload_url_ttl_secs() -> u64 {
+    std::env::var("UPLOAD_URL_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_UPLOAD_URL_TTL_SECS)
+        .min(MAX_UPLOAD_URL_TTL_SECS)
+}
+
+fn pending_key(video_id: Uuid) -> String {
+    format!("petpulse:direct_upload:{}", video_id)
+}
+
+fn confirm_lock_key(video_id: Uuid) -> String {
+    format!("petpulse:direct_upload:{}:confirming", video_id)
+}
+
+async fn redis_conn(redis_client: &redis::Client) -> Result<MultiplexedConnection, ApiError> {
+    redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// Same shape as ApiError, plus the upload paths that still work without signing.
+fn upload_url_unavailable(pet_id: i32) -> Response {
+    let message = super::i18n::translate("upload_url_unavailable");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "code": "upload_url_unavailable",
+            "message": message,
+            "error": message,
+            "fallback": {
+                "multipart": format!("/pets/{}/upload_video", pet_id),
+                "resumable": format!("/pets/{}/uploads", pet_id),
+            },
+        })),
+    )
+        .into_response()
+}
+
+#[derive(Deserialize)]
+pub struct UploadUrlRequest {
+    file_name: Option<String>,
+    #[serde(flatten)]
+    recording: RecordingMetadata,
+}
+
+// POST /pets/:id/upload_url - Sign a PUT URL the client uploads the video to directly
+pub async fn create_upload_url(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    OwnedPet(pet): OwnedPet,
+    Json(payload): Json<UploadUrlRequest>,
+) -> Result<Response, ApiError> {
+    let bucket = std::env::var("GCS_BUCKET_NAME").map_err(ApiError::internal)?;
+    let mut conn = redis_conn(&redis_client).await?;
+
+    // Refuse now rather than after the client has sent the whole file
+    if let Err(rejection) = admit_upload(&mut conn, user_id, pet.id).await {
+        return Ok(rejection);
+    }
+
+    let recording = payload
+        .recording
+        .validated()
+        .map_err(|e| ApiError::validation(vec![e]))?;
+    let file_name = payload
+        .file_name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "video.mp4".to_string());
+    let video_id = Uuid::new_v4();
+    let ext = std::path::Path::new(&file_name)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("mp4");
+    let object_name = format!("uploads/{}/{}.{}", pet.id, video_id, ext);
+    let content_type = mime_guess::from_path(&file_name)
+        .first_or_octet_stream()
+        .to_string();
+
+    let ttl = upload_url_ttl_secs();
+    let options = SignedURLOptions {
+        method: SignedURLMethod::PUT,
+        expires: std::time::Duration::from_secs(ttl),
+        content_type: Some(content_type.clone()),
+        ..Default::default()
+    };
+    let upload_url = match gcs_client
+        .signed_url(&bucket, &object_name, None, None, options)
+        .await
+    {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!("Failed to sign upload URL for pet {}: {}", pet.id, e);
+            metrics::counter!("petpulse_upload_urls_total", "result" => "sign_failed").increment(1);
+            return Ok(upload_url_unavailable(pet.id));
+        }
+    };
+
+    let pending = PendingUpload {
+        user_id,
+        pet_id: pet.id,
+        bucket,
+        object_name,
+        recording,
+    };
+    let raw = serde_json::to_string(&pending).map_err(ApiError::internal)?;
+    let _: () = conn
+        .set_ex(pending_key(video_id), raw, ttl + CONFIRM_GRACE_SECS)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::counter!("petpulse_upload_urls_total", "result" => "signed").increment(1);
+    let expires_at: DateTime<Utc> = Utc::now() + chrono::Duration::seconds(ttl as i64);
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "video_id": video_id,
+            "upload_url": upload_url,
+            "method": "PUT",
+            "headers": { "Content-Type": content_type },
+            "expires_at": expires_at,
+            "max_bytes": max_video_bytes(),
+        })),
+    )
+        .into_response())
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmUploadRequest {
+    video_id: Uuid,
+}
+
+// POST /pets/:id/uploads/confirm - Check the signed upload landed and queue the video
+pub async fn confirm_upload(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    OwnedPet(pet): OwnedPet,
+    Json(payload): Json<ConfirmUploadRequest>,
+) -> Result<Response, ApiError> {
+    let video_id = payload.video_id;
+    let mut conn = redis_conn(&redis_client).await?;
+
+    let raw: Option<String> = conn
+        .get(pending_key(video_id))
+        .await
+        .map_err(ApiError::internal)?;
+    let pending = raw
+        .and_then(|raw| serde_json::from_str::<PendingUpload>(&raw).ok())
+        .filter(|p| p.user_id == user_id && p.pet_id == pet.id)
+        .ok_or_else(|| ApiError::not_found("upload_not_found"))?;
+
+    // Only one confirmation per upload, even if the client retries
+    let locked: Option<String> = redis::cmd("SET")
+        .arg(confirm_lock_key(video_id))
+        .arg(user_id)
+        .arg("NX")
+        .arg("EX")
+        .arg(CONFIRM_LOCK_SECS)
+        .query_async(&mut conn)
+        .await
+        .map_err(ApiError::internal)?;
+    if locked.is_none() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "upload_completing"));
+    }
+
+    let result = queue_confirmed_upload(&db, &mut conn, &gcs_client, video_id, &pending).await;
+    // Quota and backpressure rejections keep the upload confirmable later
+    if matches!(&result, Ok(response) if response.status().is_success()) {
+        let _: Result<(), _> = conn.del(pending_key(video_id)).await;
+    }
+    let _: Result<(), _> = conn.del(confirm_lock_key(video_id)).await;
+    result
+}
+
+async fn queue_confirmed_upload(
+    db: &DatabaseConnection,
+    conn: &mut MultiplexedConnection,
+    gcs_client: &GcsClient,
+    video_id: Uuid,
+    pending: &PendingUpload,
+) -> Result<Response, ApiError> {
+    let object = match gcs_client
+        .get_object(&GetObjectRequest {
+            bucket: pending.bucket.clone(),
+            object: pending.object_name.clone(),
+            ..Default::default()
+        })
+        .await
+    {
+        Ok(object) => object,
+        Err(google_cloud_storage::http::Error::Response(e)) if e.code == 404 => {
+            return Err(ApiError::new(StatusCode::CONFLICT, "upload_object_missing"));
+        }
+        Err(e) => return Err(ApiError::internal(e)),
+    };
+    let gcs_path = format!("gs://{}/{}", pending.bucket, pending.object_name);
+    let size_bytes = object.size.max(0) as u64;
+
+    if size_bytes == 0 || size_bytes > max_video_bytes() {
+        // Nothing else will accept this object, so don't leave it to the reconcile run
+        tokio::spawn(storage_cleanup::delete_objects(
+            gcs_client.clone(),
+            vec![gcs_path],
+        ));
+        let _: Result<(), _> = conn.del(pending_key(video_id)).await;
+        if size_bytes > 0 {
+            return Ok(upload_too_large());
+        }
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "upload_object_empty",
+        ));
+    }
+
+    let head = video_format::object_head(gcs_client, &pending.bucket, &pending.object_name)
+        .await
+        .map_err(ApiError::internal)?;
+    let Some(content_type) = video_format::detect(&head, &pending.object_name) else {
+        tokio::spawn(storage_cleanup::delete_objects(
+            gcs_client.clone(),
+            vec![gcs_path],
+        ));
+        let _: Result<(), _> = conn.del(pending_key(video_id)).await;
+        return Err(ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_video_format",
+        ));
+    };
+
+    let admission = match admit_upload(conn, pending.user_id, pending.pet_id).await {
+        Ok(admission) => admission,
+        Err(rejection) => return Ok(rejection),
+    };
+    if let Some(rejection) = admission.check_size(pending.user_id, size_bytes) {
+        return Ok(rejection);
+    }
+
+    register_uploaded_video(
+        db,
+        conn,
+        pending.pet_id,
+        pending.user_id,
+        video_id,
+        gcs_path,
+        size_bytes as i64,
+        content_type,
+        pending.recording.clone(),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(%video_id, pet_id = pending.pet_id, size_bytes, "Direct upload confirmed");
+    Ok(admission.queued_response(video_id))
+}
@@ -0,0 +1,106 @@
+use super::i18n;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::json;
+
+/// A single invalid field in a validation error.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub code: &'static str,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, code: &'static str) -> Self {
+        Self { field, code }
+    }
+}
+
+#[derive(Serialize)]
+struct RenderedFieldError {
+    field: &'static str,
+    code: &'static str,
+    message: String,
+}
+
+/// Unified API error. `code` is stable and machine-readable; `message` is
+/// rendered from the catalog in the request's Accept-Language.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    fields: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str) -> Self {
+        Self {
+            status,
+            code,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn not_found(code: &'static str) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code)
+    }
+
+    pub fn forbidden(code: &'static str) -> Self {
+        Self::new(StatusCode::FORBIDDEN, code)
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized")
+    }
+
+    pub fn validation(fields: Vec<FieldError>) -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            code: "validation_failed",
+            fields,
+        }
+    }
+
+    /// Logs the underlying cause and returns a generic 500 so internals don't leak.
+    pub fn internal(err: impl std::fmt::Display) -> Self {
+        tracing::error!("Internal error: {}", err);
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error")
+    }
+}
+
+impl From<sea_orm::DbErr> for ApiError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        Self::internal(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let message = i18n::translate(self.code);
+        let mut body = json!({
+            "code": self.code,
+            "message": message,
+            // Kept for clients that still read the legacy field
+            "error": message,
+        });
+
+        if !self.fields.is_empty() {
+            let fields: Vec<RenderedFieldError> = self
+                .fields
+                .iter()
+                .map(|f| RenderedFieldError {
+                    field: f.field,
+                    code: f.code,
+                    message: i18n::translate(f.code),
+                })
+                .collect();
+            body["fields"] = json!(fields);
+        }
+
+        (self.status, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,66 @@
+use super::error::ApiError;
+use super::extract::ReadablePet;
+use crate::video_events::{self, VideoEvent};
+use axum::{
+    extract::Extension,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{Stream, StreamExt};
+use std::convert::Infallible;
+use std::time::Duration;
+
+const HEARTBEAT_SECS: u64 = 15;
+
+/// Counts an open event stream for as long as it lives. The stream, and with
+/// it this guard and the pub/sub connection, is dropped when the client goes
+/// away, which also ends the Redis subscription.
+struct OpenStream;
+
+impl OpenStream {
+    fn new() -> Self {
+        metrics::gauge!("petpulse_video_event_streams_open").increment(1.0);
+        Self
+    }
+}
+
+impl Drop for OpenStream {
+    fn drop(&mut self) {
+        metrics::gauge!("petpulse_video_event_streams_open").decrement(1.0);
+    }
+}
+
+// GET /pets/:id/events - Server-Sent Events for the pet's video status changes
+pub async fn stream_pet_events(
+    Extension(redis_client): Extension<redis::Client>,
+    ReadablePet(pet): ReadablePet,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let mut pubsub = redis_client
+        .get_async_pubsub()
+        .await
+        .map_err(ApiError::internal)?;
+    pubsub
+        .subscribe(video_events::channel(pet.id))
+        .await
+        .map_err(ApiError::internal)?;
+
+    let open = OpenStream::new();
+    let events = pubsub.into_on_message().filter_map(move |msg| {
+        let _open = &open;
+        let event = msg
+            .get_payload::<String>()
+            .ok()
+            .and_then(|body| {
+                let parsed: VideoEvent = serde_json::from_str(&body).ok()?;
+                Some(Event::default().event(parsed.event.name()).data(body))
+            })
+            .map(Ok);
+        async move { event }
+    });
+
+    // Comment lines keep proxies from closing an idle connection
+    Ok(Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(HEARTBEAT_SECS))
+            .text("heartbeat"),
+    ))
+}
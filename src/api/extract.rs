@@ -0,0 +1,186 @@
+use super::error::ApiError;
+use super::pet::{check_pet_access, owned_pet, readable_pet, PetAccess};
+use crate::entities::{alerts, pet};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Path},
+    http::request::Parts,
+};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// A pet from the `:id` path segment that the authenticated user may access.
+/// Rejects with the same 403/404 as [`owned_pet`].
+pub struct OwnedPet(pub pet::Model);
+
+/// Like [`OwnedPet`], but also lets accepted caretakers through. For
+/// read-only handlers.
+pub struct ReadablePet(pub pet::Model);
+
+/// An alert from the `:id` path segment whose pet the authenticated user may
+/// access, together with that pet.
+pub struct OwnedAlert {
+    pub alert: alerts::Model,
+    pub pet: pet::Model,
+}
+
+/// Like [`OwnedAlert`], but also lets the pet's accepted caretakers through.
+pub struct ReadableAlert {
+    pub alert: alerts::Model,
+    pub pet: pet::Model,
+}
+
+/// Parses the `:id` path segment. Ids that can't parse can't exist, so they
+/// get the resource's 404 rather than a path rejection.
+async fn path_id<T: FromStr, S: Send + Sync>(
+    parts: &mut Parts,
+    state: &S,
+    not_found: &'static str,
+) -> Result<T, ApiError> {
+    let Path(params) = Path::<HashMap<String, String>>::from_request_parts(parts, state)
+        .await
+        .map_err(|_| ApiError::not_found(not_found))?;
+    params
+        .get("id")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| ApiError::not_found(not_found))
+}
+
+/// Database handle and user id put on the request by the Extension layer and
+/// `auth_middleware`.
+fn request_context(parts: &Parts) -> Result<(DatabaseConnection, i32), ApiError> {
+    let user_id = *parts
+        .extensions
+        .get::<i32>()
+        .ok_or_else(ApiError::unauthorized)?;
+    let db = parts
+        .extensions
+        .get::<DatabaseConnection>()
+        .cloned()
+        .ok_or_else(|| ApiError::internal("DatabaseConnection extension missing"))?;
+    Ok((db, user_id))
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for OwnedPet {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pet_id = path_id::<i32, S>(parts, state, "pet_not_found").await?;
+        let (db, user_id) = request_context(parts)?;
+        owned_pet(&db, pet_id, user_id).await.map(OwnedPet)
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ReadablePet {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pet_id = path_id::<i32, S>(parts, state, "pet_not_found").await?;
+        let (db, user_id) = request_context(parts)?;
+        readable_pet(&db, pet_id, user_id).await.map(ReadablePet)
+    }
+}
+
+/// Loads the `:id` alert and its pet, checking the caller's access to the pet.
+async fn alert_with_pet<S: Send + Sync>(
+    parts: &mut Parts,
+    state: &S,
+    access: PetAccess,
+) -> Result<(alerts::Model, pet::Model), ApiError> {
+    let alert_id = path_id::<Uuid, S>(parts, state, "alert_not_found").await?;
+    let (db, user_id) = request_context(parts)?;
+
+    let (alert, pet) = match alerts::Entity::find_by_id(alert_id)
+        .find_also_related(pet::Entity)
+        .one(&db)
+        .await?
+    {
+        Some((alert, Some(pet))) => (alert, pet),
+        _ => return Err(ApiError::not_found("alert_not_found")),
+    };
+    check_pet_access(&db, &pet, user_id, access).await?;
+
+    Ok((alert, pet))
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for OwnedAlert {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (alert, pet) = alert_with_pet(parts, state, PetAccess::Manage).await?;
+        Ok(OwnedAlert { alert, pet })
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ReadableAlert {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (alert, pet) = alert_with_pet(parts, state, PetAccess::Read).await?;
+        Ok(ReadableAlert { alert, pet })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::error_code;
+    use axum::{body::Body, http::Request, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    /// Routes that only run the extractors. The database is disconnected, so
+    /// anything that gets as far as a query fails with a 500.
+    fn app(user_id: Option<i32>) -> Router {
+        let app = Router::new()
+            .route(
+                "/pets/:id",
+                get(|OwnedPet(pet): OwnedPet| async move { pet.name }),
+            )
+            .route(
+                "/pets/:id/read",
+                get(|ReadablePet(pet): ReadablePet| async move { pet.name }),
+            )
+            .route(
+                "/alerts/:id",
+                get(|owned: OwnedAlert| async move { owned.pet.name }),
+            )
+            .layer(Extension(DatabaseConnection::Disconnected));
+        match user_id {
+            Some(user_id) => app.layer(Extension(user_id)),
+            None => app,
+        }
+    }
+
+    async fn get_code(app: Router, uri: &str) -> (axum::http::StatusCode, String) {
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        error_code(response).await
+    }
+
+    #[tokio::test]
+    async fn unparseable_ids_are_not_found() {
+        let (status, code) = get_code(app(Some(7)), "/pets/abc").await;
+        assert_eq!((status.as_u16(), code.as_str()), (404, "pet_not_found"));
+        let (status, code) = get_code(app(Some(7)), "/pets/abc/read").await;
+        assert_eq!((status.as_u16(), code.as_str()), (404, "pet_not_found"));
+        let (status, code) = get_code(app(Some(7)), "/alerts/42").await;
+        assert_eq!((status.as_u16(), code.as_str()), (404, "alert_not_found"));
+    }
+
+    #[tokio::test]
+    async fn requests_without_a_user_are_unauthorized() {
+        let (status, code) = get_code(app(None), "/pets/5").await;
+        assert_eq!((status.as_u16(), code.as_str()), (401, "unauthorized"));
+        let uri = format!("/alerts/{}", Uuid::new_v4());
+        let (status, code) = get_code(app(None), &uri).await;
+        assert_eq!((status.as_u16(), code.as_str()), (401, "unauthorized"));
+    }
+}
@@ -0,0 +1,583 @@
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+
+/// Locales the API can render messages in. English is the fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next()?.trim().to_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Picks the highest-weighted supported locale from an Accept-Language header.
+    pub fn from_accept_language(header: &str) -> Self {
+        let mut candidates: Vec<(f32, Locale)> = header
+            .split(',')
+            .filter_map(|part| {
+                let mut pieces = part.trim().split(';');
+                let locale = Locale::from_tag(pieces.next()?)?;
+                let q = pieces
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (q > 0.0).then_some((q, locale))
+            })
+            .collect();
+
+        // Stable sort keeps header order for equal weights
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.first().map(|(_, l)| *l).unwrap_or(Locale::En)
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+/// Locale of the request currently being handled, or English outside a request.
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.try_with(|l| *l).unwrap_or(Locale::En)
+}
+
+/// Resolves the request locale from Accept-Language and makes it available to
+/// handlers (and their error responses) for the duration of the request.
+pub async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(Locale::from_accept_language)
+        .unwrap_or(Locale::En);
+
+    let mut response = CURRENT_LOCALE.scope(locale, next.run(request)).await;
+    if let Ok(value) = header::HeaderValue::from_str(locale.as_str()) {
+        response
+            .headers_mut()
+            .entry(header::CONTENT_LANGUAGE)
+            .or_insert(value);
+    }
+    response
+}
+
+// (code, en, es, fr)
+const CATALOG: &[(&str, &str, &str, &str)] = &[
+    // Generic errors
+    (
+        "unauthorized",
+        "Unauthorized",
+        "No autorizado",
+        "Non autorisé",
+    ),
+    (
+        "session_expired",
+        "Your session has expired. Please sign in again.",
+        "Tu sesión ha caducado. Inicia sesión de nuevo.",
+        "Votre session a expiré. Veuillez vous reconnecter.",
+    ),
+    (
+        "admin_required",
+        "This action requires administrator access.",
+        "Esta acción requiere acceso de administrador.",
+        "Cette action nécessite un accès administrateur.",
+    ),
+    (
+        "internal_error",
+        "Something went wrong. Please try again.",
+        "Algo salió mal. Inténtalo de nuevo.",
+        "Une erreur s'est produite. Veuillez réessayer.",
+    ),
+    (
+        "validation_failed",
+        "Some fields are invalid.",
+        "Algunos campos no son válidos.",
+        "Certains champs sont invalides.",
+    ),
+    // Resources
+    (
+        "pet_not_found",
+        "Pet not found",
+        "Mascota no encontrada",
+        "Animal introuvable",
+    ),
+    (
+        "not_your_pet",
+        "Not your pet",
+        "Esta mascota no es tuya",
+        "Cet animal ne vous appartient pas",
+    ),
+    (
+        "video_not_found",
+        "Video not found",
+        "Video no encontrado",
+        "Vidéo introuvable",
+    ),
+    (
+        "thumbnail_not_found",
+        "This video has no thumbnail yet.",
+        "Este video aún no tiene miniatura.",
+        "Cette vidéo n'a pas encore de miniature.",
+    ),
+    (
+        "video_processing",
+        "This video is being processed right now. Try again once it finishes.",
+        "Este video se está procesando ahora mismo. Inténtalo de nuevo cuando termine.",
+        "Cette vidéo est en cours de traitement. Réessayez une fois le traitement terminé.",
+    ),
+    (
+        "reprocess_rate_limited",
+        "This video was reprocessed recently. Please wait a few minutes.",
+        "Este video se reprocesó hace poco. Espera unos minutos.",
+        "Cette vidéo a été retraitée récemment. Veuillez patienter quelques minutes.",
+    ),
+    (
+        "photo_not_found",
+        "This pet has no photo yet.",
+        "Esta mascota aún no tiene foto.",
+        "Cet animal n'a pas encore de photo.",
+    ),
+    (
+        "photo_missing",
+        "Attach an image in the `photo` field.",
+        "Adjunta una imagen en el campo `photo`.",
+        "Joignez une image dans le champ `photo`.",
+    ),
+    (
+        "photo_too_large",
+        "Photos can be at most 5 MB.",
+        "Las fotos pueden ocupar como máximo 5 MB.",
+        "Les photos ne peuvent pas dépasser 5 Mo.",
+    ),
+    (
+        "photo_unsupported_type",
+        "Photos must be JPEG, PNG or WebP images.",
+        "Las fotos deben ser imágenes JPEG, PNG o WebP.",
+        "Les photos doivent être des images JPEG, PNG ou WebP.",
+    ),
+    (
+        "caretaker_not_found",
+        "Caretaker not found",
+        "Cuidador no encontrado",
+        "Soignant introuvable",
+    ),
+    (
+        "caretaker_user_not_found",
+        "No account uses this email address.",
+        "Ninguna cuenta usa esta dirección de correo.",
+        "Aucun compte n'utilise cette adresse e-mail.",
+    ),
+    (
+        "caretaker_already_added",
+        "This person is already a caretaker or has a pending invite.",
+        "Esta persona ya es cuidadora o tiene una invitación pendiente.",
+        "Cette personne est déjà soignante ou a une invitation en attente.",
+    ),
+    (
+        "upload_session_not_found",
+        "This upload session doesn't exist or has expired.",
+        "Esta sesión de subida no existe o ha caducado.",
+        "Cette session de téléversement n'existe pas ou a expiré.",
+    ),
+    (
+        "upload_chunk_empty",
+        "Upload chunks can't be empty.",
+        "Los fragmentos de subida no pueden estar vacíos.",
+        "Les fragments de téléversement ne peuvent pas être vides.",
+    ),
+    (
+        "upload_chunk_too_large",
+        "Upload chunks can be at most 16 MB.",
+        "Los fragmentos de subida pueden ocupar como máximo 16 MB.",
+        "Les fragments de téléversement ne peuvent pas dépasser 16 Mo.",
+    ),
+    (
+        "upload_too_large",
+        "This video is larger than the maximum upload size.",
+        "Este video supera el tamaño máximo de subida.",
+        "Cette vidéo dépasse la taille maximale de téléversement.",
+    ),
+    (
+        "upload_incomplete",
+        "Some chunks of this upload are still missing.",
+        "Aún faltan fragmentos de esta subida.",
+        "Certains fragments de ce téléversement sont encore manquants.",
+    ),
+    (
+        "upload_completing",
+        "This upload is already being completed.",
+        "Esta subida ya se está completando.",
+        "Ce téléversement est déjà en cours de finalisation.",
+    ),
+    (
+        "video_expired",
+        "This video's file was removed after the retention period. Its analysis is still available.",
+        "El archivo de este video se eliminó tras el periodo de retención. Su análisis sigue disponible.",
+        "Le fichier de cette vidéo a été supprimé après la période de conservation. Son analyse reste disponible.",
+    ),
+    (
+        "dlq_entry_not_found",
+        "That video isn't in the dead-letter queue.",
+        "Ese video no está en la cola de mensajes fallidos.",
+        "Cette vidéo n'est pas dans la file des échecs.",
+    ),
+    (
+        "priority_not_allowed",
+        "High-priority processing isn't available on this account.",
+        "El procesamiento de alta prioridad no está disponible en esta cuenta.",
+        "Le traitement prioritaire n'est pas disponible pour ce compte.",
+    ),
+    (
+        "queue_not_found",
+        "There's no queue by that name.",
+        "No hay ninguna cola con ese nombre.",
+        "Aucune file ne porte ce nom.",
+    ),
+    (
+        "share_locked",
+        "Too many incorrect passcodes for this link. Please try again later.",
+        "Demasiados códigos incorrectos para este enlace. Inténtalo más tarde.",
+        "Trop de codes d'accès incorrects pour ce lien. Veuillez réessayer plus tard.",
+    ),
+    (
+        "video_tag_limit",
+        "This video already has the maximum number of tags. Remove one to add another.",
+        "Este video ya tiene el número máximo de etiquetas. Elimina una para añadir otra.",
+        "Cette vidéo a déjà le nombre maximal d'étiquettes. Supprimez-en une pour en ajouter une autre.",
+    ),
+    (
+        "unsupported_video_format",
+        "This file isn't a supported video. Upload an MP4, MOV, WebM or MKV file.",
+        "Este archivo no es un video compatible. Sube un archivo MP4, MOV, WebM o MKV.",
+        "Ce fichier n'est pas une vidéo prise en charge. Envoyez un fichier MP4, MOV, WebM ou MKV.",
+    ),
+    (
+        "upload_batch_too_many",
+        "Batch uploads can include at most 20 videos.",
+        "Las subidas por lotes pueden incluir como máximo 20 videos.",
+        "Les téléversements groupés peuvent contenir au maximum 20 vidéos.",
+    ),
+    (
+        "upload_quota_exceeded",
+        "Daily upload quota exceeded",
+        "Se superó la cuota diaria de subidas",
+        "Quota quotidien de téléversement dépassé",
+    ),
+    (
+        "upload_failed",
+        "The video couldn't be stored. Try uploading it again.",
+        "No se pudo guardar el video. Intenta subirlo de nuevo.",
+        "La vidéo n'a pas pu être enregistrée. Réessayez de la téléverser.",
+    ),
+    (
+        "invalid_multipart",
+        "The multipart upload body couldn't be read.",
+        "No se pudo leer el cuerpo de la subida multiparte.",
+        "Le corps du téléversement multipart n'a pas pu être lu.",
+    ),
+    (
+        "upload_url_unavailable",
+        "Direct uploads aren't available right now. Upload the video through upload_video or a resumable upload instead.",
+        "Las subidas directas no están disponibles ahora. Sube el video con upload_video o con una subida reanudable.",
+        "Les téléversements directs ne sont pas disponibles pour le moment. Envoyez la vidéo via upload_video ou un téléversement reprenable.",
+    ),
+    (
+        "upload_not_found",
+        "This upload doesn't exist or has expired.",
+        "Esta subida no existe o ha caducado.",
+        "Ce téléversement n'existe pas ou a expiré.",
+    ),
+    (
+        "upload_object_missing",
+        "The video hasn't been uploaded to the signed URL yet.",
+        "El video aún no se ha subido a la URL firmada.",
+        "La vidéo n'a pas encore été envoyée à l'URL signée.",
+    ),
+    (
+        "upload_object_empty",
+        "The uploaded video is empty.",
+        "El video subido está vacío.",
+        "La vidéo téléversée est vide.",
+    ),
+    (
+        "alert_not_found",
+        "Alert not found",
+        "Alerta no encontrada",
+        "Alerte introuvable",
+    ),
+    (
+        "mute_not_found",
+        "No active mute for this alert type",
+        "No hay silencio activo para este tipo de alerta",
+        "Aucune mise en sourdine active pour ce type d'alerte",
+    ),
+    (
+        "alert_setting_not_found",
+        "This alert type is already using the default settings",
+        "Este tipo de alerta ya usa la configuración predeterminada",
+        "Ce type d'alerte utilise déjà les paramètres par défaut",
+    ),
+    (
+        "notification_channel_unknown",
+        "Unknown notification channel",
+        "Canal de notificación desconocido",
+        "Canal de notification inconnu",
+    ),
+    (
+        "notification_preference_not_found",
+        "This channel is already using the default preferences",
+        "Este canal ya usa las preferencias predeterminadas",
+        "Ce canal utilise déjà les préférences par défaut",
+    ),
+    (
+        "alert_type.unknown",
+        "Unknown alert type.",
+        "Tipo de alerta desconocido.",
+        "Type d'alerte inconnu.",
+    ),
+    (
+        "video_status.unknown",
+        "Unknown video status.",
+        "Estado de video desconocido.",
+        "Statut de vidéo inconnu.",
+    ),
+    (
+        "share_not_found",
+        "This link is invalid or has been revoked.",
+        "Este enlace no es válido o ha sido revocado.",
+        "Ce lien est invalide ou a été révoqué.",
+    ),
+    (
+        "share_expired",
+        "This link has expired.",
+        "Este enlace ha caducado.",
+        "Ce lien a expiré.",
+    ),
+    (
+        "share_passcode_required",
+        "A valid passcode is required to view this page.",
+        "Se requiere un código válido para ver esta página.",
+        "Un code d'accès valide est requis pour voir cette page.",
+    ),
+    (
+        "api_key_not_found",
+        "API key not found",
+        "Clave de API no encontrada",
+        "Clé d'API introuvable",
+    ),
+    (
+        "api_key_limit_reached",
+        "You have reached the maximum number of active API keys.",
+        "Has alcanzado el número máximo de claves de API activas.",
+        "Vous avez atteint le nombre maximal de clés d'API actives.",
+    ),
+    (
+        "session_not_found",
+        "Session not found",
+        "Sesión no encontrada",
+        "Session introuvable",
+    ),
+    (
+        "login_locked",
+        "Too many failed sign-in attempts. Please try again later.",
+        "Demasiados intentos fallidos de inicio de sesión. Inténtalo más tarde.",
+        "Trop de tentatives de connexion échouées. Veuillez réessayer plus tard.",
+    ),
+    (
+        "oauth_invalid_state",
+        "This sign-in link is invalid or has expired. Please try again.",
+        "Este enlace de inicio de sesión no es válido o ha caducado. Inténtalo de nuevo.",
+        "Ce lien de connexion est invalide ou a expiré. Veuillez réessayer.",
+    ),
+    (
+        "oauth_provider_error",
+        "Google sign-in failed. Please try again.",
+        "El inicio de sesión con Google ha fallado. Inténtalo de nuevo.",
+        "La connexion avec Google a échoué. Veuillez réessayer.",
+    ),
+    (
+        "oauth_email_unverified",
+        "Your Google account's email address isn't verified.",
+        "La dirección de correo de tu cuenta de Google no está verificada.",
+        "L'adresse e-mail de votre compte Google n'est pas vérifiée.",
+    ),
+    (
+        "reset_token_invalid",
+        "This reset link is invalid or has already been used.",
+        "Este enlace de restablecimiento no es válido o ya se ha usado.",
+        "Ce lien de réinitialisation est invalide ou a déjà été utilisé.",
+    ),
+    (
+        "reset_token_expired",
+        "This reset link has expired. Please request a new one.",
+        "Este enlace de restablecimiento ha caducado. Solicita uno nuevo.",
+        "Ce lien de réinitialisation a expiré. Veuillez en demander un nouveau.",
+    ),
+    // Field validation
+    (
+        "field.required",
+        "This field is required.",
+        "Este campo es obligatorio.",
+        "Ce champ est obligatoire.",
+    ),
+    (
+        "field.too_long",
+        "This value is too long.",
+        "Este valor es demasiado largo.",
+        "Cette valeur est trop longue.",
+    ),
+    (
+        "field.invalid_format",
+        "This value has an invalid format.",
+        "Este valor tiene un formato no válido.",
+        "Cette valeur a un format invalide.",
+    ),
+    (
+        "field.too_short",
+        "This value is too short.",
+        "Este valor es demasiado corto.",
+        "Cette valeur est trop courte.",
+    ),
+    (
+        "field.matches_email",
+        "This value can't be the same as your email address.",
+        "Este valor no puede ser igual a tu dirección de correo.",
+        "Cette valeur ne peut pas être identique à votre adresse e-mail.",
+    ),
+    (
+        "field.out_of_range",
+        "This value is out of range.",
+        "Este valor está fuera de rango.",
+        "Cette valeur est hors limites.",
+    ),
+    (
+        "date_range.reversed",
+        "The start date must be on or before the end date.",
+        "La fecha de inicio debe ser igual o anterior a la fecha de fin.",
+        "La date de début doit être antérieure ou égale à la date de fin.",
+    ),
+    // Severity labels
+    ("severity.info", "Info", "Información", "Information"),
+    ("severity.low", "Low", "Baja", "Faible"),
+    ("severity.medium", "Medium", "Media", "Moyenne"),
+    ("severity.high", "High", "Alta", "Élevée"),
+    ("severity.critical", "Critical", "Crítica", "Critique"),
+];
+
+fn lookup(code: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG
+        .iter()
+        .find(|(c, ..)| *c == code)
+        .map(|(_, en, es, fr)| match locale {
+            Locale::En => *en,
+            Locale::Es => *es,
+            Locale::Fr => *fr,
+        })
+}
+
+/// Renders a catalog message in the current request locale. Unknown codes
+/// fall back to the code itself so clients always get something readable.
+pub fn translate(code: &str) -> String {
+    lookup(code, current_locale())
+        .map(String::from)
+        .unwrap_or_else(|| code.to_string())
+}
+
+/// Localized display label for an alert severity level.
+pub fn severity_label(severity_level: &str) -> String {
+    lookup(&format!("severity.{}", severity_level), current_locale())
+        .map(String::from)
+        .unwrap_or_else(|| severity_level.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_highest_weighted_supported_locale() {
+        assert_eq!(Locale::from_accept_language("fr-CA,fr;q=0.9"), Locale::Fr);
+        assert_eq!(
+            Locale::from_accept_language("de-DE, es;q=0.8, fr;q=0.5"),
+            Locale::Es
+        );
+        assert_eq!(Locale::from_accept_language("en;q=0.2, es_MX"), Locale::Es);
+        // Equal weights keep header order
+        assert_eq!(Locale::from_accept_language("fr, es"), Locale::Fr);
+    }
+
+    #[test]
+    fn unsupported_or_refused_locales_fall_back_to_english() {
+        assert_eq!(Locale::from_accept_language("de, ja;q=0.5"), Locale::En);
+        assert_eq!(Locale::from_accept_language("fr;q=0"), Locale::En);
+        assert_eq!(Locale::from_accept_language("*"), Locale::En);
+        assert_eq!(Locale::from_accept_language(""), Locale::En);
+    }
+
+    #[tokio::test]
+    async fn translations_follow_the_request_locale() {
+        let high = CURRENT_LOCALE
+            .scope(Locale::Es, async { severity_label("high") })
+            .await;
+        assert_eq!(high, "Alta");
+        assert_eq!(severity_label("high"), "High");
+        // Unknown codes come back as themselves
+        let unknown = CURRENT_LOCALE
+            .scope(Locale::Fr, async { translate("no_such_code") })
+            .await;
+        assert_eq!(unknown, "no_such_code");
+    }
+
+    #[test]
+    fn catalog_codes_are_unique_and_fully_translated() {
+        let mut seen = std::collections::HashSet::new();
+        for (code, en, es, fr) in CATALOG {
+            assert!(seen.insert(*code), "{} is listed twice", code);
+            assert!(
+                !en.is_empty() && !es.is_empty() && !fr.is_empty(),
+                "{} is missing a translation",
+                code
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_sets_content_language() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/", get(|| async { translate("severity.low") }))
+            .layer(axum::middleware::from_fn(locale_middleware));
+        let response = app
+            .oneshot(
+                Request::get("/")
+                    .header(header::ACCEPT_LANGUAGE, "fr-FR")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "fr");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], "Faible".as_bytes());
+    }
+}
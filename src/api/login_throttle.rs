@@ -0,0 +1,205 @@
+//! Sliding-window limits on failed logins, tracked per email and per client
+//! IP, and on wrong passcodes per vet share link. Each is a Redis sorted set
+//! with one member per failure, scored by timestamp.
+
+use redis::aio::MultiplexedConnection;
+use uuid::Uuid;
+
+const DEFAULT_MAX_FAILURES_PER_EMAIL: u64 = 5;
+const DEFAULT_MAX_FAILURES_PER_IP: u64 = 20;
+const DEFAULT_WINDOW_MINS: u64 = 15;
+const DEFAULT_MAX_PASSCODE_FAILURES: u64 = 10;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoginThrottle {
+    max_per_email: u64,
+    max_per_ip: u64,
+    window_secs: u64,
+}
+
+/// Which limit a locked-out attempt ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockScope {
+    Email,
+    Ip,
+}
+
+impl LockScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LockScope::Email => "email",
+            LockScope::Ip => "ip",
+        }
+    }
+}
+
+pub struct Lockout {
+    pub scope: LockScope,
+    /// Seconds until the oldest failure in the window ages out
+    pub retry_after_secs: u64,
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(default)
+}
+
+/// Seconds until `key` drops below `max` failures, if it's at the limit.
+async fn over_limit(
+    conn: &mut MultiplexedConnection,
+    key: &str,
+    max: u64,
+    window_secs: u64,
+) -> redis::RedisResult<Option<u64>> {
+    let now = chrono::Utc::now().timestamp();
+    let (_, failures, oldest): ((), u64, Vec<(String, i64)>) = redis::pipe()
+        .zrembyscore(key, "-inf", now - window_secs as i64)
+        .zcard(key)
+        .zrange_withscores(key, 0, 0)
+        .query_async(conn)
+        .await?;
+    if failures < max {
+        return Ok(None);
+    }
+    let oldest = oldest.first().map(|(_, ts)| *ts).unwrap_or(now);
+    Ok(Some((oldest + window_secs as i64 - now).max(1) as u64))
+}
+
+async fn add_failure(
+    conn: &mut MultiplexedConnection,
+    keys: &[String],
+    window_secs: u64,
+) -> redis::RedisResult<()> {
+    let now = chrono::Utc::now().timestamp();
+    let member = Uuid::new_v4().to_string();
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.zadd(key, &member, now)
+            .ignore()
+            .expire(key, window_secs as i64)
+            .ignore();
+    }
+    pipe.query_async(conn).await
+}
+
+fn email_key(email: &str) -> String {
+    format!(
+        "petpulse:login_failures:email:{}",
+        email.trim().to_lowercase()
+    )
+}
+
+fn ip_key(ip: &str) -> String {
+    format!("petpulse:login_failures:ip:{}", ip)
+}
+
+impl LoginThrottle {
+    /// Reads LOGIN_MAX_FAILURES, LOGIN_MAX_FAILURES_PER_IP and
+    /// LOGIN_FAILURE_WINDOW_MINS.
+    pub fn from_env() -> Self {
+        Self {
+            max_per_email: env_u64("LOGIN_MAX_FAILURES", DEFAULT_MAX_FAILURES_PER_EMAIL),
+            max_per_ip: env_u64("LOGIN_MAX_FAILURES_PER_IP", DEFAULT_MAX_FAILURES_PER_IP),
+            window_secs: env_u64("LOGIN_FAILURE_WINDOW_MINS", DEFAULT_WINDOW_MINS) * 60,
+        }
+    }
+
+    /// Returns the lockout if either the email or the IP has used up its
+    /// failures for the current window.
+    pub async fn check(
+        &self,
+        conn: &mut MultiplexedConnection,
+        email: &str,
+        ip: Option<&str>,
+    ) -> redis::RedisResult<Option<Lockout>> {
+        let mut limits = vec![(email_key(email), self.max_per_email, LockScope::Email)];
+        if let Some(ip) = ip {
+            limits.push((ip_key(ip), self.max_per_ip, LockScope::Ip));
+        }
+
+        for (key, max, scope) in limits {
+            if let Some(retry_after_secs) = over_limit(conn, &key, max, self.window_secs).await? {
+                return Ok(Some(Lockout {
+                    scope,
+                    retry_after_secs,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn record_failure(
+        &self,
+        conn: &mut MultiplexedConnection,
+        email: &str,
+        ip: Option<&str>,
+    ) -> redis::RedisResult<()> {
+        let mut keys = vec![email_key(email)];
+        keys.extend(ip.map(ip_key));
+        add_failure(conn, &keys, self.window_secs).await
+    }
+
+    /// Clears the email's failures after a successful login. The IP counter
+    /// is left alone on purpose: a stuffing run from one address would
+    /// otherwise wipe its own record each time it logs into an account it
+    /// controls. Those failures age out with the window instead.
+    pub async fn reset(
+        &self,
+        conn: &mut MultiplexedConnection,
+        email: &str,
+    ) -> redis::RedisResult<()> {
+        redis::cmd("DEL")
+            .arg(email_key(email))
+            .query_async(conn)
+            .await
+    }
+}
+
+fn share_key(share_id: Uuid) -> String {
+    format!("petpulse:share_passcode_failures:{}", share_id)
+}
+
+/// Wrong passcodes allowed per share link before it stops checking them for
+/// the rest of the window. Counted per link rather than per IP, since
+/// addresses are cheap for someone guessing a short passcode.
+#[derive(Debug, Clone, Copy)]
+pub struct PasscodeThrottle {
+    max_failures: u64,
+    window_secs: u64,
+}
+
+impl PasscodeThrottle {
+    /// Reads SHARE_PASSCODE_MAX_FAILURES and LOGIN_FAILURE_WINDOW_MINS.
+    pub fn from_env() -> Self {
+        Self {
+            max_failures: env_u64("SHARE_PASSCODE_MAX_FAILURES", DEFAULT_MAX_PASSCODE_FAILURES),
+            window_secs: env_u64("LOGIN_FAILURE_WINDOW_MINS", DEFAULT_WINDOW_MINS) * 60,
+        }
+    }
+
+    /// Seconds until the link takes passcodes again, if it's locked.
+    pub async fn check(
+        &self,
+        conn: &mut MultiplexedConnection,
+        share_id: Uuid,
+    ) -> redis::RedisResult<Option<u64>> {
+        over_limit(
+            conn,
+            &share_key(share_id),
+            self.max_failures,
+            self.window_secs,
+        )
+        .await
+    }
+
+    pub async fn record_failure(
+        &self,
+        conn: &mut MultiplexedConnection,
+        share_id: Uuid,
+    ) -> redis::RedisResult<()> {
+        add_failure(conn, &[share_key(share_id)], self.window_secs).await
+    }
+}
@@ -1,40 +1,239 @@
 use axum::{
     extract::Request,
-    http::StatusCode,
+    http::{header, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
-    Json,
 };
-use serde_json::json;
 use tower_cookies::Cookies;
 
+use super::api_keys::{self, API_KEY_PREFIX};
+use super::error::ApiError;
+use super::session::{self, SessionClaims, SessionKeys, TokenError, SESSION_COOKIE};
+
 use crate::entities::user;
 use axum::extract::Extension;
 use sea_orm::{DatabaseConnection, EntityTrait};
 
 pub async fn auth_middleware(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(session_keys): Extension<SessionKeys>,
+    Extension(redis_client): Extension<redis::Client>,
     cookies: Cookies,
     mut request: Request,
     next: Next,
 ) -> Response {
-    if let Some(cookie) = cookies.get("petpulse_user") {
-        if let Ok(user_id) = cookie.value().parse::<i32>() {
-            // Check DB for email to log
-            if let Ok(Some(user)) = user::Entity::find_by_id(user_id).one(&db).await {
-                request.extensions_mut().insert(user_id);
-                // Record email and user_id to span
-                tracing::Span::current()
-                    .record("user_id", user_id)
-                    .record("user_email", &user.email);
+    match session_user(&db, &session_keys, &redis_client, &cookies).await {
+        Ok((user_id, claims)) => {
+            request.extensions_mut().insert(user_id);
+            request.extensions_mut().insert(claims);
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Restricts routes to admin users. Layer it inside [`auth_middleware`] so the
+/// user id is already on the request.
+pub async fn admin_middleware(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match user::Entity::find_by_id(user_id).one(&db).await {
+        Ok(Some(user)) if user.is_admin => next.run(request).await,
+        Ok(_) => ApiError::forbidden("admin_required").into_response(),
+        Err(e) => ApiError::from(e).into_response(),
+    }
+}
+
+/// Like [`auth_middleware`], but also accepts `Authorization: Bearer pp_...`
+/// device keys so cameras can call the routes behind it. Key-authenticated
+/// requests only carry the user id; there are no `SessionClaims`, so handlers
+/// behind this layer must not require them.
+pub async fn device_auth_middleware(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(session_keys): Extension<SessionKeys>,
+    Extension(redis_client): Extension<redis::Client>,
+    cookies: Cookies,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| token.starts_with(API_KEY_PREFIX))
+        .map(str::to_string);
 
-                return next.run(request).await;
+    if let Some(key) = bearer {
+        // A presented key is authoritative; don't fall back to the cookie
+        return match api_keys::resolve_api_key(&db, &key).await {
+            Some(user_id) => {
+                tracing::Span::current().record("user_id", user_id);
+                request.extensions_mut().insert(user_id);
+                next.run(request).await
             }
-        }
+            None => ApiError::unauthorized().into_response(),
+        };
     }
-    (
-        StatusCode::UNAUTHORIZED,
-        Json(json!({"error": "Unauthorized"})),
+
+    auth_middleware(
+        Extension(db),
+        Extension(session_keys),
+        Extension(redis_client),
+        cookies,
+        request,
+        next,
     )
-        .into_response()
+    .await
+}
+
+/// Resolves the session cookie to its user. Tokens past half their lifetime
+/// are re-signed and the cookie replaced, and every request slides the
+/// session's Redis TTL, so only idle sessions expire.
+async fn session_user(
+    db: &DatabaseConnection,
+    session_keys: &SessionKeys,
+    redis_client: &redis::Client,
+    cookies: &Cookies,
+) -> Result<(i32, SessionClaims), ApiError> {
+    let cookie = cookies
+        .get(SESSION_COOKIE)
+        .ok_or_else(ApiError::unauthorized)?;
+    // Tampered or forged tokens fail verification
+    let mut claims = session_keys.verify(cookie.value()).map_err(|e| match e {
+        TokenError::Expired => session_expired(),
+        TokenError::Invalid => ApiError::unauthorized(),
+    })?;
+
+    // Logged-out and idle sessions are gone from Redis even if the client kept
+    // the cookie. Fail closed when Redis is unreachable.
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to connect to session store: {}", e);
+            ApiError::unauthorized()
+        })?;
+    let active = session::session_active(&mut conn, &claims)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to check session store: {}", e);
+            false
+        });
+    if !active {
+        return Err(session_expired());
+    }
+
+    match session_keys.renew(&claims) {
+        Some(Ok((token, renewed))) => {
+            cookies.add(session_keys.cookie(token));
+            claims = renewed;
+        }
+        Some(Err(e)) => tracing::warn!("Failed to renew session token: {}", e),
+        None => {}
+    }
+    if let Err(e) = session::touch_session(&mut conn, &claims).await {
+        tracing::warn!("Failed to refresh session: {}", e);
+    }
+
+    let user_id = claims.sub;
+    // Check DB for email to log
+    let user = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(ApiError::unauthorized)?;
+    // Record email and user_id to span
+    tracing::Span::current()
+        .record("user_id", user_id)
+        .record("user_email", &user.email);
+
+    Ok((user_id, claims))
+}
+
+fn session_expired() -> ApiError {
+    ApiError::new(StatusCode::UNAUTHORIZED, "session_expired")
+}
+
+/// Body-limit rejections come back from the extractors as plain text; turn
+/// them into the JSON 413 the upload handlers send.
+pub async fn json_payload_too_large(response: Response) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return super::daily_digest::upload_too_large();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::error_code;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn app(limit: usize) -> Router {
+        Router::new()
+            .route(
+                "/upload",
+                post(|body: axum::body::Bytes| async move { body.len().to_string() }),
+            )
+            .route(
+                "/own-413",
+                post(|| async { crate::api::daily_digest::upload_too_large() }),
+            )
+            .layer(axum::middleware::map_response(json_payload_too_large))
+            .layer(axum::extract::DefaultBodyLimit::max(limit))
+    }
+
+    async fn send(app: Router, uri: &str, bytes: usize) -> Response {
+        app.oneshot(
+            Request::post(uri)
+                .body(Body::from(vec![0u8; bytes]))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn body_limit_rejection_becomes_the_json_413() {
+        let response = send(app(16), "/upload", 17).await;
+        assert_eq!(
+            error_code(response).await,
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                String::from("upload_too_large")
+            )
+        );
+        assert_eq!(send(app(16), "/upload", 16).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn handler_413_passes_through_with_its_limit() {
+        let response = send(app(16), "/own-413", 0).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body["max_bytes"],
+            crate::api::daily_digest::max_video_bytes()
+        );
+    }
+
+    #[test]
+    fn request_limit_leaves_room_for_multipart_framing() {
+        let video = crate::api::daily_digest::max_video_bytes();
+        assert!(crate::api::daily_digest::max_request_body_bytes() as u64 > video);
+    }
 }
@@ -1,10 +1,32 @@
+pub mod admin;
+pub mod alert_mutes;
+pub mod alert_settings;
+pub mod api_keys;
 pub mod auth;
+pub mod caretakers;
+pub mod catalog;
+pub mod client_ip;
 pub mod critical_alerts;
 pub mod daily_digest;
+pub mod dashboard;
+pub mod direct_upload;
 pub mod emergency_contacts;
+pub mod error;
+pub mod events;
+pub mod extract;
+pub mod i18n;
+pub mod login_throttle;
 pub mod middleware;
+pub mod notification_preferences;
+pub mod pagination;
 pub mod pet;
 pub mod quick_actions;
+pub mod secret;
+pub mod session;
+pub mod share;
+pub mod upload_quota;
+pub mod upload_session;
+pub mod usage;
 pub mod user;
 pub mod video;
 pub mod webhook;
@@ -0,0 +1,170 @@
+use super::error::{ApiError, FieldError};
+use crate::entities::notification_preference;
+use crate::notifications::preferences::{severity_rank, SEVERITY_LEVELS};
+use crate::notifications::routing::ALL_CHANNELS;
+use crate::notifications::Channel;
+use axum::{
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    Set,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct UpdatePreferenceRequest {
+    enabled: Option<bool>,
+    severity_threshold: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PreferenceView {
+    pub channel: &'static str,
+    pub enabled: bool,
+    pub severity_threshold: String,
+    /// False when the channel is on its defaults (no stored row)
+    pub customized: bool,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl PreferenceView {
+    fn new(channel: Channel, pref: Option<notification_preference::Model>) -> Self {
+        match pref {
+            Some(p) => Self {
+                channel: channel.as_str(),
+                enabled: p.enabled,
+                severity_threshold: p.severity_threshold,
+                customized: true,
+                updated_at: Some(p.updated_at),
+            },
+            None => Self {
+                channel: channel.as_str(),
+                enabled: true,
+                severity_threshold: SEVERITY_LEVELS[0].to_string(),
+                customized: false,
+                updated_at: None,
+            },
+        }
+    }
+}
+
+fn parse_channel(raw: &str) -> Result<Channel, ApiError> {
+    Channel::parse(raw).ok_or_else(|| ApiError::not_found("notification_channel_unknown"))
+}
+
+async fn find_preference(
+    db: &DatabaseConnection,
+    user_id: i32,
+    channel: Channel,
+) -> Result<Option<notification_preference::Model>, sea_orm::DbErr> {
+    notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Channel.eq(channel.as_str()))
+        .one(db)
+        .await
+}
+
+// GET /users/notification-preferences - Every channel, including ones left on defaults
+pub async fn list_preferences(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+) -> Result<Response, ApiError> {
+    let mut prefs = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .all(&db)
+        .await?;
+
+    let views: Vec<PreferenceView> = ALL_CHANNELS
+        .iter()
+        .map(|channel| {
+            let pref = prefs
+                .iter()
+                .position(|p| p.channel == channel.as_str())
+                .map(|i| prefs.swap_remove(i));
+            PreferenceView::new(*channel, pref)
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(views)).into_response())
+}
+
+// PUT /users/notification-preferences/:channel - Create or update one channel's preference
+pub async fn update_preference(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(raw_channel): Path<String>,
+    Json(payload): Json<UpdatePreferenceRequest>,
+) -> Result<Response, ApiError> {
+    let channel = parse_channel(&raw_channel)?;
+    let threshold = payload.severity_threshold.map(|t| t.trim().to_lowercase());
+    if threshold
+        .as_deref()
+        .is_some_and(|t| severity_rank(t).is_none())
+    {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "severity_threshold",
+            "field.invalid_format",
+        )]));
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let pref = match find_preference(&db, user_id, channel).await? {
+        Some(existing) => {
+            let mut active = existing.into_active_model();
+            if let Some(enabled) = payload.enabled {
+                active.enabled = Set(enabled);
+            }
+            if let Some(threshold) = threshold {
+                active.severity_threshold = Set(threshold);
+            }
+            active.updated_at = Set(now);
+            active.update(&db).await?
+        }
+        None => notification_preference::ActiveModel {
+            user_id: Set(user_id),
+            channel: Set(channel.as_str().to_string()),
+            enabled: Set(payload.enabled.unwrap_or(true)),
+            severity_threshold: Set(threshold.unwrap_or_else(|| SEVERITY_LEVELS[0].to_string())),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await?,
+    };
+
+    tracing::info!(
+        user_id,
+        channel = channel.as_str(),
+        enabled = pref.enabled,
+        threshold = %pref.severity_threshold,
+        "Notification preference updated"
+    );
+    Ok((
+        StatusCode::OK,
+        Json(PreferenceView::new(channel, Some(pref))),
+    )
+        .into_response())
+}
+
+// DELETE /users/notification-preferences/:channel - Put a channel back on its defaults
+pub async fn reset_preference(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(raw_channel): Path<String>,
+) -> Result<Response, ApiError> {
+    let channel = parse_channel(&raw_channel)?;
+    let res = notification_preference::Entity::delete_many()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Channel.eq(channel.as_str()))
+        .exec(&db)
+        .await?;
+    if res.rows_affected == 0 {
+        return Err(ApiError::not_found("notification_preference_not_found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
@@ -0,0 +1,164 @@
+use super::error::{ApiError, FieldError};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use serde::Deserialize;
+
+const DEFAULT_MAX_PAGE_SIZE: u64 = 100;
+
+/// `page` / `page_size` query parameters for list endpoints, validated so
+/// handlers can hand them straight to a paginator. `page` is 1-based and
+/// clamped to at least 1; `page_size` (or `per_page`) is clamped to
+/// `1..=MAX_PAGE_SIZE`. Values that aren't numbers are a 422 naming the field.
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination<const DEFAULT_PAGE_SIZE: u64 = 10> {
+    pub page: u64,
+    pub page_size: u64,
+}
+
+impl<const DEFAULT_PAGE_SIZE: u64> Pagination<DEFAULT_PAGE_SIZE> {
+    /// 0-based page for `Paginator::fetch_page`.
+    pub fn index(&self) -> u64 {
+        self.page - 1
+    }
+}
+
+fn max_page_size() -> u64 {
+    std::env::var("MAX_PAGE_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_PAGE_SIZE)
+}
+
+#[derive(Deserialize)]
+struct RawPagination {
+    page: Option<String>,
+    page_size: Option<String>,
+    per_page: Option<String>,
+}
+
+fn parse_number(field: &'static str, raw: Option<&str>) -> Result<Option<u64>, FieldError> {
+    match raw.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => v
+            .parse()
+            .map(Some)
+            .map_err(|_| FieldError::new(field, "field.invalid_format")),
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync, const DEFAULT_PAGE_SIZE: u64> FromRequestParts<S>
+    for Pagination<DEFAULT_PAGE_SIZE>
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(raw) = Query::<RawPagination>::try_from_uri(&parts.uri).map_err(|_| {
+            ApiError::validation(vec![FieldError::new("page", "field.invalid_format")])
+        })?;
+
+        let mut errors = Vec::new();
+        let page = parse_number("page", raw.page.as_deref()).unwrap_or_else(|e| {
+            errors.push(e);
+            None
+        });
+        let (size_field, size) = match raw.page_size.as_deref() {
+            Some(v) => ("page_size", Some(v)),
+            None => ("per_page", raw.per_page.as_deref()),
+        };
+        let page_size = parse_number(size_field, size).unwrap_or_else(|e| {
+            errors.push(e);
+            None
+        });
+        if !errors.is_empty() {
+            return Err(ApiError::validation(errors));
+        }
+
+        Ok(Self {
+            // Capped so page * page_size can't overflow the paginator's offset
+            page: page.unwrap_or(1).clamp(1, u32::MAX as u64),
+            page_size: page_size
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .clamp(1, max_page_size()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::Request, response::IntoResponse};
+
+    async fn parse(query: &str) -> Result<Pagination, ApiError> {
+        let (mut parts, _) = Request::get(format!("/pets?{}", query))
+            .body(())
+            .unwrap()
+            .into_parts();
+        Pagination::from_request_parts(&mut parts, &()).await
+    }
+
+    /// Fields named in a 422 body.
+    async fn invalid_fields(err: ApiError) -> Vec<String> {
+        let response = err.into_response();
+        assert_eq!(response.status().as_u16(), 422);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["field"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn defaults_to_the_first_page() {
+        let p = parse("").await.unwrap();
+        assert_eq!((p.page, p.page_size, p.index()), (1, 10, 0));
+        let p = parse("page=&page_size=").await.unwrap();
+        assert_eq!((p.page, p.page_size), (1, 10));
+    }
+
+    #[tokio::test]
+    async fn page_zero_is_the_first_page() {
+        let p = parse("page=0").await.unwrap();
+        assert_eq!((p.page, p.index()), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn huge_values_are_clamped() {
+        let p = parse("page=18446744073709551615&page_size=1000000")
+            .await
+            .unwrap();
+        assert_eq!(p.page, u32::MAX as u64);
+        assert_eq!(p.page_size, DEFAULT_MAX_PAGE_SIZE);
+        // The paginator's offset still fits
+        assert!(p.index().checked_mul(p.page_size).is_some());
+    }
+
+    #[tokio::test]
+    async fn page_size_zero_is_one() {
+        assert_eq!(parse("page_size=0").await.unwrap().page_size, 1);
+    }
+
+    #[tokio::test]
+    async fn per_page_is_an_alias() {
+        assert_eq!(parse("per_page=25").await.unwrap().page_size, 25);
+        // page_size wins when both are sent
+        assert_eq!(parse("per_page=25&page_size=5").await.unwrap().page_size, 5);
+    }
+
+    #[tokio::test]
+    async fn values_that_are_not_numbers_name_their_field() {
+        let err = parse("page=abc&per_page=-5").await.unwrap_err();
+        assert_eq!(invalid_fields(err).await, ["page", "per_page"]);
+        let err = parse("page_size=1.5").await.unwrap_err();
+        assert_eq!(invalid_fields(err).await, ["page_size"]);
+    }
+}
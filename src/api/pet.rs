@@ -1,132 +1,445 @@
-use crate::entities::pet;
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::extract::{OwnedPet, ReadablePet};
+use super::pagination::Pagination;
+use crate::entities::pet::Species;
+use crate::entities::{pet, pet_caretaker, pet_weight};
+use crate::monitoring::{self, ActiveHours, MonitoringSchedule};
+use crate::storage_cleanup;
 use axum::{
-    extract::{Extension, Json, Path},
-    http::StatusCode,
+    body::Body,
+    extract::{multipart::Field, Extension, Json, Multipart, Path, Query},
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Set};
+use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::get::GetObjectRequest;
+use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter, Select, Set,
+};
 use serde_json::json;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MAX_PET_NAME_LEN: usize = 100;
+const MAX_BREED_LEN: usize = 100;
+const MAX_BIO_LEN: usize = 2000;
+const MAX_PET_AGE: i32 = 100;
+const MAX_WEIGHT_KG: f64 = 500.0;
+const MAX_HEALTH_ENTRIES: usize = 20;
+const MAX_HEALTH_ENTRY_LEN: usize = 200;
+const MAX_VET_FIELD_LEN: usize = 100;
+const MAX_PHOTO_BYTES: usize = 5 * 1024 * 1024;
+/// Accepted photo types and the extension stored objects get
+const PHOTO_TYPES: &[(&str, &str)] = &[
+    ("image/jpeg", "jpg"),
+    ("image/png", "png"),
+    ("image/webp", "webp"),
+];
+
+const PNG_MAGIC: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+const JPEG_MAGIC: &[u8] = &[0xFF, 0xD8, 0xFF];
+
+/// Photo type from the leading bytes, since the client's Content-Type and
+/// file name can be anything.
+fn sniff_photo(head: &[u8]) -> Option<&'static (&'static str, &'static str)> {
+    let mime = if head.starts_with(JPEG_MAGIC) {
+        "image/jpeg"
+    } else if head.starts_with(PNG_MAGIC) {
+        "image/png"
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP") {
+        "image/webp"
+    } else {
+        return None;
+    };
+    PHOTO_TYPES.iter().find(|(m, _)| *m == mime)
+}
+
+/// Reads a photo field, giving up as soon as it passes [`MAX_PHOTO_BYTES`]
+/// rather than buffering whatever the request body limit lets through.
+async fn read_photo(field: &mut Field<'_>) -> Result<Vec<u8>, ApiError> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, "photo_missing"))?
+    {
+        if data.len() + chunk.len() > MAX_PHOTO_BYTES {
+            return Err(ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "photo_too_large",
+            ));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    if data.is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "photo_missing"));
+    }
+    Ok(data)
+}
 
 #[derive(serde::Deserialize)]
 pub struct CreatePetRequest {
     name: String,
     age: i32,
-    species: String,
+    /// Case-insensitive; unknown values are rejected with a 422 listing the
+    /// accepted ones
+    species: Species,
     breed: String,
     bio: String,
+    weight_kg: Option<f64>,
+    #[serde(default)]
+    medical_conditions: Vec<String>,
+    #[serde(default)]
+    medications: Vec<String>,
+    vet_name: Option<String>,
+    vet_phone: Option<String>,
 }
 
-pub async fn list_user_pets(
-    Extension(db): Extension<DatabaseConnection>,
-    Extension(user_id): Extension<i32>,
-) -> Response {
-    use sea_orm::ColumnTrait;
-    use sea_orm::QueryFilter;
+fn validate_name(name: &str, errors: &mut Vec<FieldError>) {
+    if name.trim().is_empty() {
+        errors.push(FieldError::new("name", "field.required"));
+    } else if name.chars().count() > MAX_PET_NAME_LEN {
+        errors.push(FieldError::new("name", "field.too_long"));
+    }
+}
 
-    match pet::Entity::find()
-        .filter(pet::Column::UserId.eq(user_id))
-        .all(&db)
-        .await
+fn validate_age(age: i32, errors: &mut Vec<FieldError>) {
+    if !(0..=MAX_PET_AGE).contains(&age) {
+        errors.push(FieldError::new("age", "field.out_of_range"));
+    }
+}
+
+fn validate_text_len(
+    field: &'static str,
+    value: &str,
+    max_len: usize,
+    errors: &mut Vec<FieldError>,
+) {
+    if value.trim().chars().count() > max_len {
+        errors.push(FieldError::new(field, "field.too_long"));
+    }
+}
+
+fn validate_weight(weight_kg: f64, errors: &mut Vec<FieldError>) {
+    if !weight_kg.is_finite() || weight_kg <= 0.0 || weight_kg > MAX_WEIGHT_KG {
+        errors.push(FieldError::new("weight_kg", "field.out_of_range"));
+    }
+}
+
+fn validate_health_list(field: &'static str, entries: &[String], errors: &mut Vec<FieldError>) {
+    if entries.len() > MAX_HEALTH_ENTRIES
+        || entries
+            .iter()
+            .any(|e| e.chars().count() > MAX_HEALTH_ENTRY_LEN)
     {
-        Ok(pets) => (StatusCode::OK, Json(pets)).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
+        errors.push(FieldError::new(field, "field.too_long"));
+    }
+}
+
+fn validate_vet_field(field: &'static str, value: &str, errors: &mut Vec<FieldError>) {
+    if value.chars().count() > MAX_VET_FIELD_LEN {
+        errors.push(FieldError::new(field, "field.too_long"));
+    }
+}
+
+/// Trimmed, non-empty entries as a JSON array; None when nothing is left.
+fn health_list(entries: Vec<String>) -> Option<serde_json::Value> {
+    let entries: Vec<String> = entries
+        .into_iter()
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .collect();
+    (!entries.is_empty()).then(|| json!(entries))
+}
+
+/// Empty strings clear optional text fields.
+fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// What the caller wants to do with a pet. Accepted caretakers can read a
+/// pet's videos, alerts and digests; everything else is owner-only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PetAccess {
+    Read,
+    Manage,
+}
+
+/// Whether `user_id` may act on `pet`. Every pet-scoped access check goes
+/// through here so shared access only has to be added in one place.
+pub(crate) async fn check_pet_access(
+    db: &DatabaseConnection,
+    pet: &pet::Model,
+    user_id: i32,
+    access: PetAccess,
+) -> Result<(), ApiError> {
+    if pet.user_id == user_id
+        || (access == PetAccess::Read && pet_caretaker::is_caretaker(db, pet.id, user_id).await?)
+    {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden("not_your_pet"))
+    }
+}
+
+async fn find_pet(db: &DatabaseConnection, pet_id: i32) -> Result<pet::Model, ApiError> {
+    pet::Entity::find_by_id(pet_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("pet_not_found"))
+}
+
+/// Loads a pet the caller owns, or the matching 403/404. Archived pets are
+/// treated as missing.
+pub(crate) async fn owned_pet(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    user_id: i32,
+) -> Result<pet::Model, ApiError> {
+    let pet = owned_pet_including_archived(db, pet_id, user_id).await?;
+    if pet.is_archived() {
+        return Err(ApiError::not_found("pet_not_found"));
+    }
+    Ok(pet)
+}
+
+/// Like [`owned_pet`], but also returns archived pets (for restore and hard delete).
+pub(crate) async fn owned_pet_including_archived(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    user_id: i32,
+) -> Result<pet::Model, ApiError> {
+    let pet = find_pet(db, pet_id).await?;
+    check_pet_access(db, &pet, user_id, PetAccess::Manage).await?;
+    Ok(pet)
+}
+
+/// Loads an active pet the caller owns or cares for.
+pub(crate) async fn readable_pet(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    user_id: i32,
+) -> Result<pet::Model, ApiError> {
+    let pet = find_pet(db, pet_id).await?;
+    check_pet_access(db, &pet, user_id, PetAccess::Read).await?;
+    if pet.is_archived() {
+        return Err(ApiError::not_found("pet_not_found"));
+    }
+    Ok(pet)
+}
+
+/// Active pets the user owns plus those they've accepted a caretaker invite for.
+pub(crate) async fn accessible_pets(
+    db: &DatabaseConnection,
+    user_id: i32,
+) -> Result<Vec<pet::Model>, DbErr> {
+    let cared_for = pet_caretaker::caretaker_pet_ids(db, user_id).await?;
+    pet::Entity::find_active()
+        .filter(
+            Condition::any()
+                .add(pet::Column::UserId.eq(user_id))
+                .add(pet::Column::Id.is_in(cared_for)),
         )
-            .into_response(),
+        .all(db)
+        .await
+}
+
+#[derive(serde::Deserialize)]
+pub struct ListPetsParams {
+    #[serde(default)]
+    include_archived: bool,
+    /// Case-insensitive substring of the name or species
+    q: Option<String>,
+}
+
+/// `%`/`_` in user input are literal, not wildcards.
+fn like_pattern(q: &str) -> String {
+    let escaped = q
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// The caller's pets matching `params`, newest first.
+fn list_pets_query(user_id: i32, params: &ListPetsParams) -> Select<pet::Entity> {
+    use sea_orm::sea_query::{Expr, Func};
+    use sea_orm::QueryOrder;
+
+    let mut query = if params.include_archived {
+        pet::Entity::find()
+    } else {
+        pet::Entity::find_active()
+    }
+    .filter(pet::Column::UserId.eq(user_id));
+
+    if let Some(q) = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let pattern = like_pattern(q);
+        query = query.filter(
+            Condition::any()
+                .add(Expr::expr(Func::lower(Expr::col(pet::Column::Name))).like(pattern.clone()))
+                .add(Expr::expr(Func::lower(Expr::col(pet::Column::Species))).like(pattern)),
+        );
     }
+    query.order_by_desc(pet::Column::CreatedAt)
+}
+
+pub async fn list_user_pets(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    pagination: Pagination<50>,
+    Query(params): Query<ListPetsParams>,
+) -> Result<Response, ApiError> {
+    use sea_orm::PaginatorTrait;
+
+    let per_page = pagination.page_size;
+    let paginator = list_pets_query(user_id, &params).paginate(&db, per_page);
+    let total = paginator.num_items().await?;
+    let pets = paginator.fetch_page(pagination.index()).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "pets": pets,
+            "total": total,
+            "page": pagination.page,
+            "per_page": per_page,
+        })),
+    )
+        .into_response())
 }
 
 pub async fn create_pet(
     Extension(db): Extension<DatabaseConnection>,
     Extension(user_id): Extension<i32>,
     Json(payload): Json<CreatePetRequest>,
-) -> Response {
+) -> Result<Response, ApiError> {
+    let mut errors = Vec::new();
+    validate_name(&payload.name, &mut errors);
+    validate_age(payload.age, &mut errors);
+    validate_text_len("breed", &payload.breed, MAX_BREED_LEN, &mut errors);
+    validate_text_len("bio", &payload.bio, MAX_BIO_LEN, &mut errors);
+    if let Some(weight_kg) = payload.weight_kg {
+        validate_weight(weight_kg, &mut errors);
+    }
+    validate_health_list(
+        "medical_conditions",
+        &payload.medical_conditions,
+        &mut errors,
+    );
+    validate_health_list("medications", &payload.medications, &mut errors);
+    if let Some(vet_name) = &payload.vet_name {
+        validate_vet_field("vet_name", vet_name, &mut errors);
+    }
+    if let Some(vet_phone) = &payload.vet_phone {
+        validate_vet_field("vet_phone", vet_phone, &mut errors);
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
     let now = chrono::Utc::now().naive_utc();
     let new_pet = pet::ActiveModel {
         user_id: Set(user_id),
         name: Set(payload.name),
         age: Set(payload.age),
-        species: Set(payload.species),
-        breed: Set(payload.breed),
-        bio: Set(payload.bio),
+        species: Set(payload.species.as_str().to_string()),
+        breed: Set(payload.breed.trim().to_string()),
+        bio: Set(payload.bio.trim().to_string()),
+        weight_kg: Set(payload.weight_kg),
+        medical_conditions: Set(health_list(payload.medical_conditions)),
+        medications: Set(health_list(payload.medications)),
+        vet_name: Set(payload.vet_name.and_then(non_empty)),
+        vet_phone: Set(payload.vet_phone.and_then(non_empty)),
         created_at: Set(now),
         updated_at: Set(now),
         ..Default::default()
     };
 
-    match new_pet.insert(&db).await {
-        Ok(pet) => {
-            tracing::info!(pet_id = pet.id, user_id = pet.user_id, "New pet created");
-            metrics::counter!("petpulse_pets_created_total").increment(1);
-            metrics::gauge!("petpulse_pets_total").increment(1.0);
-
-            // Increment per-user count
-            let db_clone = db.clone();
-            tokio::spawn(async move {
-                crate::metrics::increment_user_pets(&db_clone, pet.user_id).await;
-            });
-            (StatusCode::CREATED, Json(pet)).into_response()
-        }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
-        )
-            .into_response(),
+    let pet = new_pet.insert(&db).await?;
+    if let Some(weight_kg) = pet.weight_kg {
+        pet_weight::record(&db, pet.id, weight_kg, now).await?;
     }
+
+    tracing::info!(pet_id = pet.id, user_id = pet.user_id, "New pet created");
+    metrics::counter!("petpulse_pets_created_total").increment(1);
+    metrics::gauge!("petpulse_pets_total").increment(1.0);
+
+    // Increment per-user count
+    let db_clone = db.clone();
+    let owner_id = pet.user_id;
+    tokio::spawn(async move {
+        crate::metrics::increment_user_pets(&db_clone, owner_id).await;
+    });
+
+    Ok((StatusCode::CREATED, Json(pet)).into_response())
 }
 
-pub async fn get_pet(
-    Extension(db): Extension<DatabaseConnection>,
-    Path(pet_id): Path<i32>,
-) -> Response {
-    match pet::Entity::find_by_id(pet_id).one(&db).await {
-        Ok(Some(p)) => (StatusCode::OK, Json(p)).into_response(),
-        Ok(None) => (
-            StatusCode::NOT_FOUND,
-            Json(json!({"error": "Pet not found"})),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
-        )
-            .into_response(),
-    }
+pub async fn get_pet(ReadablePet(pet): ReadablePet) -> Result<Response, ApiError> {
+    Ok((StatusCode::OK, Json(pet)).into_response())
 }
 
 #[derive(serde::Deserialize)]
 pub struct UpdatePetRequest {
     name: Option<String>,
     age: Option<i32>,
-    species: Option<String>,
+    species: Option<Species>,
     breed: Option<String>,
     bio: Option<String>,
+    static_check_disabled: Option<bool>,
+    weight_kg: Option<f64>,
+    /// Replaces the whole list; an empty list clears it
+    medical_conditions: Option<Vec<String>>,
+    medications: Option<Vec<String>>,
+    /// Empty strings clear the vet details
+    vet_name: Option<String>,
+    vet_phone: Option<String>,
 }
 
 pub async fn update_pet(
     Extension(db): Extension<DatabaseConnection>,
-    Path(pet_id): Path<i32>,
+    OwnedPet(pet): OwnedPet,
     Json(payload): Json<UpdatePetRequest>,
-) -> Response {
-    let pet = match pet::Entity::find_by_id(pet_id).one(&db).await {
-        Ok(Some(p)) => p,
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Pet not found"})),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+) -> Result<Response, ApiError> {
+    let mut errors = Vec::new();
+    if let Some(name) = &payload.name {
+        validate_name(name, &mut errors);
+    }
+    if let Some(age) = payload.age {
+        validate_age(age, &mut errors);
+    }
+    if let Some(breed) = &payload.breed {
+        validate_text_len("breed", breed, MAX_BREED_LEN, &mut errors);
+    }
+    if let Some(bio) = &payload.bio {
+        validate_text_len("bio", bio, MAX_BIO_LEN, &mut errors);
+    }
+    if let Some(weight_kg) = payload.weight_kg {
+        validate_weight(weight_kg, &mut errors);
+    }
+    if let Some(conditions) = &payload.medical_conditions {
+        validate_health_list("medical_conditions", conditions, &mut errors);
+    }
+    if let Some(medications) = &payload.medications {
+        validate_health_list("medications", medications, &mut errors);
+    }
+    if let Some(vet_name) = &payload.vet_name {
+        validate_vet_field("vet_name", vet_name, &mut errors);
+    }
+    if let Some(vet_phone) = &payload.vet_phone {
+        validate_vet_field("vet_phone", vet_phone, &mut errors);
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
 
+    // Only an actual change is a new weigh-in
+    let new_weight = payload.weight_kg.filter(|w| pet.weight_kg != Some(*w));
     let mut active_pet = pet.into_active_model();
     if let Some(name) = payload.name {
         active_pet.name = Set(name);
@@ -135,41 +448,509 @@ pub async fn update_pet(
         active_pet.age = Set(age);
     }
     if let Some(species) = payload.species {
-        active_pet.species = Set(species);
+        active_pet.species = Set(species.as_str().to_string());
     }
     if let Some(breed) = payload.breed {
-        active_pet.breed = Set(breed);
+        active_pet.breed = Set(breed.trim().to_string());
     }
     if let Some(bio) = payload.bio {
-        active_pet.bio = Set(bio);
+        active_pet.bio = Set(bio.trim().to_string());
     }
-    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+    if let Some(static_check_disabled) = payload.static_check_disabled {
+        active_pet.static_check_disabled = Set(static_check_disabled);
+    }
+    if let Some(weight_kg) = payload.weight_kg {
+        active_pet.weight_kg = Set(Some(weight_kg));
+    }
+    if let Some(conditions) = payload.medical_conditions {
+        active_pet.medical_conditions = Set(health_list(conditions));
+    }
+    if let Some(medications) = payload.medications {
+        active_pet.medications = Set(health_list(medications));
+    }
+    if let Some(vet_name) = payload.vet_name {
+        active_pet.vet_name = Set(non_empty(vet_name));
+    }
+    if let Some(vet_phone) = payload.vet_phone {
+        active_pet.vet_phone = Set(non_empty(vet_phone));
+    }
+    let now = chrono::Utc::now().naive_utc();
+    active_pet.updated_at = Set(now);
 
-    match active_pet.update(&db).await {
-        Ok(p) => (StatusCode::OK, Json(p)).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
-        )
-            .into_response(),
+    let pet = active_pet.update(&db).await?;
+    if let Some(weight_kg) = new_weight {
+        pet_weight::record(&db, pet.id, weight_kg, now).await?;
     }
+    Ok((StatusCode::OK, Json(pet)).into_response())
 }
 
+#[derive(serde::Deserialize)]
+pub struct DeletePetParams {
+    /// Permanently delete the pet and everything recorded for it
+    #[serde(default)]
+    hard: bool,
+}
+
+// DELETE /pets/:id - Archive the pet, or delete it outright with `?hard=true`
 pub async fn delete_pet(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
     Path(pet_id): Path<i32>,
-) -> Response {
-    match pet::Entity::delete_by_id(pet_id).exec(&db).await {
-        Ok(res) if res.rows_affected == 0 => (
-            StatusCode::NOT_FOUND,
-            Json(json!({"error": "Pet not found"})),
-        )
-            .into_response(),
-        Ok(_) => (StatusCode::OK, Json(json!({"message": "Pet deleted"}))).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
+    Query(params): Query<DeletePetParams>,
+) -> Result<Response, ApiError> {
+    let pet = owned_pet_including_archived(&db, pet_id, user_id).await?;
+
+    if !params.hard {
+        if pet.is_archived() {
+            return Err(ApiError::not_found("pet_not_found"));
+        }
+        let mut active_pet = pet.into_active_model();
+        let now = chrono::Utc::now().naive_utc();
+        active_pet.archived_at = Set(Some(now));
+        active_pet.updated_at = Set(now);
+        let pet = active_pet.update(&db).await?;
+        crate::audit::record(
+            &db,
+            user_id,
+            "archive_pet",
+            Some(("pet", pet_id.to_string())),
+            ip,
+        );
+        return Ok((StatusCode::OK, Json(pet)).into_response());
+    }
+
+    // Collect what to clean up before the cascade removes the video rows
+    let videos = storage_cleanup::videos_for_pets(&db, vec![pet_id]).await?;
+
+    let res = pet::Entity::delete_by_id(pet_id).exec(&db).await?;
+    if res.rows_affected == 0 {
+        return Err(ApiError::not_found("pet_not_found"));
+    }
+
+    let video_ids: HashSet<Uuid> = videos.iter().map(|(id, _)| *id).collect();
+    match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => {
+            match storage_cleanup::purge_queued_jobs(
+                &mut conn,
+                &video_ids,
+                &HashSet::from([pet_id]),
+            )
+            .await
+            {
+                Ok((videos, digests)) => tracing::info!(
+                    pet_id,
+                    "Dropped {} queued video jobs and {} digest jobs for deleted pet",
+                    videos,
+                    digests
+                ),
+                Err(e) => tracing::warn!("Failed to purge queued jobs for pet {}: {}", pet_id, e),
+            }
+        }
+        Err(e) => tracing::warn!("Failed to connect to Redis for pet cleanup: {}", e),
+    }
+
+    let mut file_paths: Vec<String> = videos.into_iter().map(|(_, path)| path).collect();
+    file_paths.extend(pet.photo_path);
+    let scheduled = file_paths.len();
+    tokio::spawn(storage_cleanup::delete_objects(gcs_client, file_paths));
+
+    tracing::info!(pet_id, objects = scheduled, "Pet deleted");
+    crate::audit::record(
+        &db,
+        user_id,
+        "delete_pet",
+        Some(("pet", pet_id.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "message": "Pet deleted",
+            "objects_scheduled_for_deletion": scheduled,
+        })),
+    )
+        .into_response())
+}
+
+// POST /pets/:id/restore - Bring an archived pet back
+pub async fn restore_pet(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+) -> Result<Response, ApiError> {
+    let pet = owned_pet_including_archived(&db, pet_id, user_id).await?;
+    if !pet.is_archived() {
+        return Ok((StatusCode::OK, Json(pet)).into_response());
+    }
+
+    let mut active_pet = pet.into_active_model();
+    active_pet.archived_at = Set(None);
+    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+    let pet = active_pet.update(&db).await?;
+    tracing::info!(pet_id = pet.id, "Pet restored from archive");
+    Ok((StatusCode::OK, Json(pet)).into_response())
+}
+
+#[derive(serde::Deserialize)]
+pub struct KnownBehaviorsRequest {
+    known_behaviors: Vec<String>,
+}
+
+// PUT /pets/:id/known-behaviors - Replace the list of behaviors considered normal for this pet
+pub async fn update_known_behaviors(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+    Json(payload): Json<KnownBehaviorsRequest>,
+) -> Result<Response, ApiError> {
+    let pet = owned_pet(&db, pet_id, user_id).await?;
+
+    let behaviors = pet::merge_known_behaviors(Vec::new(), payload.known_behaviors);
+
+    let mut active_pet = pet.into_active_model();
+    active_pet.known_behaviors = Set(json!(behaviors));
+    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+
+    let pet = active_pet.update(&db).await?;
+    Ok((StatusCode::OK, Json(pet)).into_response())
+}
+
+#[derive(serde::Deserialize)]
+pub struct MonitoringScheduleRequest {
+    expected_interval_minutes: i64,
+    active_hours: Option<ActiveHours>,
+}
+
+// PUT /pets/:id/monitoring-schedule - How often the pet's camera should upload;
+// missed uploads raise an alert
+pub async fn set_monitoring_schedule(
+    Extension(db): Extension<DatabaseConnection>,
+    OwnedPet(pet): OwnedPet,
+    Json(payload): Json<MonitoringScheduleRequest>,
+) -> Result<Response, ApiError> {
+    let mut errors = Vec::new();
+    if !(monitoring::MIN_INTERVAL_MINUTES..=monitoring::MAX_INTERVAL_MINUTES)
+        .contains(&payload.expected_interval_minutes)
+    {
+        errors.push(FieldError::new(
+            "expected_interval_minutes",
+            "field.out_of_range",
+        ));
+    }
+    if payload.active_hours.is_some_and(|h| !h.is_valid()) {
+        errors.push(FieldError::new("active_hours", "field.out_of_range"));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    let schedule = MonitoringSchedule {
+        expected_interval_minutes: payload.expected_interval_minutes,
+        active_hours: payload.active_hours,
+        set_at: chrono::Utc::now(),
+    };
+
+    let mut active_pet = pet.into_active_model();
+    active_pet.monitoring_schedule = Set(Some(json!(schedule)));
+    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+
+    let pet = active_pet.update(&db).await?;
+    tracing::info!(
+        pet_id = pet.id,
+        interval_minutes = schedule.expected_interval_minutes,
+        "Monitoring schedule set"
+    );
+    Ok((StatusCode::OK, Json(pet)).into_response())
+}
+
+// DELETE /pets/:id/monitoring-schedule - Stop checking the pet for missed uploads
+pub async fn clear_monitoring_schedule(
+    Extension(db): Extension<DatabaseConnection>,
+    OwnedPet(pet): OwnedPet,
+) -> Result<Response, ApiError> {
+    let mut active_pet = pet.into_active_model();
+    active_pet.monitoring_schedule = Set(None);
+    active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+
+    let pet = active_pet.update(&db).await?;
+    Ok((StatusCode::OK, Json(pet)).into_response())
+}
+
+// POST /pets/:id/photo - Upload or replace the pet's profile photo (multipart field `photo`)
+pub async fn upload_pet_photo(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
+    OwnedPet(pet): OwnedPet,
+    mut multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let bucket = std::env::var("GCS_BUCKET_NAME").map_err(ApiError::internal)?;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, "photo_missing"))?
+    {
+        if field.name() != Some("photo") {
+            continue;
+        }
+
+        let data = read_photo(&mut field).await?;
+        let (mime_type, ext) = sniff_photo(&data).ok_or_else(|| {
+            ApiError::new(StatusCode::UNSUPPORTED_MEDIA_TYPE, "photo_unsupported_type")
+        })?;
+
+        let object_name = format!("photos/{}/{}.{}", pet.id, Uuid::new_v4(), ext);
+        let upload_type = UploadType::Simple(Media {
+            name: object_name.clone().into(),
+            content_type: (*mime_type).into(),
+            content_length: Some(data.len() as u64),
+        });
+        gcs_client
+            .upload_object(
+                &UploadObjectRequest {
+                    bucket: bucket.clone(),
+                    ..Default::default()
+                },
+                data,
+                &upload_type,
+            )
+            .await
+            .map_err(ApiError::internal)?;
+
+        let previous = pet.photo_path.clone();
+        let mut active_pet = pet.into_active_model();
+        active_pet.photo_path = Set(Some(format!("gs://{}/{}", bucket, object_name)));
+        active_pet.updated_at = Set(chrono::Utc::now().naive_utc());
+        let pet = active_pet.update(&db).await?;
+
+        if let Some(previous) = previous {
+            tokio::spawn(storage_cleanup::delete_objects(gcs_client, vec![previous]));
+        }
+
+        tracing::info!(pet_id = pet.id, "Pet photo updated");
+        return Ok((StatusCode::OK, Json(pet)).into_response());
+    }
+
+    Err(ApiError::new(StatusCode::BAD_REQUEST, "photo_missing"))
+}
+
+// GET /pets/:id/photo - The pet's profile photo
+pub async fn get_pet_photo(
+    Extension(gcs_client): Extension<GcsClient>,
+    ReadablePet(pet): ReadablePet,
+) -> Result<Response, ApiError> {
+    let path = pet
+        .photo_path
+        .ok_or_else(|| ApiError::not_found("photo_not_found"))?;
+    let (bucket, object) = storage_cleanup::parse_gs_path(&path)
+        .ok_or_else(|| ApiError::internal(format!("Invalid photo path {}", path)))?;
+
+    let data = gcs_client
+        .download_object(
+            &GetObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            },
+            &Default::default(),
         )
-            .into_response(),
+        .await
+        .map_err(ApiError::internal)?;
+
+    let content_type = mime_guess::from_path(object)
+        .first_or_octet_stream()
+        .to_string();
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+        ],
+        Body::from(data),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{error_code, pet};
+
+    // The owner check comes before any caretaker lookup, so none of these
+    // touch the (disconnected) database
+    #[tokio::test]
+    async fn owner_may_read_and_manage() {
+        let db = DatabaseConnection::Disconnected;
+        let pet = pet(1, 7);
+        assert!(check_pet_access(&db, &pet, 7, PetAccess::Manage)
+            .await
+            .is_ok());
+        assert!(check_pet_access(&db, &pet, 7, PetAccess::Read)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn other_users_may_not_manage() {
+        let db = DatabaseConnection::Disconnected;
+        let err = check_pet_access(&db, &pet(1, 7), 8, PetAccess::Manage)
+            .await
+            .unwrap_err();
+        let (status, code) = error_code(err.into_response()).await;
+        assert_eq!((status.as_u16(), code.as_str()), (403, "not_your_pet"));
+    }
+
+    #[test]
+    fn search_wildcards_are_literal() {
+        assert_eq!(like_pattern("Rex"), "%rex%");
+        assert_eq!(like_pattern("100%_dog"), "%100\\%\\_dog%");
+        assert_eq!(like_pattern("a\\b"), "%a\\\\b%");
+    }
+
+    #[test]
+    fn list_matches_name_or_species_and_hides_archived() {
+        use sea_orm::{DbBackend, QueryTrait};
+
+        let params: ListPetsParams =
+            serde_json::from_value(serde_json::json!({"q": " Cat "})).unwrap();
+        let sql = list_pets_query(7, &params)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""pets"."user_id" = 7"#), "{sql}");
+        assert!(sql.contains(r#""archived_at" IS NULL"#), "{sql}");
+        assert!(
+            sql.contains(r#"LOWER("name") LIKE '%cat%' OR LOWER("species") LIKE '%cat%'"#),
+            "{sql}"
+        );
+        assert!(
+            sql.ends_with(r#"ORDER BY "pets"."created_at" DESC"#),
+            "{sql}"
+        );
+
+        let params: ListPetsParams =
+            serde_json::from_value(serde_json::json!({"include_archived": true, "q": "  "}))
+                .unwrap();
+        let sql = list_pets_query(7, &params)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(!sql.contains("IS NULL"), "{sql}");
+        assert!(!sql.contains("LIKE"), "{sql}");
+    }
+
+    #[test]
+    fn photo_type_comes_from_the_bytes() {
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8 ".to_vec();
+        webp.extend_from_slice(&[0; 8]);
+        assert_eq!(
+            sniff_photo(&[0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10]),
+            Some(&("image/jpeg", "jpg"))
+        );
+        assert_eq!(
+            sniff_photo(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0]),
+            Some(&("image/png", "png"))
+        );
+        assert_eq!(sniff_photo(&webp), Some(&("image/webp", "webp")));
+        assert_eq!(sniff_photo(b"RIFF\0\0\0\0WAVEfmt "), None);
+        assert_eq!(
+            sniff_photo(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            None
+        );
+        assert_eq!(sniff_photo(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn photo_reads_stop_at_the_cap() {
+        let data = vec![0xFF; MAX_PHOTO_BYTES + 1];
+        let mut multipart = crate::test_support::multipart("photo", "rex.jpg", &data).await;
+        let mut field = multipart.next_field().await.unwrap().unwrap();
+        let (status, code) =
+            error_code(read_photo(&mut field).await.unwrap_err().into_response()).await;
+        assert_eq!((status.as_u16(), code.as_str()), (413, "photo_too_large"));
+    }
+
+    #[tokio::test]
+    async fn photos_are_read_whole_up_to_the_cap() {
+        let mut data = vec![0; MAX_PHOTO_BYTES];
+        data[..3].copy_from_slice(JPEG_MAGIC);
+        let mut multipart = crate::test_support::multipart("photo", "rex.png", &data).await;
+        let mut field = multipart.next_field().await.unwrap().unwrap();
+        let read = read_photo(&mut field).await.unwrap();
+        assert_eq!(read, data);
+        // The file name says PNG, the bytes say JPEG
+        assert_eq!(sniff_photo(&read), Some(&("image/jpeg", "jpg")));
+    }
+
+    #[tokio::test]
+    async fn empty_photos_are_missing() {
+        let mut multipart = crate::test_support::multipart("photo", "rex.jpg", b"").await;
+        let mut field = multipart.next_field().await.unwrap().unwrap();
+        let (status, code) =
+            error_code(read_photo(&mut field).await.unwrap_err().into_response()).await;
+        assert_eq!((status.as_u16(), code.as_str()), (400, "photo_missing"));
+    }
+
+    /// The `/pets/:id` routes as the server mounts them, called as `user_id`.
+    fn pet_routes(db: &DatabaseConnection, user_id: i32) -> axum::Router {
+        use axum::routing::get;
+
+        axum::Router::new()
+            .route(
+                "/pets/:id",
+                get(get_pet).patch(update_pet).delete(delete_pet),
+            )
+            .layer(Extension(db.clone()))
+            .layer(Extension(
+                redis::Client::open("redis://127.0.0.1:1/").unwrap(),
+            ))
+            .layer(Extension(GcsClient::new(
+                google_cloud_storage::client::ClientConfig::default().anonymous(),
+            )))
+            .layer(Extension(user_id))
+    }
+
+    async fn send(routes: axum::Router, method: &str, uri: &str, body: Body) -> (u16, String) {
+        use tower::ServiceExt;
+
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap();
+        let (status, code) = error_code(routes.oneshot(request).await.unwrap()).await;
+        (status.as_u16(), code)
+    }
+
+    #[tokio::test]
+    async fn another_users_pet_is_forbidden() {
+        let db = crate::test_support::database().await;
+        crate::test_support::owner_with_pet(&db, 1, 1).await;
+        crate::test_support::owner_with_pet(&db, 2, 2).await;
+        let forbidden = (403, String::from("not_your_pet"));
+
+        let cases = [
+            ("GET", "/pets/1", Body::empty()),
+            ("PATCH", "/pets/1", Body::from(r#"{"name":"Max"}"#)),
+            ("DELETE", "/pets/1", Body::empty()),
+            ("DELETE", "/pets/1?hard=true", Body::empty()),
+        ];
+        for (method, uri, body) in cases {
+            assert_eq!(
+                send(pet_routes(&db, 2), method, uri, body).await,
+                forbidden,
+                "{method} {uri}"
+            );
+        }
+
+        let untouched = pet::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        assert_eq!(untouched.name, "Rex");
+        assert!(untouched.archived_at.is_none());
+        // The same session still reaches its own pet
+        let (status, _) = send(pet_routes(&db, 2), "GET", "/pets/2", Body::empty()).await;
+        assert_eq!(status, 200);
     }
 }
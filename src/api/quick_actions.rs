@@ -1,16 +1,11 @@
-use axum::{
-    extract::{Extension, Path},
-    response::IntoResponse,
-    Json,
-};
+use axum::{extract::Extension, response::IntoResponse, Json};
 use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 use uuid::Uuid;
 
-use crate::entities::{
-    alerts, emergency_contact, pet, prelude::*, quick_action, EmergencyContact, QuickAction,
-};
+use crate::api::extract::{OwnedAlert, ReadableAlert};
+use crate::entities::{emergency_contact, quick_action, EmergencyContact, QuickAction};
 
 #[derive(Deserialize)]
 pub struct CreateQuickActionRequest {
@@ -37,43 +32,14 @@ pub struct QuickActionResponse {
     pub created_at: chrono::NaiveDateTime,
 }
 
-// POST /alerts/:alert_id/quick-actions - Create and execute quick action
+// POST /alerts/:id/quick-actions - Create and execute quick action
 pub async fn create_quick_action(
     Extension(db): Extension<DatabaseConnection>,
     Extension(user_id): Extension<i32>,
-    Path(alert_id): Path<Uuid>,
+    OwnedAlert { alert, .. }: OwnedAlert,
     Json(payload): Json<CreateQuickActionRequest>,
 ) -> impl IntoResponse {
-    // Verify alert belongs to user's pet
-    let alert = match Alerts::find_by_id(alert_id).one(&db).await {
-        Ok(Some(a)) => a,
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Alert not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch alert: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
-
-    // Get pet to verify ownership
-    match Pet::find_by_id(alert.pet_id).one(&db).await {
-        Ok(Some(p)) if p.user_id == user_id => {}
-        Ok(Some(_)) => {
-            return (axum::http::StatusCode::FORBIDDEN, "Not your alert").into_response()
-        }
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Pet not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch pet: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
+    let alert_id = alert.id;
 
     // Verify emergency contact belongs to user
     let contact = match EmergencyContact::find_by_id(payload.emergency_contact_id)
@@ -109,8 +75,7 @@ pub async fn create_quick_action(
     let now = chrono::Utc::now().naive_utc();
     let video_clips_json = payload
         .video_clip_ids
-        .map(|ids| serde_json::to_value(ids).ok())
-        .flatten();
+        .and_then(|ids| serde_json::to_value(ids).ok());
 
     let active_model = quick_action::ActiveModel {
         id: Set(Uuid::new_v4()),
@@ -177,41 +142,12 @@ pub async fn create_quick_action(
     (axum::http::StatusCode::CREATED, Json(response)).into_response()
 }
 
-// GET /alerts/:alert_id/quick-actions - List quick actions for an alert
+// GET /alerts/:id/quick-actions - List quick actions for an alert
 pub async fn list_alert_quick_actions(
     Extension(db): Extension<DatabaseConnection>,
-    Extension(user_id): Extension<i32>,
-    Path(alert_id): Path<Uuid>,
+    ReadableAlert { alert, .. }: ReadableAlert,
 ) -> impl IntoResponse {
-    // Verify alert belongs to user's pet
-    let alert = match Alerts::find_by_id(alert_id).one(&db).await {
-        Ok(Some(a)) => a,
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Alert not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch alert: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
-
-    match Pet::find_by_id(alert.pet_id).one(&db).await {
-        Ok(Some(p)) if p.user_id == user_id => {}
-        Ok(Some(_)) => {
-            return (axum::http::StatusCode::FORBIDDEN, "Not your alert").into_response()
-        }
-        Ok(None) => return (axum::http::StatusCode::NOT_FOUND, "Pet not found").into_response(),
-        Err(e) => {
-            error!("Failed to fetch pet: {}", e);
-            return (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Database error",
-            )
-                .into_response();
-        }
-    };
+    let alert_id = alert.id;
 
     // Get quick actions
     let actions: Vec<quick_action::Model> = match QuickAction::find()
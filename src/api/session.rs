@@ -0,0 +1,560 @@
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower_cookies::cookie::SameSite;
+use tower_cookies::Cookie;
+use uuid::Uuid;
+
+pub const SESSION_COOKIE: &str = "petpulse_session";
+const DEFAULT_SESSION_TTL_HOURS: i64 = 24;
+const DEFAULT_SESSION_MAX_AGE_DAYS: i64 = 30;
+const DEFAULT_COOKIE_MAX_AGE_HOURS: i64 = 7 * 24;
+const MIN_SECRET_LEN: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionClaims {
+    /// User id
+    pub sub: i32,
+    /// Session id, tracked in Redis so sessions can be revoked server-side
+    pub jti: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// When the user logged in; renewals keep it so sessions can't be
+    /// extended past the max age. Older tokens fall back to `iat`.
+    #[serde(default)]
+    pub auth_time: i64,
+}
+
+impl SessionClaims {
+    fn started_at(&self) -> i64 {
+        if self.auth_time > 0 {
+            self.auth_time
+        } else {
+            self.iat
+        }
+    }
+}
+
+/// Why a session token was rejected. Expired tokens get their own error code
+/// so the frontend can send the user back to the login page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    Invalid,
+    Expired,
+}
+
+/// Attributes for cookies the API sets. Defaults suit production behind
+/// HTTPS; local HTTP development needs SESSION_COOKIE_SECURE=false.
+#[derive(Debug, Clone)]
+pub struct CookieConfig {
+    secure: bool,
+    same_site: SameSite,
+    domain: Option<String>,
+    max_age: chrono::Duration,
+}
+
+impl CookieConfig {
+    /// Reads SESSION_COOKIE_SECURE, SESSION_COOKIE_SAME_SITE (lax, strict or
+    /// none), SESSION_COOKIE_DOMAIN and SESSION_COOKIE_MAX_AGE_HOURS.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let mut secure = var("SESSION_COOKIE_SECURE")
+            .map(|v| !matches!(v.trim().to_lowercase().as_str(), "false" | "0" | "no"))
+            .unwrap_or(true);
+        let same_site = match var("SESSION_COOKIE_SAME_SITE")
+            .map(|v| v.trim().to_lowercase())
+            .as_deref()
+        {
+            None | Some("lax") => SameSite::Lax,
+            Some("strict") => SameSite::Strict,
+            Some("none") => SameSite::None,
+            Some(other) => {
+                tracing::warn!("Unknown SESSION_COOKIE_SAME_SITE {:?}; using Lax", other);
+                SameSite::Lax
+            }
+        };
+        // Browsers drop SameSite=None cookies that aren't Secure
+        if same_site == SameSite::None && !secure {
+            tracing::warn!("SESSION_COOKIE_SAME_SITE=none requires Secure; enabling it");
+            secure = true;
+        }
+        let max_age_hours = var("SESSION_COOKIE_MAX_AGE_HOURS")
+            .and_then(|v| v.parse().ok())
+            .filter(|h| *h > 0)
+            .unwrap_or(DEFAULT_COOKIE_MAX_AGE_HOURS);
+
+        Self {
+            secure,
+            same_site,
+            domain: var("SESSION_COOKIE_DOMAIN"),
+            max_age: chrono::Duration::hours(max_age_hours),
+        }
+    }
+
+    /// Applies the Secure flag, domain and HttpOnly. SameSite and max-age are
+    /// left to the caller since not every cookie wants the session's.
+    pub fn apply(&self, cookie: &mut Cookie<'static>) {
+        cookie.set_http_only(true);
+        cookie.set_secure(self.secure);
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+    }
+}
+
+/// HMAC keys for signing session tokens, built once at startup from
+/// SESSION_SECRET and shared with handlers as an extension.
+#[derive(Clone)]
+pub struct SessionKeys {
+    inner: Arc<Keys>,
+}
+
+struct Keys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: chrono::Duration,
+    max_age: chrono::Duration,
+    cookie: CookieConfig,
+}
+
+impl SessionKeys {
+    pub fn from_env() -> Self {
+        let secret = std::env::var("SESSION_SECRET").expect("SESSION_SECRET must be set");
+        assert!(
+            secret.len() >= MIN_SECRET_LEN,
+            "SESSION_SECRET must be at least {} bytes",
+            MIN_SECRET_LEN
+        );
+        let ttl_hours = std::env::var("SESSION_TTL_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SESSION_TTL_HOURS);
+        let max_age_days = std::env::var("SESSION_MAX_AGE_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SESSION_MAX_AGE_DAYS);
+
+        Self {
+            inner: Arc::new(Keys {
+                encoding: EncodingKey::from_secret(secret.as_bytes()),
+                decoding: DecodingKey::from_secret(secret.as_bytes()),
+                ttl: chrono::Duration::hours(ttl_hours),
+                max_age: chrono::Duration::days(max_age_days),
+                cookie: CookieConfig::from_env(),
+            }),
+        }
+    }
+
+    pub fn ttl(&self) -> chrono::Duration {
+        self.inner.ttl
+    }
+
+    pub fn issue(
+        &self,
+        user_id: i32,
+    ) -> Result<(String, SessionClaims), jsonwebtoken::errors::Error> {
+        let now = chrono::Utc::now().timestamp();
+        self.sign(SessionClaims {
+            sub: user_id,
+            jti: Uuid::new_v4().to_string(),
+            iat: now,
+            exp: now,
+            auth_time: now,
+        })
+    }
+
+    /// Re-signs a session that is past half its lifetime so active users stay
+    /// logged in. Returns None while the token is still fresh, or once the
+    /// session has reached SESSION_MAX_AGE_DAYS.
+    pub fn renew(
+        &self,
+        claims: &SessionClaims,
+    ) -> Option<Result<(String, SessionClaims), jsonwebtoken::errors::Error>> {
+        let now = chrono::Utc::now().timestamp();
+        let fresh = claims.exp - now > self.inner.ttl.num_seconds() / 2;
+        let at_cap = claims.exp >= claims.started_at() + self.inner.max_age.num_seconds();
+        if fresh || at_cap {
+            return None;
+        }
+        Some(self.sign(SessionClaims {
+            iat: now,
+            auth_time: claims.started_at(),
+            ..claims.clone()
+        }))
+    }
+
+    /// Sets `exp` from the TTL, capped at the session's max age, and signs.
+    fn sign(
+        &self,
+        mut claims: SessionClaims,
+    ) -> Result<(String, SessionClaims), jsonwebtoken::errors::Error> {
+        claims.exp = (claims.iat + self.inner.ttl.num_seconds())
+            .min(claims.started_at() + self.inner.max_age.num_seconds());
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &self.inner.encoding,
+        )?;
+        Ok((token, claims))
+    }
+
+    /// Verifies the signature and expiry.
+    pub fn verify(&self, token: &str) -> Result<SessionClaims, TokenError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        decode::<SessionClaims>(token, &self.inner.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                jsonwebtoken::errors::ErrorKind::ExpiredSignature => TokenError::Expired,
+                _ => TokenError::Invalid,
+            })
+    }
+
+    pub fn cookie_config(&self) -> &CookieConfig {
+        &self.inner.cookie
+    }
+
+    /// Session cookie carrying `token`. Every place that sets or clears the
+    /// session cookie goes through here so the attributes always match.
+    pub fn cookie(&self, token: String) -> Cookie<'static> {
+        let config = &self.inner.cookie;
+        let mut cookie = Cookie::new(SESSION_COOKIE, token);
+        cookie.set_path("/");
+        config.apply(&mut cookie);
+        cookie.set_same_site(config.same_site);
+        // The cookie may outlive the token; an expired token then gets a
+        // session_expired response instead of looking logged out
+        cookie.set_max_age(tower_cookies::cookie::time::Duration::seconds(
+            config.max_age.num_seconds(),
+        ));
+        cookie
+    }
+
+    /// Cookie that clears the session cookie (matching path and domain).
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        let mut cookie = self.cookie(String::new());
+        cookie.make_removal();
+        cookie
+    }
+}
+
+/// Where a session was opened from, shown in the session list.
+#[derive(Debug, Clone, Default)]
+pub struct SessionMeta {
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionInfo {
+    pub id: String,
+    pub created_at: Option<i64>,
+    pub last_seen_at: Option<i64>,
+    pub expires_at: Option<i64>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+}
+
+fn session_key(jti: &str) -> String {
+    format!("petpulse:session:{}", jti)
+}
+
+fn session_meta_key(jti: &str) -> String {
+    format!("petpulse:session_meta:{}", jti)
+}
+
+fn user_sessions_key(user_id: i32) -> String {
+    format!("petpulse:user_sessions:{}", user_id)
+}
+
+/// Records a freshly issued session so the middleware will accept it.
+pub async fn store_session(
+    conn: &mut redis::aio::MultiplexedConnection,
+    claims: &SessionClaims,
+    meta: &SessionMeta,
+) -> redis::RedisResult<()> {
+    let ttl = (claims.exp - chrono::Utc::now().timestamp()).max(1) as u64;
+    let user_key = user_sessions_key(claims.sub);
+    let meta_key = session_meta_key(&claims.jti);
+    let mut fields = vec![
+        ("created_at", claims.started_at().to_string()),
+        ("last_seen_at", claims.iat.to_string()),
+        ("expires_at", claims.exp.to_string()),
+    ];
+    if let Some(user_agent) = &meta.user_agent {
+        fields.push(("user_agent", user_agent.clone()));
+    }
+    if let Some(ip) = &meta.ip {
+        fields.push(("ip", ip.clone()));
+    }
+    redis::pipe()
+        .set_ex(session_key(&claims.jti), claims.sub, ttl)
+        .hset_multiple(&meta_key, &fields)
+        .expire(&meta_key, ttl as i64)
+        .sadd(&user_key, &claims.jti)
+        .expire(&user_key, ttl as i64)
+        .query_async(conn)
+        .await
+}
+
+/// Slides the session's Redis TTL to match `claims.exp` and records activity.
+/// Called on every authenticated request, after any renewal.
+pub async fn touch_session(
+    conn: &mut redis::aio::MultiplexedConnection,
+    claims: &SessionClaims,
+) -> redis::RedisResult<()> {
+    let now = chrono::Utc::now().timestamp();
+    let ttl = (claims.exp - now).max(1);
+    let user_key = user_sessions_key(claims.sub);
+    let meta_key = session_meta_key(&claims.jti);
+    let user_ttl: i64 = conn.ttl(&user_key).await?;
+    let mut pipe = redis::pipe();
+    pipe.expire(session_key(&claims.jti), ttl)
+        .ignore()
+        .hset_multiple(
+            &meta_key,
+            &[
+                ("last_seen_at", now.to_string()),
+                ("expires_at", claims.exp.to_string()),
+            ],
+        )
+        .ignore()
+        .expire(&meta_key, ttl)
+        .ignore();
+    // The set lives as long as the user's longest session
+    if user_ttl < ttl {
+        pipe.expire(&user_key, ttl).ignore();
+    }
+    pipe.query_async(conn).await
+}
+
+/// The user's live sessions, newest first. Ids whose session already expired
+/// are dropped from the user's set along the way.
+pub async fn list_sessions(
+    conn: &mut redis::aio::MultiplexedConnection,
+    user_id: i32,
+) -> redis::RedisResult<Vec<SessionInfo>> {
+    let user_key = user_sessions_key(user_id);
+    let jtis: Vec<String> = conn.smembers(&user_key).await?;
+
+    let mut sessions = Vec::new();
+    let mut stale = Vec::new();
+    for jti in jtis {
+        let owner: Option<i32> = conn.get(session_key(&jti)).await?;
+        if owner != Some(user_id) {
+            stale.push(jti);
+            continue;
+        }
+        let meta: HashMap<String, String> = conn.hgetall(session_meta_key(&jti)).await?;
+        let number = |field: &str| meta.get(field).and_then(|v| v.parse().ok());
+        sessions.push(SessionInfo {
+            created_at: number("created_at"),
+            last_seen_at: number("last_seen_at"),
+            expires_at: number("expires_at"),
+            user_agent: meta.get("user_agent").cloned(),
+            ip: meta.get("ip").cloned(),
+            id: jti,
+        });
+    }
+    if !stale.is_empty() {
+        let _: () = conn.srem(&user_key, &stale).await?;
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    Ok(sessions)
+}
+
+/// Whether the session is still live (not logged out and not expired).
+pub async fn session_active(
+    conn: &mut redis::aio::MultiplexedConnection,
+    claims: &SessionClaims,
+) -> redis::RedisResult<bool> {
+    let owner: Option<i32> = conn.get(session_key(&claims.jti)).await?;
+    Ok(owner == Some(claims.sub))
+}
+
+pub async fn revoke_session(
+    conn: &mut redis::aio::MultiplexedConnection,
+    claims: &SessionClaims,
+) -> redis::RedisResult<()> {
+    revoke_session_id(conn, claims.sub, &claims.jti)
+        .await
+        .map(|_| ())
+}
+
+/// Revokes one of the user's sessions by id. Returns false if the user has no
+/// such session.
+pub async fn revoke_session_id(
+    conn: &mut redis::aio::MultiplexedConnection,
+    user_id: i32,
+    jti: &str,
+) -> redis::RedisResult<bool> {
+    let owner: Option<i32> = conn.get(session_key(jti)).await?;
+    if owner != Some(user_id) {
+        return Ok(false);
+    }
+    redis::pipe()
+        .del(session_key(jti))
+        .del(session_meta_key(jti))
+        .srem(user_sessions_key(user_id), jti)
+        .query_async::<()>(conn)
+        .await?;
+    Ok(true)
+}
+
+/// Ends every session a user has open, e.g. after a password change.
+pub async fn revoke_user_sessions(
+    conn: &mut redis::aio::MultiplexedConnection,
+    user_id: i32,
+) -> redis::RedisResult<()> {
+    let user_key = user_sessions_key(user_id);
+    let jtis: Vec<String> = conn.smembers(&user_key).await?;
+    let mut pipe = redis::pipe();
+    for jti in &jtis {
+        pipe.del(session_key(jti)).del(session_meta_key(jti));
+    }
+    pipe.del(&user_key).query_async(conn).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn keys(secret: &str) -> SessionKeys {
+        SessionKeys {
+            inner: Arc::new(Keys {
+                encoding: EncodingKey::from_secret(secret.as_bytes()),
+                decoding: DecodingKey::from_secret(secret.as_bytes()),
+                ttl: chrono::Duration::hours(DEFAULT_SESSION_TTL_HOURS),
+                max_age: chrono::Duration::days(DEFAULT_SESSION_MAX_AGE_DAYS),
+                cookie: CookieConfig {
+                    secure: true,
+                    same_site: SameSite::Lax,
+                    domain: None,
+                    max_age: chrono::Duration::hours(DEFAULT_COOKIE_MAX_AGE_HOURS),
+                },
+            }),
+        }
+    }
+
+    #[test]
+    fn issued_token_verifies() {
+        let keys = keys(SECRET);
+        let (token, claims) = keys.issue(42).unwrap();
+        let verified = keys.verify(&token).unwrap();
+        assert_eq!(verified.sub, 42);
+        assert_eq!(verified.jti, claims.jti);
+    }
+
+    #[test]
+    fn token_signed_with_another_secret_is_rejected() {
+        let (token, _) = keys("another-secret-another-secret-xx").issue(42).unwrap();
+        assert_eq!(
+            keys(SECRET).verify(&token).unwrap_err(),
+            TokenError::Invalid
+        );
+    }
+
+    #[test]
+    fn forged_claims_are_rejected() {
+        let keys = keys(SECRET);
+        let (mine, _) = keys.issue(42).unwrap();
+        let (theirs, _) = keys.issue(1).unwrap();
+        // Someone else's claims under my token's signature
+        let mine: Vec<&str> = mine.split('.').collect();
+        let theirs: Vec<&str> = theirs.split('.').collect();
+        let forged = [mine[0], theirs[1], mine[2]].join(".");
+        assert_eq!(keys.verify(&forged).unwrap_err(), TokenError::Invalid);
+    }
+
+    #[test]
+    fn old_style_cookie_values_are_rejected() {
+        let keys = keys(SECRET);
+        assert_eq!(keys.verify("42").unwrap_err(), TokenError::Invalid);
+        assert_eq!(keys.verify("").unwrap_err(), TokenError::Invalid);
+    }
+
+    #[test]
+    fn expired_token_reports_expired() {
+        let keys = keys(SECRET);
+        let long_ago = chrono::Utc::now().timestamp() - 3 * 24 * 3600;
+        let (token, _) = keys
+            .sign(SessionClaims {
+                sub: 42,
+                jti: Uuid::new_v4().to_string(),
+                iat: long_ago,
+                exp: long_ago,
+                auth_time: long_ago,
+            })
+            .unwrap();
+        assert_eq!(keys.verify(&token).unwrap_err(), TokenError::Expired);
+    }
+
+    fn with_cookie(cookie: CookieConfig) -> SessionKeys {
+        let base = keys(SECRET);
+        let inner = Arc::into_inner(base.inner).unwrap();
+        SessionKeys {
+            inner: Arc::new(Keys { cookie, ..inner }),
+        }
+    }
+
+    #[test]
+    fn session_cookie_carries_hardened_attributes() {
+        let cookie = keys(SECRET).cookie("token".to_string());
+        assert_eq!(cookie.name(), SESSION_COOKIE);
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(
+            cookie.max_age(),
+            Some(tower_cookies::cookie::time::Duration::hours(
+                DEFAULT_COOKIE_MAX_AGE_HOURS
+            ))
+        );
+        assert_eq!(cookie.domain(), None);
+    }
+
+    #[test]
+    fn configured_domain_and_same_site_are_applied() {
+        let keys = with_cookie(CookieConfig {
+            secure: false,
+            same_site: SameSite::Strict,
+            domain: Some("petpulse.example".to_string()),
+            max_age: chrono::Duration::hours(2),
+        });
+        let cookie = keys.cookie("token".to_string());
+        assert_eq!(cookie.secure(), Some(false));
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+        assert_eq!(cookie.domain(), Some("petpulse.example"));
+        assert_eq!(
+            cookie.max_age(),
+            Some(tower_cookies::cookie::time::Duration::hours(2))
+        );
+    }
+
+    #[test]
+    fn removal_cookie_matches_the_session_cookie() {
+        let keys = with_cookie(CookieConfig {
+            secure: true,
+            same_site: SameSite::Lax,
+            domain: Some("petpulse.example".to_string()),
+            max_age: chrono::Duration::hours(2),
+        });
+        let removal = keys.removal_cookie();
+        assert_eq!(removal.name(), SESSION_COOKIE);
+        assert_eq!(removal.value(), "");
+        assert_eq!(removal.path(), Some("/"));
+        assert_eq!(removal.domain(), Some("petpulse.example"));
+        assert_eq!(removal.http_only(), Some(true));
+        assert_eq!(
+            removal.max_age(),
+            Some(tower_cookies::cookie::time::Duration::ZERO)
+        );
+    }
+}
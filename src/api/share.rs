@@ -0,0 +1,677 @@
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::login_throttle::PasscodeThrottle;
+use super::pet::owned_pet;
+use super::secret::{hash_secret, random_secret, split_token, verify_secret};
+use crate::entities::{
+    alerts, daily_digest, pet, pet_share, pet_share_access, pet_video, pet_weight,
+};
+use axum::{
+    extract::{Extension, Json, Path},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use google_cloud_storage::client::Client as GcsClient;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+pub const SHARE_SECTIONS: &[&str] = &["digests", "unusual_clips", "alerts", "weight_history"];
+const MAX_SHARE_DAYS: i64 = 30;
+const DEFAULT_SHARE_DAYS: i64 = 7;
+const MIN_PASSCODE_LEN: usize = 4;
+/// How far back the shared view reaches.
+const SHARE_WINDOW_DAYS: i64 = 30;
+const SHARED_CLIP_LIMIT: u64 = 50;
+const SHARED_ALERT_LIMIT: u64 = 100;
+const ACCESS_LOG_LIMIT: u64 = 50;
+pub const PASSCODE_HEADER: &str = "x-share-passcode";
+
+#[derive(Deserialize)]
+pub struct CreateShareRequest {
+    sections: Option<Vec<String>>,
+    expires_in_days: Option<i64>,
+    passcode: Option<String>,
+}
+
+async fn log_access(
+    db: &DatabaseConnection,
+    share_id: Uuid,
+    ip_address: Option<String>,
+    refused: Option<&str>,
+) {
+    let entry = pet_share_access::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        share_id: Set(share_id),
+        ip_address: Set(ip_address),
+        granted: Set(refused.is_none()),
+        reason: Set(refused.map(String::from)),
+        accessed_at: Set(chrono::Utc::now().naive_utc()),
+    };
+    if let Err(e) = entry.insert(db).await {
+        tracing::error!("Failed to log share access for {}: {}", share_id, e);
+    }
+}
+
+// POST /pets/:id/share - Create an expiring read-only link for a vet
+pub async fn create_share(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+    Json(payload): Json<CreateShareRequest>,
+) -> Result<Response, ApiError> {
+    let mut errors = Vec::new();
+
+    let sections = payload
+        .sections
+        .unwrap_or_else(|| SHARE_SECTIONS.iter().map(|s| s.to_string()).collect());
+    if sections.is_empty()
+        || sections
+            .iter()
+            .any(|s| !SHARE_SECTIONS.contains(&s.as_str()))
+    {
+        errors.push(FieldError::new("sections", "field.invalid_format"));
+    }
+
+    let days = payload.expires_in_days.unwrap_or(DEFAULT_SHARE_DAYS);
+    if !(1..=MAX_SHARE_DAYS).contains(&days) {
+        errors.push(FieldError::new("expires_in_days", "field.out_of_range"));
+    }
+
+    if payload
+        .passcode
+        .as_ref()
+        .is_some_and(|p| p.chars().count() < MIN_PASSCODE_LEN)
+    {
+        errors.push(FieldError::new("passcode", "field.too_short"));
+    }
+
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let secret = random_secret();
+    let passcode_hash = match &payload.passcode {
+        Some(p) => Some(hash_secret(p)?),
+        None => None,
+    };
+
+    let now = chrono::Utc::now().naive_utc();
+    let share = pet_share::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        pet_id: Set(pet_id),
+        created_by: Set(user_id),
+        secret_hash: Set(hash_secret(&secret)?),
+        passcode_hash: Set(passcode_hash),
+        sections: Set(json!(sections)),
+        expires_at: Set(now + chrono::Duration::days(days)),
+        revoked_at: Set(None),
+        created_at: Set(now),
+    }
+    .insert(&db)
+    .await?;
+
+    tracing::info!(pet_id, user_id, share_id = %share.id, "Pet share link created");
+
+    // The token is only ever returned here
+    let token = format!("{}.{}", share.id, secret);
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "id": share.id,
+            "token": token,
+            "path": format!("/shared/pets/{}", token),
+            "sections": sections,
+            "expires_at": share.expires_at,
+            "has_passcode": share.passcode_hash.is_some(),
+        })),
+    )
+        .into_response())
+}
+
+#[derive(Serialize)]
+struct ShareView {
+    #[serde(flatten)]
+    share: pet_share::Model,
+    has_passcode: bool,
+    active: bool,
+    access_log: Vec<pet_share_access::Model>,
+}
+
+// GET /pets/:id/shares - Share links for a pet with their recent access log
+pub async fn list_shares(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+) -> Result<Response, ApiError> {
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let shares = pet_share::Entity::find()
+        .filter(pet_share::Column::PetId.eq(pet_id))
+        .order_by_desc(pet_share::Column::CreatedAt)
+        .all(&db)
+        .await?;
+
+    let share_ids: Vec<Uuid> = shares.iter().map(|s| s.id).collect();
+    let mut access = pet_share_access::Entity::find()
+        .filter(pet_share_access::Column::ShareId.is_in(share_ids))
+        .order_by_desc(pet_share_access::Column::AccessedAt)
+        .limit(ACCESS_LOG_LIMIT * shares.len().max(1) as u64)
+        .all(&db)
+        .await?;
+
+    let views: Vec<ShareView> = shares
+        .into_iter()
+        .map(|s| {
+            let mut access_log: Vec<_> = access.extract_if(.., |a| a.share_id == s.id).collect();
+            access_log.truncate(ACCESS_LOG_LIMIT as usize);
+            ShareView {
+                has_passcode: s.passcode_hash.is_some(),
+                active: s.is_live(),
+                share: s,
+                access_log,
+            }
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(views)).into_response())
+}
+
+// DELETE /pets/:id/share/:share_id - Revoke a share link
+pub async fn revoke_share(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path((pet_id, share_id)): Path<(i32, Uuid)>,
+) -> Result<Response, ApiError> {
+    owned_pet(&db, pet_id, user_id).await?;
+
+    let share = pet_share::Entity::find_by_id(share_id)
+        .filter(pet_share::Column::PetId.eq(pet_id))
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("share_not_found"))?;
+
+    if share.revoked_at.is_none() {
+        let mut active = share.into_active_model();
+        active.revoked_at = Set(Some(chrono::Utc::now().naive_utc()));
+        active.update(&db).await?;
+        tracing::info!(pet_id, user_id, share_id = %share_id, "Pet share link revoked");
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(Serialize)]
+struct SharedAlert {
+    id: Uuid,
+    alert_type: String,
+    severity_level: String,
+    message: Option<String>,
+    occurrence_count: i32,
+    created_at: chrono::NaiveDateTime,
+    acknowledged: bool,
+}
+
+#[derive(Serialize)]
+struct SharedClip {
+    id: Uuid,
+    created_at: chrono::DateTime<chrono::FixedOffset>,
+    mood: Option<String>,
+    description: Option<String>,
+    activities: Option<serde_json::Value>,
+    /// Signed GCS URL, or null when the object can't be signed. The proxy
+    /// stream isn't offered since it needs a login the viewer doesn't have.
+    stream_url: Option<String>,
+}
+
+/// Why a passcode-protected link turned a viewer away.
+#[derive(Debug, PartialEq, Eq)]
+enum PasscodeRefusal {
+    /// Too many wrong passcodes; seconds until the link takes them again
+    Locked(u64),
+    /// No passcode sent, or not the right one
+    Wrong,
+}
+
+/// Checks a supplied passcode. A locked link doesn't check passcodes at all,
+/// right or wrong, so guessing can't continue through the lockout.
+fn check_passcode(
+    locked_for: Option<u64>,
+    supplied: Option<&str>,
+    passcode_hash: &str,
+) -> Result<(), PasscodeRefusal> {
+    if let Some(retry_after_secs) = locked_for {
+        return Err(PasscodeRefusal::Locked(retry_after_secs));
+    }
+    if !supplied.is_some_and(|p| verify_secret(p, passcode_hash)) {
+        return Err(PasscodeRefusal::Wrong);
+    }
+    Ok(())
+}
+
+// GET /shared/pets/:token - Read-only pet view for whoever holds the link.
+// Unauthenticated by design: the token (and optional passcode) is the credential.
+pub async fn get_shared_pet(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(redis_client): Extension<redis::Client>,
+    Path(token): Path<String>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    // Every rejection before we know the share looks like a missing link
+    let (share_id, secret) =
+        split_token(&token).ok_or_else(|| ApiError::not_found("share_not_found"))?;
+    let share = pet_share::Entity::find_by_id(share_id)
+        .one(&db)
+        .await?
+        .filter(|s| verify_secret(secret, &s.secret_hash))
+        .ok_or_else(|| ApiError::not_found("share_not_found"))?;
+
+    if share.revoked_at.is_some() {
+        log_access(&db, share.id, ip, Some("revoked")).await;
+        return Err(ApiError::not_found("share_not_found"));
+    }
+    if !share.is_live() {
+        log_access(&db, share.id, ip, Some("expired")).await;
+        return Err(ApiError::new(StatusCode::GONE, "share_expired"));
+    }
+    if let Some(passcode_hash) = &share.passcode_hash {
+        let throttle = PasscodeThrottle::from_env();
+        let mut conn = match redis_client.get_multiplexed_async_connection().await {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                tracing::error!("Failed to connect to Redis for passcode throttling: {}", e);
+                None
+            }
+        };
+
+        let locked_for = match conn.as_mut() {
+            Some(conn) => throttle.check(conn, share.id).await.unwrap_or_else(|e| {
+                tracing::error!("Failed to check passcode throttle: {}", e);
+                None
+            }),
+            None => None,
+        };
+        let supplied = headers.get(PASSCODE_HEADER).and_then(|v| v.to_str().ok());
+        match check_passcode(locked_for, supplied, passcode_hash) {
+            Ok(()) => {}
+            Err(PasscodeRefusal::Locked(retry_after_secs)) => {
+                log_access(&db, share.id, ip, Some("locked")).await;
+                metrics::counter!("petpulse_share_passcode_lockouts_total").increment(1);
+                let mut response =
+                    ApiError::new(StatusCode::TOO_MANY_REQUESTS, "share_locked").into_response();
+                response
+                    .headers_mut()
+                    .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+                return Ok(response);
+            }
+            Err(PasscodeRefusal::Wrong) => {
+                if let Some(conn) = conn.as_mut() {
+                    if let Err(e) = throttle.record_failure(conn, share.id).await {
+                        tracing::error!("Failed to record passcode failure: {}", e);
+                    }
+                }
+                log_access(&db, share.id, ip, Some("passcode")).await;
+                return Err(ApiError::new(
+                    StatusCode::UNAUTHORIZED,
+                    "share_passcode_required",
+                ));
+            }
+        }
+    }
+
+    log_access(&db, share.id, ip, None).await;
+
+    let pet = pet::Entity::find_by_id(share.pet_id)
+        .one(&db)
+        .await?
+        .filter(|p| !p.is_archived())
+        .ok_or_else(|| ApiError::not_found("share_not_found"))?;
+
+    let now = chrono::Utc::now();
+    let since = now - chrono::Duration::days(SHARE_WINDOW_DAYS);
+    let mut body = json!({
+        "pet": {
+            "name": pet.name,
+            "species": pet.species,
+            "breed": pet.breed,
+            "age": pet.age,
+        },
+        "window_start": since,
+        "window_end": now,
+        "expires_at": share.expires_at,
+    });
+
+    if share.includes("digests") {
+        let digests = daily_digest::Entity::find()
+            .filter(daily_digest::Column::PetId.eq(pet.id))
+            .filter(daily_digest::Column::Date.gte(since.date_naive()))
+            .order_by_desc(daily_digest::Column::Date)
+            .all(&db)
+            .await?;
+        body["digests"] = json!(digests);
+    }
+
+    if share.includes("unusual_clips") {
+        let videos = pet_video::Entity::find()
+            .filter(pet_video::Column::PetId.eq(pet.id))
+            .filter(pet_video::Column::Status.eq("PROCESSED"))
+            .filter(pet_video::Column::IsUnusual.eq(true))
+            .filter(pet_video::Column::CreatedAt.gte(since.fixed_offset()))
+            .order_by_desc(pet_video::Column::CreatedAt)
+            .limit(SHARED_CLIP_LIMIT)
+            .all(&db)
+            .await?;
+
+        let mut clips = Vec::with_capacity(videos.len());
+        for v in videos {
+            clips.push(SharedClip {
+                stream_url: super::video::signed_video_url(&gcs_client, &v).await,
+                id: v.id,
+                created_at: v.created_at,
+                mood: v.mood,
+                description: v.description,
+                activities: v.activities,
+            });
+        }
+        body["unusual_clips"] = json!(clips);
+    }
+
+    if share.includes("weight_history") {
+        let weights = pet_weight::Entity::find()
+            .filter(pet_weight::Column::PetId.eq(pet.id))
+            .filter(pet_weight::Column::RecordedAt.gte(since.naive_utc()))
+            .order_by_asc(pet_weight::Column::RecordedAt)
+            .all(&db)
+            .await?;
+        body["weight_kg"] = json!(pet.weight_kg);
+        body["weight_history"] = json!(weights);
+    }
+
+    if share.includes("alerts") {
+        let alerts: Vec<SharedAlert> = alerts::Entity::find()
+            .filter(alerts::Column::PetId.eq(pet.id))
+            .filter(alerts::Column::CreatedAt.gte(since.naive_utc()))
+            .order_by_desc(alerts::Column::CreatedAt)
+            .limit(SHARED_ALERT_LIMIT)
+            .all(&db)
+            .await?
+            .into_iter()
+            .map(|a| SharedAlert {
+                id: a.id,
+                alert_type: a.alert_type,
+                severity_level: a.severity_level,
+                message: a.message,
+                occurrence_count: a.occurrence_count,
+                created_at: a.created_at,
+                acknowledged: a.user_acknowledged_at.is_some(),
+            })
+            .collect();
+        body["alerts"] = json!(alerts);
+    }
+
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{database, error_code, owner_with_pet};
+
+    struct Link<'a> {
+        sections: &'a [&'a str],
+        passcode: Option<&'a str>,
+        expires_in: chrono::Duration,
+        revoked: bool,
+    }
+
+    impl Default for Link<'_> {
+        fn default() -> Self {
+            Self {
+                sections: SHARE_SECTIONS,
+                passcode: None,
+                expires_in: chrono::Duration::days(7),
+                revoked: false,
+            }
+        }
+    }
+
+    /// Stores the link for pet 1 and returns its token.
+    async fn share(db: &DatabaseConnection, link: Link<'_>) -> (Uuid, String) {
+        let secret = random_secret();
+        let now = chrono::Utc::now().naive_utc();
+        let share = pet_share::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            pet_id: Set(1),
+            created_by: Set(1),
+            secret_hash: Set(hash_secret(&secret).unwrap()),
+            passcode_hash: Set(link.passcode.map(|p| hash_secret(p).unwrap())),
+            sections: Set(json!(link.sections)),
+            expires_at: Set(now + link.expires_in),
+            revoked_at: Set(link.revoked.then_some(now)),
+            created_at: Set(now),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        (share.id, format!("{}.{}", share.id, secret))
+    }
+
+    async fn view(db: &DatabaseConnection, token: &str, passcode: Option<&str>) -> Response {
+        let mut headers = HeaderMap::new();
+        if let Some(passcode) = passcode {
+            headers.insert(PASSCODE_HEADER, passcode.parse().unwrap());
+        }
+        let gcs = GcsClient::new(google_cloud_storage::client::ClientConfig::default().anonymous());
+        // Nothing listens here, so the passcode throttle is skipped
+        let redis = redis::Client::open("redis://127.0.0.1:1/").unwrap();
+        get_shared_pet(
+            Extension(db.clone()),
+            Extension(gcs),
+            Extension(redis),
+            Path(token.to_string()),
+            ClientIp(None),
+            headers,
+        )
+        .await
+        .unwrap_or_else(IntoResponse::into_response)
+    }
+
+    async fn refusals(db: &DatabaseConnection, share_id: Uuid) -> Vec<Option<String>> {
+        pet_share_access::Entity::find()
+            .filter(pet_share_access::Column::ShareId.eq(share_id))
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.reason)
+            .collect()
+    }
+
+    async fn body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn not_found() -> (StatusCode, String) {
+        (StatusCode::NOT_FOUND, String::from("share_not_found"))
+    }
+
+    #[tokio::test]
+    async fn unknown_tokens_look_like_a_missing_link() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (share_id, _) = share(&db, Link::default()).await;
+
+        for token in [
+            String::from("not-a-token"),
+            format!("{}.{}", Uuid::new_v4(), random_secret()),
+            // The right row with someone else's secret
+            format!("{}.{}", share_id, random_secret()),
+        ] {
+            assert_eq!(error_code(view(&db, &token, None).await).await, not_found());
+        }
+    }
+
+    #[tokio::test]
+    async fn expired_and_revoked_links_are_refused_and_logged() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+
+        let (expired_id, expired) = share(
+            &db,
+            Link {
+                expires_in: chrono::Duration::minutes(-1),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            error_code(view(&db, &expired, None).await).await,
+            (StatusCode::GONE, String::from("share_expired"))
+        );
+        assert_eq!(
+            refusals(&db, expired_id).await,
+            [Some(String::from("expired"))]
+        );
+
+        let (revoked_id, revoked) = share(
+            &db,
+            Link {
+                revoked: true,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            error_code(view(&db, &revoked, None).await).await,
+            not_found()
+        );
+        assert_eq!(
+            refusals(&db, revoked_id).await,
+            [Some(String::from("revoked"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn only_shared_sections_are_returned() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+
+        let (share_id, alerts_only) = share(
+            &db,
+            Link {
+                sections: &["alerts"],
+                ..Default::default()
+            },
+        )
+        .await;
+        let response = view(&db, &alerts_only, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body(response).await;
+        assert_eq!(json["pet"]["name"], "Rex");
+        assert!(json["alerts"].is_array());
+        for hidden in ["digests", "unusual_clips", "weight_history", "weight_kg"] {
+            assert!(json.get(hidden).is_none(), "{hidden} leaked");
+        }
+        assert_eq!(refusals(&db, share_id).await, [None]);
+    }
+
+    #[tokio::test]
+    async fn unsignable_clips_have_no_stream_url() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let mut clip: pet_video::Model = serde_json::from_value(json!({
+            "pet_id": 1,
+            "file_path": "gs://bucket/uploads/1/clip.mp4",
+            "status": "PROCESSED",
+            "retry_count": 0,
+            "created_at": chrono::Utc::now(),
+            "updated_at": chrono::Utc::now(),
+            "is_unusual": true,
+            "suppressed_by_known_behavior": false,
+            "priority": "normal",
+        }))
+        .unwrap();
+        clip.id = Uuid::new_v4();
+        clip.into_active_model()
+            .reset_all()
+            .insert(&db)
+            .await
+            .unwrap();
+
+        let (_, token) = share(
+            &db,
+            Link {
+                sections: &["unusual_clips"],
+                ..Default::default()
+            },
+        )
+        .await;
+        let json = body(view(&db, &token, None).await).await;
+        let clips = json["unusual_clips"].as_array().unwrap();
+        assert_eq!(clips.len(), 1);
+        // Never the proxy stream, which the viewer isn't logged in for
+        assert_eq!(clips[0]["stream_url"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn passcode_links_need_the_right_passcode() {
+        let db = database().await;
+        owner_with_pet(&db, 1, 1).await;
+        let (share_id, token) = share(
+            &db,
+            Link {
+                passcode: Some("4821"),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let required = (
+            StatusCode::UNAUTHORIZED,
+            String::from("share_passcode_required"),
+        );
+        assert_eq!(error_code(view(&db, &token, None).await).await, required);
+        assert_eq!(
+            error_code(view(&db, &token, Some("0000")).await).await,
+            required
+        );
+        assert_eq!(
+            view(&db, &token, Some("4821")).await.status(),
+            StatusCode::OK
+        );
+        let passcode = Some(String::from("passcode"));
+        assert_eq!(
+            refusals(&db, share_id).await,
+            [passcode.clone(), passcode, None]
+        );
+    }
+
+    #[test]
+    fn locked_links_refuse_even_the_right_passcode() {
+        let hash = hash_secret("4821").unwrap();
+        assert_eq!(check_passcode(None, Some("4821"), &hash), Ok(()));
+        assert_eq!(
+            check_passcode(Some(120), Some("4821"), &hash),
+            Err(PasscodeRefusal::Locked(120))
+        );
+        assert_eq!(
+            check_passcode(None, Some("0000"), &hash),
+            Err(PasscodeRefusal::Wrong)
+        );
+        assert_eq!(
+            check_passcode(None, None, &hash),
+            Err(PasscodeRefusal::Wrong)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Daily per-user upload allowance (video count and total bytes), tracked in
+//! a Redis hash keyed by user and UTC day so it resets at midnight UTC.
+
+use chrono::{Days, NaiveDate, NaiveDateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use serde::Serialize;
+
+const DEFAULT_DAILY_UPLOADS: u64 = 200;
+const DEFAULT_DAILY_MB: u64 = 5 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct UploadQuota {
+    max_uploads: u64,
+    max_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuotaStatus {
+    pub uploads_used: u64,
+    pub uploads_limit: u64,
+    pub uploads_remaining: u64,
+    pub bytes_used: u64,
+    pub bytes_limit: u64,
+    pub bytes_remaining: u64,
+    pub resets_at: NaiveDateTime,
+}
+
+impl QuotaStatus {
+    pub fn exhausted(&self) -> bool {
+        self.uploads_remaining == 0 || self.bytes_remaining == 0
+    }
+
+    /// Whether one more upload of `bytes` still fits.
+    pub fn allows(&self, bytes: u64) -> bool {
+        self.allows_many(1, bytes)
+    }
+
+    /// Whether `uploads` more uploads totalling `bytes` still fit.
+    pub fn allows_many(&self, uploads: u64, bytes: u64) -> bool {
+        uploads <= self.uploads_remaining && bytes <= self.bytes_remaining
+    }
+
+    pub fn retry_after_secs(&self) -> i64 {
+        (self.resets_at - Utc::now().naive_utc())
+            .num_seconds()
+            .max(1)
+    }
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(default)
+}
+
+fn quota_key(user_id: i32, day: NaiveDate) -> String {
+    format!("petpulse:upload_quota:{}:{}", user_id, day)
+}
+
+impl UploadQuota {
+    /// Reads UPLOAD_QUOTA_DAILY_COUNT and UPLOAD_QUOTA_DAILY_MB.
+    pub fn from_env() -> Self {
+        Self {
+            max_uploads: env_u64("UPLOAD_QUOTA_DAILY_COUNT", DEFAULT_DAILY_UPLOADS),
+            max_bytes: env_u64("UPLOAD_QUOTA_DAILY_MB", DEFAULT_DAILY_MB) * 1024 * 1024,
+        }
+    }
+
+    pub async fn status(
+        &self,
+        conn: &mut MultiplexedConnection,
+        user_id: i32,
+    ) -> redis::RedisResult<QuotaStatus> {
+        let today = Utc::now().date_naive();
+        let (uploads, bytes): (Option<u64>, Option<u64>) = redis::cmd("HMGET")
+            .arg(quota_key(user_id, today))
+            .arg("uploads")
+            .arg("bytes")
+            .query_async(conn)
+            .await?;
+        let (uploads, bytes) = (uploads.unwrap_or(0), bytes.unwrap_or(0));
+
+        Ok(QuotaStatus {
+            uploads_used: uploads,
+            uploads_limit: self.max_uploads,
+            uploads_remaining: self.max_uploads.saturating_sub(uploads),
+            bytes_used: bytes,
+            bytes_limit: self.max_bytes,
+            bytes_remaining: self.max_bytes.saturating_sub(bytes),
+            resets_at: (today + Days::new(1)).and_hms_opt(0, 0, 0).unwrap(),
+        })
+    }
+
+    /// Counts a stored upload against today's allowance.
+    pub async fn record(
+        &self,
+        conn: &mut MultiplexedConnection,
+        user_id: i32,
+        bytes: u64,
+    ) -> redis::RedisResult<()> {
+        let key = quota_key(user_id, Utc::now().date_naive());
+        redis::pipe()
+            .hincr(&key, "uploads", 1)
+            .ignore()
+            .hincr(&key, "bytes", bytes)
+            .ignore()
+            // Outlive the day so a late read near midnight still sees it
+            .expire(&key, 2 * 24 * 60 * 60)
+            .ignore()
+            .query_async(conn)
+            .await
+    }
+}
@@ -0,0 +1,460 @@
+//! Resumable uploads. A client opens a session, PUTs the file in numbered
+//! chunks (each stored as its own GCS object, so a dropped connection only
+//! costs the chunk in flight) and completes the session, which composes the
+//! chunks into the final video object.
+//!
+//! Session state lives in Redis and expires with the session; chunk objects
+//! left behind by abandoned sessions are removed by the storage reconcile run.
+
+use super::daily_digest::{
+    admit_upload, max_video_bytes, register_uploaded_video, upload_too_large, RecordingMetadata,
+};
+use super::error::{ApiError, FieldError};
+use super::extract::OwnedPet;
+use crate::storage_cleanup::{self, UPLOAD_SESSIONS_PREFIX};
+use crate::video_format;
+use axum::{
+    body::Bytes,
+    extract::{Extension, Json, Path},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::compose::{ComposeObjectRequest, ComposingTargets};
+use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
+use google_cloud_storage::http::objects::{Object, SourceObjects};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_SESSION_TTL_SECS: u64 = 6 * 60 * 60;
+/// Sessions never outlive this, which keeps every live session's chunks
+/// younger than the reconcile grace period.
+const MAX_SESSION_TTL_SECS: u64 = 24 * 60 * 60;
+
+const MAX_CHUNK_BYTES: usize = 16 * 1024 * 1024;
+const MAX_CHUNKS: u32 = 1024;
+
+/// GCS composes at most this many sources per request.
+const COMPOSE_BATCH: usize = 32;
+const COMPLETE_LOCK_SECS: u64 = 300;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UploadSession {
+    user_id: i32,
+    pet_id: i32,
+    file_name: String,
+    bucket: String,
+    expires_at: DateTime<Utc>,
+    #[serde(default)]
+    recording: RecordingMetadata,
+}
+
+fn session_ttl_secs() -> u64 {
+    std::env::var("UPLOAD_SESSION_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_SESSION_TTL_SECS)
+        .min(MAX_SESSION_TTL_SECS)
+}
+
+fn session_key(session_id: Uuid) -> String {
+    format!("petpulse:upload_session:{}", session_id)
+}
+
+fn chunks_key(session_id: Uuid) -> String {
+    format!("petpulse:upload_session:{}:chunks", session_id)
+}
+
+fn complete_lock_key(session_id: Uuid) -> String {
+    format!("petpulse:upload_session:{}:completing", session_id)
+}
+
+fn chunk_object(session_id: Uuid, n: u32) -> String {
+    format!("{}{}/{:05}", UPLOAD_SESSIONS_PREFIX, session_id, n)
+}
+
+async fn redis_conn(redis_client: &redis::Client) -> Result<MultiplexedConnection, ApiError> {
+    redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// Loads a live session belonging to `user_id`. Other users' sessions are
+/// reported as missing.
+async fn load_session(
+    conn: &mut MultiplexedConnection,
+    session_id: Uuid,
+    user_id: i32,
+) -> Result<UploadSession, ApiError> {
+    let raw: Option<String> = conn
+        .get(session_key(session_id))
+        .await
+        .map_err(ApiError::internal)?;
+    raw.and_then(|raw| serde_json::from_str::<UploadSession>(&raw).ok())
+        .filter(|s| s.user_id == user_id && s.expires_at > Utc::now())
+        .ok_or_else(|| ApiError::not_found("upload_session_not_found"))
+}
+
+/// Chunk sizes received so far, by chunk number.
+async fn received_chunks(
+    conn: &mut MultiplexedConnection,
+    session_id: Uuid,
+) -> Result<HashMap<u32, u64>, ApiError> {
+    conn.hgetall(chunks_key(session_id))
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// Drops the session's Redis state and deletes its chunk objects in the background.
+async fn discard_session(
+    conn: &mut MultiplexedConnection,
+    gcs_client: GcsClient,
+    session_id: Uuid,
+    session: &UploadSession,
+    chunks: impl Iterator<Item = u32>,
+) {
+    let keys = [session_key(session_id), chunks_key(session_id)];
+    if let Err(e) = conn.del::<_, ()>(&keys).await {
+        tracing::warn!("Failed to clear upload session {}: {}", session_id, e);
+    }
+    let paths = chunks
+        .map(|n| format!("gs://{}/{}", session.bucket, chunk_object(session_id, n)))
+        .collect();
+    tokio::spawn(storage_cleanup::delete_objects(gcs_client, paths));
+}
+
+#[derive(Deserialize)]
+pub struct CreateUploadSessionRequest {
+    file_name: Option<String>,
+    #[serde(flatten)]
+    recording: RecordingMetadata,
+}
+
+// POST /pets/:id/uploads - Open a resumable upload session
+pub async fn create_upload_session(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+    OwnedPet(pet): OwnedPet,
+    Json(payload): Json<CreateUploadSessionRequest>,
+) -> Result<Response, ApiError> {
+    let bucket = std::env::var("GCS_BUCKET_NAME").map_err(ApiError::internal)?;
+    let mut conn = redis_conn(&redis_client).await?;
+
+    // Refuse now rather than after the client has sent every chunk
+    if let Err(rejection) = admit_upload(&mut conn, user_id, pet.id).await {
+        return Ok(rejection);
+    }
+
+    let recording = payload
+        .recording
+        .validated()
+        .map_err(|e| ApiError::validation(vec![e]))?;
+    let session_id = Uuid::new_v4();
+    let ttl = session_ttl_secs();
+    let session = UploadSession {
+        user_id,
+        pet_id: pet.id,
+        file_name: payload
+            .file_name
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "video.mp4".to_string()),
+        bucket,
+        expires_at: Utc::now() + chrono::Duration::seconds(ttl as i64),
+        recording,
+    };
+    let raw = serde_json::to_string(&session).map_err(ApiError::internal)?;
+    let _: () = conn
+        .set_ex(session_key(session_id), raw, ttl)
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!(%session_id, pet_id = pet.id, "Upload session opened");
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "session_id": session_id,
+            "expires_at": session.expires_at,
+            "max_chunk_bytes": MAX_CHUNK_BYTES,
+            "max_chunks": MAX_CHUNKS,
+        })),
+    )
+        .into_response())
+}
+
+// PUT /uploads/:session_id/chunks/:n - Store chunk `n` (0-based); re-sending a chunk replaces it
+pub async fn put_upload_chunk(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path((session_id, n)): Path<(Uuid, u32)>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_conn(&redis_client).await?;
+    let session = load_session(&mut conn, session_id, user_id).await?;
+
+    if n >= MAX_CHUNKS {
+        return Err(ApiError::validation(vec![FieldError::new(
+            "chunk",
+            "field.out_of_range",
+        )]));
+    }
+    if body.is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "upload_chunk_empty"));
+    }
+    if body.len() > MAX_CHUNK_BYTES {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "upload_chunk_too_large",
+        ));
+    }
+
+    // Catch a file that isn't video before the client sends the rest of it
+    if n == 0 && video_format::sniffing_enabled() && video_format::sniff(&body).is_none() {
+        return Err(ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_video_format",
+        ));
+    }
+
+    let chunks = received_chunks(&mut conn, session_id).await?;
+    let other_bytes: u64 = chunks
+        .iter()
+        .filter(|(chunk, _)| **chunk != n)
+        .map(|(_, size)| size)
+        .sum();
+    let size = body.len() as u64;
+    if other_bytes + size > max_video_bytes() {
+        return Ok(upload_too_large());
+    }
+
+    let object_name = chunk_object(session_id, n);
+    let upload_type = UploadType::Simple(Media {
+        name: object_name.into(),
+        content_type: "application/octet-stream".into(),
+        content_length: Some(size),
+    });
+    gcs_client
+        .upload_object(
+            &UploadObjectRequest {
+                bucket: session.bucket.clone(),
+                ..Default::default()
+            },
+            body,
+            &upload_type,
+        )
+        .await
+        .map_err(ApiError::internal)?;
+
+    let _: () = redis::pipe()
+        .hset(chunks_key(session_id), n, size)
+        .ignore()
+        .expire_at(chunks_key(session_id), session.expires_at.timestamp())
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(ApiError::internal)?;
+
+    metrics::counter!("petpulse_upload_chunks_total").increment(1);
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "session_id": session_id,
+            "chunk": n,
+            "received_chunks": chunks.len() + usize::from(!chunks.contains_key(&n)),
+            "received_bytes": other_bytes + size,
+        })),
+    )
+        .into_response())
+}
+
+/// Concatenates `sources` into `destination`, folding batches into the
+/// destination so any number of chunks fits the per-request source limit.
+async fn compose_chunks(
+    gcs_client: &GcsClient,
+    bucket: &str,
+    destination: &str,
+    content_type: String,
+    sources: Vec<String>,
+) -> Result<(), google_cloud_storage::http::Error> {
+    let mut composed = false;
+    let mut remaining = sources.into_iter().peekable();
+    while remaining.peek().is_some() {
+        let mut batch: Vec<SourceObjects> = Vec::with_capacity(COMPOSE_BATCH);
+        if composed {
+            batch.push(SourceObjects {
+                name: destination.to_string(),
+                ..Default::default()
+            });
+        }
+        while batch.len() < COMPOSE_BATCH {
+            let Some(name) = remaining.next() else {
+                break;
+            };
+            batch.push(SourceObjects {
+                name,
+                ..Default::default()
+            });
+        }
+
+        gcs_client
+            .compose_object(&ComposeObjectRequest {
+                bucket: bucket.to_string(),
+                destination_object: destination.to_string(),
+                composing_targets: ComposingTargets {
+                    destination: Some(Object {
+                        content_type: Some(content_type.clone()),
+                        ..Default::default()
+                    }),
+                    source_objects: batch,
+                },
+                ..Default::default()
+            })
+            .await?;
+        composed = true;
+    }
+    Ok(())
+}
+
+// POST /uploads/:session_id/complete - Assemble the chunks and queue the video
+pub async fn complete_upload_session(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_conn(&redis_client).await?;
+    let session = load_session(&mut conn, session_id, user_id).await?;
+
+    let chunks = received_chunks(&mut conn, session_id).await?;
+    let count = chunks.len() as u32;
+    if count == 0 || (0..count).any(|n| !chunks.contains_key(&n)) {
+        let missing: Vec<u32> = (0..chunks.keys().max().map_or(0, |m| m + 1))
+            .filter(|n| !chunks.contains_key(n))
+            .collect();
+        // Same shape as ApiError, plus which chunks to re-send
+        let message = super::i18n::translate("upload_incomplete");
+        return Ok((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "code": "upload_incomplete",
+                "message": message,
+                "error": message,
+                "missing_chunks": missing,
+            })),
+        )
+            .into_response());
+    }
+    let size_bytes: u64 = chunks.values().sum();
+
+    let admission = match admit_upload(&mut conn, user_id, session.pet_id).await {
+        Ok(admission) => admission,
+        Err(rejection) => return Ok(rejection),
+    };
+    if let Some(rejection) = admission.check_size(user_id, size_bytes) {
+        return Ok(rejection);
+    }
+
+    let head =
+        video_format::object_head(&gcs_client, &session.bucket, &chunk_object(session_id, 0))
+            .await
+            .map_err(ApiError::internal)?;
+    let Some(content_type) = video_format::detect(&head, &session.file_name) else {
+        return Err(ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_video_format",
+        ));
+    };
+
+    // Only one completion per session, even if the client retries mid-compose
+    let locked: Option<String> = redis::cmd("SET")
+        .arg(complete_lock_key(session_id))
+        .arg(user_id)
+        .arg("NX")
+        .arg("EX")
+        .arg(COMPLETE_LOCK_SECS)
+        .query_async(&mut conn)
+        .await
+        .map_err(ApiError::internal)?;
+    if locked.is_none() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "upload_completing"));
+    }
+
+    let video_id = Uuid::new_v4();
+    let ext = std::path::Path::new(&session.file_name)
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("mp4");
+    let object_name = format!("uploads/{}/{}.{}", session.pet_id, video_id, ext);
+    let sources = (0..count).map(|n| chunk_object(session_id, n)).collect();
+
+    let result = match compose_chunks(
+        &gcs_client,
+        &session.bucket,
+        &object_name,
+        content_type.clone(),
+        sources,
+    )
+    .await
+    {
+        Ok(()) => {
+            let gcs_path = format!("gs://{}/{}", session.bucket, object_name);
+            register_uploaded_video(
+                &db,
+                &mut conn,
+                session.pet_id,
+                user_id,
+                video_id,
+                gcs_path,
+                size_bytes as i64,
+                content_type,
+                session.recording.clone(),
+            )
+            .await
+            .map_err(ApiError::internal)
+        }
+        Err(e) => Err(ApiError::internal(e)),
+    };
+    if let Err(e) = result {
+        // Leave the session in place so the client can retry completing it
+        let _: Result<(), _> = conn.del(complete_lock_key(session_id)).await;
+        return Err(e);
+    }
+
+    discard_session(&mut conn, gcs_client, session_id, &session, 0..count).await;
+    let _: Result<(), _> = conn.del(complete_lock_key(session_id)).await;
+
+    tracing::info!(%session_id, %video_id, chunks = count, "Upload session completed");
+    Ok(admission.queued_response(video_id))
+}
+
+// DELETE /uploads/:session_id - Abandon a session and drop its chunks
+pub async fn abort_upload_session(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_conn(&redis_client).await?;
+    let session = load_session(&mut conn, session_id, user_id).await?;
+    let chunks = received_chunks(&mut conn, session_id).await?;
+
+    discard_session(
+        &mut conn,
+        gcs_client,
+        session_id,
+        &session,
+        chunks.into_keys(),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
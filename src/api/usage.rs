@@ -0,0 +1,311 @@
+use super::error::{ApiError, FieldError};
+use crate::entities::{alerts, notification_log, pet, pet_video};
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Deserialize)]
+pub struct MonthParams {
+    /// YYYY-MM; defaults to the current month
+    pub month: Option<String>,
+}
+
+/// Half-open [start, end) range covering one calendar month.
+#[derive(Clone, Copy)]
+pub struct UsageMonth {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl UsageMonth {
+    pub fn parse(month: Option<&str>) -> Result<Self, ApiError> {
+        let start = match month {
+            None => {
+                let today = Utc::now().date_naive();
+                NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap()
+            }
+            Some(m) if m.len() == 7 => NaiveDate::parse_from_str(&format!("{}-01", m), "%Y-%m-%d")
+                .map_err(|_| {
+                    ApiError::validation(vec![FieldError::new("month", "field.invalid_format")])
+                })?,
+            Some(_) => {
+                return Err(ApiError::validation(vec![FieldError::new(
+                    "month",
+                    "field.invalid_format",
+                )]))
+            }
+        };
+        let end = start
+            .checked_add_months(chrono::Months::new(1))
+            .ok_or_else(|| {
+                ApiError::validation(vec![FieldError::new("month", "field.out_of_range")])
+            })?;
+        Ok(Self { start, end })
+    }
+
+    pub fn label(&self) -> String {
+        self.start.format("%Y-%m").to_string()
+    }
+
+    fn start_utc(&self) -> chrono::DateTime<chrono::FixedOffset> {
+        self.start
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .fixed_offset()
+    }
+
+    fn end_utc(&self) -> chrono::DateTime<chrono::FixedOffset> {
+        self.end
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .fixed_offset()
+    }
+}
+
+fn rate(name: &str, default: f64) -> f64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Raw usage counters for one pet (or a rollup of several).
+#[derive(Default, Clone)]
+pub struct UsageTotals {
+    videos_uploaded: i64,
+    footage_seconds: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+    storage_bytes: i64,
+    notifications: BTreeMap<String, i64>,
+}
+
+impl UsageTotals {
+    fn add(&mut self, other: &UsageTotals) {
+        self.videos_uploaded += other.videos_uploaded;
+        self.footage_seconds += other.footage_seconds;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.storage_bytes += other.storage_bytes;
+        for (channel, count) in &other.notifications {
+            *self.notifications.entry(channel.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Estimated spend from Gemini tokens, storage held at month end, and SMS sends.
+    /// Rates are per million tokens, per GB-month and per message.
+    pub fn estimated_cost_usd(&self) -> f64 {
+        let tokens = self.input_tokens as f64 / 1e6 * rate("GEMINI_INPUT_COST_PER_MTOK", 1.25)
+            + self.output_tokens as f64 / 1e6 * rate("GEMINI_OUTPUT_COST_PER_MTOK", 5.0);
+        let storage = self.storage_bytes as f64 / 1e9 * rate("STORAGE_COST_PER_GB_MONTH", 0.02);
+        let sms =
+            *self.notifications.get("sms").unwrap_or(&0) as f64 * rate("SMS_COST_USD", 0.0079);
+        ((tokens + storage + sms) * 10_000.0).round() / 10_000.0
+    }
+
+    pub fn report(&self, month: &UsageMonth) -> UsageReport {
+        UsageReport {
+            month: month.label(),
+            videos_uploaded: self.videos_uploaded,
+            footage_minutes: (self.footage_seconds as f64 / 60.0 * 10.0).round() / 10.0,
+            gemini_input_tokens: self.input_tokens,
+            gemini_output_tokens: self.output_tokens,
+            estimated_cost_usd: self.estimated_cost_usd(),
+            storage_bytes: self.storage_bytes,
+            notifications: self.notifications.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct UsageReport {
+    pub month: String,
+    pub videos_uploaded: i64,
+    pub footage_minutes: f64,
+    pub gemini_input_tokens: i64,
+    pub gemini_output_tokens: i64,
+    pub estimated_cost_usd: f64,
+    pub storage_bytes: i64,
+    /// Sent notifications by channel
+    pub notifications: BTreeMap<String, i64>,
+}
+
+/// Computes per-pet usage for the month with one grouped query per source.
+/// `pet_ids = None` covers every pet (admin overview).
+pub async fn usage_by_pet(
+    db: &DatabaseConnection,
+    pet_ids: Option<&[i32]>,
+    month: &UsageMonth,
+) -> Result<HashMap<i32, UsageTotals>, DbErr> {
+    let mut videos = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::PetId)
+        .column_as(pet_video::Column::Id.count(), "videos")
+        .column_as(
+            Expr::cust("COALESCE(SUM(duration_seconds), 0)::bigint"),
+            "footage_seconds",
+        )
+        .column_as(
+            Expr::cust("COALESCE(SUM(input_tokens), 0)::bigint"),
+            "input_tokens",
+        )
+        .column_as(
+            Expr::cust("COALESCE(SUM(output_tokens), 0)::bigint"),
+            "output_tokens",
+        )
+        .filter(pet_video::Column::CreatedAt.gte(month.start_utc()))
+        .filter(pet_video::Column::CreatedAt.lt(month.end_utc()))
+        .group_by(pet_video::Column::PetId);
+
+    // Storage held at month end: everything uploaded before then that still exists
+    let mut storage = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::PetId)
+        .column_as(
+            Expr::cust("COALESCE(SUM(size_bytes), 0)::bigint"),
+            "storage_bytes",
+        )
+        .filter(pet_video::Column::CreatedAt.lt(month.end_utc()))
+        .group_by(pet_video::Column::PetId);
+
+    let mut notifications = notification_log::Entity::find()
+        .select_only()
+        .column(alerts::Column::PetId)
+        .column(notification_log::Column::Channel)
+        .column_as(notification_log::Column::Id.count(), "count")
+        .join(JoinType::InnerJoin, notification_log::Relation::Alert.def())
+        .filter(notification_log::Column::Status.eq("sent"))
+        .filter(notification_log::Column::CreatedAt.gte(month.start.and_hms_opt(0, 0, 0).unwrap()))
+        .filter(notification_log::Column::CreatedAt.lt(month.end.and_hms_opt(0, 0, 0).unwrap()))
+        .group_by(alerts::Column::PetId)
+        .group_by(notification_log::Column::Channel);
+
+    if let Some(ids) = pet_ids {
+        videos = videos.filter(pet_video::Column::PetId.is_in(ids.to_vec()));
+        storage = storage.filter(pet_video::Column::PetId.is_in(ids.to_vec()));
+        notifications = notifications.filter(alerts::Column::PetId.is_in(ids.to_vec()));
+    }
+
+    let (videos, storage, notifications) = tokio::join!(
+        videos.into_tuple::<(i32, i64, i64, i64, i64)>().all(db),
+        storage.into_tuple::<(i32, i64)>().all(db),
+        notifications.into_tuple::<(i32, String, i64)>().all(db),
+    );
+
+    let mut totals: HashMap<i32, UsageTotals> = HashMap::new();
+    for (pet_id, count, footage, input, output) in videos? {
+        let t = totals.entry(pet_id).or_default();
+        t.videos_uploaded = count;
+        t.footage_seconds = footage;
+        t.input_tokens = input;
+        t.output_tokens = output;
+    }
+    for (pet_id, bytes) in storage? {
+        totals.entry(pet_id).or_default().storage_bytes = bytes;
+    }
+    for (pet_id, channel, count) in notifications? {
+        totals
+            .entry(pet_id)
+            .or_default()
+            .notifications
+            .insert(channel, count);
+    }
+
+    Ok(totals)
+}
+
+pub fn rollup<'a>(totals: impl IntoIterator<Item = &'a UsageTotals>) -> UsageTotals {
+    let mut sum = UsageTotals::default();
+    for t in totals {
+        sum.add(t);
+    }
+    sum
+}
+
+// GET /pets/:id/usage?month=YYYY-MM - Monthly usage and estimated cost for one pet
+pub async fn get_pet_usage(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(pet_id): Path<i32>,
+    Query(params): Query<MonthParams>,
+) -> Result<Response, ApiError> {
+    let month = UsageMonth::parse(params.month.as_deref())?;
+
+    super::pet::owned_pet(&db, pet_id, user_id).await?;
+
+    let totals = usage_by_pet(&db, Some(&[pet_id]), &month).await?;
+    let report = totals
+        .get(&pet_id)
+        .cloned()
+        .unwrap_or_default()
+        .report(&month);
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "pet_id": pet_id, "usage": report })),
+    )
+        .into_response())
+}
+
+#[derive(Serialize)]
+struct PetUsage {
+    pet_id: i32,
+    name: String,
+    usage: UsageReport,
+}
+
+// GET /users/usage/monthly?month=YYYY-MM - Usage rollup across the user's pets
+pub async fn get_user_monthly_usage(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Query(params): Query<MonthParams>,
+) -> Result<Response, ApiError> {
+    let month = UsageMonth::parse(params.month.as_deref())?;
+
+    // Archived pets still count; they incurred the cost
+    let pets = pet::Entity::find()
+        .filter(pet::Column::UserId.eq(user_id))
+        .all(&db)
+        .await?;
+    let pet_ids: Vec<i32> = pets.iter().map(|p| p.id).collect();
+
+    let totals = if pet_ids.is_empty() {
+        HashMap::new()
+    } else {
+        usage_by_pet(&db, Some(&pet_ids), &month).await?
+    };
+
+    let per_pet: Vec<PetUsage> = pets
+        .into_iter()
+        .map(|p| PetUsage {
+            usage: totals
+                .get(&p.id)
+                .cloned()
+                .unwrap_or_default()
+                .report(&month),
+            pet_id: p.id,
+            name: p.name,
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "total": rollup(totals.values()).report(&month),
+            "pets": per_pet,
+        })),
+    )
+        .into_response())
+}
@@ -1,16 +1,145 @@
-use crate::entities::user;
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::pagination::Pagination;
+use super::session;
+use super::upload_quota::UploadQuota;
+use crate::agent::comfort_loop::open_alert_condition;
+use crate::entities::{alerts, audit_log, daily_digest, pet, pet_video, user};
+use crate::storage_cleanup;
 use axum::{
     extract::{Extension, Json},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Set};
+use google_cloud_storage::client::Client as GcsClient;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Set,
+};
 use serde_json::json;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+#[derive(serde::Serialize)]
+pub struct UserSummary {
+    pub pet_count: u64,
+    pub processed_videos: u64,
+    pub unacknowledged_alerts: u64,
+    pub latest_digest_date: Option<chrono::NaiveDate>,
+}
+
+/// Alerts still waiting on the owner, by the same rule the dashboard and the
+/// comfort loop use, so the header badge never disagrees with them.
+fn unacknowledged_alerts_query(pet_ids: Vec<i32>) -> Select<alerts::Entity> {
+    alerts::Entity::find()
+        .filter(alerts::Column::PetId.is_in(pet_ids))
+        .filter(open_alert_condition())
+}
+
+// GET /users/summary - Header counts for the dashboard in one round trip
+pub async fn get_user_summary(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+) -> Result<Response, ApiError> {
+    let pet_ids: Vec<i32> = pet::Entity::find_active()
+        .select_only()
+        .column(pet::Column::Id)
+        .filter(pet::Column::UserId.eq(user_id))
+        .into_tuple()
+        .all(&db)
+        .await?;
+
+    let processed_videos = pet_video::Entity::find()
+        .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .count(&db);
+    let unacknowledged_alerts = unacknowledged_alerts_query(pet_ids.clone()).count(&db);
+    let latest_digest_date = daily_digest::Entity::find()
+        .select_only()
+        .column(daily_digest::Column::Date)
+        .filter(daily_digest::Column::PetId.is_in(pet_ids.clone()))
+        .order_by_desc(daily_digest::Column::Date)
+        .into_tuple::<chrono::NaiveDate>()
+        .one(&db);
+
+    let (processed_videos, unacknowledged_alerts, latest_digest_date) =
+        tokio::try_join!(processed_videos, unacknowledged_alerts, latest_digest_date)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(UserSummary {
+            pet_count: pet_ids.len() as u64,
+            processed_videos,
+            unacknowledged_alerts,
+            latest_digest_date,
+        }),
+    )
+        .into_response())
+}
+
+// GET /users/quota - Today's upload allowance and what's left of it
+pub async fn get_user_quota(
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+) -> Result<Response, ApiError> {
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+    let status = UploadQuota::from_env()
+        .status(&mut conn, user_id)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok((StatusCode::OK, Json(status)).into_response())
+}
+
+// GET /users/audit-log - Sign-ins and destructive actions on this account, newest first
+pub async fn list_audit_log(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    pagination: Pagination,
+) -> Result<Response, ApiError> {
+    let query = audit_log::Entity::find()
+        .filter(audit_log::Column::UserId.eq(user_id))
+        .order_by_desc(audit_log::Column::CreatedAt);
+
+    let total = query.clone().count(&db).await?;
+    let entries = query
+        .paginate(&db, pagination.page_size)
+        .fetch_page(pagination.index())
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "entries": entries,
+            "total": total,
+            "page": pagination.page,
+            "page_size": pagination.page_size,
+        })),
+    )
+        .into_response())
+}
 
 #[derive(serde::Deserialize)]
 pub struct UpdateUserRequest {
     name: Option<String>,
     email: Option<String>,
+    recovery_notifications_enabled: Option<bool>,
+    /// E.164 number, e.g. +14155550123. An empty string clears it.
+    phone: Option<String>,
+    /// IANA zone name, e.g. America/Los_Angeles
+    timezone: Option<String>,
+}
+
+/// E.164: a leading +, then up to 15 digits with no leading zero.
+fn is_e164(phone: &str) -> bool {
+    let Some(digits) = phone.strip_prefix('+') else {
+        return false;
+    };
+    (2..=15).contains(&digits.len())
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && !digits.starts_with('0')
 }
 
 pub async fn get_user(
@@ -20,7 +149,15 @@ pub async fn get_user(
     match user::Entity::find_by_id(user_id).one(&db).await {
         Ok(Some(u)) => (
             StatusCode::OK,
-            Json(json!({"id": u.id, "email": u.email, "name": u.name, "created_at": u.created_at})),
+            Json(json!({
+                "id": u.id,
+                "email": u.email,
+                "name": u.name,
+                "created_at": u.created_at,
+                "recovery_notifications_enabled": u.recovery_notifications_enabled,
+                "phone": u.phone,
+                "timezone": u.timezone,
+            })),
         )
             .into_response(),
         Ok(None) => (
@@ -41,6 +178,23 @@ pub async fn update_user(
     Extension(user_id): Extension<i32>,
     Json(payload): Json<UpdateUserRequest>,
 ) -> Response {
+    let mut errors = Vec::new();
+    let phone = payload.phone.map(|p| p.trim().to_string());
+    if let Some(phone) = phone.as_deref().filter(|p| !p.is_empty()) {
+        if !is_e164(phone) {
+            errors.push(FieldError::new("phone", "field.invalid_format"));
+        }
+    }
+    let timezone = payload.timezone.map(|tz| tz.trim().to_string());
+    if let Some(tz) = &timezone {
+        if crate::timezone::parse(tz).is_none() {
+            errors.push(FieldError::new("timezone", "field.invalid_format"));
+        }
+    }
+    if !errors.is_empty() {
+        return ApiError::validation(errors).into_response();
+    }
+
     let user = match user::Entity::find_by_id(user_id).one(&db).await {
         Ok(Some(u)) => u,
         Ok(None) => {
@@ -66,12 +220,28 @@ pub async fn update_user(
     if let Some(email) = payload.email {
         active_user.email = Set(email);
     }
+    if let Some(enabled) = payload.recovery_notifications_enabled {
+        active_user.recovery_notifications_enabled = Set(enabled);
+    }
+    if let Some(phone) = phone {
+        active_user.phone = Set((!phone.is_empty()).then_some(phone));
+    }
+    if let Some(timezone) = timezone {
+        active_user.timezone = Set(timezone);
+    }
     active_user.updated_at = Set(chrono::Utc::now().naive_utc());
 
     match active_user.update(&db).await {
         Ok(u) => (
             StatusCode::OK,
-            Json(json!({"id": u.id, "email": u.email, "name": u.name})),
+            Json(json!({
+                "id": u.id,
+                "email": u.email,
+                "name": u.name,
+                "recovery_notifications_enabled": u.recovery_notifications_enabled,
+                "phone": u.phone,
+                "timezone": u.timezone,
+            })),
         )
             .into_response(),
         Err(e) => (
@@ -82,17 +252,133 @@ pub async fn update_user(
     }
 }
 
+// DELETE /users - Delete the account. Rows cascade in the database; stored
+// videos, pet photos and queued jobs are cleaned up here.
 pub async fn delete_user(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
     Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
 ) -> Response {
+    // Collect what to clean up before the cascade removes the rows
+    let pets: Vec<(i32, Option<String>)> = match pet::Entity::find()
+        .select_only()
+        .column(pet::Column::Id)
+        .column(pet::Column::PhotoPath)
+        .filter(pet::Column::UserId.eq(user_id))
+        .into_tuple()
+        .all(&db)
+        .await
+    {
+        Ok(pets) => pets,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+    let (pet_ids, photo_paths): (Vec<i32>, Vec<Option<String>>) = pets.into_iter().unzip();
+    let videos = match storage_cleanup::videos_for_pets(&db, pet_ids.clone()).await {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+
     match user::Entity::delete_by_id(user_id).exec(&db).await {
-        Ok(res) if res.rows_affected == 0 => (
-            StatusCode::NOT_FOUND,
-            Json(json!({"error": "User not found"})),
-        )
-            .into_response(),
-        Ok(_) => (StatusCode::OK, Json(json!({"message": "User deleted"}))).into_response(),
+        Ok(res) if res.rows_affected == 0 => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "User not found"})),
+            )
+                .into_response()
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+
+    let video_ids: HashSet<Uuid> = videos.iter().map(|(id, _)| *id).collect();
+    let pet_ids: HashSet<i32> = pet_ids.into_iter().collect();
+    match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => {
+            match storage_cleanup::purge_queued_jobs(&mut conn, &video_ids, &pet_ids).await {
+                Ok((videos, digests)) => tracing::info!(
+                    user_id,
+                    "Dropped {} queued video jobs and {} digest jobs for deleted user",
+                    videos,
+                    digests
+                ),
+                Err(e) => tracing::warn!("Failed to purge queued jobs for user {}: {}", user_id, e),
+            }
+            if let Err(e) = session::revoke_user_sessions(&mut conn, user_id).await {
+                tracing::warn!("Failed to revoke sessions for user {}: {}", user_id, e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to connect to Redis for user cleanup: {}", e),
+    }
+
+    let scheduled = videos.len();
+    let mut file_paths: Vec<String> = videos.into_iter().map(|(_, path)| path).collect();
+    file_paths.extend(photo_paths.into_iter().flatten());
+    tokio::spawn(storage_cleanup::delete_objects(gcs_client, file_paths));
+
+    tracing::info!(user_id, videos = scheduled, "User deleted");
+    crate::audit::record(&db, user_id, "delete_user", None, ip);
+    (
+        StatusCode::OK,
+        Json(json!({
+            "message": "User deleted",
+            "videos_scheduled_for_deletion": scheduled,
+        })),
+    )
+        .into_response()
+}
+
+const SCHEDULED_NOTIFICATIONS_LIMIT: u64 = 50;
+
+// GET /users/notifications/scheduled - Notifications queued for later delivery
+pub async fn list_scheduled_notifications(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+) -> Response {
+    match crate::notifications::scheduled::upcoming_for_user(
+        &db,
+        user_id,
+        SCHEDULED_NOTIFICATIONS_LIMIT,
+    )
+    .await
+    {
+        Ok(rows) => {
+            let upcoming: Vec<serde_json::Value> = rows
+                .into_iter()
+                .map(|n| {
+                    json!({
+                        "id": n.id,
+                        "alert_id": n.alert_id,
+                        "channel": n.channel,
+                        "template": n.template,
+                        "subject": n.payload.get("subject"),
+                        "due_at": n.due_at,
+                        "status": n.status,
+                        "attempts": n.attempts,
+                    })
+                })
+                .collect();
+            (StatusCode::OK, Json(upcoming)).into_response()
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": e.to_string()})),
@@ -100,3 +386,21 @@ pub async fn delete_user(
             .into_response(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{DbBackend, QueryTrait};
+
+    #[test]
+    fn summary_counts_open_alerts_like_the_dashboard() {
+        let sql = unacknowledged_alerts_query(vec![1, 2])
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r#""alerts"."pet_id" IN (1, 2)"#), "{sql}");
+        assert!(sql.contains(r#""user_acknowledged_at" IS NULL"#), "{sql}");
+        // Alerts the comfort loop closed with a "Resolution: ..." outcome count as closed
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolved%'"#), "{sql}");
+        assert!(sql.contains(r#""outcome" NOT LIKE 'Resolution%'"#), "{sql}");
+    }
+}
@@ -1,33 +1,169 @@
-use crate::entities::{pet, pet_video};
+use super::client_ip::ClientIp;
+use super::error::{ApiError, FieldError};
+use super::extract::ReadablePet;
+use super::pagination::Pagination;
+use super::pet::{accessible_pets, check_pet_access, PetAccess};
+use crate::entities::{alerts, pet, pet_video, video_tag};
+use crate::storage_cleanup::parse_gs_path;
+use crate::video_events::{self, VideoEventKind};
+use crate::video_queue::Priority;
 use axum::{
     body::Body,
     extract::{Extension, Path, Query},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::NaiveTime;
+use futures::TryStreamExt;
 use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::download::Range;
 use google_cloud_storage::http::objects::get::GetObjectRequest;
+use google_cloud_storage::sign::{SignedURLMethod, SignedURLOptions};
+use redis::AsyncCommands;
+use sea_orm::sea_query::{Expr, Func, OnConflict};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize)]
-pub struct PaginationParams {
-    #[serde(default = "default_page")]
-    pub page: u64,
-    #[serde(default = "default_per_page")]
-    pub per_page: u64,
+pub struct VideoListParams {
+    /// Comma-separated statuses to include; defaults to PROCESSED
+    pub status: Option<String>,
+    /// First UTC day to include
+    pub from: Option<chrono::NaiveDate>,
+    /// Last UTC day to include
+    pub to: Option<chrono::NaiveDate>,
+    /// Case-insensitive match on the clip's summary mood
+    pub mood: Option<String>,
+    pub is_unusual: Option<bool>,
+    /// Only videos carrying this tag, e.g. `favorite`
+    pub tag: Option<String>,
+    /// Add `status_counts` to the response; costs an extra grouped query
+    #[serde(default)]
+    pub include_counts: bool,
+    /// Add each video's `alerts`; one extra query for the page
+    #[serde(default)]
+    pub include_alerts: bool,
+}
+
+/// Compact view of a video used when embedding it in other resources (e.g. alerts).
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoPreview {
+    pub id: uuid::Uuid,
+    pub thumbnail_url: Option<String>,
+    pub duration_seconds: Option<u32>,
+    pub mood: Option<String>,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub stream_url: String,
+}
+
+const DEFAULT_SIGNED_URL_TTL_SECS: u64 = 300;
+
+fn signed_url_ttl() -> std::time::Duration {
+    let secs = std::env::var("SIGNED_URL_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SIGNED_URL_TTL_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Parses "HH:MM:SS" / "MM:SS" / "SS" timestamps as emitted by Gemini.
+fn parse_timestamp_secs(ts: &str) -> Option<u32> {
+    ts.trim().split(':').try_fold(0u32, |acc, part| {
+        acc.checked_mul(60)?.checked_add(part.parse::<u32>().ok()?)
+    })
+}
+
+/// Approximates the clip duration from the latest activity end time.
+pub(crate) fn activities_duration_secs(activities: &Option<serde_json::Value>) -> Option<u32> {
+    activities
+        .as_ref()?
+        .as_array()?
+        .iter()
+        .filter_map(|a| a.get("endtime")?.as_str())
+        .filter_map(parse_timestamp_secs)
+        .max()
+}
+
+/// Signs a short-lived GCS URL for the video, or `None` when the object path
+/// is malformed or the client has no signing credentials.
+pub(crate) async fn signed_video_url(
+    gcs_client: &GcsClient,
+    video: &pet_video::Model,
+) -> Option<String> {
+    let (bucket, object) = parse_gs_path(&video.file_path)?;
+
+    let options = SignedURLOptions {
+        method: SignedURLMethod::GET,
+        expires: signed_url_ttl(),
+        ..Default::default()
+    };
+
+    match gcs_client
+        .signed_url(bucket, object, None, None, options)
+        .await
+    {
+        Ok(url) => Some(url),
+        Err(e) => {
+            tracing::warn!("Failed to sign URL for video {}: {}", video.id, e);
+            None
+        }
+    }
 }
 
-fn default_page() -> u64 {
-    1
+fn proxy_stream_url(video: &pet_video::Model) -> String {
+    format!("/videos/{}/stream", video.id)
 }
 
-fn default_per_page() -> u64 {
-    10
+/// Returns a short-lived signed GCS URL for the video, falling back to the
+/// authenticated streaming endpoint when the object cannot be signed.
+pub async fn video_stream_url(gcs_client: &GcsClient, video: &pet_video::Model) -> String {
+    signed_video_url(gcs_client, video)
+        .await
+        .unwrap_or_else(|| proxy_stream_url(video))
+}
+
+/// Loads previews for a batch of video ids in a single query. Ids that no
+/// longer exist are simply absent from the returned map.
+pub async fn load_video_previews(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    video_ids: Vec<uuid::Uuid>,
+) -> Result<std::collections::HashMap<uuid::Uuid, VideoPreview>, sea_orm::DbErr> {
+    let mut previews = std::collections::HashMap::new();
+    if video_ids.is_empty() {
+        return Ok(previews);
+    }
+
+    let videos = pet_video::Entity::find()
+        .filter(pet_video::Column::Id.is_in(video_ids))
+        .all(db)
+        .await?;
+
+    for video in videos {
+        let stream_url = video_stream_url(gcs_client, &video).await;
+        previews.insert(
+            video.id,
+            VideoPreview {
+                id: video.id,
+                thumbnail_url: video
+                    .thumbnail_path
+                    .as_ref()
+                    .map(|_| format!("/videos/{}/thumbnail", video.id)),
+                duration_seconds: activities_duration_secs(&video.activities),
+                mood: video.mood,
+                created_at: video.created_at,
+                stream_url,
+            },
+        );
+    }
+
+    Ok(previews)
 }
 
 #[derive(Debug, Serialize)]
@@ -35,6 +171,58 @@ pub struct VideoWithPet {
     #[serde(flatten)]
     pub video: pet_video::Model,
     pub pet: Option<pet::Model>,
+    pub tags: Vec<String>,
+    /// Alerts the video raised, when the listing asked for them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alerts: Option<Vec<VideoAlertSummary>>,
+}
+
+/// An alert as shown next to the video that raised it.
+#[derive(Debug, Serialize)]
+pub struct VideoAlertSummary {
+    pub id: uuid::Uuid,
+    pub alert_type: String,
+    pub severity_level: String,
+    pub acknowledged: bool,
+}
+
+/// Alerts raised by each of `video_ids`, newest first, in one query.
+async fn alerts_by_video(
+    db: &DatabaseConnection,
+    video_ids: Vec<uuid::Uuid>,
+) -> Result<std::collections::HashMap<uuid::Uuid, Vec<VideoAlertSummary>>, DbErr> {
+    if video_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+    let rows = alerts::Entity::find()
+        .filter(alerts::Column::VideoId.is_in(video_ids))
+        .order_by_desc(alerts::Column::CreatedAt)
+        .all(db)
+        .await?;
+    Ok(group_alerts_by_video(rows))
+}
+
+/// Groups alert rows under the video that raised them, keeping row order.
+fn group_alerts_by_video(
+    rows: Vec<alerts::Model>,
+) -> std::collections::HashMap<uuid::Uuid, Vec<VideoAlertSummary>> {
+    let mut by_video: std::collections::HashMap<uuid::Uuid, Vec<VideoAlertSummary>> =
+        std::collections::HashMap::new();
+    for alert in rows {
+        let Some(video_id) = alert.video_id else {
+            continue;
+        };
+        by_video
+            .entry(video_id)
+            .or_default()
+            .push(VideoAlertSummary {
+                id: alert.id,
+                alert_type: alert.alert_type,
+                severity_level: alert.severity_level,
+                acknowledged: alert.user_acknowledged_at.is_some(),
+            });
+    }
+    by_video
 }
 
 #[derive(Debug, Serialize)]
@@ -44,239 +232,561 @@ pub struct VideoListResponse {
     pub page: u64,
     pub per_page: u64,
     pub total_pages: u64,
+    /// Videos per status across the listed pets, ignoring the status filter.
+    /// Only with `?include_counts=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_counts: Option<BTreeMap<String, u64>>,
+    pub filters: AppliedVideoFilters,
 }
 
-pub async fn list_user_videos(
-    Extension(db): Extension<DatabaseConnection>,
-    Extension(user_id): Extension<i32>,
-    Query(params): Query<PaginationParams>,
-) -> Response {
-    let user_pets = match pet::Entity::find()
-        .filter(pet::Column::UserId.eq(user_id))
-        .all(&db)
-        .await
-    {
-        Ok(pets) => pets,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
+const DEFAULT_LIST_STATUS: &str = "PROCESSED";
+
+fn status_filter(raw: Option<&str>) -> Result<Vec<&'static str>, ApiError> {
+    let Some(raw) = raw.filter(|r| !r.trim().is_empty()) else {
+        return Ok(vec![DEFAULT_LIST_STATUS]);
     };
+    raw.split(',')
+        .map(|s| {
+            pet_video::parse_status(s).ok_or_else(|| {
+                ApiError::validation(vec![FieldError::new("status", "video_status.unknown")])
+            })
+        })
+        .collect()
+}
+
+/// Filters a listing was narrowed by, echoed back in the response.
+#[derive(Debug, Serialize)]
+pub struct AppliedVideoFilters {
+    pub status: Vec<&'static str>,
+    pub from: Option<chrono::NaiveDate>,
+    pub to: Option<chrono::NaiveDate>,
+    pub mood: Option<String>,
+    pub is_unusual: Option<bool>,
+    pub tag: Option<String>,
+}
+
+/// Filters from the query string, ANDed together on top of the pet scope.
+fn list_condition(params: &VideoListParams) -> Result<(Condition, AppliedVideoFilters), ApiError> {
+    let statuses = status_filter(params.status.as_deref())?;
+    let mut condition = Condition::all().add(pet_video::Column::Status.is_in(statuses.clone()));
+
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err(ApiError::validation(vec![FieldError::new(
+                "from",
+                "date_range.reversed",
+            )]));
+        }
+    }
+    // Whole days in UTC, both ends inclusive
+    if let Some(from) = params.from {
+        condition = condition
+            .add(pet_video::Column::CreatedAt.gte(from.and_time(NaiveTime::MIN).and_utc()));
+    }
+    if let Some(to) = params.to {
+        let end = (to + chrono::Days::new(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        condition = condition.add(pet_video::Column::CreatedAt.lt(end));
+    }
 
-    let pet_ids: Vec<i32> = user_pets.iter().map(|p| p.id).collect();
+    let mood = params
+        .mood
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .filter(|m| !m.is_empty());
+    if let Some(mood) = &mood {
+        condition = condition
+            .add(Expr::expr(Func::lower(Expr::col(pet_video::Column::Mood))).eq(mood.as_str()));
+    }
+    if let Some(is_unusual) = params.is_unusual {
+        condition = condition.add(pet_video::Column::IsUnusual.eq(is_unusual));
+    }
+    let tag = params
+        .tag
+        .as_deref()
+        .map(video_tag::normalize)
+        .filter(|t| !t.is_empty());
+    if let Some(tag) = &tag {
+        condition = condition.add(
+            pet_video::Column::Id.in_subquery(
+                sea_orm::sea_query::Query::select()
+                    .column(video_tag::Column::VideoId)
+                    .from(video_tag::Entity)
+                    .and_where(video_tag::Column::Tag.eq(tag.as_str()))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    Ok((
+        condition,
+        AppliedVideoFilters {
+            status: statuses,
+            from: params.from,
+            to: params.to,
+            mood,
+            is_unusual: params.is_unusual,
+            tag,
+        },
+    ))
+}
 
+/// Video count per status across `pet_ids`, with every known status present.
+async fn status_counts(
+    db: &DatabaseConnection,
+    pet_ids: &[i32],
+) -> Result<BTreeMap<String, u64>, DbErr> {
+    let mut counts: BTreeMap<String, u64> = pet_video::STATUSES
+        .iter()
+        .map(|s| (s.to_string(), 0))
+        .collect();
     if pet_ids.is_empty() {
-        return (
-            StatusCode::OK,
-            Json(VideoListResponse {
-                videos: vec![],
-                total: 0,
-                page: params.page,
-                per_page: params.per_page,
-                total_pages: 0,
-            }),
-        )
-            .into_response();
+        return Ok(counts);
     }
 
-    let paginator = pet_video::Entity::find()
-        .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
-        .filter(pet_video::Column::Status.eq("PROCESSED"))
-        .order_by_desc(pet_video::Column::CreatedAt)
-        .paginate(&db, params.per_page);
+    let rows: Vec<(String, i64)> = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::Status)
+        .column_as(pet_video::Column::Id.count(), "count")
+        .filter(pet_video::Column::PetId.is_in(pet_ids.to_vec()))
+        .group_by(pet_video::Column::Status)
+        .into_tuple()
+        .all(db)
+        .await?;
+    for (status, count) in rows {
+        *counts.entry(status).or_insert(0) += count as u64;
+    }
+    Ok(counts)
+}
 
-    let total = match paginator.num_pages().await {
-        Ok(pages) => pages,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
+/// One page of videos across `pets`, newest first.
+async fn video_page(
+    db: &DatabaseConnection,
+    pets: Vec<pet::Model>,
+    pagination: Pagination,
+    params: &VideoListParams,
+) -> Result<VideoListResponse, ApiError> {
+    let (condition, filters) = list_condition(params)?;
+    let pet_ids: Vec<i32> = pets.iter().map(|p| p.id).collect();
+    let status_counts = if params.include_counts {
+        Some(status_counts(db, &pet_ids).await?)
+    } else {
+        None
     };
 
-    let videos = match paginator.fetch_page(params.page - 1).await {
-        Ok(v) => v,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+    if pet_ids.is_empty() {
+        return Ok(VideoListResponse {
+            videos: vec![],
+            total: 0,
+            page: pagination.page,
+            per_page: pagination.page_size,
+            total_pages: 0,
+            status_counts,
+            filters,
+        });
+    }
+
+    let paginator = pet_video::Entity::find()
+        .filter(pet_video::Column::PetId.is_in(pet_ids))
+        .filter(condition)
+        .order_by_desc(pet_video::Column::CreatedAt)
+        .paginate(db, pagination.page_size);
+
+    let totals = paginator.num_items_and_pages().await?;
+    let videos = paginator.fetch_page(pagination.index()).await?;
 
     let pet_map: std::collections::HashMap<i32, pet::Model> =
-        user_pets.into_iter().map(|p| (p.id, p)).collect();
+        pets.into_iter().map(|p| (p.id, p)).collect();
+    let video_ids: Vec<uuid::Uuid> = videos.iter().map(|v| v.id).collect();
+    let mut tags = video_tag::tags_by_video(db, video_ids.clone()).await?;
+    let mut alerts = if params.include_alerts {
+        Some(alerts_by_video(db, video_ids).await?)
+    } else {
+        None
+    };
 
     let videos_with_pets: Vec<VideoWithPet> = videos
         .into_iter()
         .map(|video| VideoWithPet {
             pet: pet_map.get(&video.pet_id).cloned(),
+            tags: tags.remove(&video.id).unwrap_or_default(),
+            alerts: alerts
+                .as_mut()
+                .map(|a| a.remove(&video.id).unwrap_or_default()),
             video,
         })
         .collect();
 
-    let total_items = match pet_video::Entity::find()
-        .filter(pet_video::Column::PetId.is_in(pet_ids))
-        .filter(pet_video::Column::Status.eq("PROCESSED"))
-        .count(&db)
-        .await
-    {
-        Ok(count) => count,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+    Ok(VideoListResponse {
+        videos: videos_with_pets,
+        total: totals.number_of_items,
+        page: pagination.page,
+        per_page: pagination.page_size,
+        total_pages: totals.number_of_pages,
+        status_counts,
+        filters,
+    })
+}
 
-    (
-        StatusCode::OK,
-        Json(VideoListResponse {
-            videos: videos_with_pets,
-            total: total_items,
-            page: params.page,
-            per_page: params.per_page,
-            total_pages: total,
-        }),
-    )
-        .into_response()
+pub async fn list_user_videos(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    pagination: Pagination,
+    Query(params): Query<VideoListParams>,
+) -> Result<Response, ApiError> {
+    let pets = accessible_pets(&db, user_id).await?;
+    let page = video_page(&db, pets, pagination, &params).await?;
+    Ok((StatusCode::OK, Json(page)).into_response())
 }
 
 pub async fn list_pet_videos(
     Extension(db): Extension<DatabaseConnection>,
-    Path(pet_id): Path<i32>,
-    Query(params): Query<PaginationParams>,
-) -> Response {
+    ReadablePet(pet): ReadablePet,
+    pagination: Pagination,
+    Query(params): Query<VideoListParams>,
+) -> Result<Response, ApiError> {
+    let page = video_page(&db, vec![pet], pagination, &params).await?;
+    Ok((StatusCode::OK, Json(page)).into_response())
+}
+
+/// What `GET /videos/search` matches against; the GIN index on `pet_video`
+/// is built over exactly this expression.
+const SEARCH_DOCUMENT: &str =
+    "to_tsvector('english', coalesce(description, '') || ' ' || coalesce(mood, ''))";
+const SEARCH_MIN_CHARS: usize = 2;
+const SEARCH_MAX_CHARS: usize = 200;
+const SNIPPET_START: &str = "<mark>";
+const SNIPPET_STOP: &str = "</mark>";
+
+#[derive(Debug, Deserialize)]
+pub struct VideoSearchParams {
+    pub q: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoSearchHit {
+    #[serde(flatten)]
+    pub video: VideoWithPet,
+    /// Excerpt of the description with matched words wrapped in `<mark>`;
+    /// everything else is HTML-escaped
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoSearchResponse {
+    pub query: String,
+    pub results: Vec<VideoSearchHit>,
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+}
+
+fn search_query(raw: Option<&str>) -> Result<String, ApiError> {
+    let q = raw.map(str::trim).unwrap_or_default();
+    let len = q.chars().count();
+    let error = if len == 0 {
+        Some("field.required")
+    } else if len < SEARCH_MIN_CHARS {
+        Some("field.too_short")
+    } else if len > SEARCH_MAX_CHARS {
+        Some("field.too_long")
+    } else {
+        None
+    };
+    match error {
+        Some(code) => Err(ApiError::validation(vec![FieldError::new("q", code)])),
+        None => Ok(q.to_string()),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escapes a `ts_headline` result while keeping its highlight tags.
+fn escape_snippet(headline: &str) -> String {
+    headline
+        .split(SNIPPET_START)
+        .enumerate()
+        .map(|(i, part)| match part.split_once(SNIPPET_STOP) {
+            Some((marked, rest)) if i > 0 => format!(
+                "{}{}{}{}",
+                SNIPPET_START,
+                escape_html(marked),
+                SNIPPET_STOP,
+                escape_html(rest)
+            ),
+            _ => escape_html(part),
+        })
+        .collect()
+}
+
+/// Highlighted description excerpts for `video_ids`.
+async fn search_snippets(
+    db: &DatabaseConnection,
+    video_ids: Vec<uuid::Uuid>,
+    q: &str,
+) -> Result<std::collections::HashMap<uuid::Uuid, String>, DbErr> {
+    if video_ids.is_empty() {
+        return Ok(Default::default());
+    }
+    let options = format!(
+        "StartSel={}, StopSel={}, MaxWords=25, MinWords=10",
+        SNIPPET_START, SNIPPET_STOP
+    );
+    let rows: Vec<(uuid::Uuid, Option<String>)> = pet_video::Entity::find()
+        .select_only()
+        .column(pet_video::Column::Id)
+        .column_as(
+            Expr::cust_with_values(
+                "ts_headline('english', description, plainto_tsquery('english', $1), $2)",
+                [q.to_string(), options],
+            ),
+            "snippet",
+        )
+        .filter(pet_video::Column::Id.is_in(video_ids))
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, snippet)| Some((id, escape_snippet(&snippet?))))
+        .collect())
+}
+
+// GET /videos/search?q=limping - Processed videos whose description or mood
+// match, best match first
+pub async fn search_videos(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    pagination: Pagination,
+    Query(params): Query<VideoSearchParams>,
+) -> Result<Response, ApiError> {
+    let q = search_query(params.q.as_deref())?;
+    let pets = accessible_pets(&db, user_id).await?;
+    let pet_ids: Vec<i32> = pets.iter().map(|p| p.id).collect();
+    if pet_ids.is_empty() {
+        return Ok(Json(VideoSearchResponse {
+            query: q,
+            results: vec![],
+            total: 0,
+            page: pagination.page,
+            per_page: pagination.page_size,
+            total_pages: 0,
+        })
+        .into_response());
+    }
+
+    let matches = format!("{} @@ plainto_tsquery('english', $1)", SEARCH_DOCUMENT);
+    let rank = format!(
+        "ts_rank({}, plainto_tsquery('english', $1))",
+        SEARCH_DOCUMENT
+    );
     let paginator = pet_video::Entity::find()
-        .filter(pet_video::Column::PetId.eq(pet_id))
+        .filter(pet_video::Column::PetId.is_in(pet_ids))
         .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(Expr::cust_with_values(&matches, [q.clone()]))
+        .order_by(
+            Expr::cust_with_values(&rank, [q.clone()]),
+            sea_orm::Order::Desc,
+        )
         .order_by_desc(pet_video::Column::CreatedAt)
-        .paginate(&db, params.per_page);
-
-    let total = match paginator.num_pages().await {
-        Ok(pages) => pages,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+        .paginate(&db, pagination.page_size);
 
-    let videos = match paginator.fetch_page(params.page - 1).await {
-        Ok(v) => v,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+    let totals = paginator.num_items_and_pages().await?;
+    let videos = paginator.fetch_page(pagination.index()).await?;
 
-    let pet = pet::Entity::find_by_id(pet_id)
-        .one(&db)
-        .await
-        .ok()
-        .flatten();
+    let video_ids: Vec<uuid::Uuid> = videos.iter().map(|v| v.id).collect();
+    let mut snippets = search_snippets(&db, video_ids.clone(), &q).await?;
+    let mut tags = video_tag::tags_by_video(&db, video_ids).await?;
+    let pet_map: std::collections::HashMap<i32, pet::Model> =
+        pets.into_iter().map(|p| (p.id, p)).collect();
 
-    let videos_with_pets: Vec<VideoWithPet> = videos
+    let results = videos
         .into_iter()
-        .map(|video| VideoWithPet {
-            pet: pet.clone(),
-            video,
+        .map(|video| VideoSearchHit {
+            snippet: snippets.remove(&video.id),
+            video: VideoWithPet {
+                pet: pet_map.get(&video.pet_id).cloned(),
+                tags: tags.remove(&video.id).unwrap_or_default(),
+                alerts: None,
+                video,
+            },
         })
         .collect();
 
-    let total_items = match pet_video::Entity::find()
-        .filter(pet_video::Column::PetId.eq(pet_id))
-        .filter(pet_video::Column::Status.eq("PROCESSED"))
-        .count(&db)
-        .await
-    {
-        Ok(count) => count,
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
-        }
-    };
+    Ok(Json(VideoSearchResponse {
+        query: q,
+        results,
+        total: totals.number_of_items,
+        page: pagination.page,
+        per_page: pagination.page_size,
+        total_pages: totals.number_of_pages,
+    })
+    .into_response())
+}
 
-    (
-        StatusCode::OK,
-        Json(VideoListResponse {
-            videos: videos_with_pets,
-            total: total_items,
-            page: params.page,
-            per_page: params.per_page,
-            total_pages: total,
-        }),
+pub async fn serve_video(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    // The caller must be able to see the video's pet
+    let video = readable_video(&db, video_id, user_id).await?;
+    ensure_not_expired(&video)?;
+
+    Ok(
+        stream_video(&gcs_client, &redis_client, &video, &headers, |file_name| {
+            format!("inline; filename=\"{}\"", file_name)
+        })
+        .await,
     )
-        .into_response()
 }
 
-pub async fn serve_video(
+// GET /videos/:id/download - The clip as an attachment named after the pet and day
+pub async fn download_video(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
     Extension(gcs_client): Extension<GcsClient>,
-    Path(video_id): Path<String>,
-) -> Response {
-    // Parse video ID as UUID
-    let video_uuid = match uuid::Uuid::parse_str(&video_id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": "Invalid video ID"})),
-            )
-                .into_response()
-        }
-    };
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let (video, pet) = video_and_pet_with_access(&db, video_id, user_id, PetAccess::Read).await?;
+    ensure_not_expired(&video)?;
 
-    // Get video from database
-    let video = match pet_video::Entity::find_by_id(video_uuid).one(&db).await {
-        Ok(Some(v)) => v,
-        Ok(None) => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Video not found"})),
-            )
-                .into_response()
-        }
-        Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response()
+    let prefix = format!(
+        "{}-{}-{}",
+        file_name_slug(&pet.name),
+        video.created_at.date_naive(),
+        video.id
+    );
+    Ok(
+        stream_video(&gcs_client, &redis_client, &video, &headers, |file_name| {
+            let ext = std::path::Path::new(file_name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("mp4");
+            format!("attachment; filename=\"{}.{}\"", prefix, ext)
+        })
+        .await,
+    )
+}
+
+/// Lowercase ASCII letters and digits joined by single dashes, for filenames.
+fn file_name_slug(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "pet".to_string()
+    } else {
+        slug
+    }
+}
+
+/// How long object metadata is reused across requests for the same video.
+const OBJECT_META_CACHE_SECS: u64 = 60;
+
+/// What a response needs to know about a video's object before streaming it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ObjectMeta {
+    size: u64,
+    /// Changes whenever the object is overwritten
+    generation: i64,
+}
+
+impl ObjectMeta {
+    fn etag(&self, video_id: uuid::Uuid) -> String {
+        format!("\"{}-{}\"", video_id.simple(), self.generation)
+    }
+}
+
+fn object_meta_key(video_id: uuid::Uuid) -> String {
+    format!("petpulse:video_object_meta:{}", video_id)
+}
+
+/// Object metadata from a short-lived Redis cache, falling back to GCS. A
+/// player seeking through a clip sends a burst of range requests, and this
+/// saves a metadata call on each. `refresh` skips the cache, for when the
+/// cached generation turned out to be gone.
+async fn object_meta(
+    gcs_client: &GcsClient,
+    redis_client: &redis::Client,
+    video_id: uuid::Uuid,
+    request: &GetObjectRequest,
+    refresh: bool,
+) -> Result<ObjectMeta, google_cloud_storage::http::Error> {
+    let mut conn = redis_client.get_multiplexed_async_connection().await.ok();
+    if let Some(conn) = conn.as_mut().filter(|_| !refresh) {
+        let cached: Option<String> = conn.get(object_meta_key(video_id)).await.unwrap_or(None);
+        if let Some(meta) = cached.and_then(|raw| serde_json::from_str(&raw).ok()) {
+            return Ok(meta);
         }
+    }
+
+    let object = gcs_client.get_object(request).await?;
+    let meta = ObjectMeta {
+        size: object.size.max(0) as u64,
+        generation: object.generation,
     };
+    if let (Some(conn), Ok(raw)) = (conn.as_mut(), serde_json::to_string(&meta)) {
+        let _: Result<(), _> = conn
+            .set_ex(object_meta_key(video_id), raw, OBJECT_META_CACHE_SECS)
+            .await;
+    }
+    Ok(meta)
+}
+
+/// Whether an `If-None-Match` value names `etag`, using the weak comparison
+/// RFC 7232 specifies for it.
+fn none_match(value: &str, etag: &str) -> bool {
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
 
-    // Extract GCS path (remove gs:// prefix and split bucket/object)
-    let file_path = video.file_path.trim_start_matches("gs://");
-    let parts: Vec<&str> = file_path.splitn(2, '/').collect();
+/// Whether a `Range` should be honored given `If-Range`. Only a strong match
+/// on the current ETag keeps the range; a date or a stale tag gets the whole
+/// object, per RFC 7233.
+fn if_range_allows(headers: &HeaderMap, etag: &str) -> bool {
+    match headers.get(header::IF_RANGE).map(|v| v.to_str()) {
+        None => true,
+        Some(Ok(value)) => value.trim() == etag,
+        Some(Err(_)) => false,
+    }
+}
 
-    if parts.len() != 2 {
+/// Streams a video's object from GCS, honoring `If-None-Match`, `Range` and
+/// `If-Range`. `disposition` builds the Content-Disposition value from the
+/// object's file name.
+async fn stream_video(
+    gcs_client: &GcsClient,
+    redis_client: &redis::Client,
+    video: &pet_video::Model,
+    headers: &HeaderMap,
+    disposition: impl FnOnce(&str) -> String,
+) -> Response {
+    let Some((bucket, object_name)) = parse_gs_path(&video.file_path) else {
         tracing::error!("Invalid file path format: {}", video.file_path);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": "Invalid file path format"})),
         )
             .into_response();
-    }
-
-    let bucket = parts[0];
-    let object_name = parts[1];
+    };
 
     tracing::info!(
         "Fetching video from GCS: bucket={}, object={}",
@@ -284,38 +794,1335 @@ pub async fn serve_video(
         object_name
     );
 
-    // Fetch video from GCS
     let request = GetObjectRequest {
         bucket: bucket.to_string(),
         object: object_name.to_string(),
         ..Default::default()
     };
 
-    match gcs_client
-        .download_object(&request, &Default::default())
-        .await
-    {
-        Ok(data) => {
-            tracing::info!("Successfully fetched video, size: {} bytes", data.len());
-            // Return video file with proper content type
-            (
-                StatusCode::OK,
-                [
-                    (header::CONTENT_TYPE, "video/mp4"),
-                    (header::CACHE_CONTROL, "public, max-age=3600"),
-                    (header::CONTENT_LENGTH, data.len().to_string().as_str()),
-                ],
-                Body::from(data),
-            )
-                .into_response()
+    // The download is pinned to the generation the ETag and size came from.
+    // If the object was overwritten since that metadata was cached, the old
+    // generation is gone, so the lookup is redone once against GCS.
+    let file_name = object_name.rsplit('/').next().unwrap_or(object_name);
+    let disposition = disposition(file_name);
+    let mut refresh = false;
+    let (size, playback_headers, range, chunks) = loop {
+        // The size gives Content-Length up front and resolves open-ended ranges
+        let meta = match object_meta(gcs_client, redis_client, video.id, &request, refresh).await {
+            Ok(meta) => meta,
+            Err(e) => {
+                tracing::error!("Failed to fetch video metadata from GCS: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": "Failed to fetch video"})),
+                )
+                    .into_response();
+            }
+        };
+        let etag = meta.etag(video.id);
+        let playback_headers = video_playback_headers(video, object_name, &disposition, &etag);
+
+        // If-None-Match is evaluated before Range, so a cached clip is never re-sent
+        let not_modified = headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|value| none_match(value, &etag));
+        if not_modified {
+            return (StatusCode::NOT_MODIFIED, playback_headers).into_response();
         }
-        Err(e) => {
-            tracing::error!("Failed to fetch video from GCS: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": "Failed to fetch video"})),
-            )
-                .into_response()
+
+        let range_header = headers
+            .get(header::RANGE)
+            .and_then(|v| v.to_str().ok())
+            .filter(|_| if_range_allows(headers, &etag));
+        let range = match range_header.map(|value| parse_range(value, meta.size)) {
+            None | Some(RangeRequest::Full) => None,
+            Some(RangeRequest::Partial(range)) => Some(range),
+            Some(RangeRequest::Unsatisfiable) => {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [
+                        (header::ACCEPT_RANGES, "bytes".to_string()),
+                        (header::CONTENT_RANGE, format!("bytes */{}", meta.size)),
+                    ],
+                )
+                    .into_response();
+            }
+        };
+
+        let gcs_range = range
+            .map(|r| Range(Some(r.start), Some(r.end)))
+            .unwrap_or_default();
+        let pinned = GetObjectRequest {
+            generation: Some(meta.generation),
+            ..request.clone()
+        };
+        match gcs_client
+            .download_streamed_object(&pinned, &gcs_range)
+            .await
+        {
+            Ok(chunks) => break (meta.size, playback_headers, range, chunks),
+            Err(google_cloud_storage::http::Error::Response(e)) if e.code == 404 && !refresh => {
+                tracing::info!(
+                    "Video {} object was rewritten; refreshing its metadata",
+                    video.id
+                );
+                refresh = true;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch video from GCS: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"error": "Failed to fetch video"})),
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    // Chunks are forwarded as they arrive, so memory per stream stays at a
+    // chunk or two. A client that goes away drops the body, which drops the
+    // GCS response and ends the download with it.
+    let video_id = video.id;
+    let body = Body::from_stream(chunks.map_err(move |e| {
+        tracing::warn!("Video {} stream from GCS broke off: {}", video_id, e);
+        std::io::Error::other(e)
+    }));
+
+    match range {
+        Some(range) => (
+            StatusCode::PARTIAL_CONTENT,
+            playback_headers,
+            [
+                (
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", range.start, range.end, size),
+                ),
+                (
+                    header::CONTENT_LENGTH,
+                    (range.end - range.start + 1).to_string(),
+                ),
+            ],
+            body,
+        )
+            .into_response(),
+        None => (
+            StatusCode::OK,
+            playback_headers,
+            [(header::CONTENT_LENGTH, size.to_string())],
+            body,
+        )
+            .into_response(),
+    }
+}
+
+/// Headers every full, partial or 304 video response carries. Videos uploaded
+/// before content sniffing have no stored type, so it's guessed from the
+/// object name. Footage is owner-only, so shared caches must not keep it.
+fn video_playback_headers(
+    video: &pet_video::Model,
+    object_name: &str,
+    disposition: &str,
+    etag: &str,
+) -> HeaderMap {
+    let content_type = video.content_type.clone().unwrap_or_else(|| {
+        mime_guess::from_path(object_name)
+            .first_or("video/mp4".parse().unwrap())
+            .to_string()
+    });
+    let mut headers = HeaderMap::new();
+    for (name, value) in [
+        (header::CONTENT_TYPE, content_type),
+        (header::CONTENT_DISPOSITION, disposition.to_string()),
+        (header::ETAG, etag.to_string()),
+        (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+        (header::ACCEPT_RANGES, "bytes".to_string()),
+    ] {
+        if let Ok(value) = value.parse() {
+            headers.insert(name, value);
         }
     }
+    headers
+}
+
+/// Inclusive byte range of an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    /// Serve the whole object. Used for multi-range requests, which players
+    /// don't send for video, and for range units other than bytes.
+    Full,
+    Partial(ByteRange),
+    Unsatisfiable,
+}
+
+/// Resolves a `Range` header against an object of `size` bytes. Supports
+/// `bytes=start-end`, open-ended `bytes=start-` and suffix `bytes=-len`;
+/// other range units are ignored, per RFC 7233.
+fn parse_range(value: &str, size: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return RangeRequest::Unsatisfiable;
+    };
+    let (first, last) = (first.trim(), last.trim());
+
+    let range = match (first.parse::<u64>().ok(), last.parse::<u64>().ok()) {
+        // bytes=-500: the final 500 bytes
+        (None, Some(suffix)) if first.is_empty() && suffix > 0 && size > 0 => ByteRange {
+            start: size.saturating_sub(suffix),
+            end: size - 1,
+        },
+        // bytes=1000-: everything from byte 1000
+        (Some(start), None) if last.is_empty() && start < size => ByteRange {
+            start,
+            end: size - 1,
+        },
+        (Some(start), Some(end)) if start <= end && start < size => ByteRange {
+            start,
+            end: end.min(size - 1),
+        },
+        _ => return RangeRequest::Unsatisfiable,
+    };
+    RangeRequest::Partial(range)
+}
+
+// Expected upper bounds per stage; stages running longer are flagged in the timeline
+const QUEUED_BUDGET_SECS: i64 = 300;
+const DOWNLOAD_BUDGET_SECS: i64 = 120;
+const ANALYSIS_BUDGET_SECS: i64 = 600;
+
+#[derive(Debug, Serialize)]
+pub struct TimelineStage {
+    pub stage: &'static str,
+    pub started_at: chrono::DateTime<chrono::FixedOffset>,
+    pub ended_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub duration_seconds: i64,
+    pub in_progress: bool,
+    pub over_budget: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoTimeline {
+    pub video_id: uuid::Uuid,
+    pub status: String,
+    pub retry_count: i32,
+    pub last_error: Option<String>,
+    pub stages: Vec<TimelineStage>,
+    pub total_seconds: Option<i64>,
+}
+
+impl VideoTimeline {
+    pub fn from_video(video: &pet_video::Model) -> Self {
+        let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+
+        let stage_bounds = [
+            (
+                "queued",
+                video.queued_at,
+                video.download_started_at,
+                QUEUED_BUDGET_SECS,
+            ),
+            (
+                "download",
+                video.download_started_at,
+                video.analysis_started_at,
+                DOWNLOAD_BUDGET_SECS,
+            ),
+            (
+                "analysis",
+                video.analysis_started_at,
+                video.completed_at,
+                ANALYSIS_BUDGET_SECS,
+            ),
+        ];
+
+        let stages = stage_bounds
+            .into_iter()
+            .filter_map(|(stage, start, end, budget)| {
+                let started_at = start?;
+                // Skipped later stages (e.g. static clips) end at completion
+                let end = end.or(video.completed_at);
+                let in_progress = end.is_none();
+                let duration_seconds = (end.unwrap_or(now) - started_at).num_seconds().max(0);
+                Some(TimelineStage {
+                    stage,
+                    started_at,
+                    ended_at: end,
+                    duration_seconds,
+                    in_progress,
+                    over_budget: duration_seconds > budget,
+                })
+            })
+            .collect();
+
+        Self {
+            video_id: video.id,
+            status: video.status.clone(),
+            retry_count: video.retry_count,
+            last_error: video.error_message.clone(),
+            stages,
+            total_seconds: video
+                .queued_at
+                .zip(video.completed_at)
+                .map(|(start, end)| (end - start).num_seconds().max(0)),
+        }
+    }
+}
+
+// GET /videos/:id/timeline - Per-stage processing timestamps for debugging slow analyses
+pub async fn get_video_timeline(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+) -> Result<Response, ApiError> {
+    let video = readable_video(&db, video_id, user_id).await?;
+    Ok((StatusCode::OK, Json(VideoTimeline::from_video(&video))).into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VideoClipsParams {
+    /// Canonical or raw activity name, case-insensitive
+    pub activity: Option<String>,
+    pub mood: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivitySegment {
+    pub activity: String,
+    pub canonical_activity: String,
+    pub mood: String,
+    pub description: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub start_seconds: u32,
+    pub end_seconds: u32,
+    pub duration_seconds: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoClipsResponse {
+    pub video_id: uuid::Uuid,
+    pub clips: Vec<ActivitySegment>,
+    /// Entries of `activities` that weren't valid segments
+    pub dropped_segments: usize,
+}
+
+/// Typed segments of a video's `activities`, ordered by start time. Entries
+/// that don't parse or whose end precedes their start are counted, not returned.
+fn activity_segments(video: &pet_video::Model, species: &str) -> (Vec<ActivitySegment>, usize) {
+    let entries = video
+        .activities
+        .as_ref()
+        .and_then(|a| a.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut dropped = 0;
+    let mut segments: Vec<ActivitySegment> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let segment = serde_json::from_value::<pet_video::Activity>(entry)
+                .ok()
+                .and_then(|a| {
+                    let start = parse_timestamp_secs(&a.starttime)?;
+                    let end = parse_timestamp_secs(&a.endtime)?;
+                    (end >= start).then(|| ActivitySegment {
+                        canonical_activity: a
+                            .canonical_activity
+                            .clone()
+                            .unwrap_or_else(|| crate::activity::normalize(&a.activity, species)),
+                        activity: a.activity,
+                        mood: a.mood,
+                        description: a.description,
+                        start_time: a.starttime,
+                        end_time: a.endtime,
+                        start_seconds: start,
+                        end_seconds: end,
+                        duration_seconds: end - start,
+                    })
+                });
+            if segment.is_none() {
+                dropped += 1;
+            }
+            segment
+        })
+        .collect();
+    segments.sort_by_key(|s| (s.start_seconds, s.end_seconds));
+    (segments, dropped)
+}
+
+// GET /videos/:id/clips - The video's activity segments, optionally filtered
+pub async fn get_video_clips(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    Query(params): Query<VideoClipsParams>,
+) -> Result<Response, ApiError> {
+    let (video, pet) = video_and_pet_with_access(&db, video_id, user_id, PetAccess::Read).await?;
+    let (mut clips, dropped_segments) = activity_segments(&video, &pet.species);
+
+    if let Some(activity) = params
+        .activity
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        let canonical = crate::activity::normalize(activity, &pet.species);
+        clips.retain(|c| {
+            c.canonical_activity == canonical || c.activity.eq_ignore_ascii_case(activity)
+        });
+    }
+    if let Some(mood) = params.mood.as_deref().and_then(crate::mood::normalize) {
+        clips.retain(|c| crate::mood::normalize(&c.mood).as_deref() == Some(mood.as_str()));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(VideoClipsResponse {
+            video_id: video.id,
+            clips,
+            dropped_segments,
+        }),
+    )
+        .into_response())
+}
+
+/// Loads a video and its pet, if the caller may access the pet at the given level.
+async fn video_and_pet_with_access(
+    db: &DatabaseConnection,
+    video_id: uuid::Uuid,
+    user_id: i32,
+    access: PetAccess,
+) -> Result<(pet_video::Model, pet::Model), ApiError> {
+    let (video, pet) = match pet_video::Entity::find_by_id(video_id)
+        .find_also_related(pet::Entity)
+        .one(db)
+        .await?
+    {
+        Some((v, Some(p))) => (v, p),
+        _ => return Err(ApiError::not_found("video_not_found")),
+    };
+    check_pet_access(db, &pet, user_id, access).await?;
+    Ok((video, pet))
+}
+
+/// Loads a video of a pet the caller may access at the given level.
+async fn video_with_access(
+    db: &DatabaseConnection,
+    video_id: uuid::Uuid,
+    user_id: i32,
+    access: PetAccess,
+) -> Result<pet_video::Model, ApiError> {
+    video_and_pet_with_access(db, video_id, user_id, access)
+        .await
+        .map(|(video, _)| video)
+}
+
+/// Loads a video of a pet the caller owns or cares for.
+async fn readable_video(
+    db: &DatabaseConnection,
+    video_id: uuid::Uuid,
+    user_id: i32,
+) -> Result<pet_video::Model, ApiError> {
+    video_with_access(db, video_id, user_id, PetAccess::Read).await
+}
+
+/// 410 for a video whose file was removed by retention.
+fn ensure_not_expired(video: &pet_video::Model) -> Result<(), ApiError> {
+    if video.status == pet_video::EXPIRED {
+        return Err(ApiError::new(StatusCode::GONE, "video_expired"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VideoUrlParams {
+    /// Answer with a 302 to the URL instead of JSON
+    #[serde(default)]
+    pub redirect: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoUrlResponse {
+    pub url: String,
+    /// False when signing is unavailable and `url` is the proxied stream
+    pub signed: bool,
+    pub expires_in_secs: Option<u64>,
+}
+
+// GET /videos/:id/url - Short-lived signed URL for direct playback from storage
+pub async fn get_video_url(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    Query(params): Query<VideoUrlParams>,
+) -> Result<Response, ApiError> {
+    let video = readable_video(&db, video_id, user_id).await?;
+    ensure_not_expired(&video)?;
+
+    let body = match signed_video_url(&gcs_client, &video).await {
+        Some(url) => VideoUrlResponse {
+            url,
+            signed: true,
+            expires_in_secs: Some(signed_url_ttl().as_secs()),
+        },
+        None => VideoUrlResponse {
+            url: proxy_stream_url(&video),
+            signed: false,
+            expires_in_secs: None,
+        },
+    };
+
+    if params.redirect {
+        return Ok((
+            StatusCode::FOUND,
+            [
+                (header::LOCATION, body.url),
+                // The signed URL expires, so it must not be cached past its TTL
+                (header::CACHE_CONTROL, "private, no-store".to_string()),
+            ],
+        )
+            .into_response());
+    }
+    Ok((StatusCode::OK, Json(body)).into_response())
+}
+
+// GET /videos/:id/thumbnail - JPEG preview frame of the video
+pub async fn get_video_thumbnail(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(gcs_client): Extension<GcsClient>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+) -> Result<Response, ApiError> {
+    let video = readable_video(&db, video_id, user_id).await?;
+    let path = video
+        .thumbnail_path
+        .ok_or_else(|| ApiError::not_found("thumbnail_not_found"))?;
+    let (bucket, object) = parse_gs_path(&path)
+        .ok_or_else(|| ApiError::internal(format!("Invalid thumbnail path {}", path)))?;
+
+    let data = gcs_client
+        .download_object(
+            &GetObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            },
+            &Range::default(),
+        )
+        .await
+        .map_err(ApiError::internal)?;
+
+    // A video's thumbnail never changes once written
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "image/jpeg"),
+            (header::CACHE_CONTROL, "private, max-age=86400, immutable"),
+        ],
+        Body::from(data),
+    )
+        .into_response())
+}
+
+/// Entries of `video_queue` scanned when estimating a queue position.
+const QUEUE_SCAN_LIMIT: isize = 10_000;
+const QUEUE_SCAN_CHUNK: isize = 500;
+
+/// 1-based position of the video's job among queued jobs, counting from the
+/// end workers pop. Every high-priority job runs before any normal one.
+async fn queue_position(
+    conn: &mut redis::aio::MultiplexedConnection,
+    video_id: uuid::Uuid,
+) -> redis::RedisResult<Option<u64>> {
+    if let Some(position) = position_in_queue(conn, crate::video_queue::HIGH, video_id).await? {
+        return Ok(Some(position));
+    }
+    let ahead: u64 = conn.llen(crate::video_queue::HIGH).await?;
+    Ok(
+        position_in_queue(conn, crate::video_queue::NORMAL, video_id)
+            .await?
+            .map(|position| position + ahead),
+    )
+}
+
+/// 1-based position of the video's job in `queue`. `None` when it isn't in
+/// the first [`QUEUE_SCAN_LIMIT`] entries.
+async fn position_in_queue(
+    conn: &mut redis::aio::MultiplexedConnection,
+    queue: &str,
+    video_id: uuid::Uuid,
+) -> redis::RedisResult<Option<u64>> {
+    let needle = video_id.to_string();
+    let mut start = 0;
+    while start < QUEUE_SCAN_LIMIT {
+        let entries: Vec<String> = conn
+            .lrange(queue, start, start + QUEUE_SCAN_CHUNK - 1)
+            .await?;
+        if let Some(offset) = entries.iter().position(|entry| {
+            serde_json::from_str::<serde_json::Value>(entry)
+                .ok()
+                .and_then(|v| v["video_id"].as_str().map(|id| id == needle))
+                .unwrap_or(false)
+        }) {
+            return Ok(Some((start as usize + offset) as u64 + 1));
+        }
+        if (entries.len() as isize) < QUEUE_SCAN_CHUNK {
+            break;
+        }
+        start += QUEUE_SCAN_CHUNK;
+    }
+    Ok(None)
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoStatusResponse {
+    pub video_id: uuid::Uuid,
+    pub status: String,
+    pub retry_count: i32,
+    pub created_at: chrono::DateTime<chrono::FixedOffset>,
+    pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+    pub queued_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub download_started_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub analysis_started_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub completed_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    /// Approximate; only while the video is waiting to be picked up
+    pub queue_position: Option<u64>,
+    /// Only once the video has failed
+    pub error_message: Option<String>,
+    /// Which step failed: `download`, `fs_write`, `analysis`, `timeout`,
+    /// `parse` or `db_update`. Only once the video has failed
+    pub error_stage: Option<String>,
+}
+
+// GET /videos/:id/status - Lightweight processing status for polling after upload
+pub async fn get_video_status(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+) -> Result<Response, ApiError> {
+    let video = readable_video(&db, video_id, user_id).await?;
+
+    let waiting = matches!(video.status.as_str(), "PENDING" | "Retrying");
+    let queue_position = if waiting {
+        match redis_client.get_multiplexed_async_connection().await {
+            Ok(mut conn) => queue_position(&mut conn, video_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to scan video_queue for {}: {}", video_id, e);
+                    None
+                }),
+            Err(e) => {
+                tracing::warn!("Failed to connect to Redis for queue position: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let failed = video.status == "FAILED";
+    let error_message = failed.then_some(video.error_message).flatten();
+    let error_stage = failed.then_some(video.error_stage).flatten();
+
+    Ok((
+        StatusCode::OK,
+        Json(VideoStatusResponse {
+            video_id: video.id,
+            status: video.status,
+            retry_count: video.retry_count,
+            created_at: video.created_at,
+            updated_at: video.updated_at,
+            queued_at: video.queued_at,
+            download_started_at: video.download_started_at,
+            analysis_started_at: video.analysis_started_at,
+            completed_at: video.completed_at,
+            queue_position,
+            error_message,
+            error_stage,
+        }),
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VideoTagRequest {
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoTagsResponse {
+    pub video_id: uuid::Uuid,
+    pub tags: Vec<String>,
+}
+
+/// Normalized tag, or a 422 naming the `tag` field.
+fn validated_tag(raw: Option<&str>) -> Result<String, ApiError> {
+    let tag = raw.map(video_tag::normalize).unwrap_or_default();
+    let error = if tag.is_empty() {
+        Some("field.required")
+    } else if tag.chars().count() > video_tag::MAX_TAG_LEN {
+        Some("field.too_long")
+    } else if !tag
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        Some("field.invalid_format")
+    } else {
+        None
+    };
+    match error {
+        Some(code) => Err(ApiError::validation(vec![FieldError::new("tag", code)])),
+        None => Ok(tag),
+    }
+}
+
+async fn tags_response(
+    db: &DatabaseConnection,
+    video_id: uuid::Uuid,
+    status: StatusCode,
+) -> Result<Response, ApiError> {
+    let tags = video_tag::tags_by_video(db, vec![video_id])
+        .await?
+        .remove(&video_id)
+        .unwrap_or_default();
+    Ok((status, Json(VideoTagsResponse { video_id, tags })).into_response())
+}
+
+// POST /videos/:id/tags - Tag a video (e.g. `favorite`); adding a tag it already has is a no-op
+pub async fn add_video_tag(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    Json(payload): Json<VideoTagRequest>,
+) -> Result<Response, ApiError> {
+    let video = video_with_access(&db, video_id, user_id, PetAccess::Manage).await?;
+    let tag = validated_tag(payload.tag.as_deref())?;
+
+    let existing = video_tag::Entity::find()
+        .filter(video_tag::Column::VideoId.eq(video.id))
+        .all(&db)
+        .await?;
+    if existing.iter().any(|t| t.tag == tag) {
+        return tags_response(&db, video.id, StatusCode::OK).await;
+    }
+    if existing.len() as u64 >= video_tag::MAX_TAGS_PER_VIDEO {
+        return Err(ApiError::new(StatusCode::CONFLICT, "video_tag_limit"));
+    }
+
+    // A concurrent request adding the same tag loses quietly to the unique index
+    let inserted = video_tag::Entity::insert(video_tag::ActiveModel {
+        video_id: Set(video.id),
+        user_id: Set(user_id),
+        tag: Set(tag),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([video_tag::Column::VideoId, video_tag::Column::Tag])
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(&db)
+    .await?;
+
+    let status = if inserted > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    tags_response(&db, video.id, status).await
+}
+
+// DELETE /videos/:id/tags?tag=favorite - Remove a tag from a video
+pub async fn remove_video_tag(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(user_id): Extension<i32>,
+    Path(video_id): Path<uuid::Uuid>,
+    Query(params): Query<VideoTagRequest>,
+) -> Result<Response, ApiError> {
+    let video = video_with_access(&db, video_id, user_id, PetAccess::Manage).await?;
+    let tag = validated_tag(params.tag.as_deref())?;
+
+    video_tag::Entity::delete_many()
+        .filter(video_tag::Column::VideoId.eq(video.id))
+        .filter(video_tag::Column::Tag.eq(tag))
+        .exec(&db)
+        .await?;
+    tags_response(&db, video.id, StatusCode::OK).await
+}
+
+const REPROCESS_COOLDOWN_SECS: u64 = 5 * 60;
+
+fn reprocess_cooldown_key(video_id: uuid::Uuid) -> String {
+    format!("petpulse:reprocess_cooldown:{}", video_id)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReprocessParams {
+    priority: Option<String>,
+}
+
+// POST /videos/:id/reprocess - Run a video through analysis again
+pub async fn reprocess_video(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(user_id): Extension<i32>,
+    ClientIp(ip): ClientIp,
+    Path(video_id): Path<uuid::Uuid>,
+    Query(params): Query<ReprocessParams>,
+) -> Result<Response, ApiError> {
+    let priority = match params.priority.as_deref() {
+        None => Priority::Normal,
+        Some(raw) => Priority::parse(raw).ok_or_else(|| {
+            ApiError::validation(vec![FieldError::new("priority", "field.invalid_format")])
+        })?,
+    };
+    let video = video_with_access(&db, video_id, user_id, PetAccess::Manage).await?;
+    ensure_not_expired(&video)?;
+    if priority == Priority::High && !crate::video_queue::user_allows_high(&db, user_id).await {
+        return Err(ApiError::forbidden("priority_not_allowed"));
+    }
+    if video.status == "PROCESSING" {
+        return Err(ApiError::new(StatusCode::CONFLICT, "video_processing"));
+    }
+
+    let mut conn = redis_client
+        .get_multiplexed_async_connection()
+        .await
+        .map_err(ApiError::internal)?;
+
+    // One reprocess per video per cooldown window
+    let key = reprocess_cooldown_key(video_id);
+    let acquired: bool = redis::cmd("SET")
+        .arg(&key)
+        .arg(user_id)
+        .arg("NX")
+        .arg("EX")
+        .arg(REPROCESS_COOLDOWN_SECS)
+        .query_async::<Option<String>>(&mut conn)
+        .await
+        .map_err(ApiError::internal)?
+        .is_some();
+    if !acquired {
+        let ttl: i64 = conn
+            .ttl(&key)
+            .await
+            .unwrap_or(REPROCESS_COOLDOWN_SECS as i64);
+        let mut response =
+            ApiError::new(StatusCode::TOO_MANY_REQUESTS, "reprocess_rate_limited").into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(ttl.max(1)));
+        return Ok(response);
+    }
+
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let mut active: pet_video::ActiveModel = video.into();
+    active.status = Set("PENDING".to_string());
+    active.retry_count = Set(0);
+    active.queued_at = Set(Some(now));
+    active.download_started_at = Set(None);
+    active.analysis_started_at = Set(None);
+    active.completed_at = Set(None);
+    active.error_message = Set(None);
+    active.error_stage = Set(None);
+    active.priority = Set(priority.as_str().to_string());
+    active.updated_at = Set(now);
+    let video = active.update(&db).await?;
+
+    let queue_position: u64 = conn
+        .rpush(
+            priority.queue(),
+            super::daily_digest::video_job_payload(video_id, priority),
+        )
+        .await
+        .map_err(ApiError::internal)?;
+    // A normal job also waits behind every high-priority one
+    let queue_position = if priority == Priority::High {
+        queue_position
+    } else {
+        let ahead: u64 = conn
+            .llen(crate::video_queue::HIGH)
+            .await
+            .map_err(ApiError::internal)?;
+        queue_position + ahead
+    };
+
+    tracing::info!(%video_id, queue_position, "Video re-queued for processing");
+    video_events::publish(&mut conn, VideoEventKind::Queued, &video).await;
+    metrics::counter!("petpulse_videos_reprocessed_total").increment(1);
+    crate::audit::record(
+        &db,
+        user_id,
+        "reprocess_video",
+        Some(("video", video_id.to_string())),
+        ip,
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "status": "queued",
+            "video_id": video_id,
+            "queue_position": queue_position,
+        })),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial(start: u64, end: u64) -> RangeRequest {
+        RangeRequest::Partial(ByteRange { start, end })
+    }
+
+    #[test]
+    fn closed_range_is_clamped_to_the_object() {
+        assert_eq!(parse_range("bytes=0-99", 1000), partial(0, 99));
+        assert_eq!(parse_range("bytes=900-5000", 1000), partial(900, 999));
+    }
+
+    #[test]
+    fn open_ended_range_runs_to_the_last_byte() {
+        assert_eq!(parse_range("bytes=1000-", 5000), partial(1000, 4999));
+        assert_eq!(
+            parse_range("bytes=5000-", 5000),
+            RangeRequest::Unsatisfiable
+        );
+    }
+
+    #[test]
+    fn suffix_range_takes_the_final_bytes() {
+        assert_eq!(parse_range("bytes=-500", 5000), partial(4500, 4999));
+        // A suffix longer than the object is the whole object
+        assert_eq!(parse_range("bytes=-9000", 5000), partial(0, 4999));
+        assert_eq!(parse_range("bytes=-0", 5000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-10", 0), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn unsatisfiable_ranges() {
+        assert_eq!(
+            parse_range("bytes=500-100", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range("bytes=abc", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=x-y", 1000), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn multiple_ranges_serve_the_whole_object() {
+        assert_eq!(parse_range("bytes=0-10, 20-30", 1000), RangeRequest::Full);
+    }
+
+    #[test]
+    fn unknown_range_unit_serves_the_whole_object() {
+        assert_eq!(parse_range("items=0-5", 100), RangeRequest::Full);
+        assert_eq!(parse_range("seconds=10-", 100), RangeRequest::Full);
+    }
+
+    #[test]
+    fn none_match_accepts_lists_wildcards_and_weak_tags() {
+        let etag = "\"abc-7\"";
+        assert!(none_match("\"abc-7\"", etag));
+        assert!(none_match("\"old-1\", W/\"abc-7\"", etag));
+        assert!(none_match("*", etag));
+        assert!(!none_match("\"abc-6\"", etag));
+    }
+
+    #[test]
+    fn if_range_needs_the_current_strong_etag() {
+        let etag = "\"abc-7\"";
+        let with = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_RANGE, value.parse().unwrap());
+            headers
+        };
+        assert!(if_range_allows(&HeaderMap::new(), etag));
+        assert!(if_range_allows(&with("\"abc-7\""), etag));
+        assert!(!if_range_allows(&with("W/\"abc-7\""), etag));
+        assert!(!if_range_allows(&with("\"abc-6\""), etag));
+        assert!(!if_range_allows(
+            &with("Wed, 21 Oct 2026 07:28:00 GMT"),
+            etag
+        ));
+    }
+
+    #[test]
+    fn etag_changes_with_the_generation() {
+        let id = uuid::Uuid::new_v4();
+        let before = ObjectMeta {
+            size: 10,
+            generation: 1,
+        };
+        let after = ObjectMeta {
+            size: 10,
+            generation: 2,
+        };
+        assert_ne!(before.etag(id), after.etag(id));
+    }
+
+    /// A bucket holding one object whose metadata and download endpoints
+    /// behave like GCS's JSON API. Metadata lookups report `generations` in
+    /// turn (the last repeats), while only `live` can still be downloaded.
+    struct FakeObject {
+        data: Vec<u8>,
+        generations: std::sync::Mutex<Vec<i64>>,
+        live: i64,
+    }
+
+    async fn fake_gcs_object(
+        axum::extract::State(object): axum::extract::State<std::sync::Arc<FakeObject>>,
+        Query(query): Query<std::collections::HashMap<String, String>>,
+        headers: HeaderMap,
+    ) -> Response {
+        if query.get("alt").map(String::as_str) != Some("media") {
+            let mut generations = object.generations.lock().unwrap();
+            let generation = if generations.len() > 1 {
+                generations.remove(0)
+            } else {
+                generations[0]
+            };
+            // GCS sends its int64 fields as strings
+            return Json(json!({
+                "name": "clip.mp4",
+                "bucket": "bucket",
+                "selfLink": "",
+                "mediaLink": "",
+                "id": "",
+                "storageClass": "STANDARD",
+                "etag": "",
+                "size": object.data.len().to_string(),
+                "generation": generation.to_string(),
+                "metageneration": "1",
+            }))
+            .into_response();
+        }
+
+        let generation = query.get("generation").and_then(|g| g.parse().ok());
+        if generation != Some(object.live) {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": {"code": 404, "errors": [], "message": "No such object"}})),
+            )
+                .into_response();
+        }
+        let range = headers
+            .get(header::RANGE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| parse_range(v, object.data.len() as u64));
+        match range {
+            Some(RangeRequest::Partial(r)) => (
+                StatusCode::PARTIAL_CONTENT,
+                object.data[r.start as usize..=r.end as usize].to_vec(),
+            )
+                .into_response(),
+            _ => object.data.clone().into_response(),
+        }
+    }
+
+    async fn gcs_serving(object: FakeObject) -> GcsClient {
+        let app = axum::Router::new()
+            .route(
+                "/storage/v1/b/:bucket/o/:object",
+                axum::routing::get(fake_gcs_object),
+            )
+            .with_state(std::sync::Arc::new(object));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        GcsClient::new(google_cloud_storage::client::ClientConfig {
+            storage_endpoint: endpoint,
+            ..google_cloud_storage::client::ClientConfig::default().anonymous()
+        })
+    }
+
+    /// Nothing listens here, so the metadata cache is skipped.
+    fn no_redis() -> redis::Client {
+        redis::Client::open("redis://127.0.0.1:1/").unwrap()
+    }
+
+    fn stored_video() -> pet_video::Model {
+        serde_json::from_value(json!({
+            "pet_id": 1,
+            "file_path": "gs://bucket/clip.mp4",
+            "status": "PROCESSED",
+            "retry_count": 0,
+            "created_at": "2026-03-09T09:00:00+00:00",
+            "updated_at": "2026-03-09T09:00:00+00:00",
+            "is_unusual": false,
+            "suppressed_by_known_behavior": false,
+            "priority": "normal",
+        }))
+        .unwrap()
+    }
+
+    fn clip_bytes() -> Vec<u8> {
+        (0..200_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    async fn serve(gcs: &GcsClient, headers: HeaderMap) -> (StatusCode, HeaderMap, Vec<u8>) {
+        let response = stream_video(gcs, &no_redis(), &stored_video(), &headers, |name| {
+            format!("inline; filename=\"{}\"", name)
+        })
+        .await;
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (parts.status, parts.headers, body.to_vec())
+    }
+
+    fn range(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, value.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn whole_object_is_streamed_with_its_length() {
+        let data = clip_bytes();
+        let gcs = gcs_serving(FakeObject {
+            data: data.clone(),
+            generations: std::sync::Mutex::new(vec![7]),
+            live: 7,
+        })
+        .await;
+
+        let (status, headers, body) = serve(&gcs, HeaderMap::new()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_LENGTH], data.len().to_string());
+        assert_eq!(headers[header::CONTENT_TYPE], "video/mp4");
+        assert_eq!(headers[header::ACCEPT_RANGES], "bytes");
+        assert_eq!(headers[header::CACHE_CONTROL], "private, max-age=3600");
+        assert_eq!(body, data);
+    }
+
+    #[tokio::test]
+    async fn matching_etag_gets_a_private_304() {
+        let gcs = gcs_serving(FakeObject {
+            data: clip_bytes(),
+            generations: std::sync::Mutex::new(vec![7]),
+            live: 7,
+        })
+        .await;
+        let (_, headers, _) = serve(&gcs, HeaderMap::new()).await;
+
+        let mut conditional = HeaderMap::new();
+        conditional.insert(header::IF_NONE_MATCH, headers[header::ETAG].clone());
+        let (status, headers, body) = serve(&gcs, conditional).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(headers[header::CACHE_CONTROL], "private, max-age=3600");
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn range_is_streamed_as_partial_content() {
+        let data = clip_bytes();
+        let gcs = gcs_serving(FakeObject {
+            data: data.clone(),
+            generations: std::sync::Mutex::new(vec![7]),
+            live: 7,
+        })
+        .await;
+
+        let (status, headers, body) = serve(&gcs, range("bytes=1000-")).await;
+        assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            headers[header::CONTENT_RANGE],
+            format!("bytes 1000-{}/{}", data.len() - 1, data.len())
+        );
+        assert_eq!(
+            headers[header::CONTENT_LENGTH],
+            (data.len() - 1000).to_string()
+        );
+        assert_eq!(body, data[1000..]);
+
+        let (status, headers, _) = serve(&gcs, range("bytes=999999-")).await;
+        assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(
+            headers[header::CONTENT_RANGE],
+            format!("bytes */{}", data.len())
+        );
+    }
+
+    #[tokio::test]
+    async fn overwritten_object_is_refetched_at_its_new_generation() {
+        let data = clip_bytes();
+        // The first lookup still reports generation 7, as a cached entry would
+        let gcs = gcs_serving(FakeObject {
+            data: data.clone(),
+            generations: std::sync::Mutex::new(vec![7, 8]),
+            live: 8,
+        })
+        .await;
+
+        let (status, headers, body) = serve(&gcs, HeaderMap::new()).await;
+        assert_eq!(status, StatusCode::OK);
+        let etag = ObjectMeta {
+            size: data.len() as u64,
+            generation: 8,
+        }
+        .etag(stored_video().id);
+        assert_eq!(headers[header::ETAG], etag.as_str());
+        assert_eq!(body, data);
+    }
+
+    #[tokio::test]
+    async fn missing_object_fails_after_one_refresh() {
+        let gcs = gcs_serving(FakeObject {
+            data: clip_bytes(),
+            generations: std::sync::Mutex::new(vec![7]),
+            live: 8,
+        })
+        .await;
+
+        let (status, _, _) = serve(&gcs, HeaderMap::new()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn duration_comes_from_the_latest_activity_end() {
+        let activities = Some(json!([
+            {"activity": "sleeping", "starttime": "00:00", "endtime": "01:05"},
+            {"activity": "pacing", "starttime": "1:05", "endtime": "0:01:30"},
+            {"activity": "eating", "starttime": "0:10", "endtime": "not a time"},
+        ]));
+        assert_eq!(activities_duration_secs(&activities), Some(90));
+        assert_eq!(activities_duration_secs(&Some(json!([]))), None);
+        assert_eq!(activities_duration_secs(&None), None);
+    }
+
+    #[tokio::test]
+    async fn unsignable_video_falls_back_to_the_proxy_stream() {
+        let gcs = GcsClient::new(google_cloud_storage::client::ClientConfig::default().anonymous());
+        let video = stored_video();
+        assert_eq!(
+            video_stream_url(&gcs, &video).await,
+            format!("/videos/{}/stream", video.id)
+        );
+    }
+
+    fn alert(video_id: Option<uuid::Uuid>, alert_type: &str, acknowledged: bool) -> alerts::Model {
+        serde_json::from_value(json!({
+            "id": uuid::Uuid::new_v4(),
+            "pet_id": 1,
+            "alert_type": alert_type,
+            "severity": "medium",
+            "payload": {},
+            "created_at": "2026-03-09T09:00:00",
+            "severity_level": "medium",
+            "user_acknowledged_at": acknowledged.then_some("2026-03-09T09:05:00"),
+            "notification_sent": false,
+            "occurrence_count": 1,
+            "video_id": video_id,
+            "reminder_count": 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn alerts_are_grouped_under_their_video_in_order() {
+        let (first, second) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let grouped = group_alerts_by_video(vec![
+            alert(Some(first), "pacing", false),
+            alert(Some(second), "vocalization", true),
+            alert(Some(first), "restlessness", true),
+            alert(None, "comfort", false),
+        ]);
+        assert_eq!(grouped.len(), 2);
+        let types: Vec<&str> = grouped[&first]
+            .iter()
+            .map(|a| a.alert_type.as_str())
+            .collect();
+        assert_eq!(types, ["pacing", "restlessness"]);
+        assert!(grouped[&second][0].acknowledged);
+    }
+
+    #[tokio::test]
+    async fn empty_page_skips_the_alert_query() {
+        let grouped = alerts_by_video(&DatabaseConnection::Disconnected, vec![])
+            .await
+            .unwrap();
+        assert!(grouped.is_empty());
+    }
+
+    #[test]
+    fn alerts_are_only_listed_when_requested() {
+        let listed = |alerts| {
+            serde_json::to_value(VideoWithPet {
+                video: stored_video(),
+                pet: None,
+                tags: vec![],
+                alerts,
+            })
+            .unwrap()
+        };
+        assert!(listed(None).get("alerts").is_none());
+        assert_eq!(listed(Some(vec![]))["alerts"], json!([]));
+    }
+
+    async fn get_as(db: &DatabaseConnection, user_id: i32, uri: &str) -> (u16, String) {
+        use tower::ServiceExt;
+
+        let routes = axum::Router::new()
+            .route("/videos/:id/stream", axum::routing::get(serve_video))
+            .route("/pets/:id/videos", axum::routing::get(list_pet_videos))
+            .layer(Extension(db.clone()))
+            .layer(Extension(no_redis()))
+            .layer(Extension(GcsClient::new(
+                google_cloud_storage::client::ClientConfig::default().anonymous(),
+            )))
+            .layer(Extension(user_id));
+        let request = axum::http::Request::get(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let (status, code) =
+            crate::test_support::error_code(routes.oneshot(request).await.unwrap()).await;
+        (status.as_u16(), code)
+    }
+
+    #[tokio::test]
+    async fn another_users_videos_are_forbidden() {
+        use sea_orm::{ActiveModelTrait, IntoActiveModel};
+
+        let db = crate::test_support::database().await;
+        crate::test_support::owner_with_pet(&db, 1, 1).await;
+        crate::test_support::owner_with_pet(&db, 2, 2).await;
+        let video = pet_video::Model {
+            id: uuid::Uuid::new_v4(),
+            ..stored_video()
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&db)
+        .await
+        .unwrap();
+        let forbidden = (403, String::from("not_your_pet"));
+
+        let stream = format!("/videos/{}/stream", video.id);
+        assert_eq!(get_as(&db, 2, &stream).await, forbidden);
+        assert_eq!(get_as(&db, 2, "/pets/1/videos").await, forbidden);
+        assert_eq!(get_as(&db, 2, "/pets/2/videos").await.0, 200);
+
+        let unknown = format!("/videos/{}/stream", uuid::Uuid::new_v4());
+        assert_eq!(
+            get_as(&db, 2, &unknown).await,
+            (404, String::from("video_not_found"))
+        );
+        assert_eq!(get_as(&db, 2, "/videos/not-a-uuid/stream").await.0, 400);
+    }
 }
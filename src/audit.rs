@@ -0,0 +1,39 @@
+//! Audit trail of sign-ins and destructive actions, stored in `audit_log`.
+
+use crate::entities::audit_log;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+
+/// What an audited action was applied to, e.g. `("pet", "42")`.
+pub type Target = (&'static str, String);
+
+/// Writes an audit entry in the background. Failures are only logged so the
+/// request that triggered it is never affected.
+pub fn record(
+    db: &DatabaseConnection,
+    user_id: i32,
+    action: &'static str,
+    target: Option<Target>,
+    ip: Option<String>,
+) {
+    let (target_type, target_id) = match target {
+        Some((kind, id)) => (Some(kind.to_string()), Some(id)),
+        None => (None, None),
+    };
+    let entry = audit_log::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        action: Set(action.to_string()),
+        target_type: Set(target_type),
+        target_id: Set(target_id),
+        ip: Set(ip),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    };
+
+    let db = db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = entry.insert(&db).await {
+            tracing::warn!(user_id, action, "Failed to write audit log entry: {}", e);
+        }
+    });
+}
@@ -0,0 +1,163 @@
+//! Per-pet behavior baseline: what a normal stretch looks like for this pet,
+//! computed from recent processed videos. It's handed to the analysis prompt
+//! so habitual behavior (a senior dog sleeping most of the day) isn't flagged
+//! as unusual.
+
+use crate::entities::{pet, pet_video, Pet, PetVideo};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    Set,
+};
+use serde::{Deserialize, Serialize};
+
+pub const BASELINE_WINDOW_DAYS: i64 = 14;
+/// Fewer videos than this isn't enough to call anything typical
+const MIN_VIDEOS: usize = 5;
+const TOP_ENTRIES: usize = 5;
+/// Digest updates trigger a refresh; this keeps it to a couple per day
+const REFRESH_INTERVAL_HOURS: i64 = 12;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Share {
+    pub name: String,
+    /// Fraction of observations, 0.0-1.0
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub computed_at: DateTime<Utc>,
+    pub window_days: i64,
+    pub video_count: usize,
+    pub unusual_rate: f64,
+    pub activities: Vec<Share>,
+    pub moods: Vec<Share>,
+}
+
+fn top_shares(counts: Vec<(String, usize)>) -> Vec<Share> {
+    let total: usize = counts.iter().map(|(_, c)| c).sum();
+    let mut counts = counts;
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+        .into_iter()
+        .take(TOP_ENTRIES)
+        .map(|(name, count)| Share {
+            name,
+            share: count as f64 / total.max(1) as f64,
+        })
+        .collect()
+}
+
+fn bump(counts: &mut Vec<(String, usize)>, name: &str) {
+    match counts.iter_mut().find(|(n, _)| n == name) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((name.to_string(), 1)),
+    }
+}
+
+/// Builds a baseline from processed videos, or None when there are too few.
+pub fn compute(species: &str, videos: &[pet_video::Model]) -> Option<Baseline> {
+    if videos.len() < MIN_VIDEOS {
+        return None;
+    }
+
+    let mut activities = Vec::new();
+    let mut moods = Vec::new();
+    for video in videos {
+        let entries = video
+            .activities
+            .as_ref()
+            .and_then(|a| a.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for entry in &entries {
+            if let Some(name) = crate::activity::canonical_of(entry, species) {
+                bump(&mut activities, &name);
+            }
+        }
+        if let Some(mood) = video.mood.as_deref().filter(|m| !m.trim().is_empty()) {
+            bump(&mut moods, &mood.trim().to_lowercase());
+        }
+    }
+
+    let unusual = videos.iter().filter(|v| v.is_unusual).count();
+    Some(Baseline {
+        computed_at: Utc::now(),
+        window_days: BASELINE_WINDOW_DAYS,
+        video_count: videos.len(),
+        unusual_rate: unusual as f64 / videos.len() as f64,
+        activities: top_shares(activities),
+        moods: top_shares(moods),
+    })
+}
+
+fn percentages(shares: &[Share]) -> String {
+    shares
+        .iter()
+        .map(|s| format!("{} ({:.0}%)", s.name, s.share * 100.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Baseline {
+    /// One-paragraph summary for the analysis prompt.
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!(
+            "Based on {} videos from the last {} days.",
+            self.video_count, self.window_days
+        )];
+        if !self.activities.is_empty() {
+            parts.push(format!(
+                "Typical activities: {}.",
+                percentages(&self.activities)
+            ));
+        }
+        if !self.moods.is_empty() {
+            parts.push(format!("Typical moods: {}.", percentages(&self.moods)));
+        }
+        parts.join(" ")
+    }
+}
+
+/// The stored baseline for a pet, if one has been computed.
+pub fn of_pet(pet: &pet::Model) -> Option<Baseline> {
+    pet.behavior_baseline
+        .clone()
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+/// Recomputes the pet's baseline from the last [`BASELINE_WINDOW_DAYS`] of
+/// processed videos unless it was refreshed recently.
+pub async fn refresh(db: &DatabaseConnection, pet_id: i32) -> Result<(), sea_orm::DbErr> {
+    let Some(pet) = Pet::find_by_id(pet_id).one(db).await? else {
+        return Ok(());
+    };
+    let now = Utc::now();
+    if of_pet(&pet).is_some_and(|b| now - b.computed_at < Duration::hours(REFRESH_INTERVAL_HOURS)) {
+        return Ok(());
+    }
+
+    let since = now - Duration::days(BASELINE_WINDOW_DAYS);
+    let videos = PetVideo::find()
+        .filter(pet_video::Column::PetId.eq(pet_id))
+        .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::Column::CreatedAt.gte(since))
+        .all(db)
+        .await?;
+
+    let Some(baseline) = compute(&pet.species, &videos) else {
+        return Ok(());
+    };
+    let mut active = pet.into_active_model();
+    active.behavior_baseline = Set(serde_json::to_value(&baseline).ok());
+    active.update(db).await?;
+
+    tracing::info!(
+        pet_id,
+        videos = baseline.video_count,
+        "Refreshed behavior baseline"
+    );
+    metrics::counter!("petpulse_behavior_baselines_refreshed_total").increment(1);
+    Ok(())
+}
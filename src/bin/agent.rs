@@ -3,6 +3,8 @@ use axum::{
     Json, Router,
 };
 use petpulse_server::agent::comfort_loop::{AlertPayload, ComfortLoop};
+use petpulse_server::agent::reminders::ReminderScheduler;
+use petpulse_server::notifications::scheduled::NotificationDispatcher;
 use sea_orm::Database;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -31,11 +33,24 @@ async fn main() {
         .await
         .expect("Failed to connect to database");
 
+    // Redis Connection
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let redis_client = redis::Client::open(redis_url).expect("Invalid Redis URL");
+
     // Create Channel for Task Queue
     let (tx, mut rx) = mpsc::channel::<AlertPayload>(100);
 
+    // Owner reminders for unacknowledged critical alerts
+    let reminder_scheduler = ReminderScheduler::new(db.clone()).await;
+    tokio::spawn(reminder_scheduler.run());
+
+    // Deliver deferred notifications persisted in scheduled_notifications
+    let dispatcher = NotificationDispatcher::new(db.clone()).await;
+    tokio::spawn(dispatcher.run());
+
     // Initialize Comfort Loop Logic (Shared)
-    let comfort_loop = Arc::new(ComfortLoop::new(db).await);
+    let comfort_loop = Arc::new(ComfortLoop::new(db, redis_client).await);
 
     // Spawn Dispatcher Task with Concurrency Limit
 
@@ -33,6 +33,9 @@ async fn main() {
         .unwrap();
     let gcs_client = google_cloud_storage::client::Client::new(gcs_config);
 
+    // Session signing keys; refuse to start without a secret
+    let session_keys = api::session::SessionKeys::from_env();
+
     // Run migrations
     use sea_orm_migration::MigratorTrait;
     migrator::Migrator::up(&db, None)
@@ -47,6 +50,7 @@ async fn main() {
         db,
         redis_client,
         gcs_client,
+        session_keys,
         prometheus_layer,
         metric_handle,
     );
@@ -54,7 +58,12 @@ async fn main() {
     let addr = SocketAddr::from(([0, 0, 0, 0], 8000));
     tracing::info!("listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
 async fn health_check() -> &'static str {
@@ -65,21 +74,55 @@ fn app(
     db: DatabaseConnection,
     redis_client: redis::Client,
     gcs_client: google_cloud_storage::client::Client,
+    session_keys: api::session::SessionKeys,
     prometheus_layer: axum_prometheus::PrometheusMetricLayer<'static>,
     metric_handle: metrics_exporter_prometheus::PrometheusHandle,
 ) -> Router {
     let auth_routes = Router::new()
         .route("/register", post(api::auth::register))
         .route("/login", post(api::auth::login))
-        .route("/webhook/alert", post(api::webhook::handle_alert));
+        .route("/auth/forgot-password", post(api::auth::forgot_password))
+        .route("/auth/reset-password", post(api::auth::reset_password))
+        .route("/webhook/alert", post(api::webhook::handle_alert))
+        // Token-authenticated vet share view; deliberately outside auth_middleware
+        .route("/shared/pets/:token", get(api::share::get_shared_pet));
+
+    // Google sign-in is only mounted when the client is configured
+    let oauth_routes = match api::auth::GoogleOAuthConfig::from_env() {
+        Some(config) => Router::new()
+            .route("/auth/google", get(api::auth::google_login))
+            .route("/auth/google/callback", get(api::auth::google_callback))
+            .layer(Extension(config)),
+        None => {
+            tracing::info!("Google OAuth not configured; /auth/google routes disabled");
+            Router::new()
+        }
+    };
 
     let protected_routes = Router::new()
+        .route("/logout", post(api::auth::logout))
+        .route("/users/sessions", get(api::auth::list_sessions))
+        .route(
+            "/users/sessions/:id",
+            axum::routing::delete(api::auth::revoke_session),
+        )
+        .route(
+            "/users/api-keys",
+            get(api::api_keys::list_api_keys).post(api::api_keys::create_api_key),
+        )
+        .route(
+            "/users/api-keys/:id",
+            axum::routing::delete(api::api_keys::revoke_api_key),
+        )
         .route(
             "/users",
             get(api::user::get_user)
                 .patch(api::user::update_user)
                 .delete(api::user::delete_user),
         )
+        .route("/users/summary", get(api::user::get_user_summary))
+        .route("/users/quota", get(api::user::get_user_quota))
+        .route("/users/audit-log", get(api::user::list_audit_log))
         .route(
             "/pets",
             get(api::pet::list_user_pets).post(api::pet::create_pet),
@@ -90,16 +133,90 @@ fn app(
                 .patch(api::pet::update_pet)
                 .delete(api::pet::delete_pet),
         )
-        .route("/videos", get(api::video::list_user_videos))
-        .route("/pets/:id/videos", get(api::video::list_pet_videos))
+        .route("/pets/:id/restore", post(api::pet::restore_pet))
+        .route(
+            "/pets/:id/photo",
+            get(api::pet::get_pet_photo).post(api::pet::upload_pet_photo),
+        )
+        .route(
+            "/pets/:id/known-behaviors",
+            axum::routing::put(api::pet::update_known_behaviors),
+        )
+        .route(
+            "/pets/:id/monitoring-schedule",
+            axum::routing::put(api::pet::set_monitoring_schedule)
+                .delete(api::pet::clear_monitoring_schedule),
+        )
+        .route("/pets/:id/usage", get(api::usage::get_pet_usage))
+        .route("/pets/:id/events", get(api::events::stream_pet_events))
+        .route("/pets/:id/share", post(api::share::create_share))
+        .route("/pets/:id/shares", get(api::share::list_shares))
+        .route(
+            "/pets/:id/share/:share_id",
+            axum::routing::delete(api::share::revoke_share),
+        )
+        .route(
+            "/pets/:id/caretakers",
+            get(api::caretakers::list_caretakers).post(api::caretakers::invite_caretaker),
+        )
+        .route(
+            "/pets/:id/caretakers/:user_id",
+            axum::routing::delete(api::caretakers::remove_caretaker),
+        )
+        .route(
+            "/caretaker-invites",
+            get(api::caretakers::list_caretaker_invites),
+        )
+        .route(
+            "/caretaker-invites/:id/accept",
+            post(api::caretakers::accept_caretaker_invite),
+        )
+        .route(
+            "/pets/:id/alert-settings",
+            get(api::alert_mutes::get_alert_settings),
+        )
+        .route(
+            "/pets/:id/alert-settings/:type",
+            axum::routing::put(api::alert_settings::update_alert_setting)
+                .delete(api::alert_settings::reset_alert_setting),
+        )
+        .route(
+            "/pets/:id/alert-types/:type/mute",
+            post(api::alert_mutes::mute_alert_type).delete(api::alert_mutes::unmute_alert_type),
+        )
+        .route(
+            "/users/notification-preferences",
+            get(api::notification_preferences::list_preferences),
+        )
+        .route(
+            "/users/notification-preferences/:channel",
+            axum::routing::put(api::notification_preferences::update_preference)
+                .delete(api::notification_preferences::reset_preference),
+        )
+        .route(
+            "/users/notifications/scheduled",
+            get(api::user::list_scheduled_notifications),
+        )
+        .route(
+            "/users/usage/monthly",
+            get(api::usage::get_user_monthly_usage),
+        )
+        .route("/dashboard", get(api::dashboard::get_dashboard))
+        .route("/catalog/activities", get(api::catalog::list_activities))
         .route("/videos/:id/stream", get(api::video::serve_video))
+        .route("/videos/:id/download", get(api::video::download_video))
+        .route("/videos/:id/clips", get(api::video::get_video_clips))
         .route(
-            "/pets/:id/upload_video",
-            post(api::daily_digest::upload_video),
+            "/videos/:id/tags",
+            post(api::video::add_video_tag).delete(api::video::remove_video_tag),
         )
+        .route("/videos/:id/timeline", get(api::video::get_video_timeline))
+        .route("/videos/:id/url", get(api::video::get_video_url))
+        .route("/videos/:id/status", get(api::video::get_video_status))
+        .route("/videos/:id/reprocess", post(api::video::reprocess_video))
         .route(
-            "/internal/generate_daily_digest",
-            post(api::daily_digest::generate_daily_digest),
+            "/videos/:id/thumbnail",
+            get(api::video::get_video_thumbnail),
         )
         // Alert routes - protected
         .route("/alerts", get(api::critical_alerts::list_user_alerts))
@@ -108,6 +225,10 @@ fn app(
             "/pets/:id/alerts",
             get(api::critical_alerts::list_pet_alerts),
         )
+        .route(
+            "/pets/:id/alerts/heatmap",
+            get(api::critical_alerts::get_pet_alert_heatmap),
+        )
         .route(
             "/alerts/:id/acknowledge",
             post(api::critical_alerts::acknowledge_alert),
@@ -116,6 +237,14 @@ fn app(
             "/alerts/:id/resolve",
             post(api::critical_alerts::resolve_alert),
         )
+        .route(
+            "/alerts/:id/timeline",
+            get(api::critical_alerts::get_alert_timeline),
+        )
+        .route(
+            "/alerts/:id/mark-expected",
+            post(api::critical_alerts::mark_alert_expected),
+        )
         // Emergency Contacts routes - protected
         .route(
             "/emergency-contacts",
@@ -129,7 +258,7 @@ fn app(
         )
         // Quick Actions routes - protected
         .route(
-            "/alerts/:alert_id/quick-actions",
+            "/alerts/:id/quick-actions",
             post(api::quick_actions::create_quick_action)
                 .get(api::quick_actions::list_alert_quick_actions),
         )
@@ -138,12 +267,95 @@ fn app(
             "/pets/:id/digests",
             get(api::daily_digest::list_pet_digests),
         )
+        .route(
+            "/pets/:id/mood-trend",
+            get(api::daily_digest::get_mood_trend),
+        )
+        .route_layer(axum::middleware::from_fn(api::middleware::auth_middleware));
+
+    // Operator views and actions across every user. The admin check runs
+    // after auth has put the user id on the request.
+    let admin_routes = Router::new()
+        .route("/admin/alerts", get(api::admin::list_alerts))
+        .route("/admin/videos", get(api::admin::list_videos))
+        .route("/admin/users", get(api::admin::list_users))
+        .route("/admin/dlq", get(api::admin::list_dead_letters))
+        .route(
+            "/admin/dlq/:video_id/requeue",
+            post(api::admin::requeue_dead_letter),
+        )
+        .route(
+            "/admin/poison/:queue",
+            get(api::admin::list_poison_messages).delete(api::admin::purge_poison_messages),
+        )
+        .route(
+            "/internal/generate_daily_digest",
+            post(api::daily_digest::generate_daily_digest),
+        )
+        .route(
+            "/internal/storage/reconcile",
+            post(api::admin::start_storage_reconcile),
+        )
+        .route(
+            "/internal/storage/reconcile/runs",
+            get(api::admin::list_storage_reconcile_runs),
+        )
+        .route(
+            "/internal/usage/monthly",
+            get(api::admin::get_usage_overview),
+        )
+        .route_layer(axum::middleware::from_fn(api::middleware::admin_middleware))
         .route_layer(axum::middleware::from_fn(api::middleware::auth_middleware));
 
+    // Routes camera devices call. These accept either a session cookie or an
+    // `Authorization: Bearer pp_...` API key from POST /users/api-keys.
+    let device_routes = Router::new()
+        .route(
+            "/pets/:id/upload_video",
+            post(api::daily_digest::upload_video),
+        )
+        .route(
+            "/pets/:id/upload_videos",
+            post(api::daily_digest::upload_videos),
+        )
+        .route(
+            "/pets/:id/uploads",
+            post(api::upload_session::create_upload_session),
+        )
+        .route(
+            "/pets/:id/upload_url",
+            post(api::direct_upload::create_upload_url),
+        )
+        .route(
+            "/pets/:id/uploads/confirm",
+            post(api::direct_upload::confirm_upload),
+        )
+        .route(
+            "/uploads/:session_id/chunks/:n",
+            axum::routing::put(api::upload_session::put_upload_chunk),
+        )
+        .route(
+            "/uploads/:session_id/complete",
+            post(api::upload_session::complete_upload_session),
+        )
+        .route(
+            "/uploads/:session_id",
+            axum::routing::delete(api::upload_session::abort_upload_session),
+        )
+        .route("/videos", get(api::video::list_user_videos))
+        .route("/videos/search", get(api::video::search_videos))
+        .route("/pets/:id/videos", get(api::video::list_pet_videos))
+        .route_layer(axum::middleware::from_fn(
+            api::middleware::device_auth_middleware,
+        ));
+
     Router::new()
         .route("/health", get(health_check))
         .merge(auth_routes)
+        .merge(oauth_routes)
         .merge(protected_routes)
+        .merge(admin_routes)
+        .merge(device_routes)
         // Critical Alert Routes (public for Grafana dashboard)
         .route(
             "/api/alerts/critical",
@@ -152,6 +364,8 @@ fn app(
         .layer(Extension(db))
         .layer(Extension(redis_client))
         .layer(Extension(gcs_client))
+        .layer(Extension(session_keys))
+        .layer(axum::middleware::from_fn(api::i18n::locale_middleware))
         .layer(tower_cookies::CookieManagerLayer::new())
         .layer(prometheus_layer)
         .layer(
@@ -238,12 +452,21 @@ fn app(
                 .allow_methods([
                     axum::http::Method::GET,
                     axum::http::Method::POST,
+                    axum::http::Method::PUT,
                     axum::http::Method::PATCH,
                     axum::http::Method::DELETE,
                 ])
-                .allow_headers([axum::http::header::CONTENT_TYPE])
+                .allow_headers([
+                    axum::http::header::CONTENT_TYPE,
+                    axum::http::HeaderName::from_static(api::share::PASSCODE_HEADER),
+                ])
                 .allow_credentials(true),
         )
         .route("/metrics", get(|| async move { metric_handle.render() }))
-        .layer(axum::extract::DefaultBodyLimit::max(100 * 1024 * 1024))
+        .layer(axum::middleware::map_response(
+            api::middleware::json_payload_too_large,
+        ))
+        .layer(axum::extract::DefaultBodyLimit::max(
+            api::daily_digest::max_request_body_bytes(),
+        ))
 }
@@ -1,5 +1,80 @@
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
 use petpulse_server::worker;
-use sea_orm::Database;
+use sea_orm::{Database, DatabaseConnection};
+use tokio_util::sync::CancellationToken;
+
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
+/// Resolves on Ctrl+C or, on unix, SIGTERM (what the orchestrator sends).
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("Unable to listen for shutdown signal: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                tracing::error!("Unable to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// 200 while every worker loop has beaten recently and Redis and Postgres
+/// answer; otherwise 503 listing what failed, so the orchestrator restarts
+/// a wedged worker.
+async fn health(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(redis_client): Extension<redis::Client>,
+    Extension(heartbeats): Extension<worker::Heartbeats>,
+) -> Response {
+    let stale = heartbeats.stale(worker::heartbeat_stale_secs());
+    let redis_ok = match redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .is_ok(),
+        Err(_) => false,
+    };
+    let db_ok = db.ping().await.is_ok();
+
+    let healthy = stale.is_empty() && redis_ok && db_ok;
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(serde_json::json!({
+            "status": if healthy { "ok" } else { "unhealthy" },
+            "stale_workers": stale,
+            "redis": redis_ok,
+            "database": db_ok,
+        })),
+    )
+        .into_response()
+}
 
 #[tokio::main]
 async fn main() {
@@ -8,22 +83,6 @@ async fn main() {
 
     petpulse_server::telemetry::init_telemetry("petpulse-worker");
 
-    let (prometheus_layer, metric_handle) = axum_prometheus::PrometheusMetricLayer::pair();
-
-    // Spawn metrics server
-    tokio::spawn(async move {
-        let app = axum::Router::new()
-            .route(
-                "/metrics",
-                axum::routing::get(|| async move { metric_handle.render() }),
-            )
-            .layer(prometheus_layer);
-        let addr = std::net::SocketAddr::from(([0, 0, 0, 0], 9091));
-        tracing::info!("Metrics server listening on {}", addr);
-        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-        axum::serve(listener, app).await.unwrap();
-    });
-
     // Database Connection
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let db = Database::connect(&database_url)
@@ -35,6 +94,27 @@ async fn main() {
         std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
     let redis_client = redis::Client::open(redis_url).expect("Invalid Redis URL");
 
+    let (prometheus_layer, metric_handle) = axum_prometheus::PrometheusMetricLayer::pair();
+    let heartbeats = worker::Heartbeats::default();
+
+    // Spawn metrics and health server
+    let app = axum::Router::new()
+        .route(
+            "/metrics",
+            axum::routing::get(|| async move { metric_handle.render() }),
+        )
+        .route("/health", axum::routing::get(health))
+        .layer(Extension(db.clone()))
+        .layer(Extension(redis_client.clone()))
+        .layer(Extension(heartbeats.clone()))
+        .layer(prometheus_layer);
+    tokio::spawn(async move {
+        let addr = std::net::SocketAddr::from(([0, 0, 0, 0], 9091));
+        tracing::info!("Metrics server listening on {}", addr);
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        axum::serve(listener, app).await.unwrap();
+    });
+
     // GCS Client
     let gcs_config = google_cloud_storage::client::ClientConfig::default()
         .with_auth()
@@ -44,15 +124,63 @@ async fn main() {
 
     tracing::info!("Starting background worker...");
 
-    // Start Video Workers (3 concurrent)
-    worker::start_workers(redis_client.clone(), db.clone(), 3, gcs_client).await;
+    // Optional periodic orphaned-object cleanup
+    petpulse_server::storage_cleanup::start_reconcile_scheduler(db.clone(), gcs_client.clone())
+        .await;
 
-    // Start Digest Workers (3 concurrent, stateless)
-    worker::start_digest_workers(redis_client.clone(), db.clone(), 3).await;
+    // Expire video files past their owner's retention period
+    petpulse_server::retention::start_retention_scheduler(db.clone(), gcs_client.clone()).await;
 
-    // Keep the main process alive
-    match tokio::signal::ctrl_c().await {
-        Ok(()) => tracing::info!("Shutting down worker process"),
-        Err(err) => tracing::error!("Unable to listen for shutdown signal: {}", err),
-    }
+    // Missed-upload alerts for pets with a monitoring schedule
+    worker::start_upload_watch(db.clone()).await;
+
+    // Failed analyses wait out their backoff in video_retry
+    worker::start_retry_scheduler(redis_client.clone()).await;
+
+    // End-of-day digests in each owner's timezone
+    worker::start_nightly_digest_scheduler(redis_client.clone(), db.clone()).await;
+
+    // Alerts the agent couldn't take wait in alert_outbox
+    petpulse_server::alert_outbox::start_drain(redis_client.clone()).await;
+
+    let shutdown = CancellationToken::new();
+
+    // Start Video Workers (WORKER_CONCURRENCY, default 3)
+    let video_workers = worker::start_workers(
+        redis_client.clone(),
+        db.clone(),
+        worker::Concurrency::video_workers(),
+        gcs_client,
+        heartbeats.clone(),
+        shutdown.clone(),
+    )
+    .await;
+
+    // Start Digest Workers (DIGEST_WORKER_CONCURRENCY, default 3; stateless)
+    let digest_workers = worker::start_digest_workers(
+        redis_client.clone(),
+        db.clone(),
+        worker::Concurrency::from_env("DIGEST_WORKER", 3),
+        heartbeats,
+        shutdown.clone(),
+    )
+    .await;
+
+    shutdown_signal().await;
+    tracing::info!("Shutting down worker process; draining in-flight jobs");
+    shutdown.cancel();
+
+    // Jobs still running after this are requeued for the next process
+    let timeout = std::env::var("WORKER_SHUTDOWN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT_SECS);
+    let timeout = tokio::time::Duration::from_secs(timeout);
+    tokio::join!(
+        video_workers.drain(&redis_client, timeout),
+        digest_workers.drain(&redis_client, timeout),
+    );
+
+    let _ = tokio::task::spawn_blocking(petpulse_server::telemetry::shutdown_telemetry).await;
+    tracing::info!("Worker process stopped");
 }
@@ -0,0 +1,109 @@
+//! Dead-letter list for video jobs that failed for good. The worker parks the
+//! job payload and its last error on `video_dlq` instead of dropping them, so
+//! an operator can look at what failed and replay it once the cause is fixed.
+
+use crate::jobs::{VideoJob, VIDEO_JOB_VERSION};
+use crate::video_queue::Priority;
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+pub const VIDEO_DLQ: &str = "video_dlq";
+
+/// Payload field carrying how many times a job has come back out of the DLQ,
+/// so a replay that fails again keeps its count.
+pub const REPLAYS_FIELD: &str = "dlq_replays";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub video_id: Uuid,
+    /// The `video_queue` job as the worker last saw it
+    pub payload: Value,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+    /// Times this job was requeued from the DLQ before failing again
+    pub replay_count: u64,
+}
+
+impl DeadLetter {
+    pub fn new(video_id: Uuid, payload: &Value, error: impl Into<String>) -> Self {
+        Self {
+            video_id,
+            payload: payload.clone(),
+            error: error.into(),
+            failed_at: Utc::now(),
+            replay_count: payload[REPLAYS_FIELD].as_u64().unwrap_or(0),
+        }
+    }
+
+    /// The job to push back onto `video_queue`, with its replay counted and
+    /// stamped as queued now. A payload the worker rejected is replaced by a
+    /// fresh job for the same video.
+    pub fn replay_payload(&self) -> String {
+        let job = serde_json::from_value::<VideoJob>(self.payload.clone())
+            .ok()
+            .filter(|job| job.version == VIDEO_JOB_VERSION && job.video_id == self.video_id)
+            .unwrap_or_else(|| VideoJob::new(self.video_id, Priority::Normal));
+        VideoJob {
+            dlq_replays: self.replay_count + 1,
+            ..job.requeued()
+        }
+        .encode()
+    }
+}
+
+pub async fn push(conn: &mut MultiplexedConnection, entry: &DeadLetter) -> redis::RedisResult<()> {
+    let raw = serde_json::to_string(entry).map_err(|e| {
+        redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "unserializable dead letter",
+            e.to_string(),
+        ))
+    })?;
+    conn.rpush(VIDEO_DLQ, raw).await
+}
+
+pub async fn len(conn: &mut MultiplexedConnection) -> redis::RedisResult<u64> {
+    conn.llen(VIDEO_DLQ).await
+}
+
+/// Entries `offset..offset + limit`, oldest first. Unreadable entries are skipped.
+pub async fn list(
+    conn: &mut MultiplexedConnection,
+    offset: u64,
+    limit: u64,
+) -> redis::RedisResult<Vec<DeadLetter>> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+    let raw: Vec<String> = conn
+        .lrange(VIDEO_DLQ, offset as isize, (offset + limit - 1) as isize)
+        .await?;
+    Ok(raw
+        .iter()
+        .filter_map(|r| serde_json::from_str(r).ok())
+        .collect())
+}
+
+/// Removes and returns the newest entry for `video_id`, if any.
+pub async fn take(
+    conn: &mut MultiplexedConnection,
+    video_id: Uuid,
+) -> redis::RedisResult<Option<DeadLetter>> {
+    let raw: Vec<String> = conn.lrange(VIDEO_DLQ, 0, -1).await?;
+    for raw in raw.into_iter().rev() {
+        let Ok(entry) = serde_json::from_str::<DeadLetter>(&raw) else {
+            continue;
+        };
+        if entry.video_id != video_id {
+            continue;
+        }
+        // Another requeue may have won the race; only report what we removed
+        let removed: i64 = conn.lrem(VIDEO_DLQ, 1, raw).await?;
+        return Ok((removed > 0).then_some(entry));
+    }
+    Ok(None)
+}
@@ -0,0 +1,66 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Condition;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "alert_mutes")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub pet_id: i32,
+    /// AlertType in snake_case, e.g. "vocalization"
+    pub alert_type: String,
+    pub muted_by: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub note: Option<String>,
+    pub muted_until: DateTime,
+    /// Set when the mute is lifted before it expires
+    pub unmuted_at: Option<DateTime>,
+    pub unmuted_by: Option<i32>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Pet,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Mutes that haven't been lifted and haven't expired yet.
+pub fn active_condition() -> Condition {
+    Condition::all()
+        .add(Column::UnmutedAt.is_null())
+        .add(Column::MutedUntil.gt(chrono::Utc::now().naive_utc()))
+}
+
+/// Whether alerts of this type are currently muted for the pet. Lookup
+/// failures count as not muted so alerts are never lost to a DB blip.
+pub async fn is_muted(db: &DatabaseConnection, pet_id: i32, alert_type: &str) -> bool {
+    match Entity::find()
+        .filter(Column::PetId.eq(pet_id))
+        .filter(Column::AlertType.eq(alert_type))
+        .filter(active_condition())
+        .one(db)
+        .await
+    {
+        Ok(mute) => mute.is_some(),
+        Err(e) => {
+            tracing::error!("Failed to check alert mutes for pet {}: {}", pet_id, e);
+            false
+        }
+    }
+}
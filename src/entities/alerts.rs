@@ -26,6 +26,14 @@ pub struct Model {
     pub user_response: Option<String>,
     pub notification_sent: bool,
     pub notification_channels: Option<Json>,
+    // Deduplication of repeating (pet, type) alerts
+    pub occurrence_count: i32,
+    pub first_seen_at: Option<DateTime>,
+    pub last_seen_at: Option<DateTime>,
+    pub video_id: Option<Uuid>,
+    // Owner reminders for unacknowledged critical alerts
+    pub reminder_count: i32,
+    pub last_reminder_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One activity segment of an analyzed video.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "clips")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub video_id: Uuid,
+    pub start_time: String,
+    pub end_time: String,
+    /// Activity name exactly as Gemini returned it
+    pub activity: String,
+    /// Taxonomy name from `crate::activity::normalize`
+    pub canonical_activity: Option<String>,
+    pub mood: String,
+    #[sea_orm(column_type = "Text")]
+    pub description: String,
+    pub created_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet_video::Entity",
+        from = "Column::VideoId",
+        to = "super::pet_video::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    PetVideo,
+}
+
+impl Related<super::pet_video::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetVideo.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
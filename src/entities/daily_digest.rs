@@ -19,6 +19,8 @@ pub struct Model {
     pub activities: Option<serde_json::Value>,
     #[sea_orm(column_type = "JsonBinary", nullable)]
     pub unusual_events: Option<serde_json::Value>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub expected_conditions: Option<serde_json::Value>,
     pub total_videos: i32,
 
     pub created_at: DateTimeWithTimeZone,
@@ -1,17 +1,47 @@
+pub mod alert_mute;
 pub mod alerts;
+pub mod api_key;
+pub mod audit_log;
+pub mod clip;
 pub mod daily_digest;
 pub mod emergency_contact;
+pub mod notification_log;
+pub mod notification_preference;
+pub mod password_reset_token;
 pub mod pet;
+pub mod pet_alert_setting;
+pub mod pet_caretaker;
+pub mod pet_share;
+pub mod pet_share_access;
 pub mod pet_video;
+pub mod pet_weight;
 pub mod quick_action;
+pub mod scheduled_notification;
+pub mod storage_reconcile_run;
 pub mod user;
+pub mod video_tag;
 
+pub use alert_mute::Entity as AlertMute;
 pub use alerts::Entity as Alerts;
+pub use api_key::Entity as ApiKey;
+pub use audit_log::Entity as AuditLog;
+pub use clip::Entity as Clip;
 pub use daily_digest::Entity as DailyDigest;
 pub use emergency_contact::Entity as EmergencyContact;
+pub use notification_log::Entity as NotificationLog;
+pub use notification_preference::Entity as NotificationPreference;
+pub use password_reset_token::Entity as PasswordResetToken;
 pub use pet::Entity as Pet;
+pub use pet_alert_setting::Entity as PetAlertSetting;
+pub use pet_caretaker::Entity as PetCaretaker;
+pub use pet_share::Entity as PetShare;
+pub use pet_share_access::Entity as PetShareAccess;
 pub use pet_video::Entity as PetVideo;
+pub use pet_weight::Entity as PetWeight;
 pub use quick_action::Entity as QuickAction;
+pub use scheduled_notification::Entity as ScheduledNotification;
+pub use storage_reconcile_run::Entity as StorageReconcileRun;
 pub use user::Entity as User;
+pub use video_tag::Entity as VideoTag;
 
 pub mod prelude;
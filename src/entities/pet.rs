@@ -1,6 +1,58 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Species a pet can have. Stored in `pets.species` as the lowercase name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Species {
+    Dog,
+    Cat,
+    Bird,
+    Rabbit,
+    Other,
+}
+
+impl Species {
+    /// Accepted values, as listed in validation errors
+    pub const NAMES: &'static [&'static str] = &["dog", "cat", "bird", "rabbit", "other"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Species::Dog => "dog",
+            Species::Cat => "cat",
+            Species::Bird => "bird",
+            Species::Rabbit => "rabbit",
+            Species::Other => "other",
+        }
+    }
+
+    /// Case-insensitive; also accepts the plurals and common synonyms older
+    /// free-text rows used (e.g. "Puppy", "kitten").
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "dog" | "dogs" | "puppy" | "canine" => Some(Species::Dog),
+            "cat" | "cats" | "kitten" | "feline" => Some(Species::Cat),
+            "bird" | "birds" | "parrot" | "budgie" => Some(Species::Bird),
+            "rabbit" | "rabbits" | "bunny" => Some(Species::Rabbit),
+            "other" => Some(Species::Other),
+            _ => None,
+        }
+    }
+
+    /// Species of a stored `pets.species` value; anything unrecognized is `Other`.
+    pub fn of(stored: &str) -> Self {
+        Self::parse(stored).unwrap_or(Species::Other)
+    }
+}
+
+impl<'de> Deserialize<'de> for Species {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Species::parse(&value)
+            .ok_or_else(|| serde::de::Error::unknown_variant(&value, Species::NAMES))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "pets")]
 pub struct Model {
@@ -9,12 +61,39 @@ pub struct Model {
     pub user_id: i32,
     pub name: String,
     pub age: i32,
+    /// Lowercase [`Species`] name
     pub species: String,
     pub breed: String,
     #[sea_orm(column_type = "Text")]
     pub bio: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    /// Behaviors the owner has marked as expected for this pet (e.g. chronic limping)
+    #[sea_orm(column_type = "JsonBinary")]
+    pub known_behaviors: Json,
+    /// Always send clips to analysis, even when they look static (e.g. post-surgery monitoring)
+    pub static_check_disabled: bool,
+    /// `gs://` path of the profile photo, served through GET /pets/:id/photo
+    #[serde(skip_serializing)]
+    pub photo_path: Option<String>,
+    pub weight_kg: Option<f64>,
+    /// Diagnosed conditions (e.g. arthritis), passed to video analysis as context
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub medical_conditions: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub medications: Option<Json>,
+    pub vet_name: Option<String>,
+    pub vet_phone: Option<String>,
+    /// Set when the owner archives the pet; archived pets are hidden and
+    /// don't alert, but keep their history until hard-deleted
+    pub archived_at: Option<DateTime>,
+    /// `crate::baseline::Baseline` computed from recent videos
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub behavior_baseline: Option<Json>,
+    /// `crate::monitoring::MonitoringSchedule`; pets without one are never
+    /// checked for missed uploads
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub monitoring_schedule: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -36,3 +115,184 @@ impl Related<super::user::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// Pets that haven't been archived. Use this instead of `find()` anywhere
+    /// archived pets shouldn't show up.
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::ArchivedAt.is_null())
+    }
+}
+
+/// Lowercased with runs of whitespace collapsed, so "Limping " and
+/// "limping" are the same behavior.
+fn normalize_behavior(behavior: &str) -> String {
+    behavior
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn string_list(value: Option<&Json>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Model {
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    pub fn species(&self) -> Species {
+        Species::of(&self.species)
+    }
+
+    pub fn known_behavior_list(&self) -> Vec<String> {
+        string_list(Some(&self.known_behaviors))
+    }
+
+    /// Health background for the analysis prompt, or None when the owner
+    /// hasn't filled in any of it.
+    pub fn health_context(&self) -> Option<String> {
+        let mut lines = vec![format!("Species: {}", self.species)];
+        if !self.breed.trim().is_empty() {
+            lines.push(format!("Breed: {}", self.breed));
+        }
+        lines.push(format!("Age: {} years", self.age));
+        let mut has_health_info = false;
+        if let Some(weight) = self.weight_kg {
+            lines.push(format!("Weight: {} kg", weight));
+            has_health_info = true;
+        }
+        let conditions = string_list(self.medical_conditions.as_ref());
+        if !conditions.is_empty() {
+            lines.push(format!(
+                "Known medical conditions: {}",
+                conditions.join(", ")
+            ));
+            has_health_info = true;
+        }
+        let medications = string_list(self.medications.as_ref());
+        if !medications.is_empty() {
+            lines.push(format!("Current medications: {}", medications.join(", ")));
+            has_health_info = true;
+        }
+        has_health_info.then(|| lines.join("\n"))
+    }
+
+    /// True only when every indicator is one of the known behaviors. Matching
+    /// is on the whole normalized behavior, not a substring, so a known
+    /// "eating" doesn't cover "not eating". An empty indicator list is never
+    /// considered covered.
+    pub fn known_behaviors_cover(&self, indicators: &[String]) -> bool {
+        let known: Vec<String> = self
+            .known_behavior_list()
+            .iter()
+            .map(|b| normalize_behavior(b))
+            .filter(|b| !b.is_empty())
+            .collect();
+
+        !indicators.is_empty()
+            && indicators.iter().all(|indicator| {
+                let indicator = normalize_behavior(indicator);
+                known.contains(&indicator)
+            })
+    }
+}
+
+/// Merges new behaviors into an existing list, dropping blanks and
+/// case-insensitive duplicates while preserving the original order.
+pub fn merge_known_behaviors(existing: Vec<String>, additions: Vec<String>) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for behavior in existing.into_iter().chain(additions) {
+        let trimmed = behavior.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let normalized = normalize_behavior(trimmed);
+        if !merged.iter().any(|b| normalize_behavior(b) == normalized) {
+            merged.push(trimmed.to_string());
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pet_with_known(known: &[&str]) -> Model {
+        let now = chrono::Utc::now().naive_utc();
+        Model {
+            id: 1,
+            user_id: 1,
+            name: "Rex".to_string(),
+            age: 12,
+            species: "dog".to_string(),
+            breed: String::new(),
+            bio: String::new(),
+            created_at: now,
+            updated_at: now,
+            known_behaviors: serde_json::json!(known),
+            static_check_disabled: false,
+            photo_path: None,
+            weight_kg: None,
+            medical_conditions: None,
+            medications: None,
+            vet_name: None,
+            vet_phone: None,
+            archived_at: None,
+            behavior_baseline: None,
+            monitoring_schedule: None,
+        }
+    }
+
+    fn indicators(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| String::from(*s)).collect()
+    }
+
+    #[test]
+    fn all_known_indicators_are_covered() {
+        let pet = pet_with_known(&["Limping", "excessive licking"]);
+        assert!(pet.known_behaviors_cover(&indicators(&["limping", "  Excessive   Licking "])));
+    }
+
+    #[test]
+    fn one_known_and_one_new_indicator_still_alerts() {
+        let pet = pet_with_known(&["limping"]);
+        assert!(!pet.known_behaviors_cover(&indicators(&["limping", "vomiting"])));
+    }
+
+    #[test]
+    fn negated_behavior_is_not_covered() {
+        let pet = pet_with_known(&["eating"]);
+        assert!(!pet.known_behaviors_cover(&indicators(&["not eating"])));
+    }
+
+    #[test]
+    fn empty_indicators_are_never_covered() {
+        let pet = pet_with_known(&["limping"]);
+        assert!(!pet.known_behaviors_cover(&[]));
+        assert!(!pet_with_known(&[]).known_behaviors_cover(&indicators(&["limping"])));
+    }
+
+    #[test]
+    fn merge_drops_blanks_and_case_duplicates() {
+        let merged = merge_known_behaviors(
+            vec!["Limping".to_string()],
+            vec![
+                "limping ".to_string(),
+                " ".to_string(),
+                "Snoring".to_string(),
+            ],
+        );
+        assert_eq!(merged, vec!["Limping".to_string(), "Snoring".to_string()]);
+    }
+}
@@ -0,0 +1,75 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Occurrences within the hour at which a repeated alert escalates to high
+/// when a pet has no setting for its type.
+pub const DEFAULT_ESCALATION_THRESHOLD: i32 = 5;
+
+/// Per-pet tuning for one alert type. Types without a row are enabled for
+/// every severity and escalate at [`DEFAULT_ESCALATION_THRESHOLD`].
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "pet_alert_settings")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub pet_id: i32,
+    /// AlertType in snake_case, e.g. "vocalization"
+    pub alert_type: String,
+    /// Disabled types are still recorded but never trigger an intervention
+    /// or a notification (critical alerts excepted)
+    pub enabled: bool,
+    /// Lowest severity level that triggers an intervention
+    pub min_severity: String,
+    pub escalation_threshold: i32,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Pet,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Whether an alert of `severity` should be acted on.
+    pub fn allows(&self, severity: &str) -> bool {
+        use crate::notifications::preferences::severity_rank;
+        match (severity_rank(severity), severity_rank(&self.min_severity)) {
+            (Some(severity), Some(min)) => self.enabled && severity >= min,
+            // An unrecognized minimum only honors the on/off switch
+            _ => self.enabled,
+        }
+    }
+}
+
+/// The pet's setting for an alert type, if it has one. Lookup errors are
+/// logged and treated as no setting, so defaults apply.
+pub async fn find_for(db: &DatabaseConnection, pet_id: i32, alert_type: &str) -> Option<Model> {
+    match Entity::find()
+        .filter(Column::PetId.eq(pet_id))
+        .filter(Column::AlertType.eq(alert_type))
+        .one(db)
+        .await
+    {
+        Ok(setting) => setting,
+        Err(e) => {
+            tracing::error!("Failed to load alert settings for pet {}: {}", pet_id, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The only role for now: read access to the pet's videos, alerts and
+/// digests, plus alert notifications. Managing the pet stays with the owner.
+pub const ROLE_CARETAKER: &str = "caretaker";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "pet_caretakers")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub pet_id: i32,
+    pub user_id: i32,
+    pub role: String,
+    pub invited_by: i32,
+    /// Null while the invite is pending
+    pub accepted_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Pet,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pet.def()
+    }
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn is_accepted(&self) -> bool {
+        self.accepted_at.is_some()
+    }
+}
+
+/// Whether the user has accepted an invite to care for the pet.
+pub async fn is_caretaker(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    user_id: i32,
+) -> Result<bool, DbErr> {
+    let count = Entity::find()
+        .filter(Column::PetId.eq(pet_id))
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::AcceptedAt.is_not_null())
+        .count(db)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Pets the user has accepted an invite to care for.
+pub async fn caretaker_pet_ids(db: &DatabaseConnection, user_id: i32) -> Result<Vec<i32>, DbErr> {
+    Ok(Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::AcceptedAt.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|c| c.pet_id)
+        .collect())
+}
+
+/// Accepted caretakers of the pet, excluding the owner.
+pub async fn accepted_users(
+    db: &DatabaseConnection,
+    pet_id: i32,
+) -> Result<Vec<super::user::Model>, DbErr> {
+    Ok(Entity::find()
+        .filter(Column::PetId.eq(pet_id))
+        .filter(Column::AcceptedAt.is_not_null())
+        .find_also_related(super::user::Entity)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(_, user)| user)
+        .collect())
+}
@@ -0,0 +1,63 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "pet_shares")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub pet_id: i32,
+    pub created_by: i32,
+    /// Argon2 hash of the secret half of the share token
+    #[serde(skip_serializing)]
+    pub secret_hash: String,
+    #[serde(skip_serializing)]
+    pub passcode_hash: Option<String>,
+    /// Section names visible through the link, e.g. ["digests", "alerts"]
+    pub sections: Json,
+    pub expires_at: DateTime,
+    pub revoked_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Pet,
+    #[sea_orm(has_many = "super::pet_share_access::Entity")]
+    Access,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pet.def()
+    }
+}
+
+impl Related<super::pet_share_access::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Access.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn section_list(&self) -> Vec<String> {
+        serde_json::from_value(self.sections.clone()).unwrap_or_default()
+    }
+
+    pub fn includes(&self, section: &str) -> bool {
+        self.section_list().iter().any(|s| s == section)
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > chrono::Utc::now().naive_utc()
+    }
+}
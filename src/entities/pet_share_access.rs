@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "pet_share_access_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub share_id: Uuid,
+    pub ip_address: Option<String>,
+    pub granted: bool,
+    /// Why access was refused: "expired", "revoked", "passcode"
+    pub reason: Option<String>,
+    pub accessed_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet_share::Entity",
+        from = "Column::ShareId",
+        to = "super::pet_share::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Share,
+}
+
+impl Related<super::pet_share::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Share.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
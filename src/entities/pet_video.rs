@@ -21,6 +21,44 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub description: Option<String>,
     pub is_unusual: bool,
+    pub suppressed_by_known_behavior: bool,
+
+    // Processing timeline
+    pub queued_at: Option<DateTimeWithTimeZone>,
+    pub download_started_at: Option<DateTimeWithTimeZone>,
+    pub analysis_started_at: Option<DateTimeWithTimeZone>,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error_message: Option<String>,
+    /// Where processing last failed: `download`, `fs_write`, `analysis`,
+    /// `timeout`, `parse` or `db_update`
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error_stage: Option<String>,
+
+    // Usage accounting
+    pub size_bytes: Option<i64>,
+    pub duration_seconds: Option<i32>,
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+
+    /// `gs://` path of the JPEG preview frame, once the worker has made one
+    pub thumbnail_path: Option<String>,
+    /// Container type detected from the upload's leading bytes
+    pub content_type: Option<String>,
+
+    // Reported by the camera; cameras often upload recordings in delayed batches
+    pub recorded_at: Option<DateTimeWithTimeZone>,
+    pub camera_id: Option<String>,
+
+    /// Integrator URL told when processing finishes; may carry a token, so
+    /// it's never sent back out
+    #[sea_orm(column_type = "Text", nullable)]
+    #[serde(skip_serializing)]
+    pub callback_url: Option<String>,
+
+    /// `crate::video_queue::Priority` of the latest upload or reprocess, so
+    /// a job rebuilt from the row goes back on the right queue
+    pub priority: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -43,12 +81,133 @@ impl Related<super::pet::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Model {
+    /// When the clip was recorded, falling back to when it was uploaded.
+    /// Digests bucket clips by this.
+    pub fn captured_at(&self) -> DateTimeWithTimeZone {
+        self.recorded_at.unwrap_or(self.created_at)
+    }
+}
+
+/// `COALESCE(recorded_at, created_at)`, the SQL form of [`Model::captured_at`].
+/// Kept identical to the expression `idx_pet_video_captured_at` is built on
+/// so range filters can use it.
+fn captured_at_expr() -> sea_orm::sea_query::SimpleExpr {
+    sea_orm::sea_query::Func::coalesce([
+        Expr::col((Entity, Column::RecordedAt)).into(),
+        Expr::col((Entity, Column::CreatedAt)).into(),
+    ])
+    .into()
+}
+
+/// Videos captured (see [`Model::captured_at`]) in `[start, end)`.
+pub fn captured_between(start: DateTimeUtc, end: DateTimeUtc) -> sea_orm::Condition {
+    sea_orm::Condition::all()
+        .add(Expr::expr(captured_at_expr()).gte(start))
+        .add(Expr::expr(captured_at_expr()).lt(end))
+}
+
+/// Pipeline states a video moves through. `Retrying` is stored mixed-case;
+/// `EXPIRED` videos had their files removed by retention but keep their analysis.
+pub const STATUSES: &[&str] = &[
+    "PENDING",
+    "PROCESSING",
+    "PROCESSED",
+    "FAILED",
+    "Retrying",
+    EXPIRED,
+];
+
+pub const EXPIRED: &str = "EXPIRED";
+
+/// Longest `error_message` stored; parse failures can echo a whole reply.
+pub const MAX_ERROR_MESSAGE_LEN: usize = 1000;
+
+/// `message` cut to [`MAX_ERROR_MESSAGE_LEN`] characters.
+pub fn truncate_error(message: &str) -> String {
+    match message.char_indices().nth(MAX_ERROR_MESSAGE_LEN) {
+        Some((end, _)) => format!("{}…", &message[..end]),
+        None => message.to_string(),
+    }
+}
+
+/// Matches a status name case-insensitively against [`STATUSES`].
+pub fn parse_status(raw: &str) -> Option<&'static str> {
+    STATUSES
+        .iter()
+        .copied()
+        .find(|s| s.eq_ignore_ascii_case(raw.trim()))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Activity {
     pub activity: String,
+    /// Filled in by the worker; absent on videos analyzed before the taxonomy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_activity: Option<String>,
     pub mood: String,
     pub description: String,
     pub starttime: String,
     pub endtime: String,
     pub duration: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use sea_orm::{DbBackend, QueryTrait};
+
+    fn video(recorded_at: Option<&str>, created_at: &str) -> Model {
+        serde_json::from_value(serde_json::json!({
+            "pet_id": 1,
+            "file_path": "gs://bucket/uploads/1/clip.mp4",
+            "status": "PROCESSED",
+            "retry_count": 0,
+            "created_at": created_at,
+            "updated_at": created_at,
+            "is_unusual": false,
+            "suppressed_by_known_behavior": false,
+            "recorded_at": recorded_at,
+            "priority": "normal",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn delayed_batch_lands_on_the_recording_days() {
+        // A camera offline over a weekend uploads everything on Monday
+        let uploaded = "2026-03-09T09:00:00+00:00";
+        let clips = [
+            video(Some("2026-03-07T10:00:00+00:00"), uploaded),
+            video(Some("2026-03-08T23:30:00+00:00"), uploaded),
+            video(None, uploaded),
+        ];
+        let days: Vec<NaiveDate> = clips
+            .iter()
+            .map(|c| crate::timezone::local_date(&c.captured_at(), chrono_tz::Tz::UTC))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                NaiveDate::from_ymd_opt(2026, 3, 7).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn captured_between_filters_on_the_indexed_expression() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 7, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 3, 10, 0, 0, 0).unwrap();
+        let sql = Entity::find()
+            .filter(captured_between(start, end))
+            .build(DbBackend::Postgres)
+            .to_string();
+        let expr = r#"COALESCE("pet_video"."recorded_at", "pet_video"."created_at")"#;
+        assert!(sql.contains(&format!("{} >= '2026-03-07 00:00:00.000000 +00:00'", expr)));
+        assert!(sql.contains(&format!("{} < '2026-03-10 00:00:00.000000 +00:00'", expr)));
+        assert!(!sql.contains(" OR "));
+    }
+}
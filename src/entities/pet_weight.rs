@@ -0,0 +1,54 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// One weigh-in. A row is added whenever the pet's `weight_kg` is set, so
+/// the profile holds the latest value and this table the history.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "pet_weights")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_serializing)]
+    pub id: i32,
+    #[serde(skip_serializing)]
+    pub pet_id: i32,
+    pub weight_kg: f64,
+    pub recorded_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Pet,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Appends a weigh-in for `pet_id`.
+pub async fn record(
+    db: &DatabaseConnection,
+    pet_id: i32,
+    weight_kg: f64,
+    recorded_at: DateTime,
+) -> Result<Model, DbErr> {
+    ActiveModel {
+        pet_id: Set(pet_id),
+        weight_kg: Set(weight_kg),
+        recorded_at: Set(recorded_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
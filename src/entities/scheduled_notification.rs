@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "scheduled_notifications")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: i32,
+    pub alert_id: Option<Uuid>,
+    /// Delivery channel: "email" or "sms"
+    pub channel: String,
+    /// Email address or phone number
+    pub recipient: String,
+    /// Which notification this is, e.g. "reminder"; copied to the notification log
+    pub template: String,
+    /// Rendered message: {"subject": ..., "body": ...}
+    pub payload: Json,
+    pub due_at: DateTime,
+    /// "pending", "sending", "sent", "failed" or "cancelled"
+    pub status: String,
+    pub attempts: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub claimed_at: Option<DateTime>,
+    pub sent_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::alerts::Entity",
+        from = "Column::AlertId",
+        to = "super::alerts::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Alert,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::alerts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Alert.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -12,6 +12,20 @@ pub struct Model {
     pub name: String,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    /// Send an "all clear" follow-up when a notified alert resolves on its own
+    pub recovery_notifications_enabled: bool,
+    /// E.164 number for SMS alerts; SMS is skipped when unset
+    pub phone: Option<String>,
+    /// IANA zone name; digests are bucketed by the owner's local day
+    pub timezone: String,
+    /// Google account subject, set once the user signs in with Google
+    #[serde(skip_serializing)]
+    pub google_sub: Option<String>,
+    /// Operators with access to the cross-user `/admin` routes. Only settable
+    /// directly in the database.
+    pub is_admin: bool,
+    /// Days to keep this user's video files, overriding `VIDEO_RETENTION_DAYS`
+    pub video_retention_days: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
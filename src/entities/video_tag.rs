@@ -0,0 +1,83 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The tag the app's favorite button sets.
+pub const FAVORITE: &str = "favorite";
+pub const MAX_TAGS_PER_VIDEO: u64 = 20;
+pub const MAX_TAG_LEN: usize = 32;
+
+/// A label the pet's owner put on a video. Tags are stored normalized (see
+/// [`normalize`]) and unique per video.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "video_tags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub video_id: Uuid,
+    /// Who added the tag
+    pub user_id: i32,
+    pub tag: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet_video::Entity",
+        from = "Column::VideoId",
+        to = "super::pet_video::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    PetVideo,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::pet_video::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetVideo.def()
+    }
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Trimmed and lowercased, so "Favorite " and "favorite" are the same tag.
+pub fn normalize(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Tags per video, oldest first, for every video in `video_ids`.
+pub async fn tags_by_video(
+    db: &DatabaseConnection,
+    video_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, Vec<String>>, DbErr> {
+    let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+    if video_ids.is_empty() {
+        return Ok(tags);
+    }
+    let rows = Entity::find()
+        .filter(Column::VideoId.is_in(video_ids))
+        .order_by_asc(Column::CreatedAt)
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await?;
+    for row in rows {
+        tags.entry(row.video_id).or_default().push(row.tag);
+    }
+    Ok(tags)
+}
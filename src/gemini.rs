@@ -1,3 +1,4 @@
+use crate::entities::pet::Species;
 use reqwest::Client;
 use serde_json::{json, Value};
 use std::env;
@@ -5,12 +6,21 @@ use std::path::Path;
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
+/// Starts the error returned when Gemini's reply isn't the JSON we asked for.
+pub const PARSE_ERROR_PREFIX: &str = "Failed to parse Gemini JSON";
+
 pub struct GeminiClient {
     client: Client,
     api_key: String,
     model: String,
 }
 
+impl Default for GeminiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GeminiClient {
     pub fn new() -> Self {
         let api_key = env::var("GEMINI_API_KEY").expect("GEMINI_API_KEY must be set");
@@ -34,9 +44,16 @@ impl GeminiClient {
         self.generate_content(&file_uri).await
     }
 
+    /// `species` tailors the behavior labels to the animal, `pet_context` is
+    /// optional background on the pet (see `pet::Model::health_context`) and
+    /// `baseline` a description of its usual behavior (see `crate::baseline`);
+    /// all of them go into the prompt.
     pub async fn analyze_video_with_usage(
         &self,
         file_path: &str,
+        species: Option<Species>,
+        pet_context: Option<&str>,
+        baseline: Option<&str>,
     ) -> Result<(Value, Option<Value>), String> {
         // 1. Upload File
         let file_uri = self.upload_file(file_path).await?;
@@ -46,10 +63,13 @@ impl GeminiClient {
         self.wait_for_file_active(&file_uri).await?;
 
         // 3. Generate Content
-        self.generate_content_with_usage(&file_uri).await
+        self.generate_content_with_usage(&file_uri, species, pet_context, baseline)
+            .await
     }
     async fn generate_content(&self, file_name: &str) -> Result<Value, String> {
-        let (val, _) = self.generate_content_with_usage(file_name).await?;
+        let (val, _) = self
+            .generate_content_with_usage(file_name, None, None, None)
+            .await?;
         Ok(val)
     }
 
@@ -144,6 +164,9 @@ impl GeminiClient {
     async fn generate_content_with_usage(
         &self,
         file_name: &str,
+        species: Option<Species>,
+        pet_context: Option<&str>,
+        baseline: Option<&str>,
     ) -> Result<(Value, Option<Value>), String> {
         // Construct the model URL
         // User asked for "Gemini 3.0 Pro".
@@ -196,6 +219,37 @@ impl GeminiClient {
         \n\
         BE CONSERVATIVE with \"critical\" classification. Only use it for genuine medical emergencies, not for behavioral issues.";
 
+        let prompt = match pet_context {
+            Some(context) => format!(
+                "PET PROFILE (use it to interpret what you see, e.g. a limp in a pet with known arthritis, and mention the relevant condition in \"critical_indicators\"): \n\
+                {} \n\
+                \n\
+                {}",
+                context, prompt
+            ),
+            None => prompt.to_string(),
+        };
+        let prompt = match species {
+            Some(species) => format!(
+                "{} \n\
+                \n\
+                {}",
+                species_guidance(species),
+                prompt
+            ),
+            None => prompt,
+        };
+        let prompt = match baseline {
+            Some(baseline) => format!(
+                "{} \n\
+                \n\
+                TYPICAL BEHAVIOR FOR THIS PET: {} \n\
+                Behavior consistent with this baseline is normal for this pet. Only set \"is_unusual\" to true when what you see clearly departs from it. Health emergencies are always critical regardless of the baseline.",
+                prompt, baseline
+            ),
+            None => prompt,
+        };
+
         let body = json!({
             "contents": [{
                 "parts": [
@@ -239,7 +293,7 @@ impl GeminiClient {
             .trim_end_matches("```");
 
         let parsed: Value = serde_json::from_str(clean_text)
-            .map_err(|e| format!("Failed to parse Gemini JSON: {} - Text: {}", e, clean_text))?;
+            .map_err(|e| format!("{}: {} - Text: {}", PARSE_ERROR_PREFIX, e, clean_text))?;
 
         Ok((parsed, usage))
     }
@@ -301,3 +355,26 @@ impl GeminiClient {
         Ok(text)
     }
 }
+
+/// Species section of the prompt. The generic label list in the prompt is
+/// dog-centric, so other species get their own and are told to leave
+/// dog-only behaviors out.
+fn species_guidance(species: Species) -> String {
+    let labels = match species {
+        Species::Dog => "'Pacing', 'Barking', 'Whining', 'Restlessness', 'Attention-seeking'",
+        Species::Cat => "'Pacing', 'Meowing', 'Hiding', 'Restlessness', 'Attention-seeking'",
+        Species::Bird | Species::Rabbit | Species::Other => {
+            "'Pacing', 'Hiding', 'Restlessness', 'Attention-seeking'"
+        }
+    };
+    let mut guidance = format!(
+        "SPECIES: {}. Only report behaviors and indicators this species can show. \
+         For non-critical unusual behavior use these labels in place of the list below: {}.",
+        species.as_str(),
+        labels
+    );
+    if species != Species::Dog {
+        guidance.push_str(" Never describe it as barking or whining.");
+    }
+    guidance
+}
@@ -0,0 +1,244 @@
+//! Message formats for `video_queue` and `digest_queue`. Every producer
+//! serializes one of these and the workers decode them with [`VideoJob::decode`]
+//! / [`DigestJob::decode`]; a payload the worker can't use goes to the
+//! queue's poison list (see [`crate::poison`]) instead of being read as empty
+//! fields.
+//!
+//! `version` is bumped whenever a change would make an older worker misread
+//! a job. Jobs from before versioning have no `version` and are version 1.
+
+use crate::video_queue::Priority;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const VIDEO_JOB_VERSION: u32 = 1;
+pub const DIGEST_JOB_VERSION: u32 = 1;
+
+fn unversioned() -> u32 {
+    1
+}
+
+/// Why a queued payload couldn't be used.
+#[derive(Debug)]
+pub enum JobError {
+    /// Not JSON, or missing or mistyped fields
+    Malformed(serde_json::Error),
+    /// Written by a newer (or unknown) producer
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for JobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobError::Malformed(e) => write!(f, "malformed job: {}", e),
+            JobError::UnsupportedVersion(v) => write!(f, "unsupported job version {}", v),
+        }
+    }
+}
+
+fn decode<T: serde::de::DeserializeOwned>(
+    raw: &str,
+    version_of: impl Fn(&T) -> u32,
+    supported: u32,
+) -> Result<T, JobError> {
+    let job: T = serde_json::from_str(raw).map_err(JobError::Malformed)?;
+    match version_of(&job) {
+        v if v == supported => Ok(job),
+        v => Err(JobError::UnsupportedVersion(v)),
+    }
+}
+
+/// One video to analyze.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoJob {
+    #[serde(default = "unversioned")]
+    pub version: u32,
+    pub video_id: Uuid,
+    #[serde(default)]
+    pub priority: Priority,
+    /// W3C trace context of the request that queued it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<HashMap<String, String>>,
+    /// When it was last pushed onto its queue, for the wait-time histogram
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enqueued_at: Option<DateTime<Utc>>,
+    /// Set while the job waits in `video_retry`; keeps each scheduled retry a
+    /// distinct member of the set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Times it has come back out of the DLQ
+    #[serde(default)]
+    pub dlq_replays: u64,
+}
+
+impl VideoJob {
+    pub fn new(video_id: Uuid, priority: Priority) -> Self {
+        Self {
+            version: VIDEO_JOB_VERSION,
+            video_id,
+            priority,
+            trace_context: None,
+            enqueued_at: Some(Utc::now()),
+            next_attempt_at: None,
+            dlq_replays: 0,
+        }
+    }
+
+    pub fn decode(raw: &str) -> Result<Self, JobError> {
+        decode(raw, |job: &Self| job.version, VIDEO_JOB_VERSION)
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("VideoJob always serializes")
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("VideoJob always serializes")
+    }
+
+    /// A copy stamped as pushed onto its queue now.
+    pub fn requeued(&self) -> Self {
+        Self {
+            enqueued_at: Some(Utc::now()),
+            next_attempt_at: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// The video a payload is about, read as leniently as possible so even jobs
+/// [`VideoJob::decode`] rejects can be traced to their row.
+pub fn video_id_of(raw: &str) -> Option<Uuid> {
+    serde_json::from_str::<Value>(raw)
+        .ok()?
+        .get("video_id")?
+        .as_str()
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// The queue a video payload belongs on, by its priority. Payloads that
+/// don't say (or can't be read) go to the normal queue, where the worker
+/// deals with them.
+pub fn queue_of(raw: &str) -> &'static str {
+    serde_json::from_str::<Value>(raw)
+        .ok()
+        .and_then(|job| serde_json::from_value::<Priority>(job.get("priority")?.clone()).ok())
+        .unwrap_or_default()
+        .queue()
+}
+
+/// A rebuild of one pet's digest for one (owner-local) day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestJob {
+    #[serde(default = "unversioned")]
+    pub version: u32,
+    pub pet_id: i32,
+    pub date: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enqueued_at: Option<DateTime<Utc>>,
+    /// Write a quiet-day digest if the pet has no clips that day; set by the
+    /// nightly scheduler
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_empty: bool,
+}
+
+impl DigestJob {
+    pub fn new(pet_id: i32, date: NaiveDate) -> Self {
+        Self {
+            version: DIGEST_JOB_VERSION,
+            pet_id,
+            date,
+            enqueued_at: Some(Utc::now()),
+            allow_empty: false,
+        }
+    }
+
+    pub fn decode(raw: &str) -> Result<Self, JobError> {
+        decode(raw, |job: &Self| job.version, DIGEST_JOB_VERSION)
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("DigestJob always serializes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video_queue::{HIGH, NORMAL};
+
+    #[test]
+    fn payloads_route_to_their_priority_queue() {
+        let high = VideoJob::new(Uuid::new_v4(), Priority::High).encode();
+        let normal = VideoJob::new(Uuid::new_v4(), Priority::Normal).encode();
+        assert_eq!(queue_of(&high), HIGH);
+        assert_eq!(queue_of(&normal), NORMAL);
+    }
+
+    #[test]
+    fn unreadable_payloads_route_to_the_normal_queue() {
+        assert_eq!(queue_of("not json"), NORMAL);
+        assert_eq!(queue_of(r#"{"video_id":"x"}"#), NORMAL);
+        assert_eq!(queue_of(r#"{"priority":"urgent"}"#), NORMAL);
+    }
+
+    #[test]
+    fn jobs_from_before_versioning_are_version_one() {
+        let id = Uuid::new_v4();
+        let job = VideoJob::decode(&format!(r#"{{"video_id":"{}"}}"#, id)).unwrap();
+        assert_eq!(job.version, 1);
+        assert_eq!(job.video_id, id);
+        assert_eq!(job.priority, Priority::Normal);
+
+        let digest = DigestJob::decode(r#"{"pet_id":3,"date":"2026-03-01"}"#).unwrap();
+        assert_eq!(digest.version, 1);
+        assert!(!digest.allow_empty);
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let raw = format!(r#"{{"version":2,"video_id":"{}"}}"#, Uuid::new_v4());
+        assert!(matches!(
+            VideoJob::decode(&raw),
+            Err(JobError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            DigestJob::decode(r#"{"version":7,"pet_id":3,"date":"2026-03-01"}"#),
+            Err(JobError::UnsupportedVersion(7))
+        ));
+    }
+
+    #[test]
+    fn malformed_payloads_are_refused() {
+        assert!(matches!(
+            VideoJob::decode("not json"),
+            Err(JobError::Malformed(_))
+        ));
+        assert!(matches!(
+            VideoJob::decode(r#"{"video_id":"not-a-uuid"}"#),
+            Err(JobError::Malformed(_))
+        ));
+        assert!(matches!(
+            DigestJob::decode(r#"{"pet_id":3}"#),
+            Err(JobError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn encoded_jobs_decode_to_the_same_job() {
+        let job = VideoJob::new(Uuid::new_v4(), Priority::High);
+        let decoded = VideoJob::decode(&job.encode()).unwrap();
+        assert_eq!(decoded.video_id, job.video_id);
+        assert_eq!(decoded.priority, Priority::High);
+        assert_eq!(decoded.version, VIDEO_JOB_VERSION);
+
+        let mut digest = DigestJob::new(4, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        digest.allow_empty = true;
+        let decoded = DigestJob::decode(&digest.encode()).unwrap();
+        assert_eq!((decoded.pet_id, decoded.date), (digest.pet_id, digest.date));
+        assert!(decoded.allow_empty);
+    }
+}
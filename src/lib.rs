@@ -1,9 +1,28 @@
+pub mod activity;
 pub mod agent;
+pub mod alert_outbox;
 pub mod api;
+pub mod audit;
+pub mod baseline;
+pub mod callbacks;
+pub mod dead_letter;
 pub mod entities;
 pub mod gemini;
+pub mod jobs;
 pub mod migrator;
+pub mod monitoring;
+pub mod mood;
+pub mod poison;
+pub mod processing_list;
+pub mod retention;
+pub mod storage_cleanup;
 pub mod telemetry;
+#[cfg(test)]
+mod test_support;
+pub mod timezone;
+pub mod video_events;
+pub mod video_format;
+pub mod video_queue;
 pub mod worker;
 
 pub use redis;
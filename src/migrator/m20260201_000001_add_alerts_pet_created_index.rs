@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Composite index for per-pet time-range scans (heatmap, escalation counts)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alerts_pet_id_created_at")
+                    .table(Alerts::Table)
+                    .col(Alerts::PetId)
+                    .col(Alerts::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_alerts_pet_id_created_at")
+                    .table(Alerts::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    PetId,
+    CreatedAt,
+}
@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .add_column(
+                        ColumnDef::new(Alerts::OccurrenceCount)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .add_column(ColumnDef::new(Alerts::FirstSeenAt).date_time())
+                    .add_column(ColumnDef::new(Alerts::LastSeenAt).date_time())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lookup of the open alert to fold a repeat into
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alerts_pet_type_last_seen")
+                    .table(Alerts::Table)
+                    .col(Alerts::PetId)
+                    .col(Alerts::AlertType)
+                    .col(Alerts::LastSeenAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_alerts_pet_type_last_seen")
+                    .table(Alerts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::OccurrenceCount)
+                    .drop_column(Alerts::FirstSeenAt)
+                    .drop_column(Alerts::LastSeenAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    PetId,
+    AlertType,
+    OccurrenceCount,
+    FirstSeenAt,
+    LastSeenAt,
+}
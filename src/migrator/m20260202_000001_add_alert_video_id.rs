@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .add_column(ColumnDef::new(Alerts::VideoId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Backfill from the webhook payload for alerts created before the column existed
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE alerts SET video_id = (payload->>'video_id')::uuid \
+                 WHERE video_id IS NULL \
+                 AND payload->>'video_id' ~* '^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$'",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alerts_video_id")
+                    .table(Alerts::Table)
+                    .col(Alerts::VideoId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_alerts_video_id")
+                    .table(Alerts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::VideoId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    VideoId,
+}
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .add_column(
+                        ColumnDef::new(Pets::KnownBehaviors)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .add_column(
+                        ColumnDef::new(PetVideo::SuppressedByKnownBehavior)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DailyDigest::Table)
+                    .add_column(
+                        ColumnDef::new(DailyDigest::ExpectedConditions)
+                            .json_binary()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DailyDigest::Table)
+                    .drop_column(DailyDigest::ExpectedConditions)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .drop_column(PetVideo::SuppressedByKnownBehavior)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .drop_column(Pets::KnownBehaviors)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    KnownBehaviors,
+}
+
+#[derive(DeriveIden)]
+enum PetVideo {
+    Table,
+    SuppressedByKnownBehavior,
+}
+
+#[derive(DeriveIden)]
+enum DailyDigest {
+    Table,
+    ExpectedConditions,
+}
@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationLog::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(NotificationLog::AlertId).uuid())
+                    .col(ColumnDef::new(NotificationLog::UserId).integer().not_null())
+                    .col(ColumnDef::new(NotificationLog::Channel).string().not_null())
+                    .col(ColumnDef::new(NotificationLog::Kind).string().not_null())
+                    .col(ColumnDef::new(NotificationLog::ReminderNumber).integer())
+                    .col(ColumnDef::new(NotificationLog::Status).string().not_null())
+                    .col(ColumnDef::new(NotificationLog::ErrorMessage).text())
+                    .col(
+                        ColumnDef::new(NotificationLog::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_log_alert")
+                            .from(NotificationLog::Table, NotificationLog::AlertId)
+                            .to(Alerts::Table, Alerts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_log_user")
+                            .from(NotificationLog::Table, NotificationLog::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_log_alert_id")
+                    .table(NotificationLog::Table)
+                    .col(NotificationLog::AlertId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_log_user_created_at")
+                    .table(NotificationLog::Table)
+                    .col(NotificationLog::UserId)
+                    .col(NotificationLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .add_column(
+                        ColumnDef::new(Alerts::ReminderCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(ColumnDef::new(Alerts::LastReminderAt).date_time().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alerts::Table)
+                    .drop_column(Alerts::ReminderCount)
+                    .drop_column(Alerts::LastReminderAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(NotificationLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationLog {
+    Table,
+    Id,
+    AlertId,
+    UserId,
+    Channel,
+    Kind,
+    ReminderNumber,
+    Status,
+    ErrorMessage,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    Id,
+    ReminderCount,
+    LastReminderAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
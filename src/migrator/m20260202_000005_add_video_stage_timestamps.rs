@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .add_column(
+                        ColumnDef::new(PetVideo::QueuedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(PetVideo::DownloadStartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(PetVideo::AnalysisStartedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(PetVideo::CompletedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(PetVideo::ErrorMessage).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .drop_column(PetVideo::QueuedAt)
+                    .drop_column(PetVideo::DownloadStartedAt)
+                    .drop_column(PetVideo::AnalysisStartedAt)
+                    .drop_column(PetVideo::CompletedAt)
+                    .drop_column(PetVideo::ErrorMessage)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetVideo {
+    Table,
+    QueuedAt,
+    DownloadStartedAt,
+    AnalysisStartedAt,
+    CompletedAt,
+    ErrorMessage,
+}
@@ -0,0 +1,89 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StorageReconcileRuns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::DryRun)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::Status)
+                            .string()
+                            .not_null()
+                            .default("running"),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::Scanned)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::Orphaned)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::Deleted)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::OrphanedBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(StorageReconcileRuns::ErrorMessage).text())
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::StartedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(StorageReconcileRuns::FinishedAt).timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StorageReconcileRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StorageReconcileRuns {
+    Table,
+    Id,
+    DryRun,
+    Status,
+    Scanned,
+    Orphaned,
+    Deleted,
+    OrphanedBytes,
+    ErrorMessage,
+    StartedAt,
+    FinishedAt,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .add_column(ColumnDef::new(PetVideo::SizeBytes).big_integer().null())
+                    .add_column(ColumnDef::new(PetVideo::DurationSeconds).integer().null())
+                    .add_column(ColumnDef::new(PetVideo::InputTokens).big_integer().null())
+                    .add_column(ColumnDef::new(PetVideo::OutputTokens).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Usage reports aggregate by pet and month
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_pet_video_pet_created")
+                    .table(PetVideo::Table)
+                    .col(PetVideo::PetId)
+                    .col(PetVideo::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_pet_video_pet_created")
+                    .table(PetVideo::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PetVideo::Table)
+                    .drop_column(PetVideo::SizeBytes)
+                    .drop_column(PetVideo::DurationSeconds)
+                    .drop_column(PetVideo::InputTokens)
+                    .drop_column(PetVideo::OutputTokens)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetVideo {
+    Table,
+    PetId,
+    CreatedAt,
+    SizeBytes,
+    DurationSeconds,
+    InputTokens,
+    OutputTokens,
+}
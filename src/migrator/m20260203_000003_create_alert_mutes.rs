@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AlertMutes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AlertMutes::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AlertMutes::PetId).integer().not_null())
+                    .col(ColumnDef::new(AlertMutes::AlertType).string().not_null())
+                    .col(ColumnDef::new(AlertMutes::MutedBy).integer().not_null())
+                    .col(ColumnDef::new(AlertMutes::Note).text())
+                    .col(
+                        ColumnDef::new(AlertMutes::MutedUntil)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AlertMutes::UnmutedAt).date_time())
+                    .col(ColumnDef::new(AlertMutes::UnmutedBy).integer())
+                    .col(ColumnDef::new(AlertMutes::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_alert_mutes_pet")
+                            .from(AlertMutes::Table, AlertMutes::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_alert_mutes_muted_by")
+                            .from(AlertMutes::Table, AlertMutes::MutedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alert_mutes_pet_type_until")
+                    .table(AlertMutes::Table)
+                    .col(AlertMutes::PetId)
+                    .col(AlertMutes::AlertType)
+                    .col(AlertMutes::MutedUntil)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AlertMutes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AlertMutes {
+    Table,
+    Id,
+    PetId,
+    AlertType,
+    MutedBy,
+    Note,
+    MutedUntil,
+    UnmutedAt,
+    UnmutedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
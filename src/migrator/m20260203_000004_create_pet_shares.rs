@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetShares::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetShares::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetShares::PetId).integer().not_null())
+                    .col(ColumnDef::new(PetShares::CreatedBy).integer().not_null())
+                    .col(ColumnDef::new(PetShares::SecretHash).string().not_null())
+                    .col(ColumnDef::new(PetShares::PasscodeHash).string())
+                    .col(ColumnDef::new(PetShares::Sections).json_binary().not_null())
+                    .col(ColumnDef::new(PetShares::ExpiresAt).date_time().not_null())
+                    .col(ColumnDef::new(PetShares::RevokedAt).date_time())
+                    .col(ColumnDef::new(PetShares::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_shares_pet")
+                            .from(PetShares::Table, PetShares::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_shares_created_by")
+                            .from(PetShares::Table, PetShares::CreatedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_shares_pet_id")
+                    .table(PetShares::Table)
+                    .col(PetShares::PetId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetShareAccessLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetShareAccessLog::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetShareAccessLog::ShareId).uuid().not_null())
+                    .col(ColumnDef::new(PetShareAccessLog::IpAddress).string())
+                    .col(
+                        ColumnDef::new(PetShareAccessLog::Granted)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PetShareAccessLog::Reason).string())
+                    .col(
+                        ColumnDef::new(PetShareAccessLog::AccessedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_share_access_log_share")
+                            .from(PetShareAccessLog::Table, PetShareAccessLog::ShareId)
+                            .to(PetShares::Table, PetShares::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_share_access_log_share_accessed")
+                    .table(PetShareAccessLog::Table)
+                    .col(PetShareAccessLog::ShareId)
+                    .col(PetShareAccessLog::AccessedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PetShareAccessLog::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PetShares::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetShares {
+    Table,
+    Id,
+    PetId,
+    CreatedBy,
+    SecretHash,
+    PasscodeHash,
+    Sections,
+    ExpiresAt,
+    RevokedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PetShareAccessLog {
+    Table,
+    Id,
+    ShareId,
+    IpAddress,
+    Granted,
+    Reason,
+    AccessedAt,
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,161 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScheduledNotifications::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ScheduledNotifications::AlertId).uuid())
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Channel)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Recipient)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Template)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Payload)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::DueAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ScheduledNotifications::LastError).text())
+                    .col(ColumnDef::new(ScheduledNotifications::ClaimedAt).date_time())
+                    .col(ColumnDef::new(ScheduledNotifications::SentAt).date_time())
+                    .col(
+                        ColumnDef::new(ScheduledNotifications::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_scheduled_notifications_user")
+                            .from(
+                                ScheduledNotifications::Table,
+                                ScheduledNotifications::UserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_scheduled_notifications_alert")
+                            .from(
+                                ScheduledNotifications::Table,
+                                ScheduledNotifications::AlertId,
+                            )
+                            .to(Alerts::Table, Alerts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Dispatcher scans pending rows by due time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_scheduled_notifications_status_due")
+                    .table(ScheduledNotifications::Table)
+                    .col(ScheduledNotifications::Status)
+                    .col(ScheduledNotifications::DueAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_scheduled_notifications_user_due")
+                    .table(ScheduledNotifications::Table)
+                    .col(ScheduledNotifications::UserId)
+                    .col(ScheduledNotifications::DueAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ScheduledNotifications::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScheduledNotifications {
+    Table,
+    Id,
+    UserId,
+    AlertId,
+    Channel,
+    Recipient,
+    Template,
+    Payload,
+    DueAt,
+    Status,
+    Attempts,
+    LastError,
+    ClaimedAt,
+    SentAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Alerts {
+    Table,
+    Id,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Clips::Table)
+                    .add_column(ColumnDef::new(Clips::CanonicalActivity).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Activity breakdowns group on the canonical name
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_clips_video_canonical_activity")
+                    .table(Clips::Table)
+                    .col(Clips::VideoId)
+                    .col(Clips::CanonicalActivity)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_clips_video_canonical_activity")
+                    .table(Clips::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Clips::Table)
+                    .drop_column(Clips::CanonicalActivity)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Clips {
+    Table,
+    VideoId,
+    CanonicalActivity,
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .add_column(ColumnDef::new(Pets::WeightKg).double().null())
+                    .add_column(ColumnDef::new(Pets::MedicalConditions).json_binary().null())
+                    .add_column(ColumnDef::new(Pets::Medications).json_binary().null())
+                    .add_column(ColumnDef::new(Pets::VetName).string().null())
+                    .add_column(ColumnDef::new(Pets::VetPhone).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Pets::Table)
+                    .drop_column(Pets::WeightKg)
+                    .drop_column(Pets::MedicalConditions)
+                    .drop_column(Pets::Medications)
+                    .drop_column(Pets::VetName)
+                    .drop_column(Pets::VetPhone)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    WeightKg,
+    MedicalConditions,
+    Medications,
+    VetName,
+    VetPhone,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetCaretakers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetCaretakers::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetCaretakers::PetId).integer().not_null())
+                    .col(ColumnDef::new(PetCaretakers::UserId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PetCaretakers::Role)
+                            .string()
+                            .not_null()
+                            .default("caretaker"),
+                    )
+                    .col(
+                        ColumnDef::new(PetCaretakers::InvitedBy)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PetCaretakers::AcceptedAt).date_time())
+                    .col(
+                        ColumnDef::new(PetCaretakers::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_caretakers_pet")
+                            .from(PetCaretakers::Table, PetCaretakers::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_caretakers_user")
+                            .from(PetCaretakers::Table, PetCaretakers::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_caretakers_pet_user")
+                    .table(PetCaretakers::Table)
+                    .col(PetCaretakers::PetId)
+                    .col(PetCaretakers::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_caretakers_user_id")
+                    .table(PetCaretakers::Table)
+                    .col(PetCaretakers::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PetCaretakers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetCaretakers {
+    Table,
+    Id,
+    PetId,
+    UserId,
+    Role,
+    InvitedBy,
+    AcceptedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,34 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Map free-text species onto the values `pet::Species` accepts; keep
+        // the synonyms in step with `Species::parse`
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE pets SET species = CASE lower(trim(species)) \
+                 WHEN 'dog' THEN 'dog' WHEN 'dogs' THEN 'dog' \
+                 WHEN 'puppy' THEN 'dog' WHEN 'canine' THEN 'dog' \
+                 WHEN 'cat' THEN 'cat' WHEN 'cats' THEN 'cat' \
+                 WHEN 'kitten' THEN 'cat' WHEN 'feline' THEN 'cat' \
+                 WHEN 'bird' THEN 'bird' WHEN 'birds' THEN 'bird' \
+                 WHEN 'parrot' THEN 'bird' WHEN 'budgie' THEN 'bird' \
+                 WHEN 'rabbit' THEN 'rabbit' WHEN 'rabbits' THEN 'rabbit' \
+                 WHEN 'bunny' THEN 'rabbit' \
+                 ELSE 'other' END",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // The original free text isn't kept, so there's nothing to restore
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetAlertSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetAlertSettings::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetAlertSettings::PetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PetAlertSettings::AlertType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PetAlertSettings::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(PetAlertSettings::MinSeverity)
+                            .string()
+                            .not_null()
+                            .default("info"),
+                    )
+                    .col(
+                        ColumnDef::new(PetAlertSettings::EscalationThreshold)
+                            .integer()
+                            .not_null()
+                            .default(5),
+                    )
+                    .col(
+                        ColumnDef::new(PetAlertSettings::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PetAlertSettings::UpdatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_alert_settings_pet")
+                            .from(PetAlertSettings::Table, PetAlertSettings::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_alert_settings_pet_type")
+                    .table(PetAlertSettings::Table)
+                    .col(PetAlertSettings::PetId)
+                    .col(PetAlertSettings::AlertType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PetAlertSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetAlertSettings {
+    Table,
+    Id,
+    PetId,
+    AlertType,
+    Enabled,
+    MinSeverity,
+    EscalationThreshold,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    Id,
+}
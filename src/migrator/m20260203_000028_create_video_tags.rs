@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VideoTags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(VideoTags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(VideoTags::VideoId).uuid().not_null())
+                    .col(ColumnDef::new(VideoTags::UserId).integer().not_null())
+                    .col(ColumnDef::new(VideoTags::Tag).string().not_null())
+                    .col(ColumnDef::new(VideoTags::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_video_tags_video")
+                            .from(VideoTags::Table, VideoTags::VideoId)
+                            .to(PetVideo::Table, PetVideo::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_video_tags_user")
+                            .from(VideoTags::Table, VideoTags::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_video_tags_video_tag")
+                    .table(VideoTags::Table)
+                    .col(VideoTags::VideoId)
+                    .col(VideoTags::Tag)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // For the `?tag=` filter on video listings
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_video_tags_tag")
+                    .table(VideoTags::Table)
+                    .col(VideoTags::Tag)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VideoTags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VideoTags {
+    Table,
+    Id,
+    VideoId,
+    UserId,
+    Tag,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum PetVideo {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
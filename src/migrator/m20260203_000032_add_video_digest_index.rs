@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Per-pet, per-status day scans (digest rebuilds)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_video_pet_id_status_created_at")
+                    .table(PetVideo::Table)
+                    .col(PetVideo::PetId)
+                    .col(PetVideo::Status)
+                    .col(PetVideo::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_pet_video_pet_id_status_created_at")
+                    .table(PetVideo::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetVideo {
+    Table,
+    PetId,
+    Status,
+    CreatedAt,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetWeights::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetWeights::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetWeights::PetId).integer().not_null())
+                    .col(ColumnDef::new(PetWeights::WeightKg).double().not_null())
+                    .col(
+                        ColumnDef::new(PetWeights::RecordedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_pet_weights_pet")
+                            .from(PetWeights::Table, PetWeights::PetId)
+                            .to(Pets::Table, Pets::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_weights_pet_recorded_at")
+                    .table(PetWeights::Table)
+                    .col(PetWeights::PetId)
+                    .col(PetWeights::RecordedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Start each pet's history with the weight already on its profile
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO pet_weights (pet_id, weight_kg, recorded_at) \
+                 SELECT id, weight_kg, updated_at FROM pets WHERE weight_kg IS NOT NULL",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PetWeights::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PetWeights {
+    Table,
+    Id,
+    PetId,
+    WeightKg,
+    RecordedAt,
+}
+
+#[derive(DeriveIden)]
+enum Pets {
+    Table,
+    Id,
+}
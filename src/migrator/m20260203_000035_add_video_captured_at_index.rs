@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Must match `pet_video::captured_between`, which digests and mood
+        // history filter on
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_pet_video_captured_at ON pet_video \
+             (pet_id, status, COALESCE(recorded_at, created_at))",
+        )
+        .await?;
+        // Cross-pet day scans (which pets need a digest) have no pet_id
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS idx_pet_video_status_captured_at ON pet_video \
+             (status, COALESCE(recorded_at, created_at))",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_pet_video_status_captured_at")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_pet_video_captured_at")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -9,6 +9,48 @@ mod m20260127_000001_create_alerts_table;
 mod m20260128_000001_enhance_alerts_table;
 mod m20260130_000001_create_emergency_contacts;
 mod m20260130_000002_create_quick_actions;
+mod m20260201_000001_add_alerts_pet_created_index;
+mod m20260201_000002_add_alert_occurrences;
+mod m20260202_000001_add_alert_video_id;
+mod m20260202_000002_add_known_behaviors;
+mod m20260202_000003_create_notification_log;
+mod m20260202_000004_add_pet_static_check;
+mod m20260202_000005_add_video_stage_timestamps;
+mod m20260203_000001_create_storage_reconcile_runs;
+mod m20260203_000002_add_video_usage_columns;
+mod m20260203_000003_create_alert_mutes;
+mod m20260203_000004_create_pet_shares;
+mod m20260203_000005_create_scheduled_notifications;
+mod m20260203_000006_add_user_recovery_notifications;
+mod m20260203_000007_create_password_reset_tokens;
+mod m20260203_000008_add_clip_canonical_activity;
+mod m20260203_000009_create_api_keys;
+mod m20260203_000010_add_user_phone;
+mod m20260203_000011_add_user_timezone;
+mod m20260203_000012_create_notification_preferences;
+mod m20260203_000013_add_user_google_sub;
+mod m20260203_000014_add_user_is_admin;
+mod m20260203_000015_create_audit_log;
+mod m20260203_000016_add_pet_photo_path;
+mod m20260203_000017_add_pet_medical_profile;
+mod m20260203_000018_add_pet_archived_at;
+mod m20260203_000019_add_pet_behavior_baseline;
+mod m20260203_000020_create_pet_caretakers;
+mod m20260203_000021_normalize_pet_species;
+mod m20260203_000022_create_pet_alert_settings;
+mod m20260203_000023_add_pet_monitoring_schedule;
+mod m20260203_000024_add_video_thumbnail_path;
+mod m20260203_000025_add_video_content_type;
+mod m20260203_000026_add_user_video_retention;
+mod m20260203_000027_add_video_recording_metadata;
+mod m20260203_000028_create_video_tags;
+mod m20260203_000029_add_video_callback_url;
+mod m20260203_000030_add_video_search_index;
+mod m20260203_000031_add_video_error_stage;
+mod m20260203_000032_add_video_digest_index;
+mod m20260203_000033_create_pet_weights;
+mod m20260203_000034_add_video_priority;
+mod m20260203_000035_add_video_captured_at_index;
 
 pub struct Migrator;
 
@@ -25,6 +67,48 @@ impl MigratorTrait for Migrator {
             Box::new(m20260128_000001_enhance_alerts_table::Migration),
             Box::new(m20260130_000001_create_emergency_contacts::Migration),
             Box::new(m20260130_000002_create_quick_actions::Migration),
+            Box::new(m20260201_000001_add_alerts_pet_created_index::Migration),
+            Box::new(m20260201_000002_add_alert_occurrences::Migration),
+            Box::new(m20260202_000001_add_alert_video_id::Migration),
+            Box::new(m20260202_000002_add_known_behaviors::Migration),
+            Box::new(m20260202_000003_create_notification_log::Migration),
+            Box::new(m20260202_000004_add_pet_static_check::Migration),
+            Box::new(m20260202_000005_add_video_stage_timestamps::Migration),
+            Box::new(m20260203_000001_create_storage_reconcile_runs::Migration),
+            Box::new(m20260203_000002_add_video_usage_columns::Migration),
+            Box::new(m20260203_000003_create_alert_mutes::Migration),
+            Box::new(m20260203_000004_create_pet_shares::Migration),
+            Box::new(m20260203_000005_create_scheduled_notifications::Migration),
+            Box::new(m20260203_000006_add_user_recovery_notifications::Migration),
+            Box::new(m20260203_000007_create_password_reset_tokens::Migration),
+            Box::new(m20260203_000008_add_clip_canonical_activity::Migration),
+            Box::new(m20260203_000009_create_api_keys::Migration),
+            Box::new(m20260203_000010_add_user_phone::Migration),
+            Box::new(m20260203_000011_add_user_timezone::Migration),
+            Box::new(m20260203_000012_create_notification_preferences::Migration),
+            Box::new(m20260203_000013_add_user_google_sub::Migration),
+            Box::new(m20260203_000014_add_user_is_admin::Migration),
+            Box::new(m20260203_000015_create_audit_log::Migration),
+            Box::new(m20260203_000016_add_pet_photo_path::Migration),
+            Box::new(m20260203_000017_add_pet_medical_profile::Migration),
+            Box::new(m20260203_000018_add_pet_archived_at::Migration),
+            Box::new(m20260203_000019_add_pet_behavior_baseline::Migration),
+            Box::new(m20260203_000020_create_pet_caretakers::Migration),
+            Box::new(m20260203_000021_normalize_pet_species::Migration),
+            Box::new(m20260203_000022_create_pet_alert_settings::Migration),
+            Box::new(m20260203_000023_add_pet_monitoring_schedule::Migration),
+            Box::new(m20260203_000024_add_video_thumbnail_path::Migration),
+            Box::new(m20260203_000025_add_video_content_type::Migration),
+            Box::new(m20260203_000026_add_user_video_retention::Migration),
+            Box::new(m20260203_000027_add_video_recording_metadata::Migration),
+            Box::new(m20260203_000028_create_video_tags::Migration),
+            Box::new(m20260203_000029_add_video_callback_url::Migration),
+            Box::new(m20260203_000030_add_video_search_index::Migration),
+            Box::new(m20260203_000031_add_video_error_stage::Migration),
+            Box::new(m20260203_000032_add_video_digest_index::Migration),
+            Box::new(m20260203_000033_create_pet_weights::Migration),
+            Box::new(m20260203_000034_add_video_priority::Migration),
+            Box::new(m20260203_000035_add_video_captured_at_index::Migration),
         ]
     }
 }
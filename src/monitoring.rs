@@ -0,0 +1,106 @@
+//! Expected-upload schedules. Owners say how often a pet's camera should send
+//! a clip, and optionally during which local hours; the worker's upload watch
+//! raises an alert when clips stop arriving.
+
+use crate::entities::pet;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_INTERVAL_MINUTES: i64 = 5;
+pub const MAX_INTERVAL_MINUTES: i64 = 24 * 60;
+/// Uploads are overdue once this many expected clips in a row are missing,
+/// so a single dropped clip never alerts.
+pub const MISSED_CLIPS_BEFORE_ALERT: i64 = 2;
+
+/// Local hours `[start_hour, end_hour)` during which clips are expected. The
+/// range may wrap midnight, e.g. 22-6 for overnight monitoring.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ActiveHours {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl ActiveHours {
+    pub fn is_valid(&self) -> bool {
+        self.start_hour < 24 && self.end_hour < 24 && self.start_hour != self.end_hour
+    }
+
+    fn contains(&self, hour: u32) -> bool {
+        if self.start_hour < self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringSchedule {
+    pub expected_interval_minutes: i64,
+    /// None means clips are expected around the clock
+    pub active_hours: Option<ActiveHours>,
+    /// When the schedule was set; silence before it doesn't count
+    pub set_at: DateTime<Utc>,
+}
+
+/// The schedule stored on the pet, if any.
+pub fn of_pet(pet: &pet::Model) -> Option<MonitoringSchedule> {
+    pet.monitoring_schedule
+        .clone()
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+/// `naive` read as a local time in `tz`, in UTC. Times skipped by a DST change
+/// fall back to reading them as UTC.
+fn local_to_utc(naive: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| naive.and_utc())
+}
+
+impl MonitoringSchedule {
+    pub fn expected_interval(&self) -> Duration {
+        Duration::minutes(self.expected_interval_minutes)
+    }
+
+    /// How long a silence has to last before uploads count as overdue.
+    pub fn overdue_after(&self) -> Duration {
+        self.expected_interval() * MISSED_CLIPS_BEFORE_ALERT as i32
+    }
+
+    /// Start of the active window containing `now`, or None when `now` is
+    /// outside active hours.
+    fn window_start(&self, now: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        let Some(hours) = self.active_hours else {
+            return Some(self.set_at);
+        };
+        let local = now.with_timezone(&tz);
+        if !hours.contains(local.hour()) {
+            return None;
+        }
+        let mut day = local.date_naive();
+        // In a window that wrapped midnight, it opened yesterday evening
+        if local.hour() < hours.start_hour {
+            day = day.pred_opt()?;
+        }
+        Some(local_to_utc(day.and_hms_opt(hours.start_hour, 0, 0)?, tz))
+    }
+
+    /// How long clips have been missing as of `now`: the time since the last
+    /// upload, the start of the current active window or the schedule being
+    /// set, whichever is latest. None outside active hours.
+    pub fn silence(
+        &self,
+        last_upload: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+        tz: Tz,
+    ) -> Option<Duration> {
+        let since = self
+            .window_start(now, tz)?
+            .max(self.set_at)
+            .max(last_upload.unwrap_or(self.set_at));
+        Some(now - since)
+    }
+}
@@ -0,0 +1,48 @@
+//! Canonical mood names. Gemini describes the same state several ways
+//! ("relaxed", "Calm", "content"); charts group on the canonical name so a
+//! pet doesn't look like it swings between moods that mean the same thing.
+
+/// Canonical mood and the normalized phrases that map onto it.
+const SYNONYMS: &[(&str, &[&str])] = &[
+    (
+        "calm",
+        &[
+            "calm", "relaxed", "peaceful", "content", "serene", "settled",
+        ],
+    ),
+    ("happy", &["happy", "joyful", "cheerful", "excited"]),
+    ("playful", &["playful", "energetic", "lively"]),
+    ("curious", &["curious", "alert", "attentive", "interested"]),
+    ("sleepy", &["sleepy", "tired", "drowsy", "lethargic"]),
+    ("bored", &["bored", "disinterested"]),
+    (
+        "anxious",
+        &["anxious", "nervous", "stressed", "worried", "uneasy"],
+    ),
+    ("fearful", &["fearful", "scared", "afraid", "frightened"]),
+    (
+        "agitated",
+        &["agitated", "restless", "irritated", "frustrated"],
+    ),
+];
+
+/// Lowercases and collapses whitespace, then maps known synonyms onto their
+/// canonical mood. Unknown moods come back normalized but otherwise as-is;
+/// blank ones come back as `None`.
+pub fn normalize(raw: &str) -> Option<String> {
+    let phrase = raw
+        .to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if phrase.is_empty() {
+        return None;
+    }
+
+    let canonical = SYNONYMS
+        .iter()
+        .find(|(_, synonyms)| synonyms.contains(&phrase.as_str()))
+        .map(|(canonical, _)| canonical.to_string());
+    Some(canonical.unwrap_or(phrase))
+}
@@ -0,0 +1,38 @@
+use super::routing::Channel;
+use crate::entities::notification_log;
+use sea_orm::{DatabaseConnection, EntityTrait, Set};
+use tracing::error;
+use uuid::Uuid;
+
+/// A single delivery attempt to be written to the notification log.
+pub struct NotificationRecord<'a> {
+    pub alert_id: Option<Uuid>,
+    pub user_id: i32,
+    pub channel: Channel,
+    pub kind: &'a str,
+    pub reminder_number: Option<i32>,
+    pub result: &'a Result<(), String>,
+}
+
+pub async fn record_notification(db: &DatabaseConnection, record: NotificationRecord<'_>) {
+    let (status, error_message) = match record.result {
+        Ok(()) => ("sent", None),
+        Err(e) => ("failed", Some(e.clone())),
+    };
+
+    let entry = notification_log::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        alert_id: Set(record.alert_id),
+        user_id: Set(record.user_id),
+        channel: Set(record.channel.as_str().to_string()),
+        kind: Set(record.kind.to_string()),
+        reminder_number: Set(record.reminder_number),
+        status: Set(status.to_string()),
+        error_message: Set(error_message),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    };
+
+    if let Err(e) = notification_log::Entity::insert(entry).exec(db).await {
+        error!("Failed to record notification: {}", e);
+    }
+}
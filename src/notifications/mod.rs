@@ -1,7 +1,15 @@
+pub mod log;
+pub mod preferences;
 pub mod pubsub_client;
+pub mod routing;
+pub mod scheduled;
 pub mod templates;
 pub mod twilio;
 
+pub use log::{record_notification, NotificationRecord};
+
 pub use pubsub_client::{AlertEmailPayload, PubSubClient};
+pub use routing::Channel;
+pub use scheduled::{schedule_notification, ScheduledNotification};
 pub use templates::NotificationTemplates;
 pub use twilio::TwilioNotifier;
@@ -0,0 +1,61 @@
+use super::routing::Channel;
+use crate::entities::notification_preference;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use tracing::error;
+
+/// Alert severity levels, least to most severe.
+pub const SEVERITY_LEVELS: &[&str] = &["info", "low", "medium", "high", "critical"];
+
+pub fn severity_rank(level: &str) -> Option<usize> {
+    SEVERITY_LEVELS
+        .iter()
+        .position(|l| l.eq_ignore_ascii_case(level))
+}
+
+/// Whether a preference row lets an alert of `severity` through.
+pub fn allows(pref: &notification_preference::Model, severity: &str) -> bool {
+    match (
+        severity_rank(severity),
+        severity_rank(&pref.severity_threshold),
+    ) {
+        (Some(severity), Some(threshold)) => pref.enabled && severity >= threshold,
+        // An unrecognized threshold only honors the on/off switch
+        _ => pref.enabled,
+    }
+}
+
+/// The subset of `channels` the user wants alerts of `severity` on. Falls
+/// back to all of `channels` if preferences can't be loaded, so a database
+/// hiccup never silences a critical alert.
+pub async fn preferred_channels(
+    db: &DatabaseConnection,
+    user_id: i32,
+    channels: &[Channel],
+    severity: &str,
+) -> Vec<Channel> {
+    let prefs = match notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .all(db)
+        .await
+    {
+        Ok(prefs) => prefs,
+        Err(e) => {
+            error!(
+                "Failed to load notification preferences for user {}: {}",
+                user_id, e
+            );
+            return channels.to_vec();
+        }
+    };
+
+    channels
+        .iter()
+        .copied()
+        .filter(|channel| {
+            prefs
+                .iter()
+                .find(|p| p.channel == channel.as_str())
+                .is_none_or(|p| allows(p, severity))
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+/// Delivery channels the notifier can reach an owner through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Email,
+    Sms,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Email => "email",
+            Channel::Sms => "sms",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "email" => Some(Channel::Email),
+            "sms" => Some(Channel::Sms),
+            _ => None,
+        }
+    }
+}
+
+/// Every channel, in the order preferences are listed.
+pub const ALL_CHANNELS: &[Channel] = &[Channel::Email, Channel::Sms];
+
+/// The subset of `channels` the owner can be reached on; SMS needs a phone
+/// number on file.
+pub fn reachable_channels(channels: &[Channel], phone: Option<&str>) -> Vec<Channel> {
+    channels
+        .iter()
+        .copied()
+        .filter(|c| *c != Channel::Sms || phone.is_some())
+        .collect()
+}
+
+/// Channels used for the initial critical notification.
+pub const CRITICAL_CHANNELS: &[Channel] = &[Channel::Email, Channel::Sms];
+
+/// Channels for the Nth owner reminder of an unacknowledged critical alert.
+/// Each step is more intrusive than the last: email, then SMS, then both.
+pub fn reminder_channels(reminder_number: i32) -> &'static [Channel] {
+    match reminder_number {
+        ..=1 => &[Channel::Email],
+        2 => &[Channel::Sms],
+        _ => &[Channel::Email, Channel::Sms],
+    }
+}
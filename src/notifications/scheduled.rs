@@ -0,0 +1,266 @@
+use super::routing::Channel;
+use super::{record_notification, NotificationRecord, TwilioNotifier};
+use crate::entities::scheduled_notification;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const DISPATCH_POLL_SECS: u64 = 15;
+const DISPATCH_BATCH_SIZE: u64 = 100;
+const MAX_ATTEMPTS: i32 = 5;
+const RETRY_BASE_SECS: i64 = 60;
+/// Claims older than this belong to a dispatcher that died mid-send.
+const STALE_CLAIM_SECS: i64 = 300;
+
+/// A notification to deliver at `due_at` instead of right away.
+pub struct ScheduledNotification<'a> {
+    pub user_id: i32,
+    pub alert_id: Option<Uuid>,
+    pub channel: Channel,
+    pub recipient: &'a str,
+    pub template: &'a str,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub due_at: chrono::NaiveDateTime,
+}
+
+/// Persists a notification for the dispatcher. Send paths opt into deferral
+/// by calling this instead of TwilioNotifier directly.
+pub async fn schedule_notification(
+    db: &DatabaseConnection,
+    notification: ScheduledNotification<'_>,
+) -> Result<Uuid, DbErr> {
+    let id = Uuid::new_v4();
+    let row = scheduled_notification::ActiveModel {
+        id: Set(id),
+        user_id: Set(notification.user_id),
+        alert_id: Set(notification.alert_id),
+        channel: Set(notification.channel.as_str().to_string()),
+        recipient: Set(notification.recipient.to_string()),
+        template: Set(notification.template.to_string()),
+        payload: Set(serde_json::json!({
+            "subject": notification.subject,
+            "body": notification.body,
+        })),
+        due_at: Set(notification.due_at),
+        status: Set("pending".to_string()),
+        attempts: Set(0),
+        last_error: Set(None),
+        claimed_at: Set(None),
+        sent_at: Set(None),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    };
+    scheduled_notification::Entity::insert(row).exec(db).await?;
+    Ok(id)
+}
+
+/// Upcoming notifications for a user, soonest first.
+pub async fn upcoming_for_user(
+    db: &DatabaseConnection,
+    user_id: i32,
+    limit: u64,
+) -> Result<Vec<scheduled_notification::Model>, DbErr> {
+    scheduled_notification::Entity::find()
+        .filter(scheduled_notification::Column::UserId.eq(user_id))
+        .filter(scheduled_notification::Column::Status.is_in(["pending", "sending"]))
+        .order_by_asc(scheduled_notification::Column::DueAt)
+        .limit(limit)
+        .all(db)
+        .await
+}
+
+/// Delivers due scheduled notifications. Rows are claimed with a conditional
+/// update so several dispatchers can run side by side, and claims left behind
+/// by a crashed process are picked up again after STALE_CLAIM_SECS.
+pub struct NotificationDispatcher {
+    db: DatabaseConnection,
+    notifier: TwilioNotifier,
+}
+
+impl NotificationDispatcher {
+    pub async fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            notifier: TwilioNotifier::new().await,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Scheduled notification dispatcher started");
+        loop {
+            self.dispatch_due().await;
+            tokio::time::sleep(tokio::time::Duration::from_secs(DISPATCH_POLL_SECS)).await;
+        }
+    }
+
+    fn claimable_condition(now: chrono::NaiveDateTime) -> Condition {
+        let stale = now - chrono::Duration::seconds(STALE_CLAIM_SECS);
+        Condition::any()
+            .add(
+                Condition::all()
+                    .add(scheduled_notification::Column::Status.eq("pending"))
+                    .add(scheduled_notification::Column::DueAt.lte(now)),
+            )
+            .add(
+                Condition::all()
+                    .add(scheduled_notification::Column::Status.eq("sending"))
+                    .add(scheduled_notification::Column::ClaimedAt.lt(stale)),
+            )
+    }
+
+    async fn dispatch_due(&self) {
+        let now = chrono::Utc::now().naive_utc();
+        let due = match scheduled_notification::Entity::find()
+            .filter(Self::claimable_condition(now))
+            .order_by_asc(scheduled_notification::Column::DueAt)
+            .limit(DISPATCH_BATCH_SIZE)
+            .all(&self.db)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("Failed to load due scheduled notifications: {}", e);
+                return;
+            }
+        };
+
+        for row in due {
+            if self.claim(&row, now).await {
+                self.deliver(row).await;
+            }
+        }
+    }
+
+    async fn claim(&self, row: &scheduled_notification::Model, now: chrono::NaiveDateTime) -> bool {
+        let claimed = scheduled_notification::Entity::update_many()
+            .col_expr(
+                scheduled_notification::Column::Status,
+                Expr::value("sending"),
+            )
+            .col_expr(scheduled_notification::Column::ClaimedAt, Expr::value(now))
+            .col_expr(
+                scheduled_notification::Column::Attempts,
+                Expr::value(row.attempts + 1),
+            )
+            .filter(scheduled_notification::Column::Id.eq(row.id))
+            .filter(scheduled_notification::Column::Attempts.eq(row.attempts))
+            .filter(Self::claimable_condition(now))
+            .exec(&self.db)
+            .await;
+
+        match claimed {
+            Ok(res) => res.rows_affected == 1,
+            Err(e) => {
+                error!("Failed to claim scheduled notification {}: {}", row.id, e);
+                false
+            }
+        }
+    }
+
+    async fn deliver(&self, row: scheduled_notification::Model) {
+        let subject = row.payload["subject"].as_str().unwrap_or_default();
+        let body = row.payload["body"].as_str().unwrap_or_default();
+
+        let (channel, result) = match row.channel.as_str() {
+            "email" => (
+                Channel::Email,
+                self.notifier
+                    .send_email(&row.recipient, subject, body)
+                    .await,
+            ),
+            "sms" => (
+                Channel::Sms,
+                self.notifier.send_sms(&row.recipient, body).await,
+            ),
+            other => {
+                error!(
+                    "Scheduled notification {} has unknown channel {}",
+                    row.id, other
+                );
+                self.finish(row.id, "failed", Some(format!("Unknown channel {}", other)))
+                    .await;
+                return;
+            }
+        };
+
+        let attempts = row.attempts + 1;
+        match &result {
+            Ok(()) => {
+                self.finish(row.id, "sent", None).await;
+                metrics::counter!("petpulse_scheduled_notifications_total", "status" => "sent")
+                    .increment(1);
+            }
+            Err(e) if attempts < MAX_ATTEMPTS => {
+                // Exponential backoff: 1, 2, 4, 8 minutes
+                let backoff = RETRY_BASE_SECS * 2i64.pow((attempts - 1) as u32);
+                warn!(
+                    "Scheduled notification {} failed (attempt {}), retrying in {}s: {}",
+                    row.id, attempts, backoff, e
+                );
+                let update = scheduled_notification::ActiveModel {
+                    id: Set(row.id),
+                    status: Set("pending".to_string()),
+                    due_at: Set(chrono::Utc::now().naive_utc() + chrono::Duration::seconds(backoff)),
+                    last_error: Set(Some(e.clone())),
+                    claimed_at: Set(None),
+                    ..Default::default()
+                };
+                if let Err(e) = scheduled_notification::Entity::update(update)
+                    .exec(&self.db)
+                    .await
+                {
+                    error!("Failed to reschedule notification {}: {}", row.id, e);
+                }
+                metrics::counter!("petpulse_scheduled_notifications_total", "status" => "retried")
+                    .increment(1);
+                // Only the final outcome goes in the notification log
+                return;
+            }
+            Err(e) => {
+                error!(
+                    "Scheduled notification {} failed permanently after {} attempts: {}",
+                    row.id, attempts, e
+                );
+                self.finish(row.id, "failed", Some(e.clone())).await;
+                metrics::counter!("petpulse_scheduled_notifications_total", "status" => "failed")
+                    .increment(1);
+            }
+        }
+
+        record_notification(
+            &self.db,
+            NotificationRecord {
+                alert_id: row.alert_id,
+                user_id: row.user_id,
+                channel,
+                kind: &row.template,
+                reminder_number: None,
+                result: &result,
+            },
+        )
+        .await;
+    }
+
+    async fn finish(&self, id: Uuid, status: &str, last_error: Option<String>) {
+        let now = chrono::Utc::now().naive_utc();
+        let update = scheduled_notification::ActiveModel {
+            id: Set(id),
+            status: Set(status.to_string()),
+            sent_at: Set((status == "sent").then_some(now)),
+            last_error: Set(last_error),
+            ..Default::default()
+        };
+        if let Err(e) = scheduled_notification::Entity::update(update)
+            .exec(&self.db)
+            .await
+        {
+            error!(
+                "Failed to mark scheduled notification {} {}: {}",
+                id, status, e
+            );
+        }
+    }
+}
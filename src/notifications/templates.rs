@@ -1,5 +1,3 @@
-use serde::Serialize;
-
 pub struct NotificationTemplates;
 
 impl NotificationTemplates {
@@ -117,4 +115,161 @@ impl NotificationTemplates {
             video_link
         )
     }
+
+    /// Follow-up email when a critical alert is still unacknowledged
+    pub fn alert_reminder_email(
+        pet_name: &str,
+        reminder_number: i32,
+        minutes_open: i64,
+        description: &str,
+        video_link: &str,
+    ) -> String {
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: Arial, sans-serif; color: #333;">
+    <h2 style="color: #d32f2f;">Reminder #{reminder_number}: {pet_name} still needs your attention</h2>
+    <p>A critical alert raised {minutes_open} minutes ago has not been acknowledged yet.</p>
+    <p>{description}</p>
+    <p><a href="{video_link}">View the alert</a></p>
+</body>
+</html>
+"#,
+            reminder_number = reminder_number,
+            pet_name = pet_name,
+            minutes_open = minutes_open,
+            description = description,
+            video_link = video_link
+        )
+    }
+
+    /// Follow-up SMS when a critical alert is still unacknowledged
+    pub fn alert_reminder_sms(
+        pet_name: &str,
+        reminder_number: i32,
+        minutes_open: i64,
+        video_link: &str,
+    ) -> String {
+        format!(
+            "⏰ PetPulse REMINDER #{}: critical alert for {} unacknowledged for {} min\nView: {}",
+            reminder_number, pet_name, minutes_open, video_link
+        )
+    }
+
+    /// Follow-up email once an alert the owner was notified about has calmed down
+    pub fn recovery_email(pet_name: &str, episode_minutes: i64, video_link: &str) -> String {
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: Arial, sans-serif; color: #333;">
+    <h2 style="color: #2e7d32;">All clear: {pet_name} is back to normal</h2>
+    <p>The unusual behavior we told you about has settled down. The episode lasted about {episode_minutes} minutes.</p>
+    <p><a href="{video_link}">View the latest video</a></p>
+</body>
+</html>
+"#,
+            pet_name = pet_name,
+            episode_minutes = episode_minutes,
+            video_link = video_link
+        )
+    }
+
+    /// Follow-up SMS once an alert the owner was notified about has calmed down
+    pub fn recovery_sms(pet_name: &str, episode_minutes: i64, video_link: &str) -> String {
+        format!(
+            "✅ PetPulse ALL CLEAR: {} is back to normal after about {} min\nView: {}",
+            pet_name, episode_minutes, video_link
+        )
+    }
+
+    /// Notice that a video was dropped after exhausting processing retries
+    pub fn processing_error_email(pet_name: &str, stage: &str, video_link: &str) -> String {
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: Arial, sans-serif; color: #333;">
+    <h2>We couldn't analyze a recent video of {pet_name}</h2>
+    <p>The {stage} step failed after several retries, so this recording window was not monitored.</p>
+    <p>If this keeps happening, please check your camera's connection.</p>
+    <p><a href="{video_link}">View the video</a></p>
+</body>
+</html>
+"#,
+            pet_name = pet_name,
+            stage = stage,
+            video_link = video_link
+        )
+    }
+
+    /// A scheduled camera has gone quiet
+    pub fn uploads_overdue_email(pet_name: &str, silent_minutes: i64) -> String {
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: Arial, sans-serif; color: #333;">
+    <h2>No new clips of {pet_name}</h2>
+    <p>We haven't received a video from {pet_name}'s camera in {silent_minutes} minutes, which is longer than its monitoring schedule allows.</p>
+    <p>Please check that the camera is powered on and connected.</p>
+    <p><a href="https://petpulse.dashboard">Open the dashboard</a></p>
+</body>
+</html>
+"#,
+            pet_name = pet_name,
+            silent_minutes = silent_minutes
+        )
+    }
+
+    /// Password reset link for the forgot-password flow
+    pub fn password_reset_email(name: &str, reset_link: &str, expires_in_mins: i64) -> String {
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: Arial, sans-serif; color: #333;">
+    <h2>Reset your PetPulse password</h2>
+    <p>Hi {name},</p>
+    <p>We received a request to reset your password. This link works once and expires in {expires_in_mins} minutes.</p>
+    <p><a href="{reset_link}">Choose a new password</a></p>
+    <p>If you didn't ask for this, you can ignore this email; your password won't change.</p>
+</body>
+</html>
+"#,
+            name = name,
+            reset_link = reset_link,
+            expires_in_mins = expires_in_mins
+        )
+    }
+
+    /// Operator-facing alert (queue backlogs and similar), firing or resolved
+    pub fn operator_alert_email(
+        title: &str,
+        message: &str,
+        resolved: bool,
+        context: Option<&serde_json::Value>,
+    ) -> String {
+        let status = if resolved { "Resolved" } else { "Firing" };
+        let details = context
+            .and_then(|c| serde_json::to_string_pretty(c).ok())
+            .unwrap_or_default();
+        format!(
+            r#"
+<!DOCTYPE html>
+<html>
+<body style="font-family: monospace; color: #333;">
+    <h2>[{status}] {title}</h2>
+    <p>{message}</p>
+    <pre>{details}</pre>
+</body>
+</html>
+"#,
+            status = status,
+            title = title,
+            message = message,
+            details = details
+        )
+    }
 }
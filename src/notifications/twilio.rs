@@ -1,4 +1,5 @@
 use super::pubsub_client::AlertEmailPayload;
+use super::routing::Channel;
 use super::NotificationTemplates;
 use super::PubSubClient; // Import PubSubClient
 use sendgrid::SGClient;
@@ -24,7 +25,7 @@ impl TwilioNotifier {
         let email_from = env::var("NOTIFICATION_EMAIL_FROM")
             .unwrap_or_else(|_| "alerts@petpulse.com".to_string());
 
-        let sendgrid_client = sendgrid_api_key.map(|key| SGClient::new(key));
+        let sendgrid_client = sendgrid_api_key.map(SGClient::new);
 
         let twilio_client =
             if let (Some(sid), Some(token)) = (twilio_account_sid, twilio_auth_token) {
@@ -148,10 +149,14 @@ impl TwilioNotifier {
         }
     }
 
+    /// Sends the alert on each of `channels`; callers narrow them down to
+    /// the ones the owner can be reached on and has opted into.
+    #[allow(clippy::too_many_arguments)]
     pub async fn notify_critical_alert(
         &self,
+        channels: &[Channel],
         owner_email: &str,
-        owner_phone: &str,
+        owner_phone: Option<&str>,
         pet_name: &str,
         severity: &str,
         description: &str,
@@ -160,7 +165,9 @@ impl TwilioNotifier {
         video_link: &str,
     ) {
         // 1. Send Email via Pub/Sub (Cloud Function)
-        if let Some(pub_sub) = &self.pub_sub_client {
+        if !channels.contains(&Channel::Email) {
+            info!("Email disabled for this alert; skipping");
+        } else if let Some(pub_sub) = &self.pub_sub_client {
             // We need an ID for the alert to generate a link, but we don't have it passed here easily unless we change the signature.
             // The Cloud Function expects 'id' for the link: /alerts/{id}
             // For now, we'll use a placeholder or generate a random one if not provided,
@@ -209,6 +216,14 @@ impl TwilioNotifier {
         }
 
         // 2. Send SMS
+        if !channels.contains(&Channel::Sms) {
+            info!("SMS disabled for this alert; skipping");
+            return;
+        }
+        let Some(owner_phone) = owner_phone else {
+            info!("Owner has no phone number on file; skipping SMS");
+            return;
+        };
         let sms_body =
             NotificationTemplates::critical_alert_sms(pet_name, severity, description, video_link);
 
@@ -219,4 +234,76 @@ impl TwilioNotifier {
             let _ = sms_notifier.send_sms(&sms_target, &sms_body).await;
         });
     }
+
+    /// Sends one reminder over a single channel and reports the outcome so
+    /// the caller can record it in the notification log.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_alert_reminder(
+        &self,
+        channel: Channel,
+        owner_email: &str,
+        owner_phone: Option<&str>,
+        pet_name: &str,
+        reminder_number: i32,
+        minutes_open: i64,
+        description: &str,
+        video_link: &str,
+    ) -> Result<(), String> {
+        match channel {
+            Channel::Email => {
+                let subject = format!(
+                    "⏰ Reminder: critical alert for {} is unacknowledged",
+                    pet_name
+                );
+                let body = NotificationTemplates::alert_reminder_email(
+                    pet_name,
+                    reminder_number,
+                    minutes_open,
+                    description,
+                    video_link,
+                );
+                self.send_email(owner_email, &subject, &body).await
+            }
+            Channel::Sms => {
+                let owner_phone = owner_phone.ok_or_else(no_phone)?;
+                let body = NotificationTemplates::alert_reminder_sms(
+                    pet_name,
+                    reminder_number,
+                    minutes_open,
+                    video_link,
+                );
+                self.send_sms(owner_phone, &body).await
+            }
+        }
+    }
+
+    /// Sends the "all clear" follow-up over a single channel.
+    pub async fn send_recovery_notice(
+        &self,
+        channel: Channel,
+        owner_email: &str,
+        owner_phone: Option<&str>,
+        pet_name: &str,
+        episode_minutes: i64,
+        video_link: &str,
+    ) -> Result<(), String> {
+        match channel {
+            Channel::Email => {
+                let subject = format!("✅ All clear: {} is back to normal", pet_name);
+                let body =
+                    NotificationTemplates::recovery_email(pet_name, episode_minutes, video_link);
+                self.send_email(owner_email, &subject, &body).await
+            }
+            Channel::Sms => {
+                let owner_phone = owner_phone.ok_or_else(no_phone)?;
+                let body =
+                    NotificationTemplates::recovery_sms(pet_name, episode_minutes, video_link);
+                self.send_sms(owner_phone, &body).await
+            }
+        }
+    }
+}
+
+fn no_phone() -> String {
+    "Owner has no phone number on file".to_string()
 }
@@ -0,0 +1,103 @@
+//! Poison lists for queue payloads the workers can't decode. Each queue has
+//! its own (`video_queue:poison`, `digest_queue:poison`) holding the raw
+//! payload and why it was rejected, so a format bug shows up somewhere an
+//! operator can look instead of vanishing from the logs.
+
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+/// Queues that have a poison list.
+pub const QUEUES: &[&str] = &["video_queue", "digest_queue"];
+
+pub fn list_key(queue: &str) -> String {
+    format!("{}:poison", queue)
+}
+
+/// The queue name as accepted from a path, if it has a poison list.
+pub fn known_queue(queue: &str) -> Option<&'static str> {
+    QUEUES.iter().copied().find(|q| *q == queue)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoisonMessage {
+    /// The payload exactly as it was popped
+    pub raw: String,
+    pub error: String,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Parks `raw` on `queue`'s poison list. If even that fails the payload is
+/// logged in full, so it's never gone without a trace.
+pub async fn push(conn: &mut MultiplexedConnection, queue: &'static str, raw: &str, error: &str) {
+    metrics::counter!("petpulse_poison_messages_total", "queue" => queue).increment(1);
+    let message = PoisonMessage {
+        raw: raw.to_string(),
+        error: error.to_string(),
+        received_at: Utc::now(),
+    };
+    let pushed: redis::RedisResult<()> = match serde_json::to_string(&message) {
+        Ok(entry) => conn.rpush(list_key(queue), entry).await,
+        Err(e) => Err(redis::RedisError::from((
+            redis::ErrorKind::TypeError,
+            "unserializable poison message",
+            e.to_string(),
+        ))),
+    };
+    match pushed {
+        Ok(()) => tracing::error!("Moved {} payload to {} ({})", queue, list_key(queue), error),
+        Err(e) => tracing::error!(
+            "Failed to park {} payload on {} ({}): {}; payload: {}",
+            queue,
+            list_key(queue),
+            e,
+            error,
+            raw
+        ),
+    }
+}
+
+pub async fn len(conn: &mut MultiplexedConnection, queue: &str) -> redis::RedisResult<u64> {
+    conn.llen(list_key(queue)).await
+}
+
+/// Entries `offset..offset + limit`, oldest first.
+pub async fn list(
+    conn: &mut MultiplexedConnection,
+    queue: &str,
+    offset: u64,
+    limit: u64,
+) -> redis::RedisResult<Vec<PoisonMessage>> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+    let raw: Vec<String> = conn
+        .lrange(
+            list_key(queue),
+            offset as isize,
+            (offset + limit - 1) as isize,
+        )
+        .await?;
+    Ok(raw
+        .into_iter()
+        .map(|entry| {
+            serde_json::from_str(&entry).unwrap_or_else(|_| PoisonMessage {
+                raw: entry,
+                error: "unreadable poison entry".to_string(),
+                received_at: DateTime::<Utc>::UNIX_EPOCH,
+            })
+        })
+        .collect())
+}
+
+/// Empties `queue`'s poison list, returning how many entries it held.
+pub async fn purge(conn: &mut MultiplexedConnection, queue: &str) -> redis::RedisResult<u64> {
+    let (len, _): (u64, i64) = redis::pipe()
+        .atomic()
+        .llen(list_key(queue))
+        .del(list_key(queue))
+        .query_async(conn)
+        .await?;
+    Ok(len)
+}
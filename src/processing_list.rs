@@ -0,0 +1,121 @@
+//! Per-worker processing lists for video_queue. A worker BLMOVEs each job
+//! into `video_processing:<instance>:<worker>` and only removes it once the
+//! job is done, so a process that dies mid-job leaves the payload behind
+//! instead of losing it. Each worker process keeps a heartbeat key alive; the
+//! recovery sweep puts back jobs from lists whose process stopped beating.
+//! Jobs always go back on the queue for their own priority.
+
+use crate::jobs;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Direction};
+
+pub const PREFIX: &str = "video_processing:";
+const ALIVE_PREFIX: &str = "video_worker_alive:";
+const DEFAULT_STALE_SECS: u64 = 300;
+
+/// `VIDEO_PROCESSING_STALE_SECS`: how long a process can miss heartbeats
+/// before its processing lists count as stranded.
+pub fn stale_after_secs() -> u64 {
+    std::env::var("VIDEO_PROCESSING_STALE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_STALE_SECS)
+}
+
+pub fn list_key(instance: &str, worker: usize) -> String {
+    format!("{}{}:{}", PREFIX, instance, worker)
+}
+
+fn alive_key(instance: &str) -> String {
+    format!("{}{}", ALIVE_PREFIX, instance)
+}
+
+/// The process a processing list belongs to.
+fn instance_of(list: &str) -> Option<&str> {
+    list.strip_prefix(PREFIX)?
+        .rsplit_once(':')
+        .map(|(instance, _)| instance)
+}
+
+pub async fn heartbeat(conn: &mut MultiplexedConnection, instance: &str) -> redis::RedisResult<()> {
+    conn.set_ex(alive_key(instance), 1, stale_after_secs())
+        .await
+}
+
+/// Drops a finished job from its worker's processing list.
+pub async fn complete(
+    conn: &mut MultiplexedConnection,
+    list: &str,
+    payload: &str,
+) -> redis::RedisResult<()> {
+    let _: i64 = conn.lrem(list, 1, payload).await?;
+    Ok(())
+}
+
+async fn all_lists(conn: &mut MultiplexedConnection) -> redis::RedisResult<Vec<String>> {
+    let mut lists = Vec::new();
+    let mut iter = conn.scan_match::<_, String>(format!("{}*", PREFIX)).await?;
+    while let Some(list) = iter.next_item().await {
+        lists.push(list);
+    }
+    Ok(lists)
+}
+
+/// Jobs currently held in processing lists, live or stranded.
+pub async fn depth(conn: &mut MultiplexedConnection) -> redis::RedisResult<u64> {
+    let mut total = 0;
+    for list in all_lists(conn).await? {
+        let len: u64 = conn.llen(&list).await?;
+        total += len;
+    }
+    Ok(total)
+}
+
+/// Every job held in a processing list, live or stranded.
+pub async fn payloads(conn: &mut MultiplexedConnection) -> redis::RedisResult<Vec<String>> {
+    let mut payloads = Vec::new();
+    for list in all_lists(conn).await? {
+        let held: Vec<String> = conn.lrange(&list, 0, -1).await?;
+        payloads.extend(held);
+    }
+    Ok(payloads)
+}
+
+/// Moves everything in `list` back onto the front of its priority's queue,
+/// returning the payloads moved. Each job is peeked to pick the queue, then
+/// moved atomically, so a job is never out of Redis; two sweeps racing on
+/// the same list can at worst misroute one.
+pub async fn requeue(
+    conn: &mut MultiplexedConnection,
+    list: &str,
+) -> redis::RedisResult<Vec<String>> {
+    let mut moved = Vec::new();
+    while let Some(next) = conn.lindex::<_, Option<String>>(list, -1).await? {
+        let queue = jobs::queue_of(&next);
+        let Some(payload) = conn
+            .lmove::<_, _, Option<String>>(list, queue, Direction::Right, Direction::Left)
+            .await?
+        else {
+            break;
+        };
+        moved.push(payload);
+    }
+    Ok(moved)
+}
+
+/// Requeues jobs from every processing list whose process has no live
+/// heartbeat, returning the payloads moved.
+pub async fn recover_stranded(conn: &mut MultiplexedConnection) -> redis::RedisResult<Vec<String>> {
+    let mut recovered = Vec::new();
+    for list in all_lists(conn).await? {
+        let Some(instance) = instance_of(&list) else {
+            continue;
+        };
+        let alive: bool = conn.exists(alive_key(instance)).await?;
+        if !alive {
+            recovered.extend(requeue(conn, &list).await?);
+        }
+    }
+    Ok(recovered)
+}
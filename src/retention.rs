@@ -0,0 +1,139 @@
+//! Video retention. Files older than the owner's retention period are
+//! removed from GCS and their rows marked `EXPIRED`; the analysis (mood,
+//! activities, description) stays so digests and trends keep working.
+//!
+//! Objects are deleted before the row is marked, so a run that dies midway
+//! leaves rows that the next run picks up again rather than orphaned objects.
+
+use crate::entities::{pet_video, PetVideo};
+use crate::storage_cleanup;
+use chrono::Utc;
+use google_cloud_storage::client::Client as GcsClient;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult, QueryFilter, Statement,
+};
+use uuid::Uuid;
+
+const BATCH_SIZE: i64 = 100;
+const DEFAULT_INTERVAL_HOURS: u64 = 24;
+
+/// `VIDEO_RETENTION_DAYS`; unset keeps videos forever unless the owner has
+/// their own `video_retention_days`.
+fn default_retention_days() -> Option<i32> {
+    std::env::var("VIDEO_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|d| *d > 0)
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ExpiringVideo {
+    id: Uuid,
+    file_path: String,
+    thumbnail_path: Option<String>,
+}
+
+/// Oldest finished videos past their owner's retention period.
+async fn expiring_videos(
+    db: &DatabaseConnection,
+    default_days: Option<i32>,
+) -> Result<Vec<ExpiringVideo>, DbErr> {
+    ExpiringVideo::find_by_statement(Statement::from_sql_and_values(
+        sea_orm::DbBackend::Postgres,
+        "SELECT v.id, v.file_path, v.thumbnail_path FROM pet_video v \
+         JOIN pets p ON p.id = v.pet_id \
+         JOIN users u ON u.id = p.user_id \
+         WHERE v.status IN ('PROCESSED', 'FAILED') \
+         AND COALESCE(u.video_retention_days, $1) IS NOT NULL \
+         AND v.created_at < NOW() - make_interval(days => COALESCE(u.video_retention_days, $1)) \
+         ORDER BY v.created_at LIMIT $2",
+        [default_days.into(), BATCH_SIZE.into()],
+    ))
+    .all(db)
+    .await
+}
+
+/// Expires one batch. Returns how many videos it expired.
+async fn expire_batch(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    videos: Vec<ExpiringVideo>,
+) -> Result<u64, DbErr> {
+    let mut expired: Vec<Uuid> = Vec::with_capacity(videos.len());
+    for video in videos {
+        let mut deleted = storage_cleanup::delete_object(gcs_client, &video.file_path).await;
+        if let Some(thumbnail) = &video.thumbnail_path {
+            deleted &= storage_cleanup::delete_object(gcs_client, thumbnail).await;
+        }
+        // Leave the row as it was so the next run retries its objects
+        if deleted {
+            expired.push(video.id);
+        }
+    }
+    if expired.is_empty() {
+        return Ok(0);
+    }
+
+    let now = Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap());
+    let result = PetVideo::update_many()
+        .col_expr(pet_video::Column::Status, Expr::value(pet_video::EXPIRED))
+        .col_expr(
+            pet_video::Column::ThumbnailPath,
+            Expr::value(Option::<String>::None),
+        )
+        .col_expr(pet_video::Column::UpdatedAt, Expr::value(now))
+        .filter(pet_video::Column::Id.is_in(expired))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Expires every video past retention, a batch at a time. Stops early when a
+/// batch makes no progress (storage unavailable) so the run can't spin.
+pub async fn run_retention(db: &DatabaseConnection, gcs_client: &GcsClient) -> Result<u64, DbErr> {
+    let default_days = default_retention_days();
+    let mut total = 0;
+    loop {
+        let videos = expiring_videos(db, default_days).await?;
+        if videos.is_empty() {
+            break;
+        }
+        let batch = videos.len();
+        let expired = expire_batch(db, gcs_client, videos).await?;
+        metrics::counter!("petpulse_videos_expired_total").increment(expired);
+        total += expired;
+        if expired == 0 {
+            tracing::warn!(
+                "Video retention stopped: none of {} videos could be deleted",
+                batch
+            );
+            break;
+        }
+    }
+    Ok(total)
+}
+
+/// Periodic retention run every `VIDEO_RETENTION_INTERVAL_HOURS` (default 24).
+pub async fn start_retention_scheduler(db: DatabaseConnection, gcs_client: GcsClient) {
+    let interval_hours = std::env::var("VIDEO_RETENTION_INTERVAL_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|h| *h > 0)
+        .unwrap_or(DEFAULT_INTERVAL_HOURS);
+
+    tokio::spawn(async move {
+        tracing::info!(
+            "Video retention scheduled every {}h (default retention: {:?} days)",
+            interval_hours,
+            default_retention_days()
+        );
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(interval_hours * 3600)).await;
+            match run_retention(&db, &gcs_client).await {
+                Ok(expired) => tracing::info!("Video retention expired {} videos", expired),
+                Err(e) => tracing::error!("Video retention failed: {}", e),
+            }
+        }
+    });
+}
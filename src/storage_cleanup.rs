@@ -0,0 +1,430 @@
+use crate::entities::{pet_video, storage_reconcile_run, PetVideo, StorageReconcileRun};
+use chrono::Utc;
+use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::delete::DeleteObjectRequest;
+use google_cloud_storage::http::objects::list::ListObjectsRequest;
+use google_cloud_storage::http::objects::Object;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Objects younger than this are never treated as orphans; uploads may still
+/// be mid-flight between the GCS write and the DB insert.
+const ORPHAN_MIN_AGE_HOURS: i64 = 48;
+const LIST_PAGE_SIZE: i32 = 1000;
+
+const UPLOADS_PREFIX: &str = "uploads/";
+/// Video thumbnails live at `thumbnails/{video_id}.jpg`.
+pub const THUMBNAILS_PREFIX: &str = "thumbnails/";
+/// Chunks of resumable uploads, `upload_sessions/{session_id}/{n}`.
+pub const UPLOAD_SESSIONS_PREFIX: &str = "upload_sessions/";
+
+const DELETE_ATTEMPTS: u32 = 3;
+const DELETE_RETRY_BASE_MS: u64 = 500;
+
+#[derive(Default)]
+struct ReconcileCounts {
+    scanned: i64,
+    orphaned: i64,
+    deleted: i64,
+    orphaned_bytes: i64,
+}
+
+fn now_fixed() -> chrono::DateTime<chrono::FixedOffset> {
+    Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap())
+}
+
+/// Creates the audit row for a run so callers can hand its id back before the
+/// (potentially long) scan finishes.
+pub async fn start_reconcile_run(
+    db: &DatabaseConnection,
+    dry_run: bool,
+) -> Result<storage_reconcile_run::Model, sea_orm::DbErr> {
+    storage_reconcile_run::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        dry_run: Set(dry_run),
+        status: Set("running".to_string()),
+        scanned: Set(0),
+        orphaned: Set(0),
+        deleted: Set(0),
+        orphaned_bytes: Set(0),
+        error_message: Set(None),
+        started_at: Set(now_fixed()),
+        finished_at: Set(None),
+    }
+    .insert(db)
+    .await
+}
+
+/// Scans the bucket for objects no pet_video row references and deletes the
+/// ones older than the grace period (or only reports them in dry-run mode).
+/// Results are written back to the run's audit row.
+pub async fn run_reconcile(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    bucket: &str,
+    run: storage_reconcile_run::Model,
+) {
+    let mut counts = ReconcileCounts::default();
+    let mut result = Ok(());
+
+    for prefix in [UPLOADS_PREFIX, THUMBNAILS_PREFIX, UPLOAD_SESSIONS_PREFIX] {
+        result = reconcile_prefix(db, gcs_client, bucket, prefix, run.dry_run, &mut counts).await;
+        if result.is_err() {
+            break;
+        }
+    }
+
+    tracing::info!(
+        "Storage reconcile {} finished (dry_run={}): scanned={}, orphaned={}, deleted={}",
+        run.id,
+        run.dry_run,
+        counts.scanned,
+        counts.orphaned,
+        counts.deleted
+    );
+
+    let mut active: storage_reconcile_run::ActiveModel = run.into();
+    active.scanned = Set(counts.scanned);
+    active.orphaned = Set(counts.orphaned);
+    active.deleted = Set(counts.deleted);
+    active.orphaned_bytes = Set(counts.orphaned_bytes);
+    active.finished_at = Set(Some(now_fixed()));
+    match result {
+        Ok(()) => active.status = Set("completed".to_string()),
+        Err(e) => {
+            tracing::error!("Storage reconcile failed: {}", e);
+            active.status = Set("failed".to_string());
+            active.error_message = Set(Some(e));
+        }
+    }
+    if let Err(e) = active.update(db).await {
+        tracing::error!("Failed to record storage reconcile run: {}", e);
+    }
+}
+
+async fn reconcile_prefix(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    bucket: &str,
+    prefix: &str,
+    dry_run: bool,
+    counts: &mut ReconcileCounts,
+) -> Result<(), String> {
+    let cutoff = (Utc::now() - chrono::Duration::hours(ORPHAN_MIN_AGE_HOURS)).timestamp();
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let page = gcs_client
+            .list_objects(&ListObjectsRequest {
+                bucket: bucket.to_string(),
+                prefix: Some(prefix.to_string()),
+                max_results: Some(LIST_PAGE_SIZE),
+                page_token: page_token.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("Failed to list gs://{}/{}: {}", bucket, prefix, e))?;
+
+        let objects = page.items.unwrap_or_default();
+        counts.scanned += objects.len() as i64;
+        metrics::counter!("petpulse_storage_reconcile_objects_total", "result" => "scanned")
+            .increment(objects.len() as u64);
+
+        // Only objects past the grace period are candidates
+        let candidates: Vec<Object> = objects
+            .into_iter()
+            .filter(|o| {
+                o.time_created
+                    .map(|t| t.unix_timestamp() < cutoff)
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let orphans = find_orphans(db, bucket, prefix, candidates)
+            .await
+            .map_err(|e| format!("Failed to look up video rows: {}", e))?;
+
+        for object in orphans {
+            counts.orphaned += 1;
+            counts.orphaned_bytes += object.size;
+            metrics::counter!("petpulse_storage_reconcile_objects_total", "result" => "orphaned")
+                .increment(1);
+
+            if dry_run {
+                tracing::info!("(Dry run) Orphaned object gs://{}/{}", bucket, object.name);
+                continue;
+            }
+
+            match gcs_client
+                .delete_object(&DeleteObjectRequest {
+                    bucket: bucket.to_string(),
+                    object: object.name.clone(),
+                    ..Default::default()
+                })
+                .await
+            {
+                Ok(()) => {
+                    counts.deleted += 1;
+                    metrics::counter!("petpulse_storage_reconcile_objects_total", "result" => "deleted")
+                        .increment(1);
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to delete orphaned object gs://{}/{}: {}",
+                    bucket,
+                    object.name,
+                    e
+                ),
+            }
+        }
+
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            return Ok(());
+        }
+    }
+}
+
+/// Returns the candidates that no pet_video row references, using one batched
+/// query per listing page.
+async fn find_orphans(
+    db: &DatabaseConnection,
+    bucket: &str,
+    prefix: &str,
+    candidates: Vec<Object>,
+) -> Result<Vec<Object>, sea_orm::DbErr> {
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if prefix == UPLOAD_SESSIONS_PREFIX {
+        // Sessions expire well inside the grace period, so any chunk that old
+        // belongs to one that was abandoned
+        return Ok(candidates);
+    }
+
+    if prefix == THUMBNAILS_PREFIX {
+        // Thumbnails are named after the video they belong to
+        let video_id_of = |o: &Object| {
+            std::path::Path::new(&o.name)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| Uuid::parse_str(s).ok())
+        };
+        let ids: Vec<Uuid> = candidates.iter().filter_map(video_id_of).collect();
+        let existing: HashSet<Uuid> = PetVideo::find()
+            .select_only()
+            .column(pet_video::Column::Id)
+            .filter(pet_video::Column::Id.is_in(ids))
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect();
+
+        return Ok(candidates
+            .into_iter()
+            .filter(|o| {
+                video_id_of(o)
+                    .map(|id| !existing.contains(&id))
+                    .unwrap_or(true)
+            })
+            .collect());
+    }
+
+    let gs_path = |o: &Object| format!("gs://{}/{}", bucket, o.name);
+    let paths: Vec<String> = candidates.iter().map(gs_path).collect();
+    let existing: HashSet<String> = PetVideo::find()
+        .select_only()
+        .column(pet_video::Column::FilePath)
+        .filter(pet_video::Column::FilePath.is_in(paths))
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(candidates
+        .into_iter()
+        .filter(|o| !existing.contains(&gs_path(o)))
+        .collect())
+}
+
+/// Most recent reconcile runs, newest first.
+pub async fn recent_runs(
+    db: &DatabaseConnection,
+    limit: u64,
+) -> Result<Vec<storage_reconcile_run::Model>, sea_orm::DbErr> {
+    use sea_orm::QueryOrder;
+    StorageReconcileRun::find()
+        .order_by_desc(storage_reconcile_run::Column::StartedAt)
+        .limit(limit)
+        .all(db)
+        .await
+}
+
+/// Optional periodic reconcile, enabled by STORAGE_RECONCILE_INTERVAL_HOURS.
+pub async fn start_reconcile_scheduler(db: DatabaseConnection, gcs_client: GcsClient) {
+    let Some(interval_hours) = std::env::var("STORAGE_RECONCILE_INTERVAL_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|h| *h > 0)
+    else {
+        return;
+    };
+    let Ok(bucket) = std::env::var("GCS_BUCKET_NAME") else {
+        tracing::warn!("GCS_BUCKET_NAME not set; scheduled storage reconcile disabled");
+        return;
+    };
+    let dry_run = std::env::var("STORAGE_RECONCILE_DRY_RUN")
+        .map(|v| v == "true")
+        .unwrap_or(false);
+
+    tokio::spawn(async move {
+        tracing::info!(
+            "Storage reconcile scheduled every {}h (dry_run={})",
+            interval_hours,
+            dry_run
+        );
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(interval_hours * 3600)).await;
+            match start_reconcile_run(&db, dry_run).await {
+                Ok(run) => run_reconcile(&db, &gcs_client, &bucket, run).await,
+                Err(e) => tracing::error!("Failed to start storage reconcile: {}", e),
+            }
+        }
+    });
+}
+
+/// Splits a `gs://bucket/object` path into bucket and object name.
+pub fn parse_gs_path(path: &str) -> Option<(&str, &str)> {
+    let (bucket, object) = path.strip_prefix("gs://")?.split_once('/')?;
+    (!bucket.is_empty() && !object.is_empty()).then_some((bucket, object))
+}
+
+/// Videos (id and storage path) belonging to any of `pet_ids`. Read these
+/// before deleting the pets, since the rows cascade away with them.
+pub async fn videos_for_pets(
+    db: &DatabaseConnection,
+    pet_ids: Vec<i32>,
+) -> Result<Vec<(Uuid, String)>, sea_orm::DbErr> {
+    if pet_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    PetVideo::find()
+        .select_only()
+        .column(pet_video::Column::Id)
+        .column(pet_video::Column::FilePath)
+        .filter(pet_video::Column::PetId.is_in(pet_ids))
+        .into_tuple::<(Uuid, String)>()
+        .all(db)
+        .await
+}
+
+/// Deletes one `gs://` object, retrying transient failures with backoff. An
+/// object that is already gone counts as deleted.
+pub async fn delete_object(gcs_client: &GcsClient, path: &str) -> bool {
+    let Some((bucket, object)) = parse_gs_path(path) else {
+        tracing::warn!("Skipping delete of unrecognized storage path {}", path);
+        return false;
+    };
+
+    for attempt in 1..=DELETE_ATTEMPTS {
+        let result = gcs_client
+            .delete_object(&DeleteObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            })
+            .await;
+        match result {
+            Ok(()) => return true,
+            Err(google_cloud_storage::http::Error::Response(e)) if e.code == 404 => return true,
+            Err(e) if attempt < DELETE_ATTEMPTS => {
+                tracing::warn!(
+                    "Failed to delete {} (attempt {}/{}): {}",
+                    path,
+                    attempt,
+                    DELETE_ATTEMPTS,
+                    e
+                );
+                tokio::time::sleep(tokio::time::Duration::from_millis(
+                    DELETE_RETRY_BASE_MS << (attempt - 1),
+                ))
+                .await;
+            }
+            Err(e) => {
+                tracing::error!("Giving up deleting {}: {}", path, e);
+                metrics::counter!("petpulse_gcs_deletes_total", "result" => "failed").increment(1);
+            }
+        }
+    }
+    false
+}
+
+/// Deletes the objects behind `file_paths`. Meant to run in a spawned task
+/// after the rows pointing at them have been removed; anything it can't
+/// delete is picked up later by the orphan reconcile.
+pub async fn delete_objects(gcs_client: GcsClient, file_paths: Vec<String>) {
+    let mut deleted = 0;
+    for path in &file_paths {
+        if delete_object(&gcs_client, path).await {
+            deleted += 1;
+        }
+    }
+
+    metrics::counter!("petpulse_gcs_deletes_total", "result" => "deleted").increment(deleted);
+    tracing::info!(
+        "Deleted {}/{} objects from storage",
+        deleted,
+        file_paths.len()
+    );
+}
+
+/// Removes queued video jobs for `video_ids` and digest jobs for `pet_ids`.
+/// Returns how many entries were dropped from each queue. Jobs a worker
+/// already popped are handled by the worker's missing-row check.
+pub async fn purge_queued_jobs(
+    conn: &mut redis::aio::MultiplexedConnection,
+    video_ids: &HashSet<Uuid>,
+    pet_ids: &HashSet<i32>,
+) -> redis::RedisResult<(u64, u64)> {
+    let is_purged_video = |job: &serde_json::Value| {
+        job["video_id"]
+            .as_str()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .is_some_and(|id| video_ids.contains(&id))
+    };
+    let videos = purge_queue(conn, crate::video_queue::NORMAL, is_purged_video).await?
+        + purge_queue(conn, crate::video_queue::HIGH, is_purged_video).await?;
+    let digests = purge_queue(conn, "digest_queue", |job| {
+        job["pet_id"]
+            .as_i64()
+            .is_some_and(|id| pet_ids.contains(&(id as i32)))
+    })
+    .await?;
+    Ok((videos, digests))
+}
+
+async fn purge_queue(
+    conn: &mut redis::aio::MultiplexedConnection,
+    queue: &str,
+    matches: impl Fn(&serde_json::Value) -> bool,
+) -> redis::RedisResult<u64> {
+    use redis::AsyncCommands;
+
+    let entries: Vec<String> = conn.lrange(queue, 0, -1).await?;
+    let mut removed = 0;
+    for entry in entries {
+        let is_match = serde_json::from_str::<serde_json::Value>(&entry)
+            .map(|job| matches(&job))
+            .unwrap_or(false);
+        if is_match {
+            let count: u64 = conn.lrem(queue, 0, &entry).await?;
+            removed += count;
+        }
+    }
+    Ok(removed)
+}
@@ -59,3 +59,9 @@ pub fn init_telemetry(service_name: &str) {
         registry.with(otel_layer).with(fmt_layer).init();
     };
 }
+
+/// Flushes spans still buffered in the OTLP batch exporter. Blocks until the
+/// export finishes, so call it from a blocking task.
+pub fn shutdown_telemetry() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
@@ -0,0 +1,155 @@
+//! Fixtures shared by the in-crate tests.
+
+use crate::entities::{
+    alerts, api_key, daily_digest, password_reset_token, pet, pet_caretaker, pet_share,
+    pet_share_access, pet_video, pet_weight, user, video_tag,
+};
+use axum::body::Bytes;
+use axum::{
+    extract::{FromRequest, Multipart},
+    http::{header, StatusCode},
+    response::Response,
+};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, Database, DatabaseConnection, EntityTrait, IntoActiveModel,
+    Schema,
+};
+
+/// An active pet `id` owned by `user_id`.
+pub fn pet(id: i32, user_id: i32) -> pet::Model {
+    let now = chrono::Utc::now().naive_utc();
+    pet::Model {
+        id,
+        user_id,
+        name: "Rex".to_string(),
+        age: 3,
+        species: "dog".to_string(),
+        breed: String::new(),
+        bio: String::new(),
+        created_at: now,
+        updated_at: now,
+        known_behaviors: serde_json::json!([]),
+        static_check_disabled: false,
+        photo_path: None,
+        weight_kg: None,
+        medical_conditions: None,
+        medications: None,
+        vet_name: None,
+        vet_phone: None,
+        archived_at: None,
+        behavior_baseline: None,
+        monitoring_schedule: None,
+    }
+}
+
+/// Status and catalog `code` of an [`crate::api::error::ApiError`] response.
+pub async fn error_code(response: Response) -> (StatusCode, String) {
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+    let code = body["code"].as_str().unwrap_or_default().to_string();
+    (status, code)
+}
+
+async fn create_table<E: EntityTrait>(db: &DatabaseConnection, entity: E) {
+    let schema = Schema::new(db.get_database_backend());
+    db.execute(
+        db.get_database_backend()
+            .build(&schema.create_table_from_entity(entity)),
+    )
+    .await
+    .unwrap();
+}
+
+/// An in-memory SQLite database with the tables handlers under test touch.
+/// The schema comes from the entities, not the (Postgres) migrations.
+pub async fn database() -> DatabaseConnection {
+    let mut options = sea_orm::ConnectOptions::new("sqlite::memory:");
+    // Every pooled connection would otherwise get its own empty database
+    options.max_connections(1).sqlx_logging(false);
+    let db = Database::connect(options).await.unwrap();
+    create_table(&db, user::Entity).await;
+    create_table(&db, pet::Entity).await;
+    create_table(&db, pet_caretaker::Entity).await;
+    create_table(&db, pet_video::Entity).await;
+    create_table(&db, video_tag::Entity).await;
+    create_table(&db, alerts::Entity).await;
+    create_table(&db, daily_digest::Entity).await;
+    create_table(&db, pet_weight::Entity).await;
+    create_table(&db, pet_share::Entity).await;
+    create_table(&db, pet_share_access::Entity).await;
+    create_table(&db, api_key::Entity).await;
+    create_table(&db, password_reset_token::Entity).await;
+    db
+}
+
+/// Stores user `id` with pet `pet_id`, returning the pet.
+pub async fn owner_with_pet(db: &DatabaseConnection, id: i32, pet_id: i32) -> pet::Model {
+    let now = chrono::Utc::now().naive_utc();
+    user::Model {
+        id,
+        email: format!("owner{}@example.com", id),
+        password_hash: String::new(),
+        name: format!("Owner {}", id),
+        created_at: now,
+        updated_at: now,
+        recovery_notifications_enabled: true,
+        phone: None,
+        timezone: String::from("UTC"),
+        google_sub: None,
+        is_admin: false,
+        video_retention_days: None,
+    }
+    .into_active_model()
+    .reset_all()
+    .insert(db)
+    .await
+    .unwrap();
+    pet(pet_id, id)
+        .into_active_model()
+        .reset_all()
+        .insert(db)
+        .await
+        .unwrap()
+}
+
+/// A multipart body with one `name` field holding `data`, sent in small
+/// pieces that arrive one at a time, the way a client's upload does.
+pub async fn multipart(name: &str, file_name: &str, data: &[u8]) -> Multipart {
+    let mut body = format!(
+        "--X\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n",
+        name, file_name
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\r\n--X--\r\n");
+    let pieces: Vec<Result<Bytes, std::io::Error>> = body
+        .chunks(4096)
+        .map(|piece| Ok(Bytes::copy_from_slice(piece)))
+        .collect();
+    let pieces = futures::StreamExt::then(
+        futures::stream::iter(pieces.into_iter().enumerate()),
+        |(i, piece)| async move {
+            if i > 0 {
+                tokio::task::yield_now().await;
+            }
+            piece
+        },
+    );
+    let request = axum::http::Request::post("/")
+        .header(header::CONTENT_TYPE, "multipart/form-data; boundary=X")
+        .body(axum::body::Body::from_stream(pieces))
+        .unwrap();
+    // Size caps are the handler's to enforce here, not axum's 2 MB default
+    let extract = tower::service_fn(|request| async move {
+        Ok::<_, std::convert::Infallible>(Multipart::from_request(request, &()).await.unwrap())
+    });
+    tower::ServiceExt::oneshot(
+        tower::Layer::layer(&axum::extract::DefaultBodyLimit::disable(), extract),
+        request,
+    )
+    .await
+    .unwrap()
+}
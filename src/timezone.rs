@@ -0,0 +1,150 @@
+//! Owner-local dates. Digests are keyed by the day in the owner's timezone,
+//! not the UTC day the video was recorded on.
+
+use crate::entities::{pet, user};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// Parses an IANA zone name, e.g. `America/Los_Angeles`.
+pub fn parse(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+/// The zone stored on a user, falling back to UTC for anything unparseable.
+pub fn of_user(user: &user::Model) -> Tz {
+    parse(&user.timezone).unwrap_or(Tz::UTC)
+}
+
+/// Zone of the pet's owner, or UTC if the pet or owner can't be loaded.
+pub async fn of_pet_owner(db: &DatabaseConnection, pet_id: i32) -> Tz {
+    match pet::Entity::find_by_id(pet_id)
+        .find_also_related(user::Entity)
+        .one(db)
+        .await
+    {
+        Ok(Some((_, Some(owner)))) => of_user(&owner),
+        Ok(_) => Tz::UTC,
+        Err(e) => {
+            tracing::warn!("Failed to load owner timezone for pet {}: {}", pet_id, e);
+            Tz::UTC
+        }
+    }
+}
+
+/// Calendar date of `at` in `tz`.
+pub fn local_date<O: TimeZone>(at: &DateTime<O>, tz: Tz) -> NaiveDate {
+    at.with_timezone(&tz).date_naive()
+}
+
+/// UTC range covering `date` in every timezone (UTC-12 through UTC+14), for
+/// prefiltering queries before bucketing rows by each owner's zone.
+pub fn widest_day_bounds(date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    (
+        midnight - chrono::Duration::hours(14),
+        midnight + chrono::Duration::hours(24 + 12),
+    )
+}
+
+/// UTC range `[start, end)` of `date` as a local day in `tz`.
+pub fn day_bounds(date: NaiveDate, tz: Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        local_midnight(date, tz),
+        local_midnight(date + chrono::Duration::days(1), tz),
+    )
+}
+
+/// First instant of `date` in `tz`. Zones whose DST change skips midnight
+/// start that day at the end of the gap instead.
+fn local_midnight(date: NaiveDate, tz: Tz) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    (0..=2)
+        .find_map(|hour| {
+            tz.from_local_datetime(&(midnight + chrono::Duration::hours(hour)))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn utc_day_is_its_own_bounds() {
+        assert_eq!(
+            day_bounds(date(2026, 3, 1), Tz::UTC),
+            (utc("2026-03-01T00:00:00Z"), utc("2026-03-02T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn local_day_shifts_by_the_offset() {
+        assert_eq!(
+            day_bounds(date(2026, 1, 15), chrono_tz::America::Los_Angeles),
+            (utc("2026-01-15T08:00:00Z"), utc("2026-01-16T08:00:00Z"))
+        );
+        assert_eq!(
+            day_bounds(date(2026, 1, 15), chrono_tz::Asia::Tokyo),
+            (utc("2026-01-14T15:00:00Z"), utc("2026-01-15T15:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn dst_days_are_23_and_25_hours() {
+        let tz = chrono_tz::America::New_York;
+        let (start, end) = day_bounds(date(2026, 3, 8), tz);
+        assert_eq!(end - start, chrono::Duration::hours(23));
+        let (start, end) = day_bounds(date(2026, 11, 1), tz);
+        assert_eq!(end - start, chrono::Duration::hours(25));
+    }
+
+    #[test]
+    fn day_without_a_midnight_starts_after_the_gap() {
+        // Chile springs forward at midnight, so 6 September 2026 starts at 01:00
+        let (start, _) = day_bounds(date(2026, 9, 6), chrono_tz::America::Santiago);
+        assert_eq!(start, utc("2026-09-06T04:00:00Z"));
+    }
+
+    #[test]
+    fn widest_bounds_cover_every_zone() {
+        let day = date(2026, 3, 1);
+        let (widest_start, widest_end) = widest_day_bounds(day);
+        for tz in [
+            chrono_tz::Pacific::Kiritimati,
+            chrono_tz::Etc::GMTPlus12,
+            Tz::UTC,
+        ] {
+            let (start, end) = day_bounds(day, tz);
+            assert!(widest_start <= start && end <= widest_end, "{}", tz);
+        }
+    }
+
+    #[test]
+    fn local_date_uses_the_zone() {
+        let at = utc("2026-03-01T03:00:00Z");
+        assert_eq!(local_date(&at, Tz::UTC), date(2026, 3, 1));
+        assert_eq!(
+            local_date(&at, chrono_tz::America::Chicago),
+            date(2026, 2, 28)
+        );
+    }
+
+    #[test]
+    fn unknown_zone_names_are_rejected() {
+        assert_eq!(parse("Europe/Paris"), Some(chrono_tz::Europe::Paris));
+        assert_eq!(parse("Mars/Olympus_Mons"), None);
+    }
+}
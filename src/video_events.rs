@@ -0,0 +1,92 @@
+//! Video lifecycle events for live UIs. Each status transition is published
+//! as JSON on the pet's `video_events:<pet_id>` pub/sub channel, which
+//! `GET /pets/:id/events` relays to the browser as Server-Sent Events.
+//! Publishing is best-effort: clients that miss an event still see the
+//! status the next time they load the video.
+
+use crate::entities::pet_video;
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const CHANNEL_PREFIX: &str = "video_events:";
+
+pub fn channel(pet_id: i32) -> String {
+    format!("{}{}", CHANNEL_PREFIX, pet_id)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoEventKind {
+    /// Waiting for a worker, including between retries
+    Queued,
+    Processing,
+    Processed,
+    Failed,
+}
+
+impl VideoEventKind {
+    /// SSE event name.
+    pub fn name(self) -> &'static str {
+        match self {
+            VideoEventKind::Queued => "queued",
+            VideoEventKind::Processing => "processing",
+            VideoEventKind::Processed => "processed",
+            VideoEventKind::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoEvent {
+    pub event: VideoEventKind,
+    pub video_id: Uuid,
+    pub pet_id: i32,
+    /// The row's status as stored, e.g. `Retrying` for a queued retry
+    pub status: String,
+    pub error_stage: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl VideoEvent {
+    pub fn new(event: VideoEventKind, video: &pet_video::Model) -> Self {
+        Self {
+            event,
+            video_id: video.id,
+            pet_id: video.pet_id,
+            status: video.status.clone(),
+            error_stage: video.error_stage.clone(),
+            at: Utc::now(),
+        }
+    }
+}
+
+/// Publishes `event` for `video` on its pet's channel.
+pub async fn publish(
+    conn: &mut MultiplexedConnection,
+    event: VideoEventKind,
+    video: &pet_video::Model,
+) {
+    let body = match serde_json::to_string(&VideoEvent::new(event, video)) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Failed to serialize video event for {}: {}", video.id, e);
+            return;
+        }
+    };
+    let published: redis::RedisResult<i64> = conn.publish(channel(video.pet_id), body).await;
+    match published {
+        Ok(_) => {
+            metrics::counter!("petpulse_video_events_published_total", "event" => event.name())
+                .increment(1);
+        }
+        Err(e) => tracing::warn!(
+            "Failed to publish {} event for {}: {}",
+            event.name(),
+            video.id,
+            e
+        ),
+    }
+}
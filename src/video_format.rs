@@ -0,0 +1,85 @@
+//! Container detection for uploaded videos. File names and client-supplied
+//! MIME types are easy to get wrong (or fake), so uploads are identified by
+//! their leading bytes before anything is queued for analysis.
+
+use google_cloud_storage::client::Client as GcsClient;
+use google_cloud_storage::http::objects::download::Range;
+use google_cloud_storage::http::objects::get::GetObjectRequest;
+
+/// How many leading bytes [`sniff`] needs to see.
+pub const SNIFF_BYTES: usize = 64;
+
+/// `ftyp` brands of ISO media files that are images, not video.
+const IMAGE_BRANDS: &[&[u8]] = &[b"heic", b"heix", b"mif1", b"msf1", b"avif", b"avis"];
+
+/// Top-level QuickTime atoms that old `.mov` files open with instead of `ftyp`.
+const QUICKTIME_ATOMS: &[&[u8]] = &[b"moov", b"mdat", b"wide", b"free", b"skip"];
+
+const EBML_MAGIC: &[u8] = &[0x1A, 0x45, 0xDF, 0xA3];
+
+/// Content type of a supported container (MP4, MOV, WebM, MKV), from the
+/// first bytes of the file.
+pub fn sniff(head: &[u8]) -> Option<&'static str> {
+    if head.starts_with(EBML_MAGIC) {
+        // Both are EBML; the DocType element in the header tells them apart
+        let header = &head[..head.len().min(SNIFF_BYTES)];
+        return Some(if header.windows(4).any(|w| w == b"webm") {
+            "video/webm"
+        } else {
+            "video/x-matroska"
+        });
+    }
+
+    let atom = head.get(4..8)?;
+    if atom == b"ftyp" {
+        let brand = head.get(8..12)?;
+        if IMAGE_BRANDS.contains(&brand) {
+            return None;
+        }
+        return Some(if brand == b"qt  " {
+            "video/quicktime"
+        } else {
+            "video/mp4"
+        });
+    }
+    QUICKTIME_ATOMS.contains(&atom).then_some("video/quicktime")
+}
+
+/// `VIDEO_SNIFFING_DISABLED=true` trusts the file name instead, for debugging
+/// with files the sniffer doesn't recognize.
+pub fn sniffing_enabled() -> bool {
+    !std::env::var("VIDEO_SNIFFING_DISABLED")
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+/// Content type to store for an upload, or `None` when it isn't a supported
+/// video container.
+pub fn detect(head: &[u8], file_name: &str) -> Option<String> {
+    if !sniffing_enabled() {
+        return Some(
+            mime_guess::from_path(file_name)
+                .first_or_octet_stream()
+                .to_string(),
+        );
+    }
+    sniff(head).map(str::to_string)
+}
+
+/// Reads the first [`SNIFF_BYTES`] of an object already in GCS.
+pub async fn object_head(
+    gcs_client: &GcsClient,
+    bucket: &str,
+    object: &str,
+) -> Result<Vec<u8>, google_cloud_storage::http::Error> {
+    gcs_client
+        .download_object(
+            &GetObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            },
+            &Range(Some(0), Some(SNIFF_BYTES as u64 - 1)),
+        )
+        .await
+}
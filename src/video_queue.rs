@@ -0,0 +1,67 @@
+//! The video job lists. Uploads someone is actively waiting on can go to
+//! `video_queue:high`, which workers always drain before `video_queue`.
+//! Each job carries its priority, so retries go back to the list they came from.
+
+use crate::entities::{user, User};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::{Deserialize, Serialize};
+
+pub const NORMAL: &str = "video_queue";
+pub const HIGH: &str = "video_queue:high";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "normal" => Some(Priority::Normal),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Normal => "normal",
+            Priority::High => "high",
+        }
+    }
+
+    pub fn queue(self) -> &'static str {
+        match self {
+            Priority::Normal => NORMAL,
+            Priority::High => HIGH,
+        }
+    }
+}
+
+/// Users listed in `HIGH_PRIORITY_USER_IDS` (comma-separated).
+fn listed_user_ids() -> Vec<i32> {
+    std::env::var("HIGH_PRIORITY_USER_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect()
+}
+
+/// Whether `user` may queue high-priority work: admins and listed users.
+pub fn allows_high(user: &user::Model) -> bool {
+    user.is_admin || listed_user_ids().contains(&user.id)
+}
+
+pub async fn user_allows_high(db: &DatabaseConnection, user_id: i32) -> bool {
+    match User::find_by_id(user_id).one(db).await {
+        Ok(Some(user)) => allows_high(&user),
+        Ok(None) => false,
+        Err(e) => {
+            tracing::error!("Failed to load user {} for priority check: {}", user_id, e);
+            false
+        }
+    }
+}
@@ -1,20 +1,266 @@
 use crate::agent::comfort_loop::{AlertPayload, AlertType};
-use crate::entities::{daily_digest, pet_video, DailyDigest, PetVideo};
+use crate::entities::{clip, daily_digest, pet_video, Clip, DailyDigest, Pet, PetVideo};
 use crate::gemini::GeminiClient;
+use crate::jobs::{self, DigestJob, VideoJob};
+use crate::poison;
+use crate::processing_list;
+use crate::video_events::{self, VideoEventKind};
+use crate::video_queue::{self, Priority};
 use chrono::{NaiveDate, Utc};
 use google_cloud_storage::client::Client as GcsClient;
 use google_cloud_storage::http::objects::download::Range;
 use google_cloud_storage::http::objects::get::GetObjectRequest;
+use google_cloud_storage::http::objects::upload::{Media, UploadObjectRequest, UploadType};
 use redis::AsyncCommands;
 use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde_json::Value;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
 use uuid::Uuid;
 
+/// Redis key the queue monitor publishes the latest video backlog to (both
+/// `video_queue` and `video_queue:high`), so the API can read it cheaply on
+/// every upload.
+pub const VIDEO_QUEUE_DEPTH_KEY: &str = "petpulse:queue_depth:video_queue";
+const QUEUE_DEPTH_KEY_TTL_SECS: u64 = 60;
+
+/// Sorted set of video jobs waiting out a retry delay, scored by the unix
+/// time they're due back on video_queue.
+pub const VIDEO_RETRY_SET: &str = "video_retry";
+const RETRY_POLL_SECS: u64 = 5;
+const RETRY_DRAIN_BATCH: isize = 100;
+
+/// How long a worker blocks on its queue before checking for shutdown.
+const QUEUE_POLL_SECS: f64 = 1.0;
+const PROCESSING_RECOVERY_SECS: u64 = 60;
+
+/// How often a parked worker checks whether it's been let back in.
+const PARKED_POLL_SECS: u64 = 1;
+const AUTOSCALE_CHECK_SECS: u64 = 15;
+/// Allowance on top of the analysis timeout for a job's download and saving.
+const HEARTBEAT_GRACE_SECS: i64 = 5 * 60;
+/// Queued jobs each active worker is expected to absorb before the
+/// autoscaler adds another.
+const AUTOSCALE_JOBS_PER_WORKER: u64 = 10;
+
+const STALE_WORK_FILE_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_ANALYSIS_TIMEOUT_SECS: u64 = 10 * 60;
+const DEFAULT_STUCK_AFTER_SECS: i64 = 30 * 60;
+const STUCK_SWEEP_SECS: u64 = 5 * 60;
+const STUCK_SWEEP_BATCH: u64 = 500;
+
+/// Raw payload each worker is running right now, by worker index.
+type InFlight = Arc<Mutex<HashMap<usize, String>>>;
+
+/// Where a pool keeps the jobs its workers have taken but not finished.
+enum JobClaims {
+    /// Held in memory, so a crashed process loses them
+    InFlight(InFlight),
+    /// Per-worker processing lists in Redis, for video_queue
+    ProcessingLists {
+        lists: Vec<String>,
+        db: Arc<DatabaseConnection>,
+    },
+}
+
+/// Workers started by `start_workers` or `start_digest_workers`. They stop
+/// taking jobs once the shutdown token is cancelled; `drain` then waits for
+/// the jobs already running.
+pub struct WorkerPool {
+    queue: &'static str,
+    handles: Vec<JoinHandle<()>>,
+    claims: JobClaims,
+}
+
+impl WorkerPool {
+    /// Waits up to `timeout` for in-flight jobs to finish. Anything still
+    /// running after that is aborted and pushed back onto the front of the
+    /// queue so the next worker process picks it up first.
+    pub async fn drain(self, redis_client: &redis::Client, timeout: tokio::time::Duration) {
+        let WorkerPool {
+            queue,
+            mut handles,
+            claims,
+        } = self;
+
+        let finished =
+            tokio::time::timeout(timeout, futures::future::join_all(handles.iter_mut())).await;
+        if finished.is_ok() {
+            tracing::info!("All {} workers finished", queue);
+            return;
+        }
+
+        for handle in &handles {
+            handle.abort();
+        }
+        // Let the aborts land so no worker touches its claims after this
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let mut conn = match redis_client.get_multiplexed_async_connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!(
+                    "No redis connection to requeue interrupted {} jobs: {}",
+                    queue,
+                    e
+                );
+                return;
+            }
+        };
+        let requeued = match claims {
+            JobClaims::InFlight(in_flight) => {
+                let interrupted: Vec<String> = in_flight
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .drain()
+                    .map(|(_, payload)| payload)
+                    .collect();
+                let mut requeued = 0;
+                for payload in interrupted {
+                    let pushed: redis::RedisResult<()> = conn.lpush(queue, &payload).await;
+                    match pushed {
+                        Ok(()) => requeued += 1,
+                        Err(e) => tracing::error!(
+                            "Failed to requeue interrupted {} job {}: {}",
+                            queue,
+                            payload,
+                            e
+                        ),
+                    }
+                }
+                requeued
+            }
+            JobClaims::ProcessingLists { lists, db } => {
+                let mut moved = Vec::new();
+                for list in &lists {
+                    match processing_list::requeue(&mut conn, list).await {
+                        Ok(payloads) => moved.extend(payloads),
+                        Err(e) => tracing::error!("Failed to requeue {}: {}", list, e),
+                    }
+                }
+                reset_requeued_videos(&db, &moved).await;
+                moved.len()
+            }
+        };
+        if requeued > 0 {
+            tracing::warn!(
+                "Shutdown timed out; requeued {} interrupted {} jobs",
+                requeued,
+                queue
+            );
+            metrics::counter!("petpulse_jobs_requeued_on_shutdown_total", "queue" => queue)
+                .increment(requeued as u64);
+        }
+    }
+}
+
+/// Resets the rows behind video jobs that were put back on video_queue.
+async fn reset_requeued_videos(db: &DatabaseConnection, payloads: &[String]) {
+    for payload in payloads {
+        if let Some(video_id) = jobs::video_id_of(payload) {
+            reset_interrupted_video(db, video_id).await;
+        }
+    }
+}
+
+/// Puts a video cut off mid-analysis back to PENDING.
+async fn reset_interrupted_video(db: &DatabaseConnection, video_id: Uuid) {
+    let reset = PetVideo::update_many()
+        .col_expr(
+            pet_video::Column::Status,
+            sea_orm::sea_query::Expr::value("PENDING"),
+        )
+        .col_expr(
+            pet_video::Column::QueuedAt,
+            sea_orm::sea_query::Expr::value(chrono::DateTime::<chrono::FixedOffset>::from(
+                Utc::now(),
+            )),
+        )
+        .filter(pet_video::Column::Id.eq(video_id))
+        .filter(pet_video::Column::Status.eq("PROCESSING"))
+        .exec(db)
+        .await;
+    if let Err(e) = reset {
+        tracing::error!("Failed to reset interrupted video {}: {}", video_id, e);
+    }
+}
+
+/// Backlog alarm for one queue. Fires when depth (or the age of the oldest
+/// entry) crosses its threshold and only clears once both fall below half of
+/// it, so a queue hovering around the limit doesn't flap.
+struct QueueAlarm {
+    queue: &'static str,
+    depth_threshold: u64,
+    age_threshold_secs: Option<i64>,
+    firing: bool,
+}
+
+impl QueueAlarm {
+    fn new(queue: &'static str, depth_env: &str, depth_default: u64) -> Self {
+        Self {
+            queue,
+            depth_threshold: env_var_or(depth_env, depth_default),
+            age_threshold_secs: None,
+            firing: false,
+        }
+    }
+
+    fn with_age_threshold(mut self, age_env: &str, age_default: i64) -> Self {
+        self.age_threshold_secs = Some(env_var_or(age_env, age_default));
+        self
+    }
+
+    /// Returns Some(firing) when the alarm changes state.
+    fn evaluate(&mut self, depth: u64, oldest_age_secs: Option<i64>) -> Option<bool> {
+        let age = oldest_age_secs.unwrap_or(0);
+        let breached =
+            depth >= self.depth_threshold || self.age_threshold_secs.is_some_and(|t| age >= t);
+        let recovered =
+            depth < self.depth_threshold / 2 && self.age_threshold_secs.is_none_or(|t| age < t / 2);
+
+        if !self.firing && breached {
+            self.firing = true;
+            Some(true)
+        } else if self.firing && recovered {
+            self.firing = false;
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+fn env_var_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Age of the entry at the head of video_queue, from its pet_video queued_at stamp.
+async fn oldest_video_queue_age_secs(
+    conn: &mut redis::aio::MultiplexedConnection,
+    db: &DatabaseConnection,
+) -> Option<i64> {
+    let head: Option<String> = conn.lindex("video_queue", 0).await.ok()?;
+    let head: Value = serde_json::from_str(&head?).ok()?;
+    let video_id = head.get("video_id")?.as_str()?.parse::<Uuid>().ok()?;
+    let video = PetVideo::find_by_id(video_id).one(db).await.ok()??;
+    let queued_at = video.queued_at?;
+    Some((Utc::now() - queued_at.with_timezone(&Utc)).num_seconds())
+}
+
 // Queue Monitoring
-pub async fn start_queue_monitor(redis_client: redis::Client) {
+pub async fn start_queue_monitor(redis_client: redis::Client, db: DatabaseConnection) {
     let redis_client = Arc::new(redis_client);
+    let mut video_alarm = QueueAlarm::new("video_queue", "VIDEO_QUEUE_ALERT_DEPTH", 500)
+        .with_age_threshold("VIDEO_QUEUE_ALERT_AGE_SECS", 900);
+    let mut digest_alarm = QueueAlarm::new("digest_queue", "DIGEST_QUEUE_ALERT_DEPTH", 1000);
 
     // Spawn a background task
     tokio::spawn(async move {
@@ -29,17 +275,93 @@ pub async fn start_queue_monitor(redis_client: redis::Client) {
                 }
             };
 
+            let high_len: redis::RedisResult<u64> = conn.llen(video_queue::HIGH).await;
+            match &high_len {
+                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => video_queue::HIGH)
+                    .set(*len as f64),
+                Err(e) => tracing::error!("Failed to get video_queue:high len: {}", e),
+            }
+
             let video_queue_len: redis::RedisResult<u64> = conn.llen("video_queue").await;
             match video_queue_len {
-                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => "video_queue")
-                    .set(len as f64),
+                Ok(len) => {
+                    metrics::gauge!("petpulse_queue_depth", "queue" => "video_queue")
+                        .set(len as f64);
+                    // High-priority jobs are taken first, so they count toward every wait
+                    if let Ok(high) = high_len {
+                        let _: redis::RedisResult<()> = conn
+                            .set_ex(VIDEO_QUEUE_DEPTH_KEY, len + high, QUEUE_DEPTH_KEY_TTL_SECS)
+                            .await;
+                    }
+
+                    let oldest_age = if len > 0 {
+                        oldest_video_queue_age_secs(&mut conn, &db).await
+                    } else {
+                        None
+                    };
+                    if let Some(age) = oldest_age {
+                        metrics::gauge!("petpulse_queue_oldest_age_seconds", "queue" => "video_queue")
+                            .set(age as f64);
+                    }
+                    if let Some(firing) = video_alarm.evaluate(len, oldest_age) {
+                        let drain = crate::api::daily_digest::estimated_wait_secs(len);
+                        tokio::spawn(send_queue_depth_webhook(
+                            video_alarm.queue,
+                            firing,
+                            len,
+                            oldest_age,
+                            drain,
+                        ));
+                    }
+                }
                 Err(e) => tracing::error!("Failed to get video_queue len: {}", e),
             }
 
+            let retry_len: redis::RedisResult<u64> = conn.zcard(VIDEO_RETRY_SET).await;
+            match retry_len {
+                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => VIDEO_RETRY_SET)
+                    .set(len as f64),
+                Err(e) => tracing::error!("Failed to get video_retry len: {}", e),
+            }
+
+            match processing_list::depth(&mut conn).await {
+                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => "video_processing")
+                    .set(len as f64),
+                Err(e) => tracing::error!("Failed to get video_processing depth: {}", e),
+            }
+
+            match crate::dead_letter::len(&mut conn).await {
+                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => crate::dead_letter::VIDEO_DLQ)
+                    .set(len as f64),
+                Err(e) => tracing::error!("Failed to get video_dlq len: {}", e),
+            }
+
+            for queue in poison::QUEUES {
+                match poison::len(&mut conn, queue).await {
+                    Ok(len) => metrics::gauge!("petpulse_poison_queue_depth", "queue" => *queue)
+                        .set(len as f64),
+                    Err(e) => {
+                        tracing::error!("Failed to get {} len: {}", poison::list_key(queue), e)
+                    }
+                }
+            }
+
             let digest_queue_len: redis::RedisResult<u64> = conn.llen("digest_queue").await;
             match digest_queue_len {
-                Ok(len) => metrics::gauge!("petpulse_queue_depth", "queue" => "digest_queue")
-                    .set(len as f64),
+                Ok(len) => {
+                    metrics::gauge!("petpulse_queue_depth", "queue" => "digest_queue")
+                        .set(len as f64);
+                    if let Some(firing) = digest_alarm.evaluate(len, None) {
+                        tokio::spawn(send_queue_depth_webhook(
+                            digest_alarm.queue,
+                            firing,
+                            len,
+                            None,
+                            // Digest rebuilds are cheap; roughly one per second per worker
+                            len,
+                        ));
+                    }
+                }
                 Err(e) => tracing::error!("Failed to get digest_queue len: {}", e),
             }
 
@@ -48,14 +370,275 @@ pub async fn start_queue_monitor(redis_client: redis::Client) {
     });
 }
 
+/// Watches pets with a monitoring schedule and raises an UploadsOverdue alert
+/// when clips stop arriving. Each pet alerts once per silence and re-arms
+/// only after a clip comes in again.
+pub async fn start_upload_watch(db: DatabaseConnection) {
+    use crate::entities::user;
+    use sea_orm::QueryOrder;
+
+    let check_secs: u64 = env_var_or("UPLOAD_WATCH_INTERVAL_SECS", 300);
+
+    tokio::spawn(async move {
+        tracing::info!("Upload watch started (every {}s)", check_secs);
+        let mut overdue: std::collections::HashSet<i32> = std::collections::HashSet::new();
+        loop {
+            let pets = match Pet::find_active()
+                .filter(crate::entities::pet::Column::MonitoringSchedule.is_not_null())
+                .find_also_related(user::Entity)
+                .all(&db)
+                .await
+            {
+                Ok(pets) => pets,
+                Err(e) => {
+                    tracing::error!("Upload watch: failed to load scheduled pets: {}", e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(check_secs)).await;
+                    continue;
+                }
+            };
+
+            // Pets whose schedule was removed start fresh if it comes back
+            overdue.retain(|id| pets.iter().any(|(p, _)| p.id == *id));
+
+            let now = Utc::now();
+            for (pet, owner) in &pets {
+                let Some(schedule) = crate::monitoring::of_pet(pet) else {
+                    continue;
+                };
+                let tz = owner
+                    .as_ref()
+                    .map(crate::timezone::of_user)
+                    .unwrap_or(chrono_tz::Tz::UTC);
+
+                let last_upload = match PetVideo::find()
+                    .filter(pet_video::Column::PetId.eq(pet.id))
+                    .order_by_desc(pet_video::Column::CreatedAt)
+                    .one(&db)
+                    .await
+                {
+                    Ok(video) => video.map(|v| v.created_at.with_timezone(&Utc)),
+                    Err(e) => {
+                        tracing::error!(
+                            "Upload watch: failed to load videos for pet {}: {}",
+                            pet.id,
+                            e
+                        );
+                        continue;
+                    }
+                };
+
+                // Outside active hours nothing is expected, and the state carries over
+                let Some(silence) = schedule.silence(last_upload, now, tz) else {
+                    continue;
+                };
+
+                if silence >= schedule.overdue_after() {
+                    if overdue.insert(pet.id) {
+                        metrics::counter!("petpulse_uploads_overdue_total").increment(1);
+                        tokio::spawn(send_uploads_overdue_webhook(
+                            pet.id,
+                            silence.num_minutes(),
+                            schedule.expected_interval_minutes,
+                            last_upload,
+                        ));
+                    }
+                } else if silence < schedule.expected_interval() && overdue.remove(&pet.id) {
+                    tracing::info!("Uploads resumed for pet {}", pet.id);
+                }
+            }
+            metrics::gauge!("petpulse_pets_uploads_overdue").set(overdue.len() as f64);
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(check_secs)).await;
+        }
+    });
+}
+
+/// How many worker loops a pool runs. Fixed unless `adaptive`, when a
+/// supervisor moves the active count between `min` and `max` with the depth
+/// of the pool's queue. Loops above the active count stay parked.
+#[derive(Debug, Clone, Copy)]
+pub struct Concurrency {
+    pub min: usize,
+    pub max: usize,
+    pub adaptive: bool,
+}
+
+impl Concurrency {
+    pub fn fixed(workers: usize) -> Self {
+        let workers = workers.max(1);
+        Self {
+            min: workers,
+            max: workers,
+            adaptive: false,
+        }
+    }
+
+    /// `<prefix>_CONCURRENCY` workers, or with `<prefix>_AUTOSCALE=true`
+    /// between `<prefix>_MIN_CONCURRENCY` (default 1) and
+    /// `<prefix>_MAX_CONCURRENCY` (default twice the fixed count).
+    pub fn from_env(prefix: &str, default: usize) -> Self {
+        let read = |name: &str| {
+            std::env::var(format!("{}_{}", prefix, name))
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+        };
+        let fixed = read("CONCURRENCY").unwrap_or(default).max(1);
+        let adaptive = std::env::var(format!("{}_AUTOSCALE", prefix))
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        if !adaptive {
+            return Self::fixed(fixed);
+        }
+        let min = read("MIN_CONCURRENCY").unwrap_or(1);
+        let max = read("MAX_CONCURRENCY").unwrap_or(fixed * 2).max(min);
+        Self {
+            min,
+            max,
+            adaptive: true,
+        }
+    }
+
+    /// The video pool's size: `WORKER_CONCURRENCY` (default 3) and the
+    /// matching `WORKER_*` autoscale settings.
+    pub fn video_workers() -> Self {
+        Self::from_env("WORKER", 3)
+    }
+
+    /// Workers wanted for a queue this deep.
+    fn target(&self, depth: u64) -> usize {
+        let wanted = depth.div_ceil(AUTOSCALE_JOBS_PER_WORKER) as usize;
+        wanted.clamp(self.min, self.max)
+    }
+}
+
+/// When each worker loop last went round, for the worker's `/health`. A loop
+/// beats on every pass, parked or not, so one that stops beating is wedged
+/// (or stuck on a single job for longer than any job should take).
+#[derive(Clone, Default)]
+pub struct Heartbeats(Arc<Mutex<HashMap<(&'static str, usize), i64>>>);
+
+impl Heartbeats {
+    fn beat(&self, pool: &'static str, worker: usize) {
+        let now = Utc::now().timestamp();
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((pool, worker), now);
+        metrics::gauge!(
+            "petpulse_worker_last_heartbeat_seconds",
+            "pool" => pool,
+            "worker" => worker.to_string()
+        )
+        .set(now as f64);
+    }
+
+    /// Loops that haven't beaten in `max_age_secs`, as `pool:index`.
+    pub fn stale(&self, max_age_secs: i64) -> Vec<String> {
+        let cutoff = Utc::now().timestamp() - max_age_secs;
+        let mut stale: Vec<String> = self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, at)| **at < cutoff)
+            .map(|((pool, worker), _)| format!("{}:{}", pool, worker))
+            .collect();
+        stale.sort();
+        stale
+    }
+}
+
+/// `WORKER_HEARTBEAT_STALE_SECS`: how long a loop may go without beating
+/// before `/health` fails. Defaults to a little over the longest a single
+/// analysis may run, since a loop doesn't beat while it's on a job.
+pub fn heartbeat_stale_secs() -> i64 {
+    std::env::var("WORKER_HEARTBEAT_STALE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(analysis_timeout_secs() as i64 + HEARTBEAT_GRACE_SECS)
+}
+
+/// Loops a pool currently lets take jobs; workers at or above it are parked.
+type ActiveWorkers = Arc<std::sync::atomic::AtomicUsize>;
+
+fn is_parked(active: &ActiveWorkers, worker: usize) -> bool {
+    worker >= active.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Adjusts a pool's active worker count to its queue depth. Scales up to the
+/// target straight away but down one worker per check, so a queue that
+/// empties between bursts doesn't bounce the pool.
+async fn start_concurrency_supervisor(
+    redis_client: Arc<redis::Client>,
+    queues: &'static [&'static str],
+    concurrency: Concurrency,
+    active: ActiveWorkers,
+    shutdown: CancellationToken,
+) {
+    use std::sync::atomic::Ordering;
+
+    let queue = queues[0];
+    metrics::gauge!("petpulse_workers_active", "queue" => queue)
+        .set(active.load(Ordering::Relaxed) as f64);
+    if !concurrency.adaptive || concurrency.min == concurrency.max {
+        return;
+    }
+    tokio::spawn(async move {
+        tracing::info!(
+            "Autoscaling {} workers between {} and {}",
+            queue,
+            concurrency.min,
+            concurrency.max
+        );
+        while !shutdown.is_cancelled() {
+            tokio::time::sleep(tokio::time::Duration::from_secs(AUTOSCALE_CHECK_SECS)).await;
+            let depth: u64 = match redis_client.get_multiplexed_async_connection().await {
+                Ok(mut conn) => match queue_depth(&mut conn, queues).await {
+                    Ok(depth) => depth,
+                    Err(e) => {
+                        tracing::error!("Autoscaler: Failed to get {} len: {}", queue, e);
+                        continue;
+                    }
+                },
+                Err(e) => {
+                    tracing::error!("Autoscaler: Failed to get redis conn: {}", e);
+                    continue;
+                }
+            };
+            let current = active.load(Ordering::Relaxed);
+            let target = concurrency.target(depth);
+            let next = if target > current {
+                target
+            } else if target < current {
+                current - 1
+            } else {
+                continue;
+            };
+            tracing::info!(
+                "Scaling {} workers from {} to {} (queue depth {})",
+                queue,
+                current,
+                next,
+                depth
+            );
+            active.store(next, Ordering::Relaxed);
+            metrics::gauge!("petpulse_workers_active", "queue" => queue).set(next as f64);
+        }
+    });
+}
+
 pub async fn start_workers(
     redis_client: redis::Client,
     db: DatabaseConnection,
-    concurrency: usize,
+    concurrency: Concurrency,
     gcs_client: GcsClient,
-) {
+    heartbeats: Heartbeats,
+    shutdown: CancellationToken,
+) -> WorkerPool {
     // Start Queue Monitor
-    start_queue_monitor(redis_client.clone()).await;
+    start_queue_monitor(redis_client.clone(), db.clone()).await;
 
     let db = Arc::new(db);
     let redis_client = Arc::new(redis_client);
@@ -63,15 +646,45 @@ pub async fn start_workers(
     // Shared Gemini Client
     let gemini_client = Arc::new(GeminiClient::new());
 
-    for i in 0..concurrency {
+    // Names this process's processing lists; a fresh one per start so a
+    // restarted worker never mistakes a dead process's lists for its own
+    let instance = Uuid::new_v4().simple().to_string();
+    start_processing_heartbeat(redis_client.clone(), instance.clone()).await;
+    start_processing_recovery(redis_client.clone(), db.clone()).await;
+    start_stuck_video_sweep(redis_client.clone(), db.clone()).await;
+    sweep_work_dir().await;
+
+    let lists: Vec<String> = (0..concurrency.max)
+        .map(|i| processing_list::list_key(&instance, i))
+        .collect();
+    let active = ActiveWorkers::new(concurrency.min.into());
+    start_concurrency_supervisor(
+        redis_client.clone(),
+        &[video_queue::NORMAL, video_queue::HIGH],
+        concurrency,
+        active.clone(),
+        shutdown.clone(),
+    )
+    .await;
+    let mut handles = Vec::with_capacity(concurrency.max);
+
+    for (i, processing) in lists.iter().cloned().enumerate() {
         let db = db.clone();
         let redis_client = redis_client.clone();
         let gcs_client = gcs_client.clone();
         let gemini = gemini_client.clone();
+        let shutdown = shutdown.clone();
+        let active = active.clone();
+        let heartbeats = heartbeats.clone();
 
-        tokio::spawn(async move {
+        handles.push(tokio::spawn(async move {
             tracing::info!("Worker {} started", i);
-            loop {
+            while !shutdown.is_cancelled() {
+                heartbeats.beat("video", i);
+                if is_parked(&active, i) {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(PARKED_POLL_SECS)).await;
+                    continue;
+                }
                 // Get connection
                 let mut conn = match redis_client.get_multiplexed_async_connection().await {
                     Ok(c) => c,
@@ -82,30 +695,39 @@ pub async fn start_workers(
                     }
                 };
 
-                let result: redis::RedisResult<(String, String)> =
-                    conn.blpop("video_queue", 0.0).await;
+                let result = claim_video_job(&mut conn, &processing).await;
 
                 match result {
-                    Ok((_key, payload_str)) => {
-                        let payload: Value = match serde_json::from_str(&payload_str) {
-                            Ok(v) => v,
-                            Err(e) => {
-                                tracing::error!("Worker {}: Bad payload: {}", i, e);
-                                continue;
+                    Ok(None) => {}
+                    Ok(Some(payload_str)) => {
+                        match VideoJob::decode(&payload_str) {
+                            Ok(job) => {
+                                process_video(
+                                    job.video_id,
+                                    &db,
+                                    &gemini,
+                                    &mut conn,
+                                    &gcs_client,
+                                    &job,
+                                )
+                                .await
                             }
-                        };
-
-                        let video_id_str = payload["video_id"].as_str().unwrap_or("");
-                        let video_id = match Uuid::parse_str(video_id_str) {
-                            Ok(id) => id,
-                            Err(_) => {
-                                tracing::error!("Worker {}: Invalid UUID", i);
-                                continue;
+                            Err(e) => {
+                                tracing::error!("Worker {}: Rejected job: {}", i, e);
+                                poison::push(&mut conn, "video_queue", &payload_str, &e.to_string())
+                                    .await
                             }
-                        };
-
-                        process_video(video_id, &db, &gemini, &mut conn, &gcs_client, &payload)
-                            .await;
+                        }
+                        if let Err(e) =
+                            processing_list::complete(&mut conn, &processing, &payload_str).await
+                        {
+                            tracing::error!(
+                                "Worker {}: Failed to clear finished job from {}: {}",
+                                i,
+                                processing,
+                                e
+                            );
+                        }
                     }
                     Err(e) => {
                         tracing::error!("Worker {}: Redis error: {}", i, e);
@@ -113,8 +735,109 @@ pub async fn start_workers(
                     }
                 }
             }
-        });
+            tracing::info!("Worker {} stopped", i);
+        }));
+    }
+
+    WorkerPool {
+        queue: "video_queue",
+        handles,
+        claims: JobClaims::ProcessingLists { lists, db },
+    }
+}
+
+/// Moves the next job into this worker's processing list, where it stays
+/// until it's done. High-priority jobs always go first; the blocking wait on
+/// the normal queue is short so high jobs and shutdowns are noticed quickly.
+async fn claim_video_job(
+    conn: &mut redis::aio::MultiplexedConnection,
+    processing: &str,
+) -> redis::RedisResult<Option<String>> {
+    let high: Option<String> = conn
+        .lmove(
+            video_queue::HIGH,
+            processing,
+            redis::Direction::Left,
+            redis::Direction::Right,
+        )
+        .await?;
+    if high.is_some() {
+        return Ok(high);
+    }
+    conn.blmove(
+        video_queue::NORMAL,
+        processing,
+        redis::Direction::Left,
+        redis::Direction::Right,
+        QUEUE_POLL_SECS,
+    )
+    .await
+}
+
+/// Combined length of `queues`.
+async fn queue_depth(
+    conn: &mut redis::aio::MultiplexedConnection,
+    queues: &[&str],
+) -> redis::RedisResult<u64> {
+    let mut depth = 0;
+    for queue in queues {
+        let len: u64 = conn.llen(*queue).await?;
+        depth += len;
     }
+    Ok(depth)
+}
+
+/// Keeps this process's heartbeat key alive so the recovery sweep leaves its
+/// processing lists alone.
+async fn start_processing_heartbeat(redis_client: Arc<redis::Client>, instance: String) {
+    let every = (processing_list::stale_after_secs() / 3).max(1);
+    tokio::spawn(async move {
+        loop {
+            match redis_client.get_multiplexed_async_connection().await {
+                Ok(mut conn) => {
+                    if let Err(e) = processing_list::heartbeat(&mut conn, &instance).await {
+                        tracing::error!("Failed to refresh worker heartbeat: {}", e);
+                    }
+                }
+                Err(e) => tracing::error!("Worker heartbeat: Failed to get redis conn: {}", e),
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(every)).await;
+        }
+    });
+}
+
+/// Puts back jobs stranded in the processing lists of worker processes that
+/// stopped heartbeating, once at startup and then every minute.
+async fn start_processing_recovery(redis_client: Arc<redis::Client>, db: Arc<DatabaseConnection>) {
+    tokio::spawn(async move {
+        loop {
+            match redis_client.get_multiplexed_async_connection().await {
+                Ok(mut conn) => match processing_list::recover_stranded(&mut conn).await {
+                    Ok(recovered) if !recovered.is_empty() => {
+                        tracing::warn!(
+                            "Requeued {} video jobs stranded by a dead worker",
+                            recovered.len()
+                        );
+                        metrics::counter!("petpulse_video_jobs_recovered_total")
+                            .increment(recovered.len() as u64);
+                        reset_requeued_videos(&db, &recovered).await;
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Processing list recovery failed: {}", e),
+                },
+                Err(e) => tracing::error!("Processing recovery: Failed to get redis conn: {}", e),
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(PROCESSING_RECOVERY_SECS)).await;
+        }
+    });
+}
+
+fn track_job(in_flight: &InFlight, worker: usize, payload: Option<&str>) {
+    let mut jobs = in_flight.lock().unwrap_or_else(|e| e.into_inner());
+    match payload {
+        Some(payload) => jobs.insert(worker, payload.to_string()),
+        None => jobs.remove(&worker),
+    };
 }
 
 async fn process_video(
@@ -123,20 +846,16 @@ async fn process_video(
     gemini: &GeminiClient,
     redis_conn: &mut redis::aio::MultiplexedConnection,
     gcs_client: &GcsClient,
-    payload: &Value,
+    job: &VideoJob,
 ) {
     // Extract Trace Context
     use opentelemetry::propagation::TextMapPropagator;
     use opentelemetry_sdk::propagation::TraceContextPropagator;
     use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-    let parent_context = if let Some(carrier_map) = payload["trace_context"].as_object() {
-        let carrier: std::collections::HashMap<String, String> = carrier_map
-            .iter()
-            .map(|(k, v)| (k.clone(), v.as_str().unwrap_or("").to_string()))
-            .collect();
+    let parent_context = if let Some(carrier) = &job.trace_context {
         let propagator = TraceContextPropagator::new();
-        propagator.extract(&carrier)
+        propagator.extract(carrier)
     } else {
         opentelemetry::Context::new()
     };
@@ -147,6 +866,7 @@ async fn process_video(
     let _enter = span.enter();
     tracing::info!("Dequeued video {} from video_queue", video_id);
     drop(_enter); // Drop guard to re-enter in async block via .instrument()
+    record_queue_wait(job.priority.queue(), job.enqueued_at);
 
     let start_time = std::time::Instant::now();
 
@@ -154,101 +874,207 @@ async fn process_video(
         // 1. Fetch Video Entity
         let video_opt = PetVideo::find_by_id(video_id).one(db).await.unwrap_or(None);
         if video_opt.is_none() {
-            tracing::error!("Video {} not found in DB", video_id);
+            // Deleted (e.g. with its owner's account) after being queued
+            tracing::info!("Video {} no longer exists; skipping", video_id);
+            metrics::counter!("petpulse_video_jobs_skipped_total", "reason" => "deleted")
+                .increment(1);
             return;
         }
         let video = video_opt.unwrap();
-        let retry_count = video.retry_count;
 
         // 2. Set Status PROCESSING
         let mut active_video: pet_video::ActiveModel = video.clone().into();
         active_video.status = Set("PROCESSING".to_string());
-        if let Err(e) = active_video.update(db).await {
-            tracing::error!("Failed to update status: {}", e);
-            metrics::counter!("petpulse_video_processing_errors_total", "stage" => "db_update").increment(1);
-            return;
+        // The stuck-video sweep measures from here
+        active_video.updated_at = Set(Utc::now().into());
+        match active_video.update(db).await {
+            Ok(v) => video_events::publish(redis_conn, VideoEventKind::Processing, &v).await,
+            Err(e) => {
+                tracing::error!("Failed to update status: {}", e);
+                metrics::counter!("petpulse_video_processing_errors_total", "stage" => "db_update").increment(1);
+                record_failure_stage(db, video_id, "db_update", &format!("Failed to update status: {}", e)).await;
+                return;
+            }
         }
 
         // 3. Download from GCS
+        mark_video_stage(db, video_id, pet_video::Column::DownloadStartedAt).await;
         let gcs_path = video.file_path.clone();
-        let temp_file_path = format!("/tmp/{}", video_id);
-
-        async {
-            // Parse bucket and object
-            // Expecting: gs://bucket/object/path
-            let parts: Vec<&str> = gcs_path
-                .trim_start_matches("gs://")
-                .splitn(2, '/')
-                .collect();
-            if parts.len() != 2 {
+        // Deleted when this goes out of scope, however the job ends
+        let temp_file = match video_temp_file(video_id, &gcs_path) {
+            Ok(f) => f,
+            Err(e) => {
+                tracing::error!("Failed to create temp file for {}: {}", video_id, e);
+                metrics::counter!("petpulse_video_processing_errors_total", "stage" => "fs_write").increment(1);
+                let outcome =
+                    retry_or_fail(db, redis_conn, &video, job, VideoOutcome::DownloadFailed, "fs_write", e.to_string()).await;
+                record_outcome(outcome, start_time);
+                return;
+            }
+        };
+        let temp_file_path = temp_file.path().to_string_lossy().into_owned();
+
+        let download_failed = async {
+            let Some((bucket, object)) = crate::storage_cleanup::parse_gs_path(&gcs_path) else {
                 tracing::error!("Invalid GCS URI: {}", gcs_path);
                 // Fail
+                let error = format!("Invalid GCS URI: {}", gcs_path);
                 let mut active: pet_video::ActiveModel = video.clone().into();
                 active.status = Set("FAILED".to_string());
-                let _ = active.update(db).await;
-                metrics::counter!("petpulse_video_processing_errors_total", "stage" => "download").increment(1);
-                return;
-            }
-            let bucket = parts[0];
-            let object = parts[1];
-
-            let data = match gcs_client
-                .download_object(
-                    &GetObjectRequest {
-                        bucket: bucket.to_string(),
-                        object: object.to_string(),
-                        ..Default::default()
-                    },
-                    &Range::default(),
-                )
-                .await
-            {
-                Ok(d) => d,
-                Err(e) => {
-                    tracing::error!("Failed to download from GCS: {}", e);
-                    // Fail or Retry logic?
-                    // Let's retry if transient, fail for now to keep simple.
-                    metrics::counter!("petpulse_video_processing_errors_total", "stage" => "download").increment(1);
-                    return;
+                active.error_stage = Set(Some("download".to_string()));
+                active.error_message = Set(Some(pet_video::truncate_error(&error)));
+                if let Ok(v) = active.update(db).await {
+                    video_events::publish(redis_conn, VideoEventKind::Failed, &v).await;
+                    tokio::spawn(crate::callbacks::send_processing_callback(v));
                 }
+                dead_letter(redis_conn, video_id, &job.to_value(), &error).await;
+                tokio::spawn(send_processing_error_webhook(video_id, video.pet_id, "download", error));
+                metrics::counter!("petpulse_video_processing_errors_total", "stage" => "download").increment(1);
+                return Some(VideoOutcome::DownloadFailed);
             };
 
-            if let Err(e) = tokio::fs::write(&temp_file_path, data).await {
-                tracing::error!("Failed to write temp file: {}", e);
-                metrics::counter!("petpulse_video_processing_errors_total", "stage" => "fs_write").increment(1);
-                return;
+            let download_started = std::time::Instant::now();
+            match download_to_file(gcs_client, bucket, object, &temp_file_path).await {
+                Ok(bytes) => {
+                    metrics::counter!("petpulse_download_bytes_total").increment(bytes);
+                    metrics::histogram!("petpulse_video_download_duration_seconds")
+                        .record(download_started.elapsed().as_secs_f64());
+                }
+                Err((stage, e)) => {
+                    tracing::error!("Failed to download {} from GCS: {}", gcs_path, e);
+                    metrics::counter!("petpulse_video_processing_errors_total", "stage" => stage).increment(1);
+                    return Some(
+                        retry_or_fail(db, redis_conn, &video, job, VideoOutcome::DownloadFailed, stage, e)
+                            .await,
+                    );
+                }
             }
+            None
         }.instrument(tracing::info_span!("download_video_gcs")).await;
+        if let Some(outcome) = download_failed {
+            record_outcome(outcome, start_time);
+            return;
+        }
 
+        // 3a. Preview frame for the video list; best-effort
+        store_thumbnail(db, gcs_client, &video, &temp_file_path)
+            .instrument(tracing::info_span!("generate_thumbnail"))
+            .await;
+
+        // 3b. Skip the Gemini call for clips with no motion
+        let (pet, owner) = match Pet::find_by_id(video.pet_id)
+            .find_also_related(crate::entities::user::Entity)
+            .one(db)
+            .await
+        {
+            Ok(Some((pet, owner))) => (Some(pet), owner),
+            _ => (None, None),
+        };
+        let static_check_enabled = pet.as_ref().map(|p| !p.static_check_disabled).unwrap_or(true);
+        let pet_context = pet.as_ref().and_then(|p| p.health_context());
+        let baseline = pet
+            .as_ref()
+            .and_then(crate::baseline::of_pet)
+            .map(|b| b.describe());
+        let pet_archived = pet.as_ref().is_some_and(|p| p.is_archived());
+        let species_kind = pet.as_ref().map(|p| p.species());
+        let species = pet.map(|p| p.species).unwrap_or_default();
+        // Digests are keyed by the owner's local day
+        let owner_tz = owner
+            .as_ref()
+            .map(crate::timezone::of_user)
+            .unwrap_or(chrono_tz::Tz::UTC);
+        if static_check_enabled && is_static_video(&temp_file_path).await {
+            tracing::info!("Video {} has no notable motion; skipping analysis", video_id);
+            metrics::counter!("petpulse_videos_skipped_static_total").increment(1);
+
+            let mut active: pet_video::ActiveModel = video.clone().into();
+            active.status = Set("PROCESSED".to_string());
+            active.activities = Set(Some(serde_json::json!([])));
+            active.mood = Set(None);
+            active.description = Set(Some(NO_ACTIVITY_DESCRIPTION.to_string()));
+            active.is_unusual = Set(false);
+            active.completed_at = Set(Some(Utc::now().into()));
+            active.error_message = Set(None);
+            active.error_stage = Set(None);
+
+            let saved = active.update(db).await;
+            match &saved {
+                Ok(v) => {
+                    let v = v.clone();
+                    queue_digest_for_video(redis_conn, v.pet_id, crate::timezone::local_date(&v.captured_at(), owner_tz)).await;
+                    metrics::counter!("petpulse_video_processed_total").increment(1);
+                    video_events::publish(redis_conn, VideoEventKind::Processed, &v).await;
+                    tokio::spawn(crate::callbacks::send_processing_callback(v));
+                }
+                Err(e) => {
+                    tracing::error!("Failed to update static video {}: {}", video_id, e);
+                    metrics::counter!("petpulse_video_processing_errors_total", "stage" => "db_final_update").increment(1);
+                    record_failure_stage(db, video_id, "db_update", &format!("Failed to save result: {}", e)).await;
+                }
+            }
+
+            record_outcome(saved_outcome(&saved), start_time);
+            return;
+        }
 
         // 4. Analyze
-        async {
-            match gemini.analyze_video_with_usage(&temp_file_path).await {
+        mark_video_stage(db, video_id, pet_video::Column::AnalysisStartedAt).await;
+        let outcome = async {
+            // Gemini can sit in wait_for_file_active for a long time; don't
+            // let one video hold a worker slot indefinitely
+            let timeout_secs = analysis_timeout_secs();
+            let (analysis, timed_out) = match tokio::time::timeout(
+                tokio::time::Duration::from_secs(timeout_secs),
+                gemini.analyze_video_with_usage(&temp_file_path, species_kind, pet_context.as_deref(), baseline.as_deref()),
+            )
+            .await
+            {
+                Ok(analysis) => (analysis, false),
+                Err(_) => {
+                    tracing::warn!("Analysis of {} timed out after {}s", video_id, timeout_secs);
+                    metrics::counter!("petpulse_video_processing_errors_total", "stage" => "timeout").increment(1);
+                    (Err(format!("timed out after {}s", timeout_secs)), true)
+                }
+            };
+            let outcome = match analysis {
                 Ok((analysis_result, usage_metadata)) => {
                     tracing::info!("Analysis successful for {}", video_id);
                     tracing::info!("Raw Analysis Result: {:?}", analysis_result);
                     tracing::info!("Usage Metadata: {:?}", usage_metadata);
 
+                    // Update Status PROCESSED
+                    let mut active: pet_video::ActiveModel = video.clone().into();
+                    active.status = Set("PROCESSED".to_string());
+                    active.completed_at = Set(Some(Utc::now().into()));
+                    active.error_message = Set(None);
+                    active.error_stage = Set(None);
+
                     // Record Token Usage
                     if let Some(usage) = usage_metadata {
                         if let Some(input_tokens) = usage["promptTokenCount"].as_i64() {
                              metrics::counter!("petpulse_gemini_tokens_total", "type" => "input").increment(input_tokens as u64);
+                             active.input_tokens = Set(Some(input_tokens));
                         }
                         if let Some(output_tokens) = usage["candidatesTokenCount"].as_i64() {
                              metrics::counter!("petpulse_gemini_tokens_total", "type" => "output").increment(output_tokens as u64);
+                             active.output_tokens = Set(Some(output_tokens));
                         }
                     }
 
-                    // Update Status PROCESSED
-                    let mut active: pet_video::ActiveModel = video.clone().into();
-                    active.status = Set("PROCESSED".to_string());
-
                     // Save Analysis directly to PetVideo
                     if let Some(activities_value) = analysis_result.get("activities") {
                         if let Ok(_activities) =
                             serde_json::from_value::<Vec<pet_video::Activity>>(activities_value.clone())
                         {
-                            active.activities = Set(Some(activities_value.clone()));
+                            // Keep Gemini's raw name and add the taxonomy name alongside it
+                            let mut activities_value = activities_value.clone();
+                            crate::activity::annotate_activities(&mut activities_value, &species);
+                            active.duration_seconds = Set(
+                                crate::api::video::activities_duration_secs(&Some(activities_value.clone()))
+                                    .map(|d| d as i32),
+                            );
+                            active.activities = Set(Some(activities_value));
                         } else {
                             tracing::error!(
                                 "Failed to parse activities matching schema: {:?}",
@@ -267,7 +1093,7 @@ async fn process_video(
                     active.is_unusual = Set(analysis_result["is_unusual"].as_bool().unwrap_or(false));
 
                     // Extract severity level (Phase 3 enhancement)
-                    let severity_level = analysis_result["severity_level"]
+                    let mut severity_level = analysis_result["severity_level"]
                         .as_str()
                         .unwrap_or("low")
                         .to_string();
@@ -292,6 +1118,47 @@ async fn process_video(
                         })
                         .unwrap_or_default();
 
+                    // Skip alerting when every indicator is a behavior the owner already expects
+                    let is_unusual = active.is_unusual.clone().unwrap();
+                    let suppressed = if severity_level == "critical" || is_unusual {
+                        match Pet::find_by_id(video.pet_id).one(db).await {
+                            Ok(Some(pet)) => pet.known_behaviors_cover(&critical_indicators),
+                            Ok(None) => false,
+                            Err(e) => {
+                                tracing::error!("Failed to load known behaviors for pet {}: {}", video.pet_id, e);
+                                false
+                            }
+                        }
+                    } else {
+                        false
+                    };
+
+                    if suppressed {
+                        tracing::info!(
+                            "Suppressing alert for video {}: indicators {:?} match known behaviors",
+                            video_id,
+                            critical_indicators
+                        );
+                        metrics::counter!("petpulse_alerts_suppressed_total").increment(1);
+                        severity_level = "info".to_string();
+                        active.suppressed_by_known_behavior = Set(true);
+                    }
+
+                    // Per-pet type mutes silence non-critical alerts; analysis is still saved
+                    let type_muted = is_unusual
+                        && !suppressed
+                        && severity_level != "critical"
+                        && crate::entities::alert_mute::is_muted(
+                            db,
+                            video.pet_id,
+                            &AlertType::UnusualBehavior.to_string(),
+                        )
+                        .await;
+                    if type_muted {
+                        tracing::info!("Alert type unusual_behavior muted for pet {}; skipping alert", video.pet_id);
+                        metrics::counter!("petpulse_alerts_muted_total", "source" => "worker").increment(1);
+                    }
+
                     tracing::info!(
                         "Updating video {} with: mood={:?}, unusual={:?}, severity={}",
                         video_id,
@@ -301,7 +1168,10 @@ async fn process_video(
                     );
 
                     // Route alerts based on severity level (Phase 3)
-                    if severity_level == "critical" {
+                    if pet_archived && (severity_level == "critical" || is_unusual) {
+                        tracing::info!("Pet {} is archived; not alerting for video {}", video.pet_id, video_id);
+                        metrics::counter!("petpulse_alerts_suppressed_archived_total").increment(1);
+                    } else if severity_level == "critical" {
                         // CRITICAL ALERT PATH
                         metrics::counter!("petpulse_critical_alerts_total", "pet_id" => active.pet_id.clone().unwrap().to_string()).increment(1);
 
@@ -316,8 +1186,10 @@ async fn process_video(
                         let description = active.description.clone().unwrap().unwrap_or_else(|| "Critical health condition detected".to_string());
                         let mood = active.mood.clone().unwrap();
 
+                        let alert_conn = redis_conn.clone();
                         tokio::spawn(async move {
                             send_critical_alert_webhook(
+                                alert_conn,
                                 video_id,
                                 pet_id,
                                 description,
@@ -326,7 +1198,7 @@ async fn process_video(
                                 recommended_actions,
                             ).await;
                         });
-                    } else if active.is_unusual.clone().unwrap() {
+                    } else if is_unusual && !suppressed && !type_muted {
                         // NORMAL UNUSUAL BEHAVIOR PATH
                         metrics::counter!("petpulse_unusual_events_total", "pet_id" => active.pet_id.clone().unwrap().to_string()).increment(1);
 
@@ -334,78 +1206,878 @@ async fn process_video(
                         let description = active.description.clone().unwrap().unwrap_or_else(|| "Unusual activity detected".to_string());
                         let mood = active.mood.clone().unwrap();
 
+                        let alert_conn = redis_conn.clone();
                         tokio::spawn(async move {
-                            send_alert_webhook(video_id, pet_id, description, mood, severity_level).await;
+                            send_alert_webhook(alert_conn, video_id, pet_id, description, mood, severity_level).await;
                         });
                     }
 
-                    match active.update(db).await {
+                    let saved = active.update(db).await;
+                    match &saved {
                         Ok(v) => {
+                            let v = v.clone();
                             tracing::info!("Updated video successfully: {:?}", v);
+                            save_clips(db, &v).await;
 
                             // Queue digest update
-                            let date = v.created_at.date_naive();
-                            let digest_payload = serde_json::json!({
-                                "pet_id": v.pet_id,
-                                "date": date.format("%Y-%m-%d").to_string()
-                            })
-                            .to_string();
-
-                            let _: () = redis_conn
-                                .rpush("digest_queue", digest_payload)
-                                .await
-                                .unwrap_or(());
-
-                            tracing::info!(
-                                "Enqueued digest update for pet_id={} to digest_queue",
-                                v.pet_id
-                            );
+                            queue_digest_for_video(redis_conn, v.pet_id, crate::timezone::local_date(&v.captured_at(), owner_tz)).await;
 
                             metrics::counter!("petpulse_video_processed_total").increment(1);
+                            video_events::publish(redis_conn, VideoEventKind::Processed, &v).await;
+                            tokio::spawn(crate::callbacks::send_processing_callback(v));
                         }
                         Err(e) => {
                              tracing::error!("Failed to update video {}: {}", video_id, e);
                              metrics::counter!("petpulse_video_processing_errors_total", "stage" => "db_final_update").increment(1);
+                             record_failure_stage(db, video_id, "db_update", &format!("Failed to save analysis: {}", e)).await;
                         }
                     }
+                    saved_outcome(&saved)
                 }
                 Err(e) => {
                     tracing::error!("Analysis failed for {}: {}", video_id, e);
                     metrics::counter!("petpulse_gemini_api_errors_total").increment(1);
-                    // We should also record duration here effectively, but it's inside the block.
-                    // Let's rely on the outer duration. But wait, "success" label differs.
-                    // The outer block will record success=true even if this fails? No, the outer block blindly records success=true currently.
-                    // Correcting the outer block requires state.
-                    // Since I can't easily change the outer block structure in this single-tool edit without making it huge,
-                    // I will leave the outer recording as "true" for now (or I should just remove "success" label from plan).
-                    // Actually, let's fix it properly. I will add a variable `success` in outer scope.
-
-                    if retry_count < 2 {
-                        // Retry
-                        let mut active: pet_video::ActiveModel = video.clone().into();
-                        active.retry_count = Set(retry_count + 1);
-                        active.status = Set("Retrying".to_string());
-                        let _ = active.update(db).await;
-
-                        let payload = serde_json::json!({ "video_id": video_id }).to_string();
-                        let _: () = redis_conn.rpush("video_queue", payload).await.unwrap_or(());
-                    } else {
-                        // Fail
-                        let mut active: pet_video::ActiveModel = video.clone().into();
-                        active.status = Set("FAILED".to_string());
-                        let _ = active.update(db).await;
-                    }
+                    let stage = analysis_error_stage(timed_out, &e);
+                    retry_or_fail(db, redis_conn, &video, job, VideoOutcome::AnalysisFailed, stage, e).await
+                }
+            };
+            outcome
+        }.instrument(tracing::info_span!("analyze_video_gemini")).await;
+
+        record_outcome(outcome, start_time);
+    }.instrument(span).await;
+}
+
+/// `VIDEO_WORK_DIR`, where the worker keeps videos while it processes them.
+fn work_dir() -> std::path::PathBuf {
+    std::env::var("VIDEO_WORK_DIR")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("petpulse-worker"))
+}
+
+/// A fresh file in the work dir for one attempt at `video_id`, keeping the
+/// object's extension so the Gemini upload gets a sensible name. Retries
+/// never reuse an earlier attempt's partial file.
+fn video_temp_file(video_id: Uuid, gcs_path: &str) -> std::io::Result<tempfile::NamedTempFile> {
+    let dir = work_dir();
+    std::fs::create_dir_all(&dir)?;
+    let ext = std::path::Path::new(gcs_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("mp4");
+    tempfile::Builder::new()
+        .prefix(&format!("{}-", video_id))
+        .suffix(&format!(".{}", ext))
+        .tempfile_in(dir)
+}
+
+/// Deletes work-dir files older than a day, left behind by a worker that
+/// was killed before its temp files could be dropped.
+async fn sweep_work_dir() {
+    let dir = work_dir();
+    let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+        return;
+    };
+    let cutoff =
+        std::time::SystemTime::now() - std::time::Duration::from_secs(STALE_WORK_FILE_SECS);
+    let mut removed = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let stale = entry
+            .metadata()
+            .await
+            .ok()
+            .filter(|m| m.is_file())
+            .and_then(|m| m.modified().ok())
+            .is_some_and(|modified| modified < cutoff);
+        if stale && tokio::fs::remove_file(entry.path()).await.is_ok() {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        tracing::info!("Removed {} stale files from {}", removed, dir.display());
+    }
+}
+
+/// Streams a GCS object into `path` chunk by chunk, so a job holds one chunk
+/// in memory rather than the whole video. Returns the bytes written, or the
+/// error stage (`download` or `fs_write`) and message.
+async fn download_to_file(
+    gcs_client: &GcsClient,
+    bucket: &str,
+    object: &str,
+    path: &str,
+) -> Result<u64, (&'static str, String)> {
+    use futures::TryStreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let chunks = gcs_client
+        .download_streamed_object(
+            &GetObjectRequest {
+                bucket: bucket.to_string(),
+                object: object.to_string(),
+                ..Default::default()
+            },
+            &Range::default(),
+        )
+        .await
+        .map_err(|e| ("download", e.to_string()))?;
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .map_err(|e| ("fs_write", e.to_string()))?;
+    let mut reader = tokio_util::io::StreamReader::new(chunks.map_err(std::io::Error::other));
+    let written = tokio::io::copy(&mut reader, &mut file)
+        .await
+        .map_err(|e| ("download", e.to_string()))?;
+    file.flush()
+        .await
+        .map_err(|e| ("fs_write", e.to_string()))?;
+    Ok(written)
+}
+
+/// How one run of `process_video` ended, for the duration histogram and
+/// `petpulse_video_outcomes_total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoOutcome {
+    Success,
+    DownloadFailed,
+    AnalysisFailed,
+    /// Processed, but the result couldn't be saved
+    DbUpdateFailed,
+    /// Failed with retries left; the job is back in video_retry
+    Retried,
+}
+
+impl VideoOutcome {
+    /// The histogram's `success` label; only a saved result counts.
+    fn success_label(self) -> &'static str {
+        if self == VideoOutcome::Success {
+            "true"
+        } else {
+            "false"
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            VideoOutcome::Success => "success",
+            VideoOutcome::DownloadFailed => "download_failed",
+            VideoOutcome::AnalysisFailed => "analysis_failed",
+            VideoOutcome::DbUpdateFailed => "db_update_failed",
+            VideoOutcome::Retried => "retried",
+        }
+    }
+}
+
+/// A finished job's outcome from saving its result.
+fn saved_outcome<T, E>(saved: &Result<T, E>) -> VideoOutcome {
+    if saved.is_ok() {
+        VideoOutcome::Success
+    } else {
+        VideoOutcome::DbUpdateFailed
+    }
+}
+
+fn record_outcome(outcome: VideoOutcome, start_time: std::time::Instant) {
+    let duration = start_time.elapsed().as_secs_f64();
+    let success = outcome.success_label();
+    metrics::histogram!(
+        "petpulse_video_processing_duration_seconds",
+        "success" => success,
+        "outcome" => outcome.label()
+    )
+    .record(duration);
+    metrics::counter!("petpulse_video_outcomes_total", "outcome" => outcome.label()).increment(1);
+}
+
+/// Retries a video gets after its first failed attempt.
+const MAX_VIDEO_RETRIES: i32 = 2;
+
+/// What a failed attempt reports: [`VideoOutcome::Retried`] while the video
+/// has retries left, `failed` once they've run out.
+fn failure_outcome(retry_count: i32, failed: VideoOutcome) -> VideoOutcome {
+    if retry_count < MAX_VIDEO_RETRIES {
+        VideoOutcome::Retried
+    } else {
+        failed
+    }
+}
+
+/// `error_stage` for a failed analysis.
+fn analysis_error_stage(timed_out: bool, error: &str) -> &'static str {
+    if timed_out {
+        "timeout"
+    } else if error.starts_with(crate::gemini::PARSE_ERROR_PREFIX) {
+        "parse"
+    } else {
+        "analysis"
+    }
+}
+
+/// Schedules a retry after a backoff while the video has retries left;
+/// otherwise marks it FAILED, dead-letters the job and tells the owner.
+/// `failed` is the outcome reported once retries run out; `stage` is stored
+/// as the row's `error_stage` either way.
+async fn retry_or_fail(
+    db: &DatabaseConnection,
+    redis_conn: &mut redis::aio::MultiplexedConnection,
+    video: &pet_video::Model,
+    job: &VideoJob,
+    failed: VideoOutcome,
+    stage: &'static str,
+    error: String,
+) -> VideoOutcome {
+    let retry_count = video.retry_count;
+    let message = match failed {
+        VideoOutcome::DownloadFailed => format!("Download failed: {}", error),
+        _ => format!("Analysis failed: {}", error),
+    };
+    let stored_message = pet_video::truncate_error(&message);
+
+    if failure_outcome(retry_count, failed) == VideoOutcome::Retried {
+        let mut active: pet_video::ActiveModel = video.clone().into();
+        active.retry_count = Set(retry_count + 1);
+        let next_attempt_at = Utc::now() + retry_delay(retry_count as u32);
+        active.status = Set("Retrying".to_string());
+        active.updated_at = Set(Utc::now().into());
+        active.error_message = Set(Some(stored_message));
+        active.error_stage = Set(Some(stage.to_string()));
+        // Back on the queue once the delay is over, not now
+        active.queued_at = Set(Some(next_attempt_at.into()));
+        if let Ok(v) = active.update(db).await {
+            video_events::publish(redis_conn, VideoEventKind::Queued, &v).await;
+        }
+
+        metrics::counter!("petpulse_video_retries_total", "attempt" => (retry_count + 1).to_string())
+            .increment(1);
+        schedule_retry(redis_conn, job, next_attempt_at).await;
+        return VideoOutcome::Retried;
+    }
+
+    let mut active: pet_video::ActiveModel = video.clone().into();
+    active.status = Set("FAILED".to_string());
+    active.error_message = Set(Some(stored_message));
+    active.error_stage = Set(Some(stage.to_string()));
+    active.completed_at = Set(Some(Utc::now().into()));
+    if let Ok(v) = active.update(db).await {
+        video_events::publish(redis_conn, VideoEventKind::Failed, &v).await;
+        tokio::spawn(crate::callbacks::send_processing_callback(v));
+    }
+    dead_letter(redis_conn, video.id, &job.to_value(), &message).await;
+
+    // Let the owner know this window of footage wasn't analyzed
+    tokio::spawn(send_processing_error_webhook(
+        video.id,
+        video.pet_id,
+        stage,
+        error,
+    ));
+    failed
+}
+
+/// How long a job sat in `queue`, from the `enqueued_at` its producer
+/// stamped on it. Jobs queued before the field existed aren't counted.
+fn record_queue_wait(queue: &'static str, enqueued_at: Option<chrono::DateTime<Utc>>) {
+    let Some(enqueued_at) = enqueued_at else {
+        return;
+    };
+    let waited = (Utc::now() - enqueued_at).num_milliseconds().max(0) as f64 / 1000.0;
+    metrics::histogram!("petpulse_queue_wait_seconds", "queue" => queue).record(waited);
+}
+
+/// Stamps a failure on the row without changing its status. Best-effort,
+/// for failures where the full update just failed too.
+async fn record_failure_stage(db: &DatabaseConnection, video_id: Uuid, stage: &str, message: &str) {
+    if let Err(e) = PetVideo::update_many()
+        .col_expr(
+            pet_video::Column::ErrorStage,
+            sea_orm::sea_query::Expr::value(stage),
+        )
+        .col_expr(
+            pet_video::Column::ErrorMessage,
+            sea_orm::sea_query::Expr::value(pet_video::truncate_error(message)),
+        )
+        .filter(pet_video::Column::Id.eq(video_id))
+        .exec(db)
+        .await
+    {
+        tracing::warn!("Failed to record error for video {}: {}", video_id, e);
+    }
+}
+
+/// `VIDEO_ANALYSIS_TIMEOUT_SECS`: ceiling on one Gemini analysis, upload and
+/// polling included. A timeout is retried like any other analysis failure.
+fn analysis_timeout_secs() -> u64 {
+    std::env::var("VIDEO_ANALYSIS_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_ANALYSIS_TIMEOUT_SECS)
+}
+
+/// Delay before retry number `retry + 1`: `VIDEO_RETRY_BASE_SECS` (default
+/// 30) doubled per earlier retry, capped at `VIDEO_RETRY_MAX_SECS` (default
+/// 1800), then spread by up to `VIDEO_RETRY_JITTER_PCT` (default 20) percent
+/// either way so jobs that failed together don't all come back together.
+fn retry_delay(retry: u32) -> chrono::Duration {
+    use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+    let base: u64 = env_var_or("VIDEO_RETRY_BASE_SECS", 30);
+    let max: u64 = env_var_or("VIDEO_RETRY_MAX_SECS", 1800);
+    let jitter_pct: u64 = env_var_or::<u64>("VIDEO_RETRY_JITTER_PCT", 20).min(100);
+
+    let delay_ms = base
+        .saturating_mul(1u64 << retry.min(20))
+        .min(max)
+        .saturating_mul(1000);
+    let spread = delay_ms * jitter_pct / 100;
+    let jittered = if spread == 0 {
+        delay_ms
+    } else {
+        delay_ms - spread + OsRng.next_u64() % (2 * spread + 1)
+    };
+    chrono::Duration::milliseconds(jittered as i64)
+}
+
+/// Parks the job in video_retry until `due`. The job is the same one, so
+/// trace context and the DLQ replay count carry over.
+async fn schedule_retry(
+    conn: &mut redis::aio::MultiplexedConnection,
+    job: &VideoJob,
+    due: chrono::DateTime<Utc>,
+) {
+    let scheduled = VideoJob {
+        next_attempt_at: Some(due),
+        ..job.clone()
+    };
+    let result: redis::RedisResult<()> = conn
+        .zadd(VIDEO_RETRY_SET, scheduled.encode(), due.timestamp())
+        .await;
+    if let Err(e) = result {
+        // Better an immediate retry than a lost job
+        tracing::error!("Failed to schedule retry, requeueing now: {}", e);
+        let _: redis::RedisResult<()> = conn
+            .rpush(job.priority.queue(), job.requeued().encode())
+            .await;
+    }
+}
+
+/// Moves due retries from video_retry back onto video_queue. Several workers
+/// can run this; ZREM decides which one moves each job.
+async fn drain_due_retries(
+    conn: &mut redis::aio::MultiplexedConnection,
+) -> redis::RedisResult<u64> {
+    let due: Vec<String> = conn
+        .zrangebyscore_limit(
+            VIDEO_RETRY_SET,
+            "-inf",
+            Utc::now().timestamp(),
+            0,
+            RETRY_DRAIN_BATCH,
+        )
+        .await?;
+    let mut moved = 0;
+    for job in due {
+        let removed: u64 = conn.zrem(VIDEO_RETRY_SET, &job).await?;
+        if removed == 0 {
+            continue;
+        }
+        // The wait for a retry counts from when it's due, not from upload.
+        // A job that won't decode goes back as it is for the worker to reject.
+        let (queue, job) = match VideoJob::decode(&job) {
+            Ok(parsed) => (parsed.priority.queue(), parsed.requeued().encode()),
+            Err(_) => (video_queue::NORMAL, job),
+        };
+        let _: () = conn.rpush(queue, &job).await?;
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+/// Polls video_retry every few seconds for jobs whose delay is over.
+pub async fn start_retry_scheduler(redis_client: redis::Client) {
+    tokio::spawn(async move {
+        tracing::info!("Video retry scheduler started");
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_POLL_SECS)).await;
+            let mut conn = match redis_client.get_multiplexed_async_connection().await {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("Retry scheduler: Failed to get redis conn: {}", e);
+                    continue;
                 }
+            };
+            match drain_due_retries(&mut conn).await {
+                Ok(0) => {}
+                Ok(moved) => tracing::info!("Moved {} due retries back to video_queue", moved),
+                Err(e) => tracing::error!("Failed to drain video retries: {}", e),
             }
-            // Cleanup in both cases
-            let _ = tokio::fs::remove_file(&temp_file_path).await;
+        }
+    });
+}
 
-        }.instrument(tracing::info_span!("analyze_video_gemini")).await;
+/// `VIDEO_STUCK_AFTER_SECS`: how long a row can sit in PROCESSING (or past
+/// its retry time in Retrying) before the sweep treats it as abandoned.
+fn stuck_after_secs() -> i64 {
+    std::env::var("VIDEO_STUCK_AFTER_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_STUCK_AFTER_SECS)
+}
 
-        let duration = start_time.elapsed().as_secs_f64();
-        metrics::histogram!("petpulse_video_processing_duration_seconds", "success" => "true").record(duration);
+/// Videos that still have a job somewhere in Redis: queued, waiting out a
+/// retry, or claimed by a worker.
+async fn video_ids_with_jobs(
+    conn: &mut redis::aio::MultiplexedConnection,
+) -> redis::RedisResult<std::collections::HashSet<Uuid>> {
+    let mut jobs: Vec<String> = conn.lrange(video_queue::NORMAL, 0, -1).await?;
+    let high: Vec<String> = conn.lrange(video_queue::HIGH, 0, -1).await?;
+    jobs.extend(high);
+    let retries: Vec<String> = conn.zrange(VIDEO_RETRY_SET, 0, -1).await?;
+    jobs.extend(retries);
+    jobs.extend(processing_list::payloads(conn).await?);
+    Ok(jobs
+        .iter()
+        .filter_map(|job| jobs::video_id_of(job))
+        .collect())
+}
 
-    }.instrument(span).await;
+/// Finds rows left in PROCESSING or Retrying by a worker that died without
+/// its job surviving in Redis, puts them back to PENDING and queues them.
+async fn requeue_stuck_videos(
+    db: &DatabaseConnection,
+    conn: &mut redis::aio::MultiplexedConnection,
+) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+    use sea_orm::{Condition, QuerySelect};
+
+    let cutoff: chrono::DateTime<chrono::FixedOffset> =
+        (Utc::now() - chrono::Duration::seconds(stuck_after_secs())).into();
+    let stuck = PetVideo::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(pet_video::Column::Status.eq("PROCESSING"))
+                        .add(pet_video::Column::UpdatedAt.lt(cutoff)),
+                )
+                .add(
+                    Condition::all()
+                        .add(pet_video::Column::Status.eq("Retrying"))
+                        .add(pet_video::Column::UpdatedAt.lt(cutoff))
+                        .add(
+                            Condition::any()
+                                .add(pet_video::Column::QueuedAt.is_null())
+                                .add(pet_video::Column::QueuedAt.lt(cutoff)),
+                        ),
+                ),
+        )
+        .limit(STUCK_SWEEP_BATCH)
+        .all(db)
+        .await?;
+    if stuck.is_empty() {
+        return Ok(0);
+    }
+
+    // A long analysis or a retry still in the set isn't stuck, just slow
+    let held = video_ids_with_jobs(conn).await?;
+    let now: chrono::DateTime<chrono::FixedOffset> = Utc::now().into();
+    let mut requeued = 0;
+    for video in stuck.into_iter().filter(|v| !held.contains(&v.id)) {
+        let reset = PetVideo::update_many()
+            .col_expr(
+                pet_video::Column::Status,
+                sea_orm::sea_query::Expr::value("PENDING"),
+            )
+            .col_expr(
+                pet_video::Column::QueuedAt,
+                sea_orm::sea_query::Expr::value(now),
+            )
+            .col_expr(
+                pet_video::Column::UpdatedAt,
+                sea_orm::sea_query::Expr::value(now),
+            )
+            .col_expr(
+                pet_video::Column::DownloadStartedAt,
+                sea_orm::sea_query::Expr::value(
+                    Option::<chrono::DateTime<chrono::FixedOffset>>::None,
+                ),
+            )
+            .col_expr(
+                pet_video::Column::AnalysisStartedAt,
+                sea_orm::sea_query::Expr::value(
+                    Option::<chrono::DateTime<chrono::FixedOffset>>::None,
+                ),
+            )
+            // Only if nothing picked it up since it was read
+            .filter(pet_video::Column::Id.eq(video.id))
+            .filter(pet_video::Column::Status.eq(video.status.clone()))
+            .filter(pet_video::Column::UpdatedAt.eq(video.updated_at))
+            .exec(db)
+            .await?;
+        if reset.rows_affected == 0 {
+            continue;
+        }
+        let priority = Priority::parse(&video.priority).unwrap_or_default();
+        let _: () = conn
+            .rpush(
+                priority.queue(),
+                crate::api::daily_digest::video_job_payload(video.id, priority),
+            )
+            .await?;
+        tracing::warn!(video_id = %video.id, status = %video.status, "Requeued stuck video");
+        requeued += 1;
+    }
+    Ok(requeued)
+}
+
+/// Runs the stuck-video sweep at startup and then every few minutes.
+async fn start_stuck_video_sweep(redis_client: Arc<redis::Client>, db: Arc<DatabaseConnection>) {
+    tokio::spawn(async move {
+        loop {
+            match redis_client.get_multiplexed_async_connection().await {
+                Ok(mut conn) => match requeue_stuck_videos(&db, &mut conn).await {
+                    Ok(0) => {}
+                    Ok(requeued) => {
+                        tracing::warn!("Requeued {} videos stuck in processing", requeued);
+                        metrics::counter!("petpulse_videos_requeued_total").increment(requeued);
+                    }
+                    Err(e) => tracing::error!("Stuck video sweep failed: {}", e),
+                },
+                Err(e) => tracing::error!("Stuck video sweep: Failed to get redis conn: {}", e),
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(STUCK_SWEEP_SECS)).await;
+        }
+    });
+}
+
+/// Parks a job that failed for good on the DLQ for an operator to replay.
+async fn dead_letter(
+    conn: &mut redis::aio::MultiplexedConnection,
+    video_id: Uuid,
+    payload: &Value,
+    error: &str,
+) {
+    let entry = crate::dead_letter::DeadLetter::new(video_id, payload, error);
+    match crate::dead_letter::push(conn, &entry).await {
+        Ok(()) => {
+            tracing::warn!(%video_id, replay_count = entry.replay_count, "Video job moved to the DLQ");
+            metrics::counter!("petpulse_video_dlq_total").increment(1);
+        }
+        Err(e) => tracing::error!("Failed to dead-letter video {}: {}", video_id, e),
+    }
+}
+
+/// Stamps a processing stage on the video row. Best-effort: a failed timeline
+/// write is logged and never fails the job.
+async fn mark_video_stage(db: &DatabaseConnection, video_id: Uuid, column: pet_video::Column) {
+    let now: chrono::DateTime<chrono::FixedOffset> = Utc::now().into();
+    if let Err(e) = PetVideo::update_many()
+        .col_expr(column, sea_orm::sea_query::Expr::value(now))
+        .filter(pet_video::Column::Id.eq(video_id))
+        .exec(db)
+        .await
+    {
+        tracing::warn!(
+            "Failed to record {:?} for video {}: {}",
+            column,
+            video_id,
+            e
+        );
+    }
+}
+
+/// Replaces the clip rows for a video with its current activity segments.
+async fn save_clips(db: &DatabaseConnection, video: &pet_video::Model) {
+    let activities: Vec<pet_video::Activity> = video
+        .activities
+        .clone()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    if let Err(e) = Clip::delete_many()
+        .filter(clip::Column::VideoId.eq(video.id))
+        .exec(db)
+        .await
+    {
+        tracing::error!("Failed to clear clips for video {}: {}", video.id, e);
+        return;
+    }
+    if activities.is_empty() {
+        return;
+    }
+
+    let rows = activities.into_iter().map(|a| clip::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        video_id: Set(video.id),
+        start_time: Set(a.starttime),
+        end_time: Set(a.endtime),
+        activity: Set(a.activity),
+        canonical_activity: Set(a.canonical_activity),
+        mood: Set(a.mood),
+        description: Set(a.description),
+        created_at: Set(Some(Utc::now().into())),
+    });
+    if let Err(e) = Clip::insert_many(rows).exec(db).await {
+        tracing::error!("Failed to save clips for video {}: {}", video.id, e);
+    }
+}
+
+/// Marks a digest rebuild as already queued for a pet's day. Set when the
+/// job is pushed and cleared when a digest worker picks it up, so videos
+/// finishing in the meantime share one rebuild instead of queueing their own.
+fn digest_pending_key(pet_id: i32, date: NaiveDate) -> String {
+    format!("digest_pending:{}:{}", pet_id, date.format("%Y-%m-%d"))
+}
+
+/// Bounds how long a lost job (worker killed mid-digest) can hold off the
+/// next rebuild for that day.
+const DIGEST_PENDING_TTL_SECS: u64 = 10 * 60;
+
+/// Queues a rebuild of `pet_id`'s digest for `date` unless one is already
+/// waiting. Returns whether a job was pushed. Digests are only ever built by
+/// the digest workers, so anything that wants one rebuilt goes through here.
+pub async fn enqueue_digest_update(
+    redis_conn: &mut redis::aio::MultiplexedConnection,
+    pet_id: i32,
+    date: NaiveDate,
+) -> redis::RedisResult<bool> {
+    enqueue_digest_job(redis_conn, DigestJob::new(pet_id, date)).await
+}
+
+async fn enqueue_digest_job(
+    redis_conn: &mut redis::aio::MultiplexedConnection,
+    job: DigestJob,
+) -> redis::RedisResult<bool> {
+    let (pet_id, date) = (job.pet_id, job.date);
+    let marker = digest_pending_key(pet_id, date);
+    let marked: redis::RedisResult<Option<String>> = redis::cmd("SET")
+        .arg(&marker)
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(DIGEST_PENDING_TTL_SECS)
+        .query_async(redis_conn)
+        .await;
+    // A failed check still queues; a duplicate rebuild beats a missing one
+    if let Ok(None) = marked {
+        tracing::debug!(
+            "Digest update for pet_id={} on {} already queued",
+            pet_id,
+            date
+        );
+        metrics::counter!("petpulse_digest_enqueues_skipped_total").increment(1);
+        return Ok(false);
+    }
+
+    let digest_payload = job.encode();
+
+    let pushed: redis::RedisResult<()> = redis_conn.rpush("digest_queue", digest_payload).await;
+    if let Err(e) = pushed {
+        let _: redis::RedisResult<()> = redis_conn.del(&marker).await;
+        return Err(e);
+    }
+
+    tracing::info!(
+        "Enqueued digest update for pet_id={} to digest_queue",
+        pet_id
+    );
+    Ok(true)
+}
+
+/// [`enqueue_digest_update`] for a video that just finished; a failure is
+/// only logged, since the next video that day queues the rebuild again.
+async fn queue_digest_for_video(
+    redis_conn: &mut redis::aio::MultiplexedConnection,
+    pet_id: i32,
+    date: NaiveDate,
+) {
+    if let Err(e) = enqueue_digest_update(redis_conn, pet_id, date).await {
+        tracing::error!(
+            "Failed to enqueue digest update for pet_id={}: {}",
+            pet_id,
+            e
+        );
+    }
+}
+
+// ============================================================================
+// Static Video Pre-check
+// ============================================================================
+
+const THUMBNAIL_WIDTH: u32 = 480;
+
+/// Grabs a representative frame of the clip as a JPEG.
+async fn extract_thumbnail(video_path: &str) -> Option<Vec<u8>> {
+    let thumb_path = format!("{}.thumb.jpg", video_path);
+    let filter = format!("thumbnail,scale={}:-2", THUMBNAIL_WIDTH);
+
+    let data = match tokio::process::Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-y",
+            "-i",
+            video_path,
+            "-vf",
+            &filter,
+            "-frames:v",
+            "1",
+            &thumb_path,
+        ])
+        .output()
+        .await
+    {
+        Ok(o) if o.status.success() => tokio::fs::read(&thumb_path).await.ok(),
+        Ok(o) => {
+            tracing::warn!(
+                "ffmpeg thumbnail extraction failed for {} (status {}): {}",
+                video_path,
+                o.status,
+                String::from_utf8_lossy(&o.stderr).trim()
+            );
+            None
+        }
+        Err(e) => {
+            tracing::warn!("ffmpeg unavailable for thumbnail extraction: {}", e);
+            None
+        }
+    };
+    let _ = tokio::fs::remove_file(&thumb_path).await;
+    data
+}
+
+/// Uploads a thumbnail next to the video and records its path. Failures are
+/// logged and counted but never fail the analysis job.
+async fn store_thumbnail(
+    db: &DatabaseConnection,
+    gcs_client: &GcsClient,
+    video: &pet_video::Model,
+    video_path: &str,
+) {
+    let Some((bucket, _)) = crate::storage_cleanup::parse_gs_path(&video.file_path) else {
+        return;
+    };
+    let Some(data) = extract_thumbnail(video_path).await else {
+        metrics::counter!("petpulse_thumbnails_total", "result" => "failed").increment(1);
+        return;
+    };
+
+    let object_name = format!(
+        "{}{}.jpg",
+        crate::storage_cleanup::THUMBNAILS_PREFIX,
+        video.id
+    );
+    let upload_type = UploadType::Simple(Media {
+        name: object_name.clone().into(),
+        content_type: "image/jpeg".into(),
+        content_length: Some(data.len() as u64),
+    });
+    if let Err(e) = gcs_client
+        .upload_object(
+            &UploadObjectRequest {
+                bucket: bucket.to_string(),
+                ..Default::default()
+            },
+            data,
+            &upload_type,
+        )
+        .await
+    {
+        tracing::warn!("Failed to upload thumbnail for video {}: {}", video.id, e);
+        metrics::counter!("petpulse_thumbnails_total", "result" => "failed").increment(1);
+        return;
+    }
+
+    let path = format!("gs://{}/{}", bucket, object_name);
+    match PetVideo::update_many()
+        .col_expr(
+            pet_video::Column::ThumbnailPath,
+            sea_orm::sea_query::Expr::value(path),
+        )
+        .filter(pet_video::Column::Id.eq(video.id))
+        .exec(db)
+        .await
+    {
+        Ok(_) => metrics::counter!("petpulse_thumbnails_total", "result" => "stored").increment(1),
+        Err(e) => {
+            tracing::warn!("Failed to record thumbnail for video {}: {}", video.id, e);
+            metrics::counter!("petpulse_thumbnails_total", "result" => "failed").increment(1);
+        }
+    }
+}
+
+const NO_ACTIVITY_DESCRIPTION: &str = "No notable activity detected.";
+const DEFAULT_STATIC_MOTION_THRESHOLD: f64 = 0.01;
+
+/// Filter that prints a scene score for each frame differing from the one
+/// before it by more than `threshold`.
+fn motion_filter(threshold: f64) -> String {
+    format!(
+        "select='gt(scene,{})',metadata=print:key=lavfi.scene_score",
+        threshold
+    )
+}
+
+/// Frames the motion filter printed a score for in ffmpeg's stderr.
+fn moving_frames(stderr: &str) -> usize {
+    stderr.matches("lavfi.scene_score").count()
+}
+
+/// Cheap ffmpeg scene-change pass over the downloaded clip. A clip is static
+/// when no frame differs from its predecessor by more than the configured
+/// threshold. Any ffmpeg failure errs on the side of running the analysis.
+async fn is_static_video(path: &str) -> bool {
+    let threshold = std::env::var("STATIC_VIDEO_MOTION_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(DEFAULT_STATIC_MOTION_THRESHOLD);
+
+    let filter = motion_filter(threshold);
+
+    let output = match tokio::process::Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-nostats",
+            "-i",
+            path,
+            "-vf",
+            &filter,
+            "-an",
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await
+    {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            tracing::warn!(
+                "ffmpeg motion check failed for {} (status {}); analyzing anyway",
+                path,
+                o.status
+            );
+            return false;
+        }
+        Err(e) => {
+            tracing::warn!(
+                "ffmpeg unavailable for motion check: {}; analyzing anyway",
+                e
+            );
+            return false;
+        }
+    };
+
+    let moving_frames = moving_frames(&String::from_utf8_lossy(&output.stderr));
+    tracing::debug!(
+        "Motion check for {}: {} frames above threshold",
+        path,
+        moving_frames
+    );
+    moving_frames == 0
 }
 
 // ============================================================================
@@ -415,18 +2087,40 @@ async fn process_video(
 pub async fn start_digest_workers(
     redis_client: redis::Client,
     db: DatabaseConnection,
-    concurrency: usize,
-) {
+    concurrency: Concurrency,
+    heartbeats: Heartbeats,
+    shutdown: CancellationToken,
+) -> WorkerPool {
     let db = Arc::new(db);
     let redis_client = Arc::new(redis_client);
-
-    for i in 0..concurrency {
+    let in_flight = InFlight::default();
+    let active = ActiveWorkers::new(concurrency.min.into());
+    start_concurrency_supervisor(
+        redis_client.clone(),
+        &["digest_queue"],
+        concurrency,
+        active.clone(),
+        shutdown.clone(),
+    )
+    .await;
+    let mut handles = Vec::with_capacity(concurrency.max);
+
+    for i in 0..concurrency.max {
         let db = db.clone();
         let redis_client = redis_client.clone();
+        let in_flight = in_flight.clone();
+        let shutdown = shutdown.clone();
+        let active = active.clone();
+        let heartbeats = heartbeats.clone();
 
-        tokio::spawn(async move {
+        handles.push(tokio::spawn(async move {
             tracing::info!("Digest Worker {} started", i);
-            loop {
+            while !shutdown.is_cancelled() {
+                heartbeats.beat("digest", i);
+                if is_parked(&active, i) {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(PARKED_POLL_SECS)).await;
+                    continue;
+                }
                 // Get connection
                 let mut conn = match redis_client.get_multiplexed_async_connection().await {
                     Ok(c) => c,
@@ -437,30 +2131,40 @@ pub async fn start_digest_workers(
                     }
                 };
 
-                let result: redis::RedisResult<(String, String)> =
-                    conn.blpop("digest_queue", 0.0).await;
+                let result: redis::RedisResult<Option<(String, String)>> =
+                    conn.blpop("digest_queue", QUEUE_POLL_SECS).await;
 
                 match result {
-                    Ok((_key, payload_str)) => {
-                        let payload: Value = match serde_json::from_str(&payload_str) {
-                            Ok(v) => v,
+                    Ok(None) => {}
+                    Ok(Some((_key, payload_str))) => {
+                        let DigestJob {
+                            pet_id,
+                            date,
+                            enqueued_at,
+                            allow_empty,
+                            ..
+                        } = match DigestJob::decode(&payload_str) {
+                            Ok(job) => job,
                             Err(e) => {
-                                tracing::error!("Digest Worker {}: Bad payload: {}", i, e);
-                                continue;
-                            }
-                        };
-
-                        let pet_id = payload["pet_id"].as_i64().unwrap_or(0) as i32;
-                        let date_str = payload["date"].as_str().unwrap_or("");
-                        let date = match NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-                            Ok(d) => d,
-                            Err(_) => {
-                                tracing::error!("Digest Worker {}: Invalid date: {}", i, date_str);
+                                tracing::error!("Digest Worker {}: Rejected job: {}", i, e);
+                                poison::push(
+                                    &mut conn,
+                                    "digest_queue",
+                                    &payload_str,
+                                    &e.to_string(),
+                                )
+                                .await;
                                 continue;
                             }
                         };
-
-                        process_digest_update(pet_id, date, &db, i).await;
+                        record_queue_wait("digest_queue", enqueued_at);
+
+                        // Videos finishing from here on need a rebuild of their own
+                        let _: redis::RedisResult<()> =
+                            conn.del(digest_pending_key(pet_id, date)).await;
+                        track_job(&in_flight, i, Some(&payload_str));
+                        process_digest_update(pet_id, date, allow_empty, &db, i).await;
+                        track_job(&in_flight, i, None);
                     }
                     Err(e) => {
                         tracing::error!("Digest Worker {}: Redis error: {}", i, e);
@@ -468,13 +2172,21 @@ pub async fn start_digest_workers(
                     }
                 }
             }
-        });
+            tracing::info!("Digest Worker {} stopped", i);
+        }));
+    }
+
+    WorkerPool {
+        queue: "digest_queue",
+        handles,
+        claims: JobClaims::InFlight(in_flight),
     }
 }
 
 async fn process_digest_update(
     pet_id: i32,
     date: NaiveDate,
+    allow_empty: bool,
     db: &DatabaseConnection,
     worker_id: usize,
 ) {
@@ -483,7 +2195,7 @@ async fn process_digest_update(
         "otel.name" = "process_digest_job",
         pet_id = pet_id
     );
-    process_digest_update_impl(pet_id, date, db, worker_id)
+    process_digest_update_impl(pet_id, date, allow_empty, db, worker_id)
         .instrument(span)
         .await
 }
@@ -491,6 +2203,7 @@ async fn process_digest_update(
 async fn process_digest_update_impl(
     pet_id: i32,
     date: NaiveDate,
+    allow_empty: bool,
     db: &DatabaseConnection,
     worker_id: usize,
 ) {
@@ -506,10 +2219,30 @@ async fn process_digest_update_impl(
         date
     );
 
-    // 1. Query all PROCESSED videos for this pet and date
-    let videos = match PetVideo::find()
+    match Pet::find_by_id(pet_id).one(db).await {
+        Ok(Some(pet)) if !pet.is_archived() => {}
+        Ok(_) => {
+            tracing::info!(
+                "Digest Worker {}: Pet {} is archived or no longer exists; skipping",
+                worker_id,
+                pet_id
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Digest Worker {}: Failed to load pet: {}", worker_id, e);
+            return;
+        }
+    }
+
+    // 1. Query PROCESSED videos for this pet captured on `date`, which is
+    // the owner's local day
+    let owner_tz = crate::timezone::of_pet_owner(db, pet_id).await;
+    let (start_of_day, end_of_day) = crate::timezone::day_bounds(date, owner_tz);
+    let videos_for_date = match PetVideo::find()
         .filter(pet_video::Column::PetId.eq(pet_id))
         .filter(pet_video::Column::Status.eq("PROCESSED"))
+        .filter(pet_video::captured_between(start_of_day, end_of_day))
         .all(db)
         .await
     {
@@ -520,12 +2253,10 @@ async fn process_digest_update_impl(
         }
     };
 
-    // Filter by date (since we need to compare DateTimeWithTimeZone)
-    let videos_for_date: Vec<_> = videos
-        .into_iter()
-        .filter(|v| v.created_at.date_naive() == date)
-        .collect();
-
+    if videos_for_date.is_empty() && allow_empty {
+        write_quiet_day_digest(pet_id, date, db, worker_id).await;
+        return;
+    }
     if videos_for_date.is_empty() {
         tracing::warn!(
             "Digest Worker {}: No processed videos found for pet_id={}, date={}",
@@ -544,11 +2275,20 @@ async fn process_digest_update_impl(
         date
     );
 
+    let species = Pet::find_by_id(pet_id)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .map(|p| p.species)
+        .unwrap_or_default();
+
     // 2. Aggregate data from all videos
     let mut all_activities_json = Vec::new();
     let mut all_moods = Vec::new();
     let mut all_descriptions = Vec::new();
     let mut unusual_events_list = Vec::new();
+    let mut expected_conditions_list = Vec::new();
 
     for video in &videos_for_date {
         // Parse activities
@@ -556,7 +2296,10 @@ async fn process_digest_update_impl(
             if let Ok(_activities) =
                 serde_json::from_value::<Vec<pet_video::Activity>>(activities_json.clone())
             {
-                // Store raw activity objects for JSON column
+                // Store activity objects for JSON column, with canonical names
+                // for videos analyzed before the taxonomy
+                let mut activities_json = activities_json.clone();
+                crate::activity::annotate_activities(&mut activities_json, &species);
                 if let Some(arr) = activities_json.as_array() {
                     all_activities_json.extend(arr.clone());
                 }
@@ -573,34 +2316,65 @@ async fn process_digest_update_impl(
             all_descriptions.push(desc.clone());
         }
 
-        // Collect unusual events
-        if video.is_unusual {
+        // Clips matching the pet's known behaviors get their own section
+        if video.suppressed_by_known_behavior {
+            expected_conditions_list.push(serde_json::json!({
+                "video_id": video.id.to_string(),
+                "description": video.description.clone().unwrap_or("Expected behavior observed".to_string()),
+                "timestamp": video.captured_at().to_rfc3339()
+            }));
+        } else if video.is_unusual {
             // Create a structured object for unusual event
             let event_obj = serde_json::json!({
                 "video_id": video.id.to_string(),
                 "description": video.description.clone().unwrap_or("Unusual activity detected".to_string()),
                 // We could add "timestamp" here if needed
-                "timestamp": video.created_at.to_rfc3339()
+                "timestamp": video.captured_at().to_rfc3339()
             });
             unusual_events_list.push(event_obj);
         }
     }
 
+    // Activity breakdown grouped by canonical name, most frequent first
+    let mut activity_counts: Vec<(String, usize)> = Vec::new();
+    for name in all_activities_json
+        .iter()
+        .filter_map(|a| a["canonical_activity"].as_str())
+    {
+        match activity_counts.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => activity_counts.push((name.to_string(), 1)),
+        }
+    }
+    activity_counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
     // 3. Generate summary
     let summary = format!(
         "Daily Summary for Pet {}\n\n\
         Videos Processed: {}\n\
+        Activities: {}\n\
         Moods: {}\n\
-        Unusual Events: {}\n\n\
+        Unusual Events: {}\n\
+        Expected Conditions: {}\n\n\
         Descriptions:\n{}",
         pet_id,
         videos_for_date.len(),
+        if activity_counts.is_empty() {
+            "None".to_string()
+        } else {
+            activity_counts
+                .iter()
+                .map(|(name, count)| format!("{} ({})", name, count))
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
         if all_moods.is_empty() {
             "None".to_string()
         } else {
             all_moods.join(", ")
         },
         unusual_events_list.len(),
+        expected_conditions_list.len(),
         if all_descriptions.is_empty() {
             "No descriptions available.".to_string()
         } else {
@@ -612,6 +2386,8 @@ async fn process_digest_update_impl(
     let activities_json =
         serde_json::to_value(all_activities_json).unwrap_or(serde_json::json!([]));
     let unusual_json = serde_json::to_value(unusual_events_list).unwrap_or(serde_json::json!([]));
+    let expected_json =
+        serde_json::to_value(expected_conditions_list).unwrap_or(serde_json::json!([]));
 
     // 4. UPSERT daily_digest
     // First, try to find existing digest
@@ -629,6 +2405,7 @@ async fn process_digest_update_impl(
         active.moods = Set(Some(moods_json));
         active.activities = Set(Some(activities_json));
         active.unusual_events = Set(Some(unusual_json));
+        active.expected_conditions = Set(Some(expected_json));
         active.total_videos = Set(videos_for_date.len() as i32);
         active.updated_at = Set(Utc::now().into());
         active.update(db).await
@@ -642,6 +2419,7 @@ async fn process_digest_update_impl(
             moods: Set(Some(moods_json)),
             activities: Set(Some(activities_json)),
             unusual_events: Set(Some(unusual_json)),
+            expected_conditions: Set(Some(expected_json)),
             total_videos: Set(videos_for_date.len() as i32),
             created_at: Set(Utc::now().into()),
             updated_at: Set(Utc::now().into()),
@@ -658,6 +2436,14 @@ async fn process_digest_update_impl(
                 date
             );
             metrics::counter!("petpulse_daily_digests_generated_total").increment(1);
+
+            if let Err(e) = crate::baseline::refresh(db, pet_id).await {
+                tracing::warn!(
+                    "Failed to refresh behavior baseline for pet {}: {}",
+                    pet_id,
+                    e
+                );
+            }
         }
         Err(e) => {
             tracing::error!(
@@ -669,11 +2455,218 @@ async fn process_digest_update_impl(
     }
 }
 
+/// Records a day with no clips as a digest of its own, so the owner sees a
+/// quiet day rather than a gap. A digest that already exists is left alone.
+async fn write_quiet_day_digest(
+    pet_id: i32,
+    date: NaiveDate,
+    db: &DatabaseConnection,
+    worker_id: usize,
+) {
+    match DailyDigest::find()
+        .filter(daily_digest::Column::PetId.eq(pet_id))
+        .filter(daily_digest::Column::Date.eq(date))
+        .one(db)
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(_)) => return,
+        Err(e) => {
+            tracing::error!("Digest Worker {}: Failed to load digest: {}", worker_id, e);
+            return;
+        }
+    }
+
+    let quiet_day = daily_digest::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        pet_id: Set(pet_id),
+        date: Set(date),
+        summary: Set(format!(
+            "Daily Summary for Pet {}\n\nVideos Processed: 0\n\nQuiet day: no clips were recorded.",
+            pet_id
+        )),
+        moods: Set(Some(serde_json::json!([]))),
+        activities: Set(Some(serde_json::json!([]))),
+        unusual_events: Set(Some(serde_json::json!([]))),
+        expected_conditions: Set(Some(serde_json::json!([]))),
+        total_videos: Set(0),
+        created_at: Set(Utc::now().into()),
+        updated_at: Set(Utc::now().into()),
+    };
+    match quiet_day.insert(db).await {
+        Ok(_) => {
+            tracing::info!(
+                "Digest Worker {}: Wrote quiet-day digest for pet_id={}, date={}",
+                worker_id,
+                pet_id,
+                date
+            );
+            metrics::counter!("petpulse_daily_digests_generated_total").increment(1);
+        }
+        Err(e) => tracing::error!(
+            "Digest Worker {}: Failed to insert quiet-day digest: {}",
+            worker_id,
+            e
+        ),
+    }
+}
+
+// ============================================================================
+// Nightly Digest Scheduler
+// ============================================================================
+
+const NIGHTLY_DIGEST_CHECK_SECS: u64 = 5 * 60;
+/// Local hour the nightly run happens at, unless NIGHTLY_DIGEST_HOUR says otherwise
+const DEFAULT_NIGHTLY_DIGEST_HOUR: u32 = 23;
+/// Outlives the hour the lock guards, so no replica reruns the same day
+const NIGHTLY_DIGEST_LOCK_TTL_SECS: u64 = 2 * 24 * 3600;
+
+/// Held by whichever worker process runs a zone's nightly pass for a date.
+fn nightly_digest_lock_key(tz: chrono_tz::Tz, date: NaiveDate) -> String {
+    format!("nightly_digest:{}:{}", tz.name(), date.format("%Y-%m-%d"))
+}
+
+/// Queues each pet's digest for the day once it's nearly over, at
+/// NIGHTLY_DIGEST_HOUR (default 23) in the owner's timezone, so the summary
+/// doesn't depend on when the last clip finished. With
+/// NIGHTLY_DIGEST_EMPTY_DAYS=true pets without clips get a quiet-day digest.
+/// Setting NIGHTLY_DIGEST_ENABLED=false turns the scheduler off.
+pub async fn start_nightly_digest_scheduler(redis_client: redis::Client, db: DatabaseConnection) {
+    if !env_var_or("NIGHTLY_DIGEST_ENABLED", true) {
+        tracing::info!("Nightly digest scheduler disabled");
+        return;
+    }
+    let hour = std::env::var("NIGHTLY_DIGEST_HOUR")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|h| *h < 24)
+        .unwrap_or(DEFAULT_NIGHTLY_DIGEST_HOUR);
+    let empty_days: bool = env_var_or("NIGHTLY_DIGEST_EMPTY_DAYS", false);
+
+    tokio::spawn(async move {
+        tracing::info!(
+            "Nightly digests scheduled at {:02}:00 owner-local time (empty days: {})",
+            hour,
+            empty_days
+        );
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(NIGHTLY_DIGEST_CHECK_SECS)).await;
+            let mut conn = match redis_client.get_multiplexed_async_connection().await {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("Nightly digests: Failed to get redis conn: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = run_nightly_digests(&mut conn, &db, hour, empty_days).await {
+                tracing::error!("Nightly digests failed: {}", e);
+            }
+        }
+    });
+}
+
+/// One pass: every timezone where it's currently `hour` gets its pets'
+/// digests queued for the local date, once per zone and date.
+async fn run_nightly_digests(
+    conn: &mut redis::aio::MultiplexedConnection,
+    db: &DatabaseConnection,
+    hour: u32,
+    empty_days: bool,
+) -> Result<(), sea_orm::DbErr> {
+    use crate::entities::user;
+    use chrono::Timelike;
+    use sea_orm::QuerySelect;
+
+    let mut pets_by_tz: HashMap<chrono_tz::Tz, Vec<i32>> = HashMap::new();
+    for (pet, owner) in Pet::find_active()
+        .find_also_related(user::Entity)
+        .all(db)
+        .await?
+    {
+        let tz = owner
+            .as_ref()
+            .map(crate::timezone::of_user)
+            .unwrap_or(chrono_tz::Tz::UTC);
+        pets_by_tz.entry(tz).or_default().push(pet.id);
+    }
+
+    let now = Utc::now();
+    for (tz, pet_ids) in pets_by_tz {
+        let local = now.with_timezone(&tz);
+        if local.hour() != hour {
+            continue;
+        }
+        let date = local.date_naive();
+
+        let locked: redis::RedisResult<Option<String>> = redis::cmd("SET")
+            .arg(nightly_digest_lock_key(tz, date))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(NIGHTLY_DIGEST_LOCK_TTL_SECS)
+            .query_async(conn)
+            .await;
+        match locked {
+            Ok(Some(_)) => {}
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("Nightly digests: Failed to lock {} for {}: {}", tz, date, e);
+                continue;
+            }
+        }
+
+        let (start_of_day, end_of_day) = crate::timezone::day_bounds(date, tz);
+        let with_videos: std::collections::HashSet<i32> = PetVideo::find()
+            .select_only()
+            .column(pet_video::Column::PetId)
+            .distinct()
+            .filter(pet_video::Column::PetId.is_in(pet_ids.clone()))
+            .filter(pet_video::Column::Status.eq("PROCESSED"))
+            .filter(pet_video::captured_between(start_of_day, end_of_day))
+            .into_tuple::<i32>()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut queued = 0;
+        for pet_id in pet_ids {
+            let has_videos = with_videos.contains(&pet_id);
+            if !has_videos && !empty_days {
+                continue;
+            }
+            let job = DigestJob {
+                allow_empty: !has_videos,
+                ..DigestJob::new(pet_id, date)
+            };
+            match enqueue_digest_job(conn, job).await {
+                Ok(true) => queued += 1,
+                Ok(false) => {}
+                Err(e) => tracing::error!(
+                    "Nightly digests: Failed to enqueue pet_id={} for {}: {}",
+                    pet_id,
+                    date,
+                    e
+                ),
+            }
+        }
+        metrics::counter!("petpulse_nightly_digests_queued_total").increment(queued);
+        tracing::info!(
+            "Nightly digests: Queued {} digests for {} on {}",
+            queued,
+            tz,
+            date
+        );
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Alert Webhook Helper
 // ============================================================================
 
 async fn send_alert_webhook(
+    mut redis_conn: redis::aio::MultiplexedConnection,
     video_id: Uuid,
     pet_id: i32,
     description: String,
@@ -723,25 +2716,7 @@ async fn send_alert_webhook(
         severity_level
     );
 
-    let client = reqwest::Client::new();
-    match client.post(&agent_url).json(&alert_payload).send().await {
-        Ok(resp) => {
-            if resp.status().is_success() {
-                tracing::info!("Successfully sent alert webhook to agent service");
-            } else {
-                tracing::error!(
-                    "Agent service returned error: {} - {}",
-                    resp.status(),
-                    resp.text()
-                        .await
-                        .unwrap_or_else(|_| "<unable to read response>".to_string())
-                );
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to send alert webhook to agent service: {}", e);
-        }
-    }
+    crate::alert_outbox::deliver(&mut redis_conn, &agent_url, &alert_payload).await;
 }
 
 // ============================================================================
@@ -749,6 +2724,7 @@ async fn send_alert_webhook(
 // ============================================================================
 
 async fn send_critical_alert_webhook(
+    mut redis_conn: redis::aio::MultiplexedConnection,
     video_id: Uuid,
     pet_id: i32,
     description: String,
@@ -792,26 +2768,386 @@ async fn send_critical_alert_webhook(
         critical_indicators
     );
 
+    crate::alert_outbox::deliver(&mut redis_conn, &agent_url, &alert_payload).await;
+}
+
+// ============================================================================
+// Processing Error Webhook
+// ============================================================================
+
+/// Reports a video that permanently failed processing. The agent stores it as
+/// a low-severity ProcessingError alert and only sends an email notice.
+async fn send_processing_error_webhook(
+    video_id: Uuid,
+    pet_id: i32,
+    stage: &'static str,
+    error: String,
+) {
+    let agent_url = std::env::var("AGENT_SERVICE_URL")
+        .unwrap_or_else(|_| "http://agent:3002/alert".to_string());
+
+    let message = format!(
+        "A video could not be analyzed ({} failed), so this recording window was not monitored.",
+        stage
+    );
+
+    let alert_payload = AlertPayload {
+        alert_id: Uuid::new_v4().to_string(),
+        pet_id: pet_id.to_string(),
+        alert_type: AlertType::ProcessingError,
+        severity: "low".to_string(),
+        message: Some(message),
+        metric_value: None,
+        baseline_value: None,
+        deviation_factor: None,
+        video_id: Some(video_id.to_string()),
+        timestamp: Some(Utc::now().to_rfc3339()),
+        context: Some(serde_json::json!({
+            "stage": stage,
+            "error": error,
+        })),
+        title: Some("Video Processing Failed".to_string()),
+        state: Some("alerting".to_string()),
+        eval_matches: None,
+        severity_level: Some("low".to_string()),
+        critical_indicators: None,
+        recommended_actions: None,
+    };
+
+    tracing::info!(
+        "Sending processing error webhook for video_id={}, pet_id={}, stage={}",
+        video_id,
+        pet_id,
+        stage
+    );
+
     let client = reqwest::Client::new();
     match client.post(&agent_url).json(&alert_payload).send().await {
-        Ok(resp) => {
-            if resp.status().is_success() {
-                tracing::info!("✅ Successfully sent CRITICAL alert webhook to agent service");
-            } else {
-                tracing::error!(
-                    "❌ Agent service returned error for CRITICAL alert: {} - {}",
-                    resp.status(),
-                    resp.text()
-                        .await
-                        .unwrap_or_else(|_| "<unable to read response>".to_string())
-                );
-            }
+        Ok(resp) if resp.status().is_success() => {
+            tracing::info!("Successfully sent processing error webhook to agent service");
         }
-        Err(e) => {
-            tracing::error!(
-                "❌ Failed to send CRITICAL alert webhook to agent service: {}",
-                e
-            );
+        Ok(resp) => tracing::error!(
+            "Agent service returned error for processing error alert: {}",
+            resp.status()
+        ),
+        Err(e) => tracing::error!("Failed to send processing error webhook: {}", e),
+    }
+}
+
+/// Reports a scheduled pet whose camera has gone quiet. The agent treats it
+/// like a processing error: a low-severity alert and an email notice.
+async fn send_uploads_overdue_webhook(
+    pet_id: i32,
+    silent_minutes: i64,
+    expected_interval_minutes: i64,
+    last_upload: Option<chrono::DateTime<Utc>>,
+) {
+    let agent_url = std::env::var("AGENT_SERVICE_URL")
+        .unwrap_or_else(|_| "http://agent:3002/alert".to_string());
+
+    let message = format!(
+        "No clips received in {} minutes (expected one every {} minutes).",
+        silent_minutes, expected_interval_minutes
+    );
+
+    let alert_payload = AlertPayload {
+        alert_id: Uuid::new_v4().to_string(),
+        pet_id: pet_id.to_string(),
+        alert_type: AlertType::UploadsOverdue,
+        severity: "low".to_string(),
+        message: Some(message),
+        metric_value: Some(silent_minutes as f64),
+        baseline_value: Some(expected_interval_minutes as f64),
+        deviation_factor: None,
+        video_id: None,
+        timestamp: Some(Utc::now().to_rfc3339()),
+        context: Some(serde_json::json!({
+            "last_upload_at": last_upload,
+            "expected_interval_minutes": expected_interval_minutes,
+        })),
+        title: Some("Camera Uploads Overdue".to_string()),
+        state: Some("alerting".to_string()),
+        eval_matches: None,
+        severity_level: Some("low".to_string()),
+        critical_indicators: None,
+        recommended_actions: None,
+    };
+
+    tracing::warn!(
+        "Uploads overdue for pet_id={}: silent for {} min",
+        pet_id,
+        silent_minutes
+    );
+
+    let client = reqwest::Client::new();
+    match client.post(&agent_url).json(&alert_payload).send().await {
+        Ok(resp) if resp.status().is_success() => {
+            tracing::info!("Successfully sent uploads overdue webhook to agent service");
+        }
+        Ok(resp) => tracing::error!(
+            "Agent service returned error for uploads overdue alert: {}",
+            resp.status()
+        ),
+        Err(e) => tracing::error!("Failed to send uploads overdue webhook: {}", e),
+    }
+}
+
+// ============================================================================
+// Queue Depth Webhook
+// ============================================================================
+
+/// Reports a queue backlog alarm (or its recovery) to the agent, which routes
+/// it to operators rather than pet owners.
+async fn send_queue_depth_webhook(
+    queue: &'static str,
+    firing: bool,
+    depth: u64,
+    oldest_age_secs: Option<i64>,
+    estimated_drain_secs: u64,
+) {
+    let agent_url = std::env::var("AGENT_SERVICE_URL")
+        .unwrap_or_else(|_| "http://agent:3002/alert".to_string());
+
+    let message = if firing {
+        format!(
+            "{} backlog is {} entries (estimated drain {}s)",
+            queue, depth, estimated_drain_secs
+        )
+    } else {
+        format!("{} backlog recovered ({} entries)", queue, depth)
+    };
+
+    let alert_payload = AlertPayload {
+        alert_id: Uuid::new_v4().to_string(),
+        // Operator alerts aren't tied to a pet
+        pet_id: "system".to_string(),
+        alert_type: AlertType::QueueDepthHigh,
+        severity: if firing { "high" } else { "info" }.to_string(),
+        message: Some(message),
+        metric_value: Some(depth as f64),
+        baseline_value: None,
+        deviation_factor: None,
+        video_id: None,
+        timestamp: Some(Utc::now().to_rfc3339()),
+        context: Some(serde_json::json!({
+            "queue": queue,
+            "depth": depth,
+            "oldest_age_seconds": oldest_age_secs,
+            "estimated_drain_seconds": estimated_drain_secs,
+        })),
+        title: Some(format!("Queue depth high: {}", queue)),
+        state: Some(if firing { "alerting" } else { "ok" }.to_string()),
+        eval_matches: None,
+        severity_level: Some(if firing { "high" } else { "info" }.to_string()),
+        critical_indicators: None,
+        recommended_actions: None,
+    };
+
+    tracing::warn!(
+        "Queue alarm {} for {}: depth={}, oldest_age={:?}",
+        if firing { "firing" } else { "resolved" },
+        queue,
+        depth,
+        oldest_age_secs
+    );
+
+    let client = reqwest::Client::new();
+    match client.post(&agent_url).json(&alert_payload).send().await {
+        Ok(resp) if resp.status().is_success() => {
+            tracing::info!("Successfully sent queue depth webhook to agent service");
+        }
+        Ok(resp) => tracing::error!(
+            "Agent service returned error for queue depth alert: {}",
+            resp.status()
+        ),
+        Err(e) => tracing::error!("Failed to send queue depth webhook: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_analysis_retries_then_reports_analysis_failed() {
+        assert_eq!(
+            failure_outcome(0, VideoOutcome::AnalysisFailed),
+            VideoOutcome::Retried
+        );
+        assert_eq!(
+            failure_outcome(MAX_VIDEO_RETRIES - 1, VideoOutcome::AnalysisFailed),
+            VideoOutcome::Retried
+        );
+        let last = failure_outcome(MAX_VIDEO_RETRIES, VideoOutcome::AnalysisFailed);
+        assert_eq!(last, VideoOutcome::AnalysisFailed);
+        assert_eq!(last.label(), "analysis_failed");
+        assert_eq!(last.success_label(), "false");
+    }
+
+    #[test]
+    fn unsaved_result_is_not_a_success() {
+        let saved: Result<(), &str> = Ok(());
+        let failed: Result<(), &str> = Err("connection reset");
+        assert_eq!(saved_outcome(&saved), VideoOutcome::Success);
+        assert_eq!(saved_outcome(&saved).success_label(), "true");
+        assert_eq!(saved_outcome(&failed), VideoOutcome::DbUpdateFailed);
+        assert_eq!(saved_outcome(&failed).success_label(), "false");
+    }
+
+    #[test]
+    fn timeout_stage_comes_from_the_flag_not_the_message() {
+        assert_eq!(analysis_error_stage(true, "anything"), "timeout");
+        assert_eq!(
+            analysis_error_stage(false, "timed out after 30s upstream"),
+            "analysis"
+        );
+        let parse_error = format!("{}: eof", crate::gemini::PARSE_ERROR_PREFIX);
+        assert_eq!(analysis_error_stage(false, &parse_error), "parse");
+    }
+
+    #[test]
+    fn retry_delay_doubles_within_its_jitter_and_caps() {
+        // Defaults: 30s base, 1800s cap, 20% jitter
+        for _ in 0..50 {
+            let first = retry_delay(0).num_milliseconds();
+            assert!((24_000..=36_000).contains(&first), "{}", first);
+            let third = retry_delay(2).num_milliseconds();
+            assert!((96_000..=144_000).contains(&third), "{}", third);
+            let late = retry_delay(30).num_milliseconds();
+            assert!((1_440_000..=2_160_000).contains(&late), "{}", late);
+        }
+    }
+
+    fn alarm(depth_threshold: u64, age_threshold_secs: Option<i64>) -> QueueAlarm {
+        QueueAlarm {
+            queue: "video_queue",
+            depth_threshold,
+            age_threshold_secs,
+            firing: false,
+        }
+    }
+
+    #[test]
+    fn queue_alarm_fires_once_and_clears_below_half() {
+        let mut alarm = alarm(100, None);
+        assert_eq!(alarm.evaluate(99, None), None);
+        assert_eq!(alarm.evaluate(100, None), Some(true));
+        assert_eq!(alarm.evaluate(150, None), None);
+        // Still at or above half the threshold, so it keeps firing
+        assert_eq!(alarm.evaluate(50, None), None);
+        assert_eq!(alarm.evaluate(49, None), Some(false));
+        assert_eq!(alarm.evaluate(10, None), None);
+    }
+
+    #[test]
+    fn queue_alarm_on_age_needs_both_to_recover() {
+        let mut alarm = alarm(100, Some(600));
+        assert_eq!(alarm.evaluate(5, Some(600)), Some(true));
+        // Depth is fine but the head is still old
+        assert_eq!(alarm.evaluate(5, Some(400)), None);
+        assert_eq!(alarm.evaluate(5, Some(299)), Some(false));
+        // An empty queue has no oldest entry
+        assert_eq!(alarm.evaluate(0, None), None);
+    }
+
+    fn pool(handles: Vec<JoinHandle<()>>, in_flight: InFlight) -> WorkerPool {
+        WorkerPool {
+            queue: "digest_queue",
+            handles,
+            claims: JobClaims::InFlight(in_flight),
+        }
+    }
+
+    /// Nothing listens here, so a requeue attempt can only fail.
+    fn no_redis() -> redis::Client {
+        redis::Client::open("redis://127.0.0.1:1/").unwrap()
+    }
+
+    #[test]
+    fn track_job_records_and_clears_each_worker() {
+        let in_flight = InFlight::default();
+        track_job(&in_flight, 0, Some("{\"n\":0}"));
+        track_job(&in_flight, 1, Some("{\"n\":1}"));
+        track_job(&in_flight, 0, None);
+        let jobs = in_flight.lock().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[&1], "{\"n\":1}");
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_jobs_that_finish_in_time() {
+        let in_flight = InFlight::default();
+        let finished = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let handle = tokio::spawn({
+            let in_flight = in_flight.clone();
+            let finished = finished.clone();
+            async move {
+                track_job(&in_flight, 0, Some("{}"));
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                track_job(&in_flight, 0, None);
+                finished.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        });
+
+        pool(vec![handle], in_flight.clone())
+            .drain(&no_redis(), tokio::time::Duration::from_secs(5))
+            .await;
+        assert!(finished.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn drain_aborts_jobs_still_running_at_the_timeout() {
+        struct Dropped(Arc<std::sync::atomic::AtomicBool>);
+        impl Drop for Dropped {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
         }
+
+        let in_flight = InFlight::default();
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let handle = tokio::spawn({
+            let in_flight = in_flight.clone();
+            let guard = Dropped(dropped.clone());
+            async move {
+                let _guard = guard;
+                track_job(&in_flight, 0, Some("{\"digest\":1}"));
+                std::future::pending::<()>().await;
+            }
+        });
+        tokio::task::yield_now().await;
+
+        pool(vec![handle], in_flight.clone())
+            .drain(&no_redis(), tokio::time::Duration::from_millis(50))
+            .await;
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+        // With Redis unreachable the cut-off job stays claimed rather than lost
+        assert_eq!(in_flight.lock().unwrap()[&0], "{\"digest\":1}");
+    }
+
+    #[test]
+    fn motion_filter_selects_frames_over_the_threshold() {
+        assert_eq!(
+            motion_filter(0.01),
+            "select='gt(scene,0.01)',metadata=print:key=lavfi.scene_score"
+        );
+    }
+
+    #[test]
+    fn moving_frames_counts_printed_scene_scores() {
+        let stderr = "Input #0, mov,mp4 from 'clip.mp4':\n\
+            frame:41 pts:41 pts_time:1.366\n\
+            lavfi.scene_score=0.052\n\
+            frame:97 pts:97 pts_time:3.233\n\
+            lavfi.scene_score=0.210\n";
+        assert_eq!(moving_frames(stderr), 2);
+        assert_eq!(moving_frames("Input #0, mov,mp4 from 'clip.mp4':\n"), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_motion_check_still_analyzes_the_clip() {
+        // Whether ffmpeg is missing or can't read the file, the clip isn't skipped
+        assert!(!is_static_video("/nonexistent/petpulse-clip.mp4").await);
     }
 }